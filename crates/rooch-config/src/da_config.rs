@@ -38,14 +38,26 @@ pub enum OpenDAScheme {
     // root
     // credential （it's okay to pass credential file path here, it'll be handled it automatically）
     Gcs,
-    // s3 config:
+    // s3(and S3-compatible services, e.g. MinIO) config:
     // root
     // bucket
-    // region
+    // region (default: us-east-1)
     // endpoint
     // access_key_id
     // secret_access_key
     S3,
+    // in-memory storage, for testing only, data is lost after restart
+    Memory,
+}
+
+// compression codec of the chunk which packs batches
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DAChunkCompression {
+    None,
+    #[default]
+    Zstd,
+    Lz4,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -208,6 +220,34 @@ pub struct DAServerCelestiaConfig {
         help = "max segment size, striking a balance between throughput and the constraints on blob size."
     )]
     pub max_segment_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "compression",
+        long,
+        help = "compression codec of chunk: 'zstd', 'lz4' or 'none', default is 'zstd'."
+    )]
+    pub compression: Option<DAChunkCompression>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "max-chunk-batches",
+        long,
+        help = "max number of consecutive batches packed into one chunk, default is 1, at most 64. more batches bring better compression ratio but longer delay."
+    )]
+    pub max_chunk_batches: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "max-chunk-age-secs",
+        long,
+        help = "max seconds a batch is buffered before its chunk is written even if the chunk is not full, default is 60."
+    )]
+    pub max_chunk_age_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "fec-parity-segments",
+        long,
+        help = "number of Reed-Solomon parity segments of each chunk, FEC is disabled if it's 0 or not set. the chunk could be rebuilt when no more than n segments are missing or corrupted."
+    )]
+    pub fec_parity_segments: Option<u64>,
 }
 
 impl Default for DAServerCelestiaConfig {
@@ -217,6 +257,10 @@ impl Default for DAServerCelestiaConfig {
             conn: None,
             auth_token: None,
             max_segment_size: Some(1024 * 1024),
+            compression: None,
+            max_chunk_batches: None,
+            max_chunk_age_secs: None,
+            fec_parity_segments: None,
         }
     }
 }
@@ -295,6 +339,34 @@ pub struct DAServerOpenDAConfig {
         help = "max segment size, striking a balance between throughput and the constraints on blob size."
     )]
     pub max_segment_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "compression",
+        long,
+        help = "compression codec of chunk: 'zstd', 'lz4' or 'none', default is 'zstd'."
+    )]
+    pub compression: Option<DAChunkCompression>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "max-chunk-batches",
+        long,
+        help = "max number of consecutive batches packed into one chunk, default is 1, at most 64. more batches bring better compression ratio but longer delay."
+    )]
+    pub max_chunk_batches: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "max-chunk-age-secs",
+        long,
+        help = "max seconds a batch is buffered before its chunk is written even if the chunk is not full, default is 60."
+    )]
+    pub max_chunk_age_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "fec-parity-segments",
+        long,
+        help = "number of Reed-Solomon parity segments of each chunk, FEC is disabled if it's 0 or not set. the chunk could be rebuilt when no more than n segments are missing or corrupted."
+    )]
+    pub fec_parity_segments: Option<u64>,
 }
 
 impl FromStr for DAServerSubmitStrategy {
@@ -321,6 +393,30 @@ impl Display for OpenDAScheme {
             OpenDAScheme::Fs => write!(f, "fs"),
             OpenDAScheme::Gcs => write!(f, "gcs"),
             OpenDAScheme::S3 => write!(f, "s3"),
+            OpenDAScheme::Memory => write!(f, "memory"),
+        }
+    }
+}
+
+impl Display for DAChunkCompression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DAChunkCompression::None => write!(f, "none"),
+            DAChunkCompression::Zstd => write!(f, "zstd"),
+            DAChunkCompression::Lz4 => write!(f, "lz4"),
+        }
+    }
+}
+
+impl FromStr for DAChunkCompression {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(DAChunkCompression::None),
+            "zstd" => Ok(DAChunkCompression::Zstd),
+            "lz4" => Ok(DAChunkCompression::Lz4),
+            _ => Err("da chunk compression no match"),
         }
     }
 }
@@ -333,6 +429,7 @@ impl FromStr for OpenDAScheme {
             "gcs" => Ok(OpenDAScheme::Gcs),
             "s3" => Ok(OpenDAScheme::S3),
             "fs" => Ok(OpenDAScheme::Fs),
+            "memory" => Ok(OpenDAScheme::Memory),
             _ => Err("open-da scheme no match"),
         }
     }
@@ -403,7 +500,7 @@ mod tests {
     #[test]
     fn test_internal_da_server_config_str() {
         let celestia_config_str = r#"{"celestia": {"namespace": "test_namespace", "conn": "test_conn", "auth_token": "test_token", "max_segment_size": 2048}}"#;
-        let openda_config_str = r#"{"open-da": {"scheme": "gcs", "config": {"Param1": "value1", "param2": "Value2"}, "max_segment_size": 2048}}"#;
+        let openda_config_str = r#"{"open-da": {"scheme": "gcs", "config": {"Param1": "value1", "param2": "Value2"}, "max_segment_size": 2048, "compression": "lz4", "max_chunk_batches": 8}}"#;
         let invalid_config_str = r#"{"unknown": {...}}"#;
 
         match InternalDAServerConfigType::from_str(celestia_config_str) {
@@ -415,6 +512,10 @@ mod tests {
                         conn: Some("test_conn".to_string()),
                         auth_token: Some("test_token".to_string()),
                         max_segment_size: Some(2048),
+                        compression: None,
+                        max_chunk_batches: None,
+                        max_chunk_age_secs: None,
+                        fec_parity_segments: None,
                     }
                 );
             }
@@ -438,6 +539,10 @@ mod tests {
                         scheme: OpenDAScheme::Gcs,
                         config,
                         max_segment_size: Some(2048),
+                        compression: Some(DAChunkCompression::Lz4),
+                        max_chunk_batches: Some(8),
+                        max_chunk_age_secs: None,
+                        fec_parity_segments: None,
                     }
                 );
             }
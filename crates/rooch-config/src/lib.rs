@@ -92,6 +92,27 @@ pub struct RoochOpt {
     #[clap(long, env = "ETH_RPC_URL")]
     pub eth_rpc_url: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long, env = "ETH_START_BLOCK_HEIGHT")]
+    /// The block height of the Ethereum chain to start relaying from if no block has been relayed,
+    /// default is the latest block.
+    pub eth_start_block_height: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long, env = "ETH_END_BLOCK_HEIGHT")]
+    /// The end block height of the Ethereum chain to stop relaying from, default is none.
+    pub eth_end_block_height: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long, env = "ETH_RELAY_BATCH_SIZE")]
+    /// The max number of Ethereum blocks fetched in one sync tick, default is 10.
+    pub eth_relay_batch_size: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long, env = "ETH_RELAYER_FAILURE_POLICY")]
+    /// What the Ethereum relayer does when a relayed block fails to execute: `continue`, `pause` or `retry:<times>`, default is `continue`.
+    pub eth_relayer_failure_policy: Option<RelayerFailurePolicy>,
+
     /// The Bitcoin RPC URL to connect to for relay L1 block and transaction to L2.
     /// If not set, the relayer service will not start.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -121,12 +142,36 @@ pub struct RoochOpt {
     /// The interval of sync BTC block, default is none.
     pub btc_sync_block_interval: Option<u64>,
 
+    #[clap(long, env = "BTC_MEMPOOL_PREVIEW")]
+    /// Poll the Bitcoin mempool to preview the pending transactions, the preview is read-only and never written to the state.
+    pub btc_mempool_preview: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long, env = "BTC_MEMPOOL_POLL_INTERVAL")]
+    /// The interval in seconds of polling the Bitcoin mempool, default is 10.
+    pub btc_mempool_poll_interval: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long, env = "BTC_RELAYER_FAILURE_POLICY")]
+    /// What the Bitcoin relayer does when a relayed block or transaction fails to execute: `continue`, `pause` or `retry:<times>`, default is `continue`.
+    pub btc_relayer_failure_policy: Option<RelayerFailurePolicy>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long, env = "BTC_BLOCK_DIR", conflicts_with = "btc_rpc_url")]
+    /// Relay the Bitcoin blocks from the local block files instead of the Bitcoin RPC, for importing the history offline.
+    /// The dir contains the `blk*.dat` files of bitcoind or the raw serialized `*.block` files.
+    pub btc_block_dir: Option<PathBuf>,
+
     /// The address of the sequencer account
     #[clap(long)]
     pub sequencer_account: Option<String>,
     /// The address of the proposer account
     #[clap(long)]
     pub proposer_account: Option<String>,
+    /// Set the proposer account as the proposer of the on-chain SCC on startup, if it is not yet.
+    /// The transaction is signed by the sequencer, the proposer account must hold gas coins to submit the block commitments.
+    #[clap(long)]
+    pub set_scc_proposer: bool,
 
     #[clap(long, default_value_t)]
     pub da: DAConfig,
@@ -159,13 +204,22 @@ impl RoochOpt {
             store: StoreConfig::default(),
             port: None,
             eth_rpc_url: None,
+            eth_start_block_height: None,
+            eth_end_block_height: None,
+            eth_relay_batch_size: None,
+            eth_relayer_failure_policy: None,
             btc_rpc_url: None,
             btc_rpc_username: None,
             btc_rpc_password: None,
             btc_end_block_height: None,
             btc_sync_block_interval: None,
+            btc_mempool_preview: false,
+            btc_mempool_poll_interval: None,
+            btc_relayer_failure_policy: None,
+            btc_block_dir: None,
             sequencer_account: None,
             proposer_account: None,
+            set_scc_proposer: false,
             da: DAConfig::default(),
             data_import_flag: false,
             base: None,
@@ -205,6 +259,10 @@ impl RoochOpt {
             .as_ref()
             .map(|eth_rpc_url| EthereumRelayerConfig {
                 eth_rpc_url: eth_rpc_url.clone(),
+                eth_start_block_height: self.eth_start_block_height,
+                eth_end_block_height: self.eth_end_block_height,
+                eth_relay_batch_size: self.eth_relay_batch_size,
+                failure_policy: self.eth_relayer_failure_policy.unwrap_or_default(),
             })
     }
 
@@ -216,9 +274,22 @@ impl RoochOpt {
             btc_rpc_password: self.btc_rpc_password.clone().unwrap(),
             btc_end_block_height: self.btc_end_block_height,
             btc_sync_block_interval: self.btc_sync_block_interval,
+            btc_mempool_preview: self.btc_mempool_preview,
+            btc_mempool_poll_interval: self.btc_mempool_poll_interval,
+            failure_policy: self.btc_relayer_failure_policy.unwrap_or_default(),
         })
     }
 
+    pub fn bitcoin_block_file_relayer_config(&self) -> Option<BitcoinBlockFileRelayerConfig> {
+        self.btc_block_dir
+            .as_ref()
+            .map(|btc_block_dir| BitcoinBlockFileRelayerConfig {
+                btc_block_dir: btc_block_dir.clone(),
+                btc_end_block_height: self.btc_end_block_height,
+                failure_policy: self.btc_relayer_failure_policy.unwrap_or_default(),
+            })
+    }
+
     pub fn port(&self) -> u16 {
         self.port.unwrap_or(6767)
     }
@@ -270,6 +341,10 @@ impl RoochOpt {
 #[derive(Debug, Clone)]
 pub struct EthereumRelayerConfig {
     pub eth_rpc_url: String,
+    pub eth_start_block_height: Option<u64>,
+    pub eth_end_block_height: Option<u64>,
+    pub eth_relay_batch_size: Option<usize>,
+    pub failure_policy: RelayerFailurePolicy,
 }
 
 #[derive(Debug, Clone)]
@@ -279,6 +354,58 @@ pub struct BitcoinRelayerConfig {
     pub btc_rpc_password: String,
     pub btc_end_block_height: Option<u64>,
     pub btc_sync_block_interval: Option<u64>,
+    pub btc_mempool_preview: bool,
+    pub btc_mempool_poll_interval: Option<u64>,
+    pub failure_policy: RelayerFailurePolicy,
+}
+
+#[derive(Debug, Clone)]
+pub struct BitcoinBlockFileRelayerConfig {
+    pub btc_block_dir: PathBuf,
+    pub btc_end_block_height: Option<u64>,
+    pub failure_policy: RelayerFailurePolicy,
+}
+
+/// What the relayer does when a relayed L1 block or transaction fails to execute
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelayerFailurePolicy {
+    /// Log the error and continue relaying
+    #[default]
+    Continue,
+    /// Retry the block or transaction failed before it is sequenced up to the given times, then pause the relayer.
+    /// The sequenced block or transaction is never retried, the relayer is paused directly.
+    Retry(u32),
+    /// Pause the relayer until it is resumed by the `rooch_resumeRelayer` RPC or the node is restarted
+    Pause,
+}
+
+impl FromStr for RelayerFailurePolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "continue" => Ok(RelayerFailurePolicy::Continue),
+            "pause" => Ok(RelayerFailurePolicy::Pause),
+            policy => match policy.strip_prefix("retry:") {
+                Some(times) => Ok(RelayerFailurePolicy::Retry(times.parse()?)),
+                None => Err(anyhow::anyhow!(
+                    "Invalid relayer failure policy: {}, expect `continue`, `pause` or `retry:<times>`",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for RelayerFailurePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RelayerFailurePolicy::Continue => write!(f, "continue"),
+            RelayerFailurePolicy::Retry(times) => write!(f, "retry:{}", times),
+            RelayerFailurePolicy::Pause => write!(f, "pause"),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -354,3 +481,24 @@ impl ServerOpt {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relayer_failure_policy_from_str() {
+        for policy in [
+            RelayerFailurePolicy::Continue,
+            RelayerFailurePolicy::Retry(3),
+            RelayerFailurePolicy::Pause,
+        ] {
+            assert_eq!(
+                RelayerFailurePolicy::from_str(policy.to_string().as_str()).unwrap(),
+                policy
+            );
+        }
+        assert!(RelayerFailurePolicy::from_str("retry").is_err());
+        assert!(RelayerFailurePolicy::from_str("stop").is_err());
+    }
+}
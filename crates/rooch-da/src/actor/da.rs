@@ -1,7 +1,8 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::{Arc, RwLock};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
@@ -9,35 +10,242 @@ use coerce::actor::context::ActorContext;
 use coerce::actor::message::Handler;
 use coerce::actor::system::ActorSystem;
 use coerce::actor::{Actor, IntoActor};
-use futures::stream::FuturesUnordered;
-use futures::StreamExt;
+use prometheus::Registry;
 
-use rooch_config::da_config::{DAConfig, InternalDAServerConfigType};
+use rooch_config::da_config::{
+    DAConfig, DAServerCelestiaConfig, DAServerOpenDAConfig, InternalDAServerConfigType,
+};
+use rooch_store::da_store::DAStore;
+use rooch_store::RoochStore;
+use rooch_types::da::PendingDABatch;
 
-use crate::messages::{Batch, PutBatchInternalDAMessage};
+use crate::chunk::DABatchV0;
+use crate::messages::{
+    Batch, BatchQuery, FlushChunkInternalDAMessage, GetBatchInternalDAMessage, GetBatchMessage,
+    PutBatchInternalDAMessage, PutBatchMessage, PutBatchState, PutPolicy, RetryPendingBatches,
+    StreamBatchesMessage,
+};
+use crate::metrics::DAMetrics;
+use crate::segment_indexer::RocksDBSegmentIndexer;
 use crate::server::celestia::actor::server::DAServerCelestiaActor;
 use crate::server::celestia::proxy::DAServerCelestiaProxy;
 use crate::server::openda::actor::server::DAServerOpenDAActor;
 use crate::server::openda::proxy::DAServerOpenDAProxy;
 use crate::server::serverproxy::DAServerProxy;
 
+// backoff of a DA server after continuous submit failures: 1s, 2s, 4s ... 60s
+const SUBMIT_BACKOFF_BASE: Duration = Duration::from_secs(1);
+const SUBMIT_BACKOFF_MAX: Duration = Duration::from_secs(60);
+
 // TODO tx buffer for building batch
+/// DAActor persists batches into the outbox of rooch store before submitting,
+/// so a batch would not be lost from DA if a server fails: it is retried with backoff
+/// until all the servers confirm it is durably written.
 pub struct DAActor {
-    internal_servers: InternalServers,
+    backends: Vec<DABackend>,
+    submit_threshold: usize,
+    rooch_store: RoochStore,
+    metrics: DAMetrics,
 }
 
-struct InternalServers {
-    servers: Arc<RwLock<Vec<Arc<dyn DAServerProxy + Send + Sync>>>>,
-    submit_threshold: usize,
+// DA server with its submission progress
+struct DABackend {
+    // the stable id of the server, it's the key of the persisted progress
+    name: String,
+    server: Arc<dyn DAServerProxy + Send + Sync>,
+    // the last block number accepted by the server, it may be only buffered in the server's memory.
+    // batches are submitted in block number order.
+    accepted: Option<u128>,
+    // the last block number durably written by the server
+    confirmed: Option<u128>,
+    // continuous submit failures
+    failures: u32,
+    next_retry_at: Option<Instant>,
+}
+
+impl DABackend {
+    fn new(
+        name: String,
+        server: Arc<dyn DAServerProxy + Send + Sync>,
+        rooch_store: &RoochStore,
+    ) -> Result<Self> {
+        // the batches buffered by the server before restart are lost, submit them again from the outbox
+        // to rebuild the server's buffer
+        let confirmed = rooch_store.get_da_backend_confirmed(name.clone())?;
+        Ok(Self {
+            name,
+            server,
+            accepted: confirmed,
+            confirmed,
+            failures: 0,
+            next_retry_at: None,
+        })
+    }
+
+    fn is_accepted(&self, block_number: u128) -> bool {
+        self.accepted
+            .map_or(false, |accepted| accepted >= block_number)
+    }
+
+    fn is_confirmed(&self, block_number: u128) -> bool {
+        self.confirmed
+            .map_or(false, |confirmed| confirmed >= block_number)
+    }
+
+    fn is_backing_off(&self) -> bool {
+        self.next_retry_at
+            .map_or(false, |next_retry_at| Instant::now() < next_retry_at)
+    }
+
+    fn on_failure(&mut self) -> Duration {
+        self.failures += 1;
+        let backoff = SUBMIT_BACKOFF_BASE
+            .saturating_mul(2u32.saturating_pow(self.failures - 1))
+            .min(SUBMIT_BACKOFF_MAX);
+        self.next_retry_at = Some(Instant::now() + backoff);
+        backoff
+    }
+
+    // submit the pending batches which are not accepted by the server in order,
+    // stop at the first failure to keep the order.
+    async fn submit_pending(
+        &mut self,
+        pending: &[PendingDABatch],
+        rooch_store: &RoochStore,
+        metrics: &DAMetrics,
+    ) {
+        if self.is_backing_off() {
+            return;
+        }
+        for batch in pending {
+            if self.is_accepted(batch.block_number) {
+                continue;
+            }
+            let result = self
+                .server
+                .public_batch(PutBatchInternalDAMessage {
+                    batch: Batch {
+                        block_number: batch.block_number,
+                        batch_hash: batch.batch_hash,
+                        data: batch.data.clone(),
+                    },
+                })
+                .await
+                .and_then(|state| {
+                    if state == PutBatchState::Written {
+                        rooch_store
+                            .save_da_backend_confirmed(self.name.clone(), batch.block_number)?;
+                    }
+                    Ok(state)
+                });
+            match result {
+                Ok(state) => {
+                    self.accepted = Some(batch.block_number);
+                    if state == PutBatchState::Written {
+                        self.confirmed = Some(batch.block_number);
+                    }
+                    self.failures = 0;
+                    self.next_retry_at = None;
+                }
+                Err(e) => {
+                    metrics
+                        .backend_submit_failures
+                        .with_label_values(&[self.name.as_str()])
+                        .inc();
+                    let backoff = self.on_failure();
+                    log::warn!(
+                        "{:?}, fail to submit batch {} to da server {}, retry after {:?}.",
+                        e,
+                        batch.block_number,
+                        self.name,
+                        backoff
+                    );
+                    return;
+                }
+            }
+        }
+        // the accepted but not written batches are written by the server once they are buffered too long
+        if self.accepted > self.confirmed {
+            self.flush_chunk(rooch_store, metrics).await;
+        }
+    }
+
+    async fn flush_chunk(&mut self, rooch_store: &RoochStore, metrics: &DAMetrics) {
+        let result = self
+            .server
+            .flush_chunk(FlushChunkInternalDAMessage {})
+            .await
+            .and_then(|written| {
+                if let Some(block_number) = written {
+                    rooch_store.save_da_backend_confirmed(self.name.clone(), block_number)?;
+                }
+                Ok(written)
+            });
+        match result {
+            Ok(Some(block_number)) => {
+                self.confirmed = Some(block_number);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                metrics
+                    .backend_submit_failures
+                    .with_label_values(&[self.name.as_str()])
+                    .inc();
+                let backoff = self.on_failure();
+                log::warn!(
+                    "{:?}, fail to flush buffered batches to da server {}, retry after {:?}.",
+                    e,
+                    self.name,
+                    backoff
+                );
+            }
+        }
+    }
+}
+
+// The id of a DA server is derived from where the batches are stored rather than the config order,
+// so the persisted progress and segment index still match the server if the config is reordered.
+fn celestia_backend_id(config: &DAServerCelestiaConfig) -> String {
+    format!(
+        "DAServerCelestia-{}",
+        config.namespace.clone().unwrap_or_default()
+    )
+}
+
+fn openda_backend_id(config: &DAServerOpenDAConfig) -> String {
+    let location = ["endpoint", "bucket", "root"]
+        .iter()
+        .filter_map(|key| config.config.get(*key).cloned())
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("DAServerOpenDA-{}-{}", config.scheme, location)
+}
+
+fn check_unique_backend_id(
+    servers: &[(String, Arc<dyn DAServerProxy + Send + Sync>)],
+    id: &str,
+) -> Result<()> {
+    if servers.iter().any(|(other, _)| other == id) {
+        return Err(anyhow!(
+            "duplicate da server: {}, servers must not share the same storage",
+            id
+        ));
+    }
+    Ok(())
 }
 
 impl Actor for DAActor {}
 
 impl DAActor {
-    pub async fn new(da_config: DAConfig, actor_system: &ActorSystem) -> Result<Self> {
+    pub async fn new(
+        da_config: DAConfig,
+        actor_system: &ActorSystem,
+        rooch_store: RoochStore,
+        registry: &Registry,
+    ) -> Result<Self> {
         // internal servers
 
-        let mut servers: Vec<Arc<dyn DAServerProxy + Send + Sync>> = Vec::new();
+        let mut servers: Vec<(String, Arc<dyn DAServerProxy + Send + Sync>)> = Vec::new();
         let mut submit_threshold = 1;
         let mut success_count = 0;
 
@@ -48,29 +256,45 @@ impl DAActor {
 
             for server_config_type in &server_config.servers {
                 if let InternalDAServerConfigType::Celestia(celestia_config) = server_config_type {
-                    let da_server = DAServerCelestiaActor::new(celestia_config)
+                    let name = celestia_backend_id(celestia_config);
+                    check_unique_backend_id(&servers, &name)?;
+                    let indexer = Arc::new(RocksDBSegmentIndexer::new(
+                        name.clone(),
+                        rooch_store.clone(),
+                    ));
+                    let da_server = DAServerCelestiaActor::new(celestia_config, indexer)
                         .await
-                        .into_actor(Some("DAServerCelestia"), actor_system)
+                        .into_actor(Some(name.clone()), actor_system)
                         .await?;
-                    servers.push(Arc::new(DAServerCelestiaProxy::new(
-                        da_server.clone().into(),
-                    )));
+                    servers.push((
+                        name,
+                        Arc::new(DAServerCelestiaProxy::new(da_server.clone().into())),
+                    ));
                     success_count += 1;
                 }
                 if let InternalDAServerConfigType::OpenDa(openda_config) = server_config_type {
-                    let da_server = DAServerOpenDAActor::new(openda_config)
+                    let name = openda_backend_id(openda_config);
+                    check_unique_backend_id(&servers, &name)?;
+                    let indexer = Arc::new(RocksDBSegmentIndexer::new(
+                        name.clone(),
+                        rooch_store.clone(),
+                    ));
+                    let da_server = DAServerOpenDAActor::new(openda_config, indexer)
                         .await?
-                        .into_actor(
-                            Some(format!("DAServerOpenDA-{}", openda_config.scheme)),
-                            actor_system,
-                        )
+                        .into_actor(Some(name.clone()), actor_system)
                         .await?;
-                    servers.push(Arc::new(DAServerOpenDAProxy::new(da_server.clone().into())));
+                    servers.push((
+                        name,
+                        Arc::new(DAServerOpenDAProxy::new(da_server.clone().into())),
+                    ));
                     success_count += 1;
                 }
             }
         } else {
-            servers.push(Arc::new(crate::server::serverproxy::DAServerNopProxy {}));
+            servers.push((
+                "DAServerNop".to_string(),
+                Arc::new(crate::server::serverproxy::DAServerNopProxy {}),
+            ));
             success_count += 1;
         }
 
@@ -82,65 +306,535 @@ impl DAActor {
             ));
         }
 
+        let backends = servers
+            .into_iter()
+            .map(|(name, server)| DABackend::new(name, server, &rooch_store))
+            .collect::<Result<Vec<_>>>()?;
+
         Ok(Self {
-            internal_servers: InternalServers {
-                servers: Arc::new(RwLock::new(servers)),
-                submit_threshold,
-            },
+            backends,
+            submit_threshold,
+            rooch_store,
+            metrics: DAMetrics::new(registry),
         })
     }
 
-    pub async fn submit_batch(&self, batch: Batch) -> Result<()> {
+    /// Persist the batch into outbox, then submit it (and the earlier pending batches) to servers.
+    /// Ok if the batch is accepted by enough servers according to the put policy,
+    /// it's kept in the outbox until all the servers have durably written it.
+    pub async fn submit_batch(&mut self, batch: Batch, policy: Option<PutPolicy>) -> Result<()> {
         // TODO calc checksum
-        // TODO richer policy for multi servers
         // TODO verify checksum
-        // TODO retry policy & log
-
-        let servers = self.internal_servers.servers.read().unwrap().to_vec();
-        let submit_threshold = self.internal_servers.submit_threshold;
-
-        let mut futures_unordered = FuturesUnordered::new();
-        for server in servers {
-            let server = Arc::clone(&server);
-            let batch = batch.clone();
-            futures_unordered.push(async move {
-                server
-                    .public_batch(PutBatchInternalDAMessage {
-                        batch: batch.clone(),
-                    })
-                    .await
-            });
+
+        let submit_threshold = match policy {
+            Some(policy) => policy.threshold(self.backends.len()),
+            None => self.submit_threshold,
+        };
+        let block_number = batch.block_number;
+        self.rooch_store.save_pending_da_batch(PendingDABatch::new(
+            block_number,
+            batch.batch_hash,
+            batch.data,
+        ))?;
+
+        self.submit_pending_batches().await?;
+
+        let success_count = self
+            .backends
+            .iter()
+            .filter(|backend| backend.is_accepted(block_number))
+            .count();
+        if success_count >= submit_threshold {
+            return Ok(());
         }
+        if success_count == 0 {
+            // no server has the batch, the block could be proposed again
+            self.rooch_store
+                .remove_pending_da_batches(vec![block_number])?;
+            return Err(anyhow::Error::msg(format!(
+                "not enough successful submissions. exp>= {} act: {}",
+                submit_threshold, success_count
+            )));
+        }
+        // the batch is public in some servers, it can't be withdrawn:
+        // the others will get it by retry.
+        log::warn!(
+            "batch {} is only accepted by {} da servers, exp>= {}, the others will be retried.",
+            block_number,
+            success_count,
+            submit_threshold
+        );
+        Ok(())
+    }
 
-        let mut success_count = 0;
-        while let Some(result) = futures_unordered.next().await {
-            match result {
-                Ok(_) => {
-                    success_count += 1;
-                    if success_count >= submit_threshold {
-                        return Ok(());
-                    }
-                }
+    /// Submit the pending batches in outbox to the servers which have not accepted them,
+    /// then clean up the batches durably written by all the servers.
+    pub async fn submit_pending_batches(&mut self) -> Result<()> {
+        let pending = self.rooch_store.get_pending_da_batches()?;
+        let latest_block_number = match pending.last() {
+            Some(batch) => batch.block_number,
+            None => return Ok(()),
+        };
+
+        let rooch_store = &self.rooch_store;
+        let metrics = &self.metrics;
+        futures::future::join_all(
+            self.backends
+                .iter_mut()
+                .map(|backend| backend.submit_pending(&pending, rooch_store, metrics)),
+        )
+        .await;
+
+        for backend in &self.backends {
+            let lag = pending
+                .iter()
+                .filter(|batch| !backend.is_confirmed(batch.block_number))
+                .count();
+            self.metrics
+                .backend_lag
+                .with_label_values(&[backend.name.as_str()])
+                .set(lag as i64);
+            if let Some(confirmed) = backend.confirmed {
+                self.metrics
+                    .backend_confirmed_block_number
+                    .with_label_values(&[backend.name.as_str()])
+                    .set(confirmed as i64);
+            }
+        }
+
+        let confirmed_by_all = pending
+            .iter()
+            .map(|batch| batch.block_number)
+            .filter(|block_number| {
+                self.backends
+                    .iter()
+                    .all(|backend| backend.is_confirmed(*block_number))
+            })
+            .collect::<Vec<_>>();
+        if !confirmed_by_all.is_empty() {
+            self.rooch_store
+                .remove_pending_da_batches(confirmed_by_all)?;
+        }
+        log::debug!(
+            "[DA] pending batches: {}, latest block number: {}",
+            pending.len(),
+            latest_block_number
+        );
+        Ok(())
+    }
+}
+
+impl DAActor {
+    /// Get batch from servers in order, return the first found one.
+    /// A server which fails to return a valid batch is skipped, so are the write-only servers.
+    pub async fn get_batch(&self, query: BatchQuery) -> Result<Option<DABatchV0>> {
+        if !self
+            .backends
+            .iter()
+            .any(|backend| backend.server.is_readable())
+        {
+            return Err(anyhow!(
+                "get batch {:?} is unsupported, all the DA servers are write-only",
+                query
+            ));
+        }
+        let mut last_err = None;
+        for backend in self
+            .backends
+            .iter()
+            .filter(|backend| backend.server.is_readable())
+        {
+            match backend
+                .server
+                .get_batch(GetBatchInternalDAMessage {
+                    query: query.clone(),
+                })
+                .await
+            {
+                Ok(Some(batch)) => return Ok(Some(batch)),
+                Ok(None) => {}
                 Err(e) => {
-                    log::warn!("{:?}, fail to submit batch to da server.", e); // TODO add da server name
+                    log::warn!("{:?}, fail to get batch {:?} from da server.", e, query);
+                    last_err = Some(e);
                 }
             }
         }
+        match last_err {
+            Some(e) => Err(e),
+            None => Ok(None),
+        }
+    }
 
-        if success_count < submit_threshold {
-            Err(anyhow::Error::msg(format!(
-                "not enough successful submissions. exp>= {} act: {}",
-                submit_threshold, success_count
-            )))
-        } else {
-            Ok(())
+    pub async fn stream_batches(
+        &self,
+        start_block_number: u128,
+        max_count: u64,
+    ) -> Result<Vec<DABatchV0>> {
+        let mut batches = Vec::new();
+        let mut block_number = start_block_number;
+        while (batches.len() as u64) < max_count {
+            match self
+                .get_batch(BatchQuery::BlockNumber(block_number))
+                .await?
+            {
+                Some(batch) => batches.push(batch),
+                None => break,
+            }
+            block_number += 1;
+        }
+        Ok(batches)
+    }
+}
+
+#[async_trait]
+impl Handler<PutBatchMessage> for DAActor {
+    async fn handle(&mut self, msg: PutBatchMessage, _ctx: &mut ActorContext) -> Result<()> {
+        self.submit_batch(msg.batch, msg.policy).await
+    }
+}
+
+#[async_trait]
+impl Handler<RetryPendingBatches> for DAActor {
+    async fn handle(&mut self, _msg: RetryPendingBatches, _ctx: &mut ActorContext) {
+        if let Err(e) = self.submit_pending_batches().await {
+            log::error!("[DA] retry pending batches failed: {:?}", e);
         }
     }
 }
 
 #[async_trait]
-impl Handler<Batch> for DAActor {
-    async fn handle(&mut self, msg: Batch, _ctx: &mut ActorContext) -> Result<()> {
-        self.submit_batch(msg).await
+impl Handler<GetBatchMessage> for DAActor {
+    async fn handle(
+        &mut self,
+        msg: GetBatchMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<DABatchV0>> {
+        self.get_batch(msg.query).await
+    }
+}
+
+#[async_trait]
+impl Handler<StreamBatchesMessage> for DAActor {
+    async fn handle(
+        &mut self,
+        msg: StreamBatchesMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Vec<DABatchV0>> {
+        self.stream_batches(msg.start_block_number, msg.max_count)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use moveos_types::h256;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    // MockDAServer writes the buffered batches every `chunk_batches` batches,
+    // or when they are flushed after `chunk_expired` is set
+    struct MockDAServer {
+        chunk_batches: usize,
+        chunk_expired: AtomicBool,
+        fail: AtomicBool,
+        readable: AtomicBool,
+        put_count: AtomicUsize,
+        buffered: Mutex<Vec<u128>>,
+        written: Mutex<Vec<u128>>,
+    }
+
+    impl MockDAServer {
+        fn new(chunk_batches: usize) -> Arc<Self> {
+            Arc::new(Self {
+                chunk_batches,
+                chunk_expired: AtomicBool::new(false),
+                fail: AtomicBool::new(false),
+                readable: AtomicBool::new(true),
+                put_count: AtomicUsize::new(0),
+                buffered: Mutex::new(Vec::new()),
+                written: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn set_fail(&self, fail: bool) {
+            self.fail.store(fail, Ordering::SeqCst);
+        }
+
+        fn put_count(&self) -> usize {
+            self.put_count.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl DAServerProxy for MockDAServer {
+        async fn public_batch(&self, request: PutBatchInternalDAMessage) -> Result<PutBatchState> {
+            self.put_count.fetch_add(1, Ordering::SeqCst);
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(anyhow!("mock da server failure"));
+            }
+            let mut buffered = self.buffered.lock().unwrap();
+            if !buffered.contains(&request.batch.block_number) {
+                buffered.push(request.batch.block_number);
+            }
+            if buffered.len() < self.chunk_batches {
+                return Ok(PutBatchState::Buffered);
+            }
+            self.written.lock().unwrap().append(&mut buffered);
+            Ok(PutBatchState::Written)
+        }
+
+        async fn get_batch(
+            &self,
+            _request: GetBatchInternalDAMessage,
+        ) -> Result<Option<DABatchV0>> {
+            if !self.is_readable() {
+                return Err(anyhow!("mock da server is write-only"));
+            }
+            Ok(None)
+        }
+
+        fn is_readable(&self) -> bool {
+            self.readable.load(Ordering::SeqCst)
+        }
+
+        async fn flush_chunk(&self, _request: FlushChunkInternalDAMessage) -> Result<Option<u128>> {
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(anyhow!("mock da server failure"));
+            }
+            let mut buffered = self.buffered.lock().unwrap();
+            if !self.chunk_expired.load(Ordering::SeqCst) || buffered.is_empty() {
+                return Ok(None);
+            }
+            let last = buffered.last().copied();
+            self.written.lock().unwrap().append(&mut buffered);
+            Ok(last)
+        }
+    }
+
+    fn new_da_actor(
+        servers: Vec<Arc<MockDAServer>>,
+        submit_threshold: usize,
+        rooch_store: &RoochStore,
+    ) -> DAActor {
+        let backends = servers
+            .into_iter()
+            .enumerate()
+            .map(|(i, server)| {
+                DABackend::new(format!("MockDAServer-{}", i), server, rooch_store).unwrap()
+            })
+            .collect();
+        DAActor {
+            backends,
+            submit_threshold,
+            rooch_store: rooch_store.clone(),
+            metrics: DAMetrics::new(&Registry::new()),
+        }
+    }
+
+    fn new_batch(block_number: u128) -> Batch {
+        let data = vec![block_number as u8; 10];
+        Batch {
+            block_number,
+            batch_hash: h256::sha3_256_of(&data),
+            data,
+        }
+    }
+
+    fn pending_block_numbers(rooch_store: &RoochStore) -> Vec<u128> {
+        rooch_store
+            .get_pending_da_batches()
+            .unwrap()
+            .into_iter()
+            .map(|batch| batch.block_number)
+            .collect()
+    }
+
+    #[test]
+    fn test_put_policy_threshold() {
+        assert_eq!(PutPolicy::All.threshold(3), 3);
+        assert_eq!(PutPolicy::Quorum.threshold(3), 2);
+        assert_eq!(PutPolicy::Quorum.threshold(4), 3);
+        assert_eq!(PutPolicy::AnyOne.threshold(3), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_policy() {
+        let (rooch_store, _tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let servers = vec![MockDAServer::new(1), MockDAServer::new(1)];
+        let mut da = new_da_actor(servers.clone(), 2, &rooch_store);
+
+        // no server accepts the batch, it's withdrawn from the outbox
+        servers.iter().for_each(|server| server.set_fail(true));
+        assert!(da
+            .submit_batch(new_batch(0), Some(PutPolicy::AnyOne))
+            .await
+            .is_err());
+        assert!(pending_block_numbers(&rooch_store).is_empty());
+
+        // the batch is accepted by one server, it's kept in the outbox for the other one
+        servers[0].set_fail(false);
+        da.backends.iter_mut().for_each(|backend| {
+            backend.next_retry_at = None;
+        });
+        da.submit_batch(new_batch(0), Some(PutPolicy::All))
+            .await
+            .unwrap();
+        assert!(da.backends[0].is_confirmed(0));
+        assert!(!da.backends[1].is_accepted(0));
+        assert_eq!(pending_block_numbers(&rooch_store), vec![0]);
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff() {
+        let (rooch_store, _tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let servers = vec![MockDAServer::new(1), MockDAServer::new(1)];
+        let mut da = new_da_actor(servers.clone(), 1, &rooch_store);
+
+        servers[1].set_fail(true);
+        da.submit_batch(new_batch(0), None).await.unwrap();
+        assert_eq!(servers[1].put_count(), 1);
+        assert_eq!(da.backends[1].failures, 1);
+        assert!(da.backends[1].is_backing_off());
+
+        // the failed server is skipped until the backoff expires
+        da.submit_batch(new_batch(1), None).await.unwrap();
+        da.submit_pending_batches().await.unwrap();
+        assert_eq!(servers[1].put_count(), 1);
+        assert_eq!(pending_block_numbers(&rooch_store), vec![0, 1]);
+
+        // the pending batches are retried in order after the backoff, then cleaned up
+        servers[1].set_fail(false);
+        da.backends[1].next_retry_at = Some(Instant::now());
+        da.submit_pending_batches().await.unwrap();
+        assert_eq!(*servers[1].written.lock().unwrap(), vec![0, 1]);
+        assert_eq!(da.backends[1].failures, 0);
+        assert!(pending_block_numbers(&rooch_store).is_empty());
+        assert_eq!(
+            rooch_store
+                .get_da_backend_confirmed("MockDAServer-1".to_string())
+                .unwrap(),
+            Some(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_batch_skips_write_only_server() {
+        let (rooch_store, _tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let servers = vec![MockDAServer::new(1), MockDAServer::new(1)];
+        let da = new_da_actor(servers.clone(), 1, &rooch_store);
+
+        // the write-only server is skipped, the batch is not found in the readable one
+        servers[1].readable.store(false, Ordering::SeqCst);
+        assert_eq!(
+            da.get_batch(BatchQuery::BlockNumber(0)).await.unwrap(),
+            None
+        );
+
+        servers[0].readable.store(false, Ordering::SeqCst);
+        let err = da.get_batch(BatchQuery::BlockNumber(0)).await.unwrap_err();
+        assert!(err.to_string().contains("write-only"));
+    }
+
+    #[test]
+    fn test_backoff_is_exponential_and_bounded() {
+        let (rooch_store, _tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let mut backend = DABackend::new(
+            "MockDAServer".to_string(),
+            MockDAServer::new(1),
+            &rooch_store,
+        )
+        .unwrap();
+        let backoffs = (0..8).map(|_| backend.on_failure()).collect::<Vec<_>>();
+        assert_eq!(backoffs[0], SUBMIT_BACKOFF_BASE);
+        assert_eq!(backoffs[1], SUBMIT_BACKOFF_BASE * 2);
+        assert_eq!(backoffs[2], SUBMIT_BACKOFF_BASE * 4);
+        assert_eq!(backoffs[7], SUBMIT_BACKOFF_MAX);
+    }
+
+    #[tokio::test]
+    async fn test_buffered_batch_kept_in_outbox() {
+        let (rooch_store, _tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let server = MockDAServer::new(2);
+        let mut da = new_da_actor(vec![server.clone()], 1, &rooch_store);
+
+        // the batch is only buffered by the server, it's not confirmed
+        da.submit_batch(new_batch(0), None).await.unwrap();
+        assert!(da.backends[0].is_accepted(0));
+        assert!(!da.backends[0].is_confirmed(0));
+        assert_eq!(pending_block_numbers(&rooch_store), vec![0]);
+        assert_eq!(
+            rooch_store
+                .get_da_backend_confirmed("MockDAServer-0".to_string())
+                .unwrap(),
+            None
+        );
+
+        // the buffered batch is submitted again after restart
+        let server = MockDAServer::new(2);
+        let mut da = new_da_actor(vec![server.clone()], 1, &rooch_store);
+        da.submit_batch(new_batch(1), None).await.unwrap();
+        assert_eq!(*server.written.lock().unwrap(), vec![0, 1]);
+        assert!(pending_block_numbers(&rooch_store).is_empty());
+        assert_eq!(
+            rooch_store
+                .get_da_backend_confirmed("MockDAServer-0".to_string())
+                .unwrap(),
+            Some(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flush_expired_chunk() {
+        let (rooch_store, _tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let server = MockDAServer::new(3);
+        let mut da = new_da_actor(vec![server.clone()], 1, &rooch_store);
+
+        da.submit_batch(new_batch(0), None).await.unwrap();
+        da.submit_batch(new_batch(1), None).await.unwrap();
+        da.submit_pending_batches().await.unwrap();
+        assert!(server.written.lock().unwrap().is_empty());
+        assert_eq!(pending_block_numbers(&rooch_store), vec![0, 1]);
+
+        // no more block comes, the retry tick writes the buffered batches once they are too old
+        server.chunk_expired.store(true, Ordering::SeqCst);
+        da.submit_pending_batches().await.unwrap();
+        assert_eq!(*server.written.lock().unwrap(), vec![0, 1]);
+        assert!(da.backends[0].is_confirmed(1));
+        assert!(pending_block_numbers(&rooch_store).is_empty());
+        assert_eq!(
+            rooch_store
+                .get_da_backend_confirmed("MockDAServer-0".to_string())
+                .unwrap(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_backend_id() {
+        let fs_config = |root: &str| DAServerOpenDAConfig {
+            config: HashMap::from([("root".to_string(), root.to_string())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            openda_backend_id(&fs_config("/data/da")),
+            "DAServerOpenDA-fs-/data/da"
+        );
+        assert_ne!(
+            openda_backend_id(&fs_config("/data/da")),
+            openda_backend_id(&fs_config("/data/da2"))
+        );
+
+        let servers: Vec<(String, Arc<dyn DAServerProxy + Send + Sync>)> = vec![(
+            openda_backend_id(&fs_config("/data/da")),
+            MockDAServer::new(1) as Arc<dyn DAServerProxy + Send + Sync>,
+        )];
+        assert!(
+            check_unique_backend_id(&servers, &openda_backend_id(&fs_config("/data/da"))).is_err()
+        );
+        assert!(
+            check_unique_backend_id(&servers, &openda_backend_id(&fs_config("/data/da2"))).is_ok()
+        );
     }
 }
@@ -1,11 +1,21 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::fec::{fec_decode, fec_encode};
+use crate::messages::BatchQuery;
+use crate::segment::{
+    segment_from_bytes, Segment, SegmentID, SegmentV1, SegmentV2, SegmentVersion,
+};
+use anyhow::anyhow;
+use moveos_types::h256;
 use moveos_types::h256::H256;
-use serde::Serialize;
+use rooch_config::da_config::DAChunkCompression;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::time::{Duration, Instant};
 
 // DABatchV0 is the first version of the batch inside a chunk, each batch is a chunk
-#[derive(Serialize, Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct DABatchV0 {
     pub version: u8,
     // each batch maps to a L2 block
@@ -15,3 +25,751 @@ pub struct DABatchV0 {
     // encoded tx list
     pub data: Vec<u8>,
 }
+
+impl DABatchV0 {
+    pub fn new(block_number: u128, batch_hash: H256, data: Vec<u8>) -> Self {
+        Self {
+            version: 0,
+            block_number,
+            batch_hash,
+            data,
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let batch: DABatchV0 = bcs::from_bytes(bytes)?;
+        batch.verify()?;
+        Ok(batch)
+    }
+
+    /// Check the batch_hash is the sha3_256 hash of the batch data
+    pub fn verify(&self) -> anyhow::Result<()> {
+        let exp_batch_hash = h256::sha3_256_of(&self.data);
+        if exp_batch_hash != self.batch_hash {
+            return Err(anyhow!(
+                "da_batch_v0: batch hash mismatch, block_number: {}, exp: {:?}, act: {:?}",
+                self.block_number,
+                exp_batch_hash,
+                self.batch_hash
+            ));
+        }
+        Ok(())
+    }
+}
+
+// ChunkV1 packs consecutive batches into one chunk, the encoded batch list is compressed.
+// chunk_id is the block number of the first batch.
+// bytes: codec(u8) | compressed bcs(Vec<DABatchV0>)
+#[derive(Debug, PartialEq, Clone)]
+pub struct ChunkV1 {
+    pub compression: DAChunkCompression,
+    pub batches: Vec<DABatchV0>,
+}
+
+impl ChunkV1 {
+    pub fn new(compression: DAChunkCompression, batches: Vec<DABatchV0>) -> anyhow::Result<Self> {
+        let chunk = Self {
+            compression,
+            batches,
+        };
+        chunk.check_batches()?;
+        Ok(chunk)
+    }
+
+    pub fn chunk_id(&self) -> u128 {
+        self.batches[0].block_number
+    }
+
+    pub fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        let raw = bcs::to_bytes(&self.batches)?;
+        // the readers refuse to decompress a larger chunk
+        check_raw_size(raw.len(), MAX_CHUNK_RAW_SIZE)?;
+        let compressed = compress(self.compression, &raw)?;
+        let mut bytes = Vec::with_capacity(1 + compressed.len());
+        bytes.push(compression_to_u8(self.compression));
+        bytes.extend_from_slice(&compressed);
+        Ok(bytes)
+    }
+
+    /// Decode the chunk, batches hash and continuity are verified
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        if bytes.is_empty() {
+            return Err(anyhow!("chunk_v1: empty bytes"));
+        }
+        let compression = compression_from_u8(bytes[0])?;
+        let raw = decompress(compression, &bytes[1..], MAX_CHUNK_RAW_SIZE)?;
+        let batches: Vec<DABatchV0> = bcs::from_bytes(&raw)?;
+        for batch in batches.iter() {
+            batch.verify()?;
+        }
+        Self::new(compression, batches)
+    }
+
+    /// Split the chunk into segments, each segment data is no more than max_segment_size
+    pub fn to_segments(&self, max_segment_size: usize) -> anyhow::Result<Vec<SegmentV1>> {
+        let chunk_id = self.chunk_id();
+        let chunk_bytes = self.to_bytes()?;
+        let segs = chunk_bytes.chunks(max_segment_size);
+        let total = segs.len();
+        Ok(segs
+            .enumerate()
+            .map(|(i, data)| SegmentV1 {
+                id: SegmentID {
+                    chunk_id,
+                    segment_number: i as u64,
+                },
+                is_last: i == total - 1,
+                data_checksum: 0,
+                checksum: 0,
+                data: data.to_vec(),
+            })
+            .collect())
+    }
+
+    /// Split the chunk into k data segments and m Reed-Solomon parity segments,
+    /// the chunk could be rebuilt from any k of them.
+    pub fn to_fec_segments(
+        &self,
+        max_segment_size: usize,
+        parity_segments: usize,
+    ) -> anyhow::Result<Vec<SegmentV2>> {
+        let chunk_id = self.chunk_id();
+        let chunk_bytes = self.to_bytes()?;
+        let (data_segments, shards) = fec_encode(&chunk_bytes, max_segment_size, parity_segments)?;
+        let total = shards.len();
+        Ok(shards
+            .into_iter()
+            .enumerate()
+            .map(|(i, data)| SegmentV2 {
+                id: SegmentID {
+                    chunk_id,
+                    segment_number: i as u64,
+                },
+                is_last: i == total - 1,
+                data_segments: data_segments as u16,
+                parity_segments: parity_segments as u16,
+                chunk_len: chunk_bytes.len() as u64,
+                data_checksum: 0,
+                checksum: 0,
+                data,
+            })
+            .collect())
+    }
+
+    /// Split the chunk into segments, FEC segments are made if fec_parity_segments > 0
+    pub fn make_segments(
+        &self,
+        max_segment_size: usize,
+        fec_parity_segments: usize,
+    ) -> anyhow::Result<Vec<Box<dyn Segment + Send>>> {
+        if fec_parity_segments > 0 {
+            Ok(self
+                .to_fec_segments(max_segment_size, fec_parity_segments)?
+                .into_iter()
+                .map(|segment| Box::new(segment) as Box<dyn Segment + Send>)
+                .collect())
+        } else {
+            Ok(self
+                .to_segments(max_segment_size)?
+                .into_iter()
+                .map(|segment| Box::new(segment) as Box<dyn Segment + Send>)
+                .collect())
+        }
+    }
+
+    fn check_batches(&self) -> anyhow::Result<()> {
+        if self.batches.is_empty() {
+            return Err(anyhow!("chunk_v1: no batch in chunk"));
+        }
+        for (i, batch) in self.batches.iter().enumerate() {
+            let exp_block_number = self.batches[0].block_number + i as u128;
+            if batch.block_number != exp_block_number {
+                return Err(anyhow!(
+                    "chunk_v1: block number is not continuous, exp: {}, act: {}",
+                    exp_block_number,
+                    batch.block_number
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn compression_to_u8(compression: DAChunkCompression) -> u8 {
+    match compression {
+        DAChunkCompression::None => 0,
+        DAChunkCompression::Zstd => 1,
+        DAChunkCompression::Lz4 => 2,
+    }
+}
+
+fn compression_from_u8(num: u8) -> anyhow::Result<DAChunkCompression> {
+    match num {
+        0 => Ok(DAChunkCompression::None),
+        1 => Ok(DAChunkCompression::Zstd),
+        2 => Ok(DAChunkCompression::Lz4),
+        _ => Err(anyhow!("chunk: unknown compression codec: {}", num)),
+    }
+}
+
+fn compress(compression: DAChunkCompression, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    match compression {
+        DAChunkCompression::None => Ok(data.to_vec()),
+        DAChunkCompression::Zstd => Ok(zstd::encode_all(data, ZSTD_COMPRESSION_LEVEL)?),
+        DAChunkCompression::Lz4 => Ok(lz4::block::compress(data, None, true)?),
+    }
+}
+
+// The decompressed size is limited, so a malicious or corrupted chunk could not exhaust the memory of the readers.
+fn decompress(
+    compression: DAChunkCompression,
+    data: &[u8],
+    max_raw_size: usize,
+) -> anyhow::Result<Vec<u8>> {
+    match compression {
+        DAChunkCompression::None => {
+            check_raw_size(data.len(), max_raw_size)?;
+            Ok(data.to_vec())
+        }
+        DAChunkCompression::Zstd => {
+            let mut raw = Vec::new();
+            // read one more byte to know whether the limit is exceeded
+            zstd::stream::read::Decoder::new(data)?
+                .take(max_raw_size as u64 + 1)
+                .read_to_end(&mut raw)?;
+            check_raw_size(raw.len(), max_raw_size)?;
+            Ok(raw)
+        }
+        DAChunkCompression::Lz4 => {
+            // the raw size is prefixed as i32 little endian, it's checked before allocating the buffer
+            let size_prefix: [u8; 4] = data
+                .get(..4)
+                .and_then(|prefix| prefix.try_into().ok())
+                .ok_or_else(|| anyhow!("chunk: lz4 size prefix not found"))?;
+            let raw_size = i32::from_le_bytes(size_prefix);
+            if raw_size < 0 {
+                return Err(anyhow!("chunk: invalid lz4 raw size: {}", raw_size));
+            }
+            check_raw_size(raw_size as usize, max_raw_size)?;
+            Ok(lz4::block::decompress(data, None)?)
+        }
+    }
+}
+
+fn check_raw_size(raw_size: usize, max_raw_size: usize) -> anyhow::Result<()> {
+    if raw_size > max_raw_size {
+        return Err(anyhow!(
+            "chunk: raw size exceeds the limit, limit: {}, act: {}",
+            max_raw_size,
+            raw_size
+        ));
+    }
+    Ok(())
+}
+
+// zstd default level, good trade-off between ratio and speed
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+// the max number of batches in a chunk, the max_chunk_batches of the config is bounded by it
+pub const MAX_CHUNK_BATCHES: usize = 64;
+// the max encoded size of a batch
+pub const MAX_BATCH_SIZE: usize = 4 * 1024 * 1024;
+// the max size of the encoded batches of a chunk, it's the limit of decompressing a chunk
+pub const MAX_CHUNK_RAW_SIZE: usize = MAX_CHUNK_BATCHES * MAX_BATCH_SIZE;
+
+// the default max seconds a batch is buffered before its chunk is written even if it's not full
+pub const DEFAULT_MAX_CHUNK_AGE_SECS: u64 = 60;
+
+/// ChunkBuilder buffers consecutive batches and packs them into a ChunkV1 when the buffer is full,
+/// or when the first buffered batch is older than max_age(`build_expired`), so the last batches of a quiet chain are written too.
+/// The buffer is kept until the chunk is submitted(`clear`), so a failed chunk will be retried with the next batch.
+/// The buffer is only in memory: the batches are kept in the DA outbox until the chunk is written,
+/// and they are put into the builder again after restart.
+pub struct ChunkBuilder {
+    compression: DAChunkCompression,
+    max_batches: usize,
+    max_age: Duration,
+    buffer: Vec<DABatchV0>,
+    // when the first batch of the buffer is appended
+    first_buffered_at: Option<Instant>,
+}
+
+impl ChunkBuilder {
+    pub fn new(compression: DAChunkCompression, max_batches: usize, max_age: Duration) -> Self {
+        Self {
+            compression,
+            max_batches: max_batches.clamp(1, MAX_CHUNK_BATCHES),
+            max_age,
+            buffer: Vec::new(),
+            first_buffered_at: None,
+        }
+    }
+
+    /// Append a batch into buffer, return the chunk if the buffer is full.
+    /// Appending a batch which is already buffered is idempotent: the batch is retried after the
+    /// chunk failed to submit, so the full chunk is returned again to be resubmitted.
+    pub fn append(&mut self, batch: DABatchV0) -> anyhow::Result<Option<ChunkV1>> {
+        if let Some(buffered) = self
+            .buffer
+            .iter()
+            .find(|buffered| buffered.block_number == batch.block_number)
+        {
+            if buffered.batch_hash != batch.batch_hash {
+                return Err(anyhow!(
+                    "chunk_builder: block {} is already buffered with another batch, exp: {:?}, act: {:?}",
+                    batch.block_number,
+                    buffered.batch_hash,
+                    batch.batch_hash
+                ));
+            }
+            if self.buffer.len() >= self.max_batches {
+                return self.build().map(Some);
+            }
+            return Ok(None);
+        }
+        if let Some(last) = self.buffer.last() {
+            if batch.block_number != last.block_number + 1 {
+                return Err(anyhow!(
+                    "chunk_builder: block number is not continuous, exp: {}, act: {}",
+                    last.block_number + 1,
+                    batch.block_number
+                ));
+            }
+        }
+        if self.buffer.is_empty() {
+            self.first_buffered_at = Some(Instant::now());
+        }
+        self.buffer.push(batch);
+        if self.buffer.len() >= self.max_batches {
+            return self.build().map(Some);
+        }
+        Ok(None)
+    }
+
+    /// Build chunk with all buffered batches, the buffer is not cleaned
+    pub fn build(&self) -> anyhow::Result<ChunkV1> {
+        ChunkV1::new(self.compression, self.buffer.clone())
+    }
+
+    /// Build chunk with all buffered batches if the first one is buffered longer than max_age,
+    /// the buffer is not cleaned
+    pub fn build_expired(&self) -> anyhow::Result<Option<ChunkV1>> {
+        match self.first_buffered_at {
+            Some(first_buffered_at) if first_buffered_at.elapsed() >= self.max_age => {
+                self.build().map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Clean up the buffer after the chunk has been submitted
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.first_buffered_at = None;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Get the batch which has not been submitted yet
+    pub fn get_batch(&self, query: &BatchQuery) -> Option<DABatchV0> {
+        find_batch(self.buffer.clone(), query)
+    }
+}
+
+/// Reassemble the chunk bytes from segments (in any order).
+/// All segments from number 0 to the last one must be present.
+pub fn chunk_from_segments(
+    chunk_id: u128,
+    segments: Vec<Box<dyn Segment>>,
+) -> anyhow::Result<Vec<u8>> {
+    let mut segments = segments;
+    segments.sort_by_key(|segment| segment.get_id().segment_number);
+
+    let mut chunk_bytes = Vec::new();
+    let mut last_found = false;
+    for (i, segment) in segments.iter().enumerate() {
+        let exp_id = SegmentID {
+            chunk_id,
+            segment_number: i as u64,
+        };
+        if segment.get_id() != exp_id {
+            return Err(anyhow!(
+                "chunk: missing or unexpected segment, exp: {}",
+                exp_id
+            ));
+        }
+        chunk_bytes.extend_from_slice(segment.get_data());
+        if segment.is_last() {
+            last_found = true;
+            break;
+        }
+    }
+    if !last_found {
+        return Err(anyhow!(
+            "chunk: last segment of chunk {} not found",
+            chunk_id
+        ));
+    }
+    Ok(chunk_bytes)
+}
+
+/// Decode segments bytes (checksums are verified) and reassemble the batches of the chunk
+pub fn batches_from_segment_bytes(
+    chunk_id: u128,
+    segments_bytes: Vec<Vec<u8>>,
+) -> anyhow::Result<Vec<DABatchV0>> {
+    batches_from_raw_segments(chunk_id, segments_bytes.into_iter().map(Some).collect())
+}
+
+/// Reassemble the batches of the chunk from raw segments, None for a missing segment.
+/// For FEC segments(SegmentV2), missing segments and segments failing the checksum are rebuilt
+/// from parity; otherwise all the segments must be present and valid.
+pub fn batches_from_raw_segments(
+    chunk_id: u128,
+    raw_segments: Vec<Option<Vec<u8>>>,
+) -> anyhow::Result<Vec<DABatchV0>> {
+    let is_fec = raw_segments
+        .iter()
+        .flatten()
+        .any(|bytes| bytes.first() == Some(&u8::from(SegmentVersion::V2)));
+    if is_fec {
+        return batches_from_fec_segments(chunk_id, raw_segments);
+    }
+
+    let segments = raw_segments
+        .into_iter()
+        .enumerate()
+        .map(|(i, bytes)| match bytes {
+            Some(bytes) => segment_from_bytes(&bytes),
+            None => Err(anyhow!(
+                "chunk: segment {} of chunk {} not found",
+                i,
+                chunk_id
+            )),
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    batches_from_segments(chunk_id, segments)
+}
+
+fn batches_from_fec_segments(
+    chunk_id: u128,
+    raw_segments: Vec<Option<Vec<u8>>>,
+) -> anyhow::Result<Vec<DABatchV0>> {
+    let mut segments = Vec::new();
+    for bytes in raw_segments.iter().flatten() {
+        match SegmentV2::from_bytes(bytes) {
+            Ok(segment) if segment.id.chunk_id == chunk_id => segments.push(segment),
+            Ok(segment) => log::warn!(
+                "chunk: unexpected segment {} in chunk {}, ignored",
+                segment.id,
+                chunk_id
+            ),
+            Err(e) => log::warn!(
+                "chunk: invalid segment in chunk {}, ignored: {:?}",
+                chunk_id,
+                e
+            ),
+        }
+    }
+    // coding parameters are protected by the checksum, all the valid segments must agree
+    let (data_segments, parity_segments, chunk_len) = match segments.first() {
+        Some(segment) => (
+            segment.data_segments,
+            segment.parity_segments,
+            segment.chunk_len,
+        ),
+        None => return Err(anyhow!("chunk: no valid segment of chunk {}", chunk_id)),
+    };
+    if segments.iter().any(|segment| {
+        (
+            segment.data_segments,
+            segment.parity_segments,
+            segment.chunk_len,
+        ) != (data_segments, parity_segments, chunk_len)
+    }) {
+        return Err(anyhow!(
+            "chunk: inconsistent coding parameters in chunk {}",
+            chunk_id
+        ));
+    }
+
+    let total = data_segments as usize + parity_segments as usize;
+    let mut shards: Vec<Option<Vec<u8>>> = vec![None; total];
+    for segment in segments {
+        let segment_number = segment.id.segment_number as usize;
+        if segment_number < total {
+            shards[segment_number] = Some(segment.data);
+        }
+    }
+    let chunk_bytes = fec_decode(
+        data_segments as usize,
+        parity_segments as usize,
+        chunk_len as usize,
+        shards,
+    )?;
+    let batches = ChunkV1::from_bytes(&chunk_bytes)?.batches;
+    if batches[0].block_number != chunk_id {
+        return Err(anyhow!(
+            "chunk: block number mismatch, exp: {}, act: {}",
+            chunk_id,
+            batches[0].block_number
+        ));
+    }
+    Ok(batches)
+}
+
+/// Reassemble the batches of the chunk from decoded segments, the batches hash are verified.
+/// SegmentV0 carries a single DABatchV0, SegmentV1 carries a ChunkV1.
+/// FEC segments(SegmentV2) must be decoded by `batches_from_raw_segments`.
+pub fn batches_from_segments(
+    chunk_id: u128,
+    segments: Vec<Box<dyn Segment>>,
+) -> anyhow::Result<Vec<DABatchV0>> {
+    let version = match segments.first() {
+        Some(segment) => segment.get_version(),
+        None => return Err(anyhow!("chunk: no segment of chunk {}", chunk_id)),
+    };
+    if segments
+        .iter()
+        .any(|segment| segment.get_version() != version)
+    {
+        return Err(anyhow!(
+            "chunk: mixed segment versions in chunk {}",
+            chunk_id
+        ));
+    }
+    let chunk_bytes = chunk_from_segments(chunk_id, segments)?;
+    let batches = match version {
+        SegmentVersion::V0 => vec![DABatchV0::from_bytes(&chunk_bytes)?],
+        SegmentVersion::V1 => ChunkV1::from_bytes(&chunk_bytes)?.batches,
+        SegmentVersion::V2 => {
+            return Err(anyhow!(
+                "chunk: FEC segments must be decoded from raw segments"
+            ))
+        }
+        SegmentVersion::Unknown(_) => return Err(anyhow!("unsupported segment version")),
+    };
+    if batches[0].block_number != chunk_id {
+        return Err(anyhow!(
+            "chunk: block number mismatch, exp: {}, act: {}",
+            chunk_id,
+            batches[0].block_number
+        ));
+    }
+    Ok(batches)
+}
+
+/// Find the batch matching the query in the batches of a chunk
+pub fn find_batch(batches: Vec<DABatchV0>, query: &BatchQuery) -> Option<DABatchV0> {
+    batches.into_iter().find(|batch| match query {
+        BatchQuery::BlockNumber(block_number) => batch.block_number == *block_number,
+        BatchQuery::BatchHash(batch_hash) => batch.batch_hash == *batch_hash,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::segment::SegmentV0;
+
+    fn make_segments(batch: &DABatchV0, max_segment_size: usize) -> Vec<Vec<u8>> {
+        let chunk_bytes = bcs::to_bytes(batch).unwrap();
+        let segs = chunk_bytes.chunks(max_segment_size);
+        let total = segs.len();
+        segs.enumerate()
+            .map(|(i, data)| {
+                SegmentV0 {
+                    id: SegmentID {
+                        chunk_id: batch.block_number,
+                        segment_number: i as u64,
+                    },
+                    is_last: i == total - 1,
+                    data_checksum: 0,
+                    checksum: 0,
+                    data: data.to_vec(),
+                }
+                .to_bytes()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_from_segment_bytes() {
+        let data = vec![7u8; 1000];
+        let batch = DABatchV0 {
+            version: 0,
+            block_number: 42,
+            batch_hash: h256::sha3_256_of(&data),
+            data,
+        };
+        let mut segments_bytes = make_segments(&batch, 100);
+        // segments order does not matter
+        segments_bytes.reverse();
+        let recovered = batches_from_segment_bytes(42, segments_bytes.clone()).unwrap();
+        assert_eq!(recovered, vec![batch]);
+
+        // wrong chunk id
+        assert!(batches_from_segment_bytes(43, segments_bytes.clone()).is_err());
+
+        // missing segment
+        let mut missing = segments_bytes.clone();
+        missing.remove(3);
+        assert!(batches_from_segment_bytes(42, missing).is_err());
+
+        // corrupted segment data
+        let mut corrupted = segments_bytes;
+        let last = corrupted[0].len() - 1;
+        corrupted[0][last] ^= 0xff;
+        assert!(batches_from_segment_bytes(42, corrupted).is_err());
+    }
+
+    #[test]
+    fn test_chunk_v1_roundtrip() {
+        for compression in [
+            DAChunkCompression::None,
+            DAChunkCompression::Zstd,
+            DAChunkCompression::Lz4,
+        ] {
+            let mut builder = ChunkBuilder::new(compression, 3, Duration::from_secs(60));
+            let mut chunk = None;
+            for block_number in 10..13u128 {
+                let data = vec![block_number as u8; 1000];
+                let batch = DABatchV0::new(block_number, h256::sha3_256_of(&data), data);
+                chunk = builder.append(batch).unwrap();
+                if block_number < 12 {
+                    assert!(chunk.is_none());
+                    assert!(builder
+                        .get_batch(&BatchQuery::BlockNumber(block_number))
+                        .is_some());
+                }
+            }
+            let chunk = chunk.unwrap();
+            builder.clear();
+            assert!(builder.is_empty());
+            assert_eq!(chunk.chunk_id(), 10);
+
+            let segments_bytes = chunk
+                .to_segments(100)
+                .unwrap()
+                .iter()
+                .map(|segment| segment.to_bytes())
+                .collect::<Vec<_>>();
+            let batches = batches_from_segment_bytes(10, segments_bytes).unwrap();
+            assert_eq!(batches, chunk.batches);
+            assert_eq!(
+                find_batch(batches, &BatchQuery::BlockNumber(11)).unwrap(),
+                chunk.batches[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_chunk_fec_segments() {
+        let batches = (5..8u128)
+            .map(|block_number| {
+                let data = vec![block_number as u8; 500];
+                DABatchV0::new(block_number, h256::sha3_256_of(&data), data)
+            })
+            .collect::<Vec<_>>();
+        let chunk = ChunkV1::new(DAChunkCompression::None, batches).unwrap();
+        let segments = chunk.make_segments(100, 4).unwrap();
+        let mut raw_segments = segments
+            .iter()
+            .map(|segment| Some(segment.to_bytes()))
+            .collect::<Vec<_>>();
+        assert!(segments.last().unwrap().is_last());
+
+        // 2 missing and 2 corrupted segments are rebuilt from parity
+        raw_segments[0] = None;
+        raw_segments[3] = None;
+        let last = raw_segments[1].as_ref().unwrap().len() - 1;
+        raw_segments[1].as_mut().unwrap()[last] ^= 0xff;
+        raw_segments[7].as_mut().unwrap()[60] ^= 0xff;
+        let recovered = batches_from_raw_segments(5, raw_segments.clone()).unwrap();
+        assert_eq!(recovered, chunk.batches);
+
+        // one more is lost
+        raw_segments[2] = None;
+        assert!(batches_from_raw_segments(5, raw_segments).is_err());
+    }
+
+    #[test]
+    fn test_chunk_builder_discontinuous() {
+        let mut builder = ChunkBuilder::new(DAChunkCompression::Zstd, 4, Duration::from_secs(60));
+        let data = vec![1u8; 10];
+        let batch = DABatchV0::new(1, h256::sha3_256_of(&data), data.clone());
+        builder.append(batch).unwrap();
+        let batch = DABatchV0::new(3, h256::sha3_256_of(&data), data);
+        assert!(builder.append(batch).is_err());
+    }
+
+    #[test]
+    fn test_chunk_builder_append_buffered_batch() {
+        let mut builder = ChunkBuilder::new(DAChunkCompression::Zstd, 2, Duration::from_secs(60));
+        let data = vec![1u8; 10];
+        let batch0 = DABatchV0::new(0, h256::sha3_256_of(&data), data.clone());
+        let batch1 = DABatchV0::new(1, h256::sha3_256_of(&data), data.clone());
+        assert!(builder.append(batch0.clone()).unwrap().is_none());
+        // retry before the buffer is full
+        assert!(builder.append(batch0.clone()).unwrap().is_none());
+        let chunk = builder.append(batch1.clone()).unwrap().unwrap();
+        assert_eq!(chunk.batches, vec![batch0.clone(), batch1.clone()]);
+
+        // the chunk failed to submit, the last batch is retried
+        let retried = builder.append(batch1).unwrap().unwrap();
+        assert_eq!(retried.batches, chunk.batches);
+
+        // a different batch of the buffered block is rejected
+        let other_data = vec![2u8; 10];
+        let other = DABatchV0::new(0, h256::sha3_256_of(&other_data), other_data);
+        assert!(builder.append(other).is_err());
+
+        builder.clear();
+        assert!(builder.append(batch0).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_chunk_builder_build_expired() {
+        let data = vec![1u8; 10];
+        let batch0 = DABatchV0::new(0, h256::sha3_256_of(&data), data.clone());
+        let batch1 = DABatchV0::new(1, h256::sha3_256_of(&data), data);
+
+        let mut builder = ChunkBuilder::new(DAChunkCompression::Zstd, 4, Duration::from_secs(60));
+        assert!(builder.build_expired().unwrap().is_none());
+        builder.append(batch0.clone()).unwrap();
+        assert!(builder.build_expired().unwrap().is_none());
+
+        let mut builder = ChunkBuilder::new(DAChunkCompression::Zstd, 4, Duration::ZERO);
+        assert!(builder.build_expired().unwrap().is_none());
+        builder.append(batch0.clone()).unwrap();
+        builder.append(batch1.clone()).unwrap();
+        let chunk = builder.build_expired().unwrap().unwrap();
+        assert_eq!(chunk.batches, vec![batch0, batch1]);
+        builder.clear();
+        assert!(builder.build_expired().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_decompress_limit() {
+        let raw = vec![0u8; 10_000];
+        for compression in [
+            DAChunkCompression::None,
+            DAChunkCompression::Zstd,
+            DAChunkCompression::Lz4,
+        ] {
+            let compressed = compress(compression, &raw).unwrap();
+            assert_eq!(
+                decompress(compression, &compressed, raw.len()).unwrap(),
+                raw
+            );
+            assert!(decompress(compression, &compressed, raw.len() - 1).is_err());
+        }
+
+        // the forged lz4 size prefix is rejected before allocating
+        let mut forged = i32::MAX.to_le_bytes().to_vec();
+        forged.extend_from_slice(&[0u8; 16]);
+        assert!(decompress(DAChunkCompression::Lz4, &forged, MAX_CHUNK_RAW_SIZE).is_err());
+    }
+}
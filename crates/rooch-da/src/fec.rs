@@ -0,0 +1,127 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use reed_solomon_erasure::galois_8::ReedSolomon;
+
+// total shards(data + parity) limit of GF(2^8)
+pub const MAX_FEC_SHARDS: usize = 256;
+
+/// Split data into k data shards (padding with zero) and m parity shards by Reed-Solomon.
+/// The shard size is no more than max_shard_size unless k + m exceeds MAX_FEC_SHARDS.
+/// Returns (k, shards)
+pub fn fec_encode(
+    data: &[u8],
+    max_shard_size: usize,
+    parity_shards: usize,
+) -> anyhow::Result<(usize, Vec<Vec<u8>>)> {
+    if parity_shards == 0 || parity_shards >= MAX_FEC_SHARDS {
+        return Err(anyhow!(
+            "fec: parity shards must be in [1, {}), act: {}",
+            MAX_FEC_SHARDS,
+            parity_shards
+        ));
+    }
+    let mut shard_size = max_shard_size.max(1);
+    let mut data_shards = data.len().max(1).div_ceil(shard_size);
+    let max_data_shards = MAX_FEC_SHARDS - parity_shards;
+    if data_shards > max_data_shards {
+        data_shards = max_data_shards;
+        shard_size = data.len().div_ceil(data_shards);
+    }
+
+    // all shards must have the same size
+    let mut shards = (0..data_shards)
+        .map(|i| {
+            let start = (i * shard_size).min(data.len());
+            let end = (start + shard_size).min(data.len());
+            let mut shard = data[start..end].to_vec();
+            shard.resize(shard_size, 0);
+            shard
+        })
+        .collect::<Vec<_>>();
+    shards.extend((0..parity_shards).map(|_| vec![0u8; shard_size]));
+    ReedSolomon::new(data_shards, parity_shards)?.encode(&mut shards)?;
+    Ok((data_shards, shards))
+}
+
+/// Rebuild data from shards, missing(or corrupted) shards are None.
+/// At least data_shards of the shards must be present.
+pub fn fec_decode(
+    data_shards: usize,
+    parity_shards: usize,
+    data_len: usize,
+    mut shards: Vec<Option<Vec<u8>>>,
+) -> anyhow::Result<Vec<u8>> {
+    if shards.len() != data_shards + parity_shards {
+        return Err(anyhow!(
+            "fec: shards count mismatch, exp: {}, act: {}",
+            data_shards + parity_shards,
+            shards.len()
+        ));
+    }
+    // shards with unexpected size can't be used
+    if let Some(shard_size) = shards.iter().flatten().map(|shard| shard.len()).next() {
+        for shard in shards.iter_mut() {
+            if shard
+                .as_ref()
+                .map_or(false, |shard| shard.len() != shard_size)
+            {
+                *shard = None;
+            }
+        }
+    }
+    ReedSolomon::new(data_shards, parity_shards)?.reconstruct_data(&mut shards)?;
+
+    let mut data = Vec::new();
+    for shard in shards.into_iter().take(data_shards) {
+        data.extend(shard.ok_or_else(|| anyhow!("fec: data shard is not rebuilt"))?);
+    }
+    if data_len > data.len() {
+        return Err(anyhow!(
+            "fec: data length {} exceeds shards length {}",
+            data_len,
+            data.len()
+        ));
+    }
+    data.truncate(data_len);
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fec_encode_decode() {
+        let data = (0..1000u32).map(|i| i as u8).collect::<Vec<_>>();
+        let (data_shards, shards) = fec_encode(&data, 100, 3).unwrap();
+        assert_eq!(data_shards, 10);
+        assert_eq!(shards.len(), 13);
+
+        // lose 3 shards
+        let mut received = shards.into_iter().map(Some).collect::<Vec<_>>();
+        received[0] = None;
+        received[5] = None;
+        received[11] = None;
+        let recovered = fec_decode(data_shards, 3, data.len(), received.clone()).unwrap();
+        assert_eq!(recovered, data);
+
+        // too many lost
+        received[1] = None;
+        assert!(fec_decode(data_shards, 3, data.len(), received).is_err());
+    }
+
+    #[test]
+    fn test_fec_encode_max_shards() {
+        let data = vec![1u8; 10000];
+        let (data_shards, shards) = fec_encode(&data, 1, 6).unwrap();
+        assert_eq!(data_shards, MAX_FEC_SHARDS - 6);
+        assert_eq!(shards.len(), MAX_FEC_SHARDS);
+        let received = shards.into_iter().map(Some).collect::<Vec<_>>();
+        assert_eq!(
+            fec_decode(data_shards, 6, data.len(), received).unwrap(),
+            data
+        );
+    }
+}
@@ -3,7 +3,10 @@
 
 pub mod actor;
 pub mod chunk;
+pub mod fec;
 pub mod messages;
+pub mod metrics;
 pub mod proxy;
 pub mod segment;
+pub mod segment_indexer;
 pub mod server;
@@ -3,10 +3,13 @@
 
 use anyhow::Result;
 use coerce::actor::message::Message;
+use coerce::actor::scheduler::timer::TimerTick;
 use serde::{Deserialize, Serialize};
 
 use moveos_types::h256::H256;
 
+use crate::chunk::DABatchV0;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Batch {
     // each batch maps to a L2 block
@@ -17,16 +20,106 @@ pub struct Batch {
     pub data: Vec<u8>,
 }
 
-impl Message for Batch {
+/// How many DA servers must confirm the batch before the put returns
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PutPolicy {
+    All,
+    // >= n/2+1
+    Quorum,
+    AnyOne,
+}
+
+impl PutPolicy {
+    pub fn threshold(&self, servers_count: usize) -> usize {
+        match self {
+            PutPolicy::All => servers_count,
+            PutPolicy::Quorum => servers_count / 2 + 1,
+            PutPolicy::AnyOne => 1,
+        }
+    }
+}
+
+/// Put batch into the DA submission outbox, then submit it to DA servers.
+/// Using the submit strategy of DA config if the policy is None.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PutBatchMessage {
+    pub batch: Batch,
+    pub policy: Option<PutPolicy>,
+}
+
+impl Message for PutBatchMessage {
     type Result = Result<()>;
 }
 
+/// Retry the batches in the outbox which are not confirmed by all the DA servers
+#[derive(Clone)]
+pub struct RetryPendingBatches {}
+
+impl Message for RetryPendingBatches {
+    type Result = ();
+}
+
+impl TimerTick for RetryPendingBatches {}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PutBatchInternalDAMessage {
     pub batch: Batch,
-    // TODO add put policy
 }
 
 impl Message for PutBatchInternalDAMessage {
-    type Result = Result<()>;
+    type Result = Result<PutBatchState>;
+}
+
+/// Write the buffered batches of a DA server if they are buffered longer than the max chunk age,
+/// the result is the last block number written, None if nothing is written
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FlushChunkInternalDAMessage {}
+
+impl Message for FlushChunkInternalDAMessage {
+    type Result = Result<Option<u128>>;
+}
+
+/// The state of the batch after it's put into a DA server
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PutBatchState {
+    // the batch is buffered in memory by the server, it's put again from the DA outbox if the server restarts
+    Buffered,
+    // the batch and all the batches put before it are durably written by the server
+    Written,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum BatchQuery {
+    BlockNumber(u128),
+    BatchHash(H256),
+}
+
+/// Get batch from DA servers, the first server which has the batch wins
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetBatchMessage {
+    pub query: BatchQuery,
+}
+
+impl Message for GetBatchMessage {
+    type Result = Result<Option<DABatchV0>>;
+}
+
+/// Get continuous batches from `start_block_number`, stop at the first missing batch
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StreamBatchesMessage {
+    pub start_block_number: u128,
+    pub max_count: u64,
+}
+
+impl Message for StreamBatchesMessage {
+    type Result = Result<Vec<DABatchV0>>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetBatchInternalDAMessage {
+    pub query: BatchQuery,
+}
+
+impl Message for GetBatchInternalDAMessage {
+    type Result = Result<Option<DABatchV0>>;
 }
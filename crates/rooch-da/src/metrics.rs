@@ -0,0 +1,42 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
+    IntGaugeVec, Registry,
+};
+
+#[derive(Clone, Debug)]
+pub struct DAMetrics {
+    pub(crate) backend_lag: IntGaugeVec,
+    pub(crate) backend_confirmed_block_number: IntGaugeVec,
+    pub(crate) backend_submit_failures: IntCounterVec,
+}
+
+impl DAMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            backend_lag: register_int_gauge_vec_with_registry!(
+                "da_backend_lag",
+                "Number of pending batches which are not confirmed by the DA server yet",
+                &["backend"],
+                registry,
+            )
+            .unwrap(),
+            backend_confirmed_block_number: register_int_gauge_vec_with_registry!(
+                "da_backend_confirmed_block_number",
+                "The last block number confirmed by the DA server",
+                &["backend"],
+                registry,
+            )
+            .unwrap(),
+            backend_submit_failures: register_int_counter_vec_with_registry!(
+                "da_backend_submit_failures",
+                "Total number of failed batch submissions to the DA server",
+                &["backend"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
@@ -2,9 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use coerce::actor::ActorRef;
+use futures::stream::{self, BoxStream};
+use futures::{StreamExt, TryStreamExt};
+use moveos_types::h256::H256;
 
 use crate::actor::da::DAActor;
-use crate::messages::Batch;
+use crate::chunk::DABatchV0;
+use crate::messages::{
+    Batch, BatchQuery, GetBatchMessage, PutBatchMessage, PutPolicy, StreamBatchesMessage,
+};
+
+// how many batches fetched from DA actor in one round when streaming
+const STREAM_BATCHES_PAGE_SIZE: u64 = 16;
 
 #[derive(Clone)]
 pub struct DAProxy {
@@ -17,6 +26,80 @@ impl DAProxy {
     }
 
     pub async fn submit_batch(&self, batch: Batch) -> anyhow::Result<()> {
-        self.actor.send(batch).await?
+        self.actor
+            .send(PutBatchMessage {
+                batch,
+                policy: None,
+            })
+            .await?
+    }
+
+    pub async fn submit_batch_with_policy(
+        &self,
+        batch: Batch,
+        policy: PutPolicy,
+    ) -> anyhow::Result<()> {
+        self.actor
+            .send(PutBatchMessage {
+                batch,
+                policy: Some(policy),
+            })
+            .await?
+    }
+
+    pub async fn get_batch(&self, block_number: u128) -> anyhow::Result<Option<DABatchV0>> {
+        self.actor
+            .send(GetBatchMessage {
+                query: BatchQuery::BlockNumber(block_number),
+            })
+            .await?
+    }
+
+    pub async fn get_batch_by_hash(&self, batch_hash: H256) -> anyhow::Result<Option<DABatchV0>> {
+        self.actor
+            .send(GetBatchMessage {
+                query: BatchQuery::BatchHash(batch_hash),
+            })
+            .await?
+    }
+
+    pub async fn get_batches(
+        &self,
+        start_block_number: u128,
+        max_count: u64,
+    ) -> anyhow::Result<Vec<DABatchV0>> {
+        self.actor
+            .send(StreamBatchesMessage {
+                start_block_number,
+                max_count,
+            })
+            .await?
+    }
+
+    /// Stream continuous batches from `start_block_number`,
+    /// the stream ends at the first batch which is not available yet.
+    pub fn stream_batches(
+        &self,
+        start_block_number: u128,
+    ) -> BoxStream<'static, anyhow::Result<DABatchV0>> {
+        let proxy = self.clone();
+        stream::try_unfold(
+            (proxy, start_block_number),
+            |(proxy, block_number)| async move {
+                let batches = proxy
+                    .get_batches(block_number, STREAM_BATCHES_PAGE_SIZE)
+                    .await?;
+                if batches.is_empty() {
+                    return Ok(None);
+                }
+                let next_block_number = block_number + batches.len() as u128;
+                Ok(Some((
+                    stream::iter(batches.into_iter().map(Ok)),
+                    (proxy, next_block_number),
+                )))
+            },
+        )
+        .try_flatten()
+        .boxed()
     }
 }
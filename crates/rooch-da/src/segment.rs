@@ -9,7 +9,12 @@ use xxhash_rust::xxh3::xxh3_64;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum SegmentVersion {
+    // segments of a chunk which is a single DABatchV0
     V0,
+    // segments of a ChunkV1: compressed consecutive batches
+    V1,
+    // FEC segments of a ChunkV1: k data segments and m Reed-Solomon parity segments
+    V2,
     Unknown(u8),
 }
 
@@ -17,6 +22,8 @@ impl From<u8> for SegmentVersion {
     fn from(num: u8) -> Self {
         match num {
             0 => SegmentVersion::V0,
+            1 => SegmentVersion::V1,
+            2 => SegmentVersion::V2,
             // ...
             _ => Self::Unknown(num),
         }
@@ -27,6 +34,8 @@ impl From<SegmentVersion> for u8 {
     fn from(version: SegmentVersion) -> Self {
         match version {
             SegmentVersion::V0 => 0,
+            SegmentVersion::V1 => 1,
+            SegmentVersion::V2 => 2,
             SegmentVersion::Unknown(num) => num,
         }
     }
@@ -37,6 +46,8 @@ pub trait Segment: Send {
     fn to_bytes(&self) -> Vec<u8>;
     fn get_version(&self) -> SegmentVersion;
     fn get_id(&self) -> SegmentID;
+    fn is_last(&self) -> bool;
+    fn get_data(&self) -> &[u8];
 }
 
 pub const SEGMENT_V0_DATA_OFFSET: usize = 42;
@@ -57,28 +68,144 @@ impl SegmentV0 {
     where
         Self: Sized,
     {
-        if bytes.len() < SEGMENT_V0_DATA_OFFSET {
+        let (id, is_last, data_checksum, checksum, data) =
+            decode_segment(SegmentVersion::V0, "segment_v0", bytes)?;
+        Ok(Self {
+            id,
+            is_last,
+            data_checksum,
+            checksum,
+            data,
+        })
+    }
+}
+
+impl Segment for SegmentV0 {
+    fn to_bytes(&self) -> Vec<u8> {
+        encode_segment(SegmentVersion::V0, &self.id, self.is_last, &self.data)
+    }
+
+    fn get_version(&self) -> SegmentVersion {
+        SegmentVersion::V0
+    }
+
+    fn get_id(&self) -> SegmentID {
+        self.id.clone()
+    }
+
+    fn is_last(&self) -> bool {
+        self.is_last
+    }
+
+    fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+// SegmentV1 shares the layout of SegmentV0, the version tells readers the data is a part of ChunkV1
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct SegmentV1 {
+    pub id: SegmentID,
+    pub is_last: bool,      // is last segment in chunk
+    pub data_checksum: u64, // checksum of data, xxh3_64
+    pub checksum: u64, // checksum of above fields(exclude data) and version after to_bytes, xxh3_64
+
+    pub data: Vec<u8>,
+}
+
+impl SegmentV1 {
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        let (id, is_last, data_checksum, checksum, data) =
+            decode_segment(SegmentVersion::V1, "segment_v1", bytes)?;
+        Ok(Self {
+            id,
+            is_last,
+            data_checksum,
+            checksum,
+            data,
+        })
+    }
+}
+
+impl Segment for SegmentV1 {
+    fn to_bytes(&self) -> Vec<u8> {
+        encode_segment(SegmentVersion::V1, &self.id, self.is_last, &self.data)
+    }
+
+    fn get_version(&self) -> SegmentVersion {
+        SegmentVersion::V1
+    }
+
+    fn get_id(&self) -> SegmentID {
+        self.id.clone()
+    }
+
+    fn is_last(&self) -> bool {
+        self.is_last
+    }
+
+    fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+pub const SEGMENT_V2_DATA_OFFSET: usize = 54;
+pub const SEGMENT_V2_CHECKSUM_OFFSET: usize = 46;
+
+// SegmentV2 is a shard of the Reed-Solomon coded ChunkV1, the coding parameters are in the header,
+// so the chunk could be rebuilt from any data_segments of the segments.
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct SegmentV2 {
+    pub id: SegmentID,
+    pub is_last: bool,        // is last segment(including parity segments) in chunk
+    pub data_segments: u16,   // k
+    pub parity_segments: u16, // m
+    pub chunk_len: u64,       // length of the chunk bytes, data segments are padded with zero
+    pub data_checksum: u64,   // checksum of data, xxh3_64
+    pub checksum: u64, // checksum of above fields(exclude data) and version after to_bytes, xxh3_64
+
+    pub data: Vec<u8>,
+}
+
+impl SegmentV2 {
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        if bytes.len() < SEGMENT_V2_DATA_OFFSET {
+            return Err(anyhow::anyhow!(
+                "segment_v2: bytes less than {}",
+                SEGMENT_V2_DATA_OFFSET
+            ));
+        }
+        if SegmentVersion::from(bytes[0]) != SegmentVersion::V2 {
             return Err(anyhow::anyhow!(
-                "segment_v0: bytes less than {}",
-                SEGMENT_V0_DATA_OFFSET
+                "segment_v2: version mismatch: {}",
+                bytes[0]
             ));
         }
 
         let chunk_id = u128::from_le_bytes(bytes[1..17].try_into()?);
         let segment_number = u64::from_le_bytes(bytes[17..25].try_into()?);
         let is_last = bytes[25] != 0;
-        let data_checksum = u64::from_le_bytes(bytes[26..34].try_into()?);
-        let checksum = u64::from_le_bytes(bytes[34..SEGMENT_V0_DATA_OFFSET].try_into()?);
-        let data = bytes[SEGMENT_V0_DATA_OFFSET..].to_vec();
-
-        let exp_checksum = xxh3_64(&bytes[0..SEGMENT_V0_CHECKSUM_OFFSET]);
+        let data_segments = u16::from_le_bytes(bytes[26..28].try_into()?);
+        let parity_segments = u16::from_le_bytes(bytes[28..30].try_into()?);
+        let chunk_len = u64::from_le_bytes(bytes[30..38].try_into()?);
+        let data_checksum = u64::from_le_bytes(bytes[38..46].try_into()?);
+        let checksum = u64::from_le_bytes(bytes[46..SEGMENT_V2_DATA_OFFSET].try_into()?);
+        let data = bytes[SEGMENT_V2_DATA_OFFSET..].to_vec();
+
+        let exp_checksum = xxh3_64(&bytes[0..SEGMENT_V2_CHECKSUM_OFFSET]);
         if exp_checksum != checksum {
-            return Err(anyhow::anyhow!("segment_v0: checksum mismatch"));
+            return Err(anyhow::anyhow!("segment_v2: checksum mismatch"));
         }
 
         let exp_data_checksum = xxh3_64(&data);
         if exp_data_checksum != data_checksum {
-            return Err(anyhow::anyhow!("segment_v0: data checksum mismatch"));
+            return Err(anyhow::anyhow!("segment_v2: data checksum mismatch"));
         }
 
         Ok(Self {
@@ -87,50 +214,135 @@ impl SegmentV0 {
                 segment_number,
             },
             is_last,
+            data_segments,
+            parity_segments,
+            chunk_len,
             data_checksum,
             checksum,
             data,
         })
     }
+
+    pub fn total_segments(&self) -> u64 {
+        self.data_segments as u64 + self.parity_segments as u64
+    }
 }
 
-impl Segment for SegmentV0 {
+impl Segment for SegmentV2 {
     fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(SEGMENT_V0_DATA_OFFSET + self.data.len());
-        bytes.push(SegmentVersion::V0.into()); // version
+        let mut bytes = Vec::with_capacity(SEGMENT_V2_DATA_OFFSET + self.data.len());
+        bytes.push(SegmentVersion::V2.into()); // version
         bytes.extend_from_slice(&self.id.chunk_id.to_le_bytes());
         bytes.extend_from_slice(&self.id.segment_number.to_le_bytes());
         bytes.push(self.is_last as u8);
+        bytes.extend_from_slice(&self.data_segments.to_le_bytes());
+        bytes.extend_from_slice(&self.parity_segments.to_le_bytes());
+        bytes.extend_from_slice(&self.chunk_len.to_le_bytes());
         let data_checksum = xxh3_64(&self.data);
         bytes.extend_from_slice(&data_checksum.to_le_bytes());
-        let checksum = xxh3_64(&bytes[0..SEGMENT_V0_CHECKSUM_OFFSET]);
+        let checksum = xxh3_64(&bytes[0..SEGMENT_V2_CHECKSUM_OFFSET]);
         bytes.extend_from_slice(&checksum.to_le_bytes());
         bytes.extend_from_slice(&self.data);
         bytes
     }
 
     fn get_version(&self) -> SegmentVersion {
-        SegmentVersion::V0
+        SegmentVersion::V2
     }
 
     fn get_id(&self) -> SegmentID {
         self.id.clone()
     }
+
+    fn is_last(&self) -> bool {
+        self.is_last
+    }
+
+    fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+fn encode_segment(version: SegmentVersion, id: &SegmentID, is_last: bool, data: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(get_data_offset(SegmentVersion::V0) + data.len());
+    bytes.push(version.into()); // version
+    bytes.extend_from_slice(&id.chunk_id.to_le_bytes());
+    bytes.extend_from_slice(&id.segment_number.to_le_bytes());
+    bytes.push(is_last as u8);
+    let data_checksum = xxh3_64(data);
+    bytes.extend_from_slice(&data_checksum.to_le_bytes());
+    let checksum = xxh3_64(&bytes[0..SEGMENT_V0_CHECKSUM_OFFSET]);
+    bytes.extend_from_slice(&checksum.to_le_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+// decode fields of the V0 layout: (id, is_last, data_checksum, checksum, data), checksums are verified
+#[allow(clippy::type_complexity)]
+fn decode_segment(
+    version: SegmentVersion,
+    name: &str,
+    bytes: &[u8],
+) -> anyhow::Result<(SegmentID, bool, u64, u64, Vec<u8>)> {
+    if bytes.len() < SEGMENT_V0_DATA_OFFSET {
+        return Err(anyhow::anyhow!(
+            "{}: bytes less than {}",
+            name,
+            SEGMENT_V0_DATA_OFFSET
+        ));
+    }
+    if SegmentVersion::from(bytes[0]) != version {
+        return Err(anyhow::anyhow!("{}: version mismatch: {}", name, bytes[0]));
+    }
+
+    let chunk_id = u128::from_le_bytes(bytes[1..17].try_into()?);
+    let segment_number = u64::from_le_bytes(bytes[17..25].try_into()?);
+    let is_last = bytes[25] != 0;
+    let data_checksum = u64::from_le_bytes(bytes[26..34].try_into()?);
+    let checksum = u64::from_le_bytes(bytes[34..SEGMENT_V0_DATA_OFFSET].try_into()?);
+    let data = bytes[SEGMENT_V0_DATA_OFFSET..].to_vec();
+
+    let exp_checksum = xxh3_64(&bytes[0..SEGMENT_V0_CHECKSUM_OFFSET]);
+    if exp_checksum != checksum {
+        return Err(anyhow::anyhow!("{}: checksum mismatch", name));
+    }
+
+    let exp_data_checksum = xxh3_64(&data);
+    if exp_data_checksum != data_checksum {
+        return Err(anyhow::anyhow!("{}: data checksum mismatch", name));
+    }
+
+    Ok((
+        SegmentID {
+            chunk_id,
+            segment_number,
+        },
+        is_last,
+        data_checksum,
+        checksum,
+        data,
+    ))
 }
 
 pub fn get_data_offset(version: SegmentVersion) -> usize {
     match version {
-        SegmentVersion::V0 => SEGMENT_V0_DATA_OFFSET,
+        SegmentVersion::V0 | SegmentVersion::V1 => SEGMENT_V0_DATA_OFFSET,
+        SegmentVersion::V2 => SEGMENT_V2_DATA_OFFSET,
         SegmentVersion::Unknown(_) => panic!("unsupported segment version"),
     }
 }
 
 // falling back to Result here to cater for corrupted data etc
 pub fn segment_from_bytes(bytes: &[u8]) -> anyhow::Result<Box<dyn Segment>> {
+    if bytes.is_empty() {
+        return Err(anyhow::anyhow!("segment: empty bytes"));
+    }
     let version = bytes[0];
 
     match SegmentVersion::from(version) {
         SegmentVersion::V0 => Ok(Box::new(SegmentV0::from_bytes(bytes)?)),
+        SegmentVersion::V1 => Ok(Box::new(SegmentV1::from_bytes(bytes)?)),
+        SegmentVersion::V2 => Ok(Box::new(SegmentV2::from_bytes(bytes)?)),
         // ...
         SegmentVersion::Unknown(_) => Err(anyhow::anyhow!("unsupported segment version")),
     }
@@ -200,7 +412,36 @@ mod tests {
             data: vec![1, 2, 3, 4, 5],
         };
 
-        let segments: Vec<Box<dyn Segment>> = vec![Box::new(segment_v0.clone())];
+        let mut segment_v1 = SegmentV1 {
+            id: SegmentID {
+                chunk_id: 1234567890,
+                segment_number: 12345678,
+            },
+            is_last: false,
+            data_checksum: 0,
+            checksum: 0,
+            data: vec![6, 7, 8],
+        };
+
+        let mut segment_v2 = SegmentV2 {
+            id: SegmentID {
+                chunk_id: 1234567890,
+                segment_number: 3,
+            },
+            is_last: true,
+            data_segments: 2,
+            parity_segments: 2,
+            chunk_len: 5,
+            data_checksum: 0,
+            checksum: 0,
+            data: vec![9, 10, 11],
+        };
+
+        let segments: Vec<Box<dyn Segment>> = vec![
+            Box::new(segment_v0.clone()),
+            Box::new(segment_v1.clone()),
+            Box::new(segment_v2.clone()),
+        ];
 
         for segment in segments {
             let bytes = segment.to_bytes();
@@ -214,6 +455,31 @@ mod tests {
                     segment_v0.data_checksum = recovered_segment.data_checksum;
                     assert_eq!(&segment_v0, &recovered_segment)
                 }
+                SegmentVersion::V1 => {
+                    let recovered_segment =
+                        SegmentV1::from_bytes(&bytes).expect("successful deserialization");
+                    segment_v1.checksum = recovered_segment.checksum;
+                    segment_v1.data_checksum = recovered_segment.data_checksum;
+                    assert_eq!(&segment_v1, &recovered_segment);
+                    assert!(SegmentV0::from_bytes(&bytes).is_err());
+                    assert_eq!(
+                        segment_from_bytes(&bytes).unwrap().get_version(),
+                        SegmentVersion::V1
+                    )
+                }
+                SegmentVersion::V2 => {
+                    let recovered_segment =
+                        SegmentV2::from_bytes(&bytes).expect("successful deserialization");
+                    segment_v2.checksum = recovered_segment.checksum;
+                    segment_v2.data_checksum = recovered_segment.data_checksum;
+                    assert_eq!(&segment_v2, &recovered_segment);
+                    assert_eq!(recovered_segment.total_segments(), 4);
+
+                    // coding parameters are protected by the checksum
+                    let mut corrupted = bytes.clone();
+                    corrupted[27] ^= 0xff;
+                    assert!(SegmentV2::from_bytes(&corrupted).is_err());
+                }
 
                 _ => panic!("unsupported segment version"),
             };
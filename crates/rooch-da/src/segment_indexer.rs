@@ -0,0 +1,90 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::SystemTime;
+
+use anyhow::Result;
+use moveos_types::h256::H256;
+use rooch_store::da_store::DAStore;
+use rooch_store::RoochStore;
+use rooch_types::da::{DAChunkIndex, DASegmentIndex};
+
+use crate::chunk::ChunkV1;
+
+/// SegmentIndexer records where the segments of each chunk are stored in a DA backend,
+/// so the data of a block could be found without listing the whole storage.
+pub trait SegmentIndexer: Send + Sync {
+    fn index_chunk(&self, index: DAChunkIndex) -> Result<()>;
+
+    fn get_chunk_index(&self, chunk_id: u128) -> Result<Option<DAChunkIndex>>;
+
+    fn get_chunk_index_by_block(&self, block_number: u128) -> Result<Option<DAChunkIndex>>;
+
+    fn get_block_number_by_batch_hash(&self, batch_hash: H256) -> Result<Option<u128>>;
+}
+
+/// RocksDB backed SegmentIndexer, the indexes are kept in rooch store by backend name
+pub struct RocksDBSegmentIndexer {
+    backend: String,
+    rooch_store: RoochStore,
+}
+
+impl RocksDBSegmentIndexer {
+    pub fn new(backend: String, rooch_store: RoochStore) -> Self {
+        Self {
+            backend,
+            rooch_store,
+        }
+    }
+}
+
+impl SegmentIndexer for RocksDBSegmentIndexer {
+    fn index_chunk(&self, index: DAChunkIndex) -> Result<()> {
+        self.rooch_store
+            .save_da_chunk_index(self.backend.clone(), index)
+    }
+
+    fn get_chunk_index(&self, chunk_id: u128) -> Result<Option<DAChunkIndex>> {
+        self.rooch_store
+            .get_da_chunk_index(self.backend.clone(), chunk_id)
+    }
+
+    fn get_chunk_index_by_block(&self, block_number: u128) -> Result<Option<DAChunkIndex>> {
+        self.rooch_store
+            .get_da_chunk_index_by_block(self.backend.clone(), block_number)
+    }
+
+    fn get_block_number_by_batch_hash(&self, batch_hash: H256) -> Result<Option<u128>> {
+        self.rooch_store
+            .get_da_block_number_by_batch_hash(batch_hash)
+    }
+}
+
+/// Build the index of a submitted chunk, keep the created time if the chunk is rewritten
+pub fn build_chunk_index(
+    indexer: &dyn SegmentIndexer,
+    chunk: &ChunkV1,
+    segments: Vec<DASegmentIndex>,
+) -> Result<DAChunkIndex> {
+    let chunk_id = chunk.chunk_id();
+    let now = now_millis();
+    let created_at = indexer
+        .get_chunk_index(chunk_id)?
+        .map_or(now, |index| index.created_at);
+    Ok(DAChunkIndex {
+        chunk_id,
+        start_block_number: chunk_id,
+        end_block_number: chunk_id + chunk.batches.len() as u128 - 1,
+        batch_hashes: chunk.batches.iter().map(|batch| batch.batch_hash).collect(),
+        segment_count: segments.len() as u64,
+        segments,
+        created_at,
+        updated_at: now,
+    })
+}
+
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as u64)
+}
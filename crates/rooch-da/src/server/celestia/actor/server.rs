@@ -7,30 +7,35 @@ use celestia_types::nmt::Namespace;
 use coerce::actor::context::ActorContext;
 use coerce::actor::message::Handler;
 use coerce::actor::Actor;
+use std::sync::Arc;
+use std::time::Duration;
 
-use crate::chunk::DABatchV0;
+use crate::chunk::{ChunkBuilder, ChunkV1, DABatchV0, DEFAULT_MAX_CHUNK_AGE_SECS};
 use rooch_config::da_config::DAServerCelestiaConfig;
+use rooch_types::da::{DASegmentIndex, DASegmentLocation};
 
-use crate::messages::PutBatchInternalDAMessage;
-use crate::segment::{SegmentID, SegmentV0};
+use crate::messages::{
+    FlushChunkInternalDAMessage, GetBatchInternalDAMessage, PutBatchInternalDAMessage,
+    PutBatchState,
+};
+use crate::segment::Segment;
+use crate::segment_indexer::{build_chunk_index, now_millis, SegmentIndexer};
 use crate::server::celestia::backend::Backend;
 
 pub struct DAServerCelestiaActor {
     max_segment_size: usize,
     backend: Backend,
+    chunk_builder: ChunkBuilder,
+    // FEC is disabled if it's 0
+    fec_parity_segments: usize,
+    // the height & commitment of the submitted blobs are kept in the index
+    indexer: Arc<dyn SegmentIndexer>,
 }
 
-// TODO get request and response
-// 1. get by block number
-// 2. get by batch hash
-// 3. pull by stream
-//
-
 impl Actor for DAServerCelestiaActor {}
 
-// TODO add FEC get for SDC protection (wrong response attacks)
 impl DAServerCelestiaActor {
-    pub async fn new(cfg: &DAServerCelestiaConfig) -> Self {
+    pub async fn new(cfg: &DAServerCelestiaConfig, indexer: Arc<dyn SegmentIndexer>) -> Self {
         let namespace_str = cfg.namespace.as_ref().unwrap().clone();
         let namespace: Namespace = serde_yaml::from_str(&namespace_str).unwrap();
         let conn_str = cfg.conn.as_ref().unwrap().clone();
@@ -39,52 +44,80 @@ impl DAServerCelestiaActor {
         Self {
             max_segment_size: cfg.max_segment_size.unwrap() as usize,
             backend: Backend::new(namespace, &conn_str, &token).await,
+            chunk_builder: ChunkBuilder::new(
+                cfg.compression.unwrap_or_default(),
+                cfg.max_chunk_batches.unwrap_or(1) as usize,
+                Duration::from_secs(cfg.max_chunk_age_secs.unwrap_or(DEFAULT_MAX_CHUNK_AGE_SECS)),
+            ),
+            fec_parity_segments: cfg.fec_parity_segments.unwrap_or(0) as usize,
+            indexer,
         }
     }
 
     // TODO reuse public_batch logic in openda
-    pub async fn public_batch(&self, batch: PutBatchInternalDAMessage) -> Result<()> {
-        // TODO using chunk builder to make segments:
-        // 1. persist batch into buffer then return ok
-        // 2. collect batch for better compression ratio
-        // 3. split chunk into segments
-        // 4. submit segments to celestia node
-        // 5. record segment id in order
-        // 6. clean up batch buffer
-        // TODO more chunk version supports
-        let chunk = DABatchV0 {
-            version: 0,
-            block_number: batch.batch.block_number,
-            batch_hash: batch.batch.batch_hash,
-            data: batch.batch.data,
+    pub async fn public_batch(
+        &mut self,
+        batch: PutBatchInternalDAMessage,
+    ) -> Result<PutBatchState> {
+        // the buffered batches are kept in the DA outbox until the chunk is written, they are put again after restart
+        let batch = DABatchV0::new(
+            batch.batch.block_number,
+            batch.batch.batch_hash,
+            batch.batch.data,
+        );
+        let chunk = match self.chunk_builder.append(batch)? {
+            Some(chunk) => chunk,
+            None => return Ok(PutBatchState::Buffered),
         };
-        let chunk_bytes = bcs::to_bytes(&chunk).unwrap();
-        let segs = chunk_bytes.chunks(self.max_segment_size);
-        let total = segs.len();
+        // the buffer is kept if the chunk fails, the chunk is submitted again when the batch is retried
+        self.submit_chunk(&chunk).await?;
+        self.chunk_builder.clear();
+        Ok(PutBatchState::Written)
+    }
 
-        let chunk_id = batch.batch.block_number;
-        let segments = segs
-            .enumerate()
-            .map(|(i, data)| {
-                SegmentV0 {
-                    id: SegmentID {
-                        chunk_id,
-                        segment_number: i as u64,
-                    },
-                    is_last: i == total - 1, // extra info overhead is much smaller than max_block_size - max_segment_size
-                    data_checksum: 0,
-                    checksum: 0,
-                    data: data.to_vec(),
-                }
-            })
-            .collect::<Vec<_>>();
+    /// Write the buffered batches if the first one is older than the max chunk age,
+    /// so the batches of a quiet chain are not kept in the buffer forever.
+    pub async fn flush_chunk(&mut self) -> Result<Option<u128>> {
+        let chunk = match self.chunk_builder.build_expired()? {
+            Some(chunk) => chunk,
+            None => return Ok(None),
+        };
+        self.submit_chunk(&chunk).await?;
+        self.chunk_builder.clear();
+        Ok(chunk.batches.last().map(|batch| batch.block_number))
+    }
 
+    async fn submit_chunk(&self, chunk: &ChunkV1) -> Result<()> {
+        let segments = chunk.make_segments(self.max_segment_size, self.fec_parity_segments)?;
+        let mut segment_indexes = Vec::with_capacity(segments.len());
         for segment in segments {
-            // TODO record ok segment in order
-            // TODO segment indexer trait (local file, db, etc)
-            self.backend.submit(Box::new(segment)).await?;
+            let is_last = segment.is_last();
+            let result = self.backend.submit(segment).await?;
+            segment_indexes.push(DASegmentIndex {
+                segment_number: result.segment_id.segment_number,
+                is_last,
+                location: DASegmentLocation::Celestia {
+                    height: result.height,
+                    namespace: result.namespace.as_bytes().to_vec(),
+                    commitment: result.commitment.0.to_vec(),
+                },
+                submitted_at: now_millis(),
+            });
         }
-        Ok(())
+        self.indexer.index_chunk(build_chunk_index(
+            self.indexer.as_ref(),
+            chunk,
+            segment_indexes,
+        )?)
+    }
+
+    /// Celestia is write-only: a blob could only be got by the height & commitment kept in the
+    /// segment index of the node which submitted it, so the batches are read from OpenDA.
+    pub async fn get_batch(&self, msg: GetBatchInternalDAMessage) -> Result<Option<DABatchV0>> {
+        Err(anyhow::anyhow!(
+            "get batch {:?} is unsupported, celestia DA server is write-only",
+            msg.query
+        ))
     }
 }
 
@@ -94,7 +127,29 @@ impl Handler<PutBatchInternalDAMessage> for DAServerCelestiaActor {
         &mut self,
         msg: PutBatchInternalDAMessage,
         _ctx: &mut ActorContext,
-    ) -> Result<()> {
+    ) -> Result<PutBatchState> {
         self.public_batch(msg).await
     }
 }
+
+#[async_trait]
+impl Handler<FlushChunkInternalDAMessage> for DAServerCelestiaActor {
+    async fn handle(
+        &mut self,
+        _msg: FlushChunkInternalDAMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<u128>> {
+        self.flush_chunk().await
+    }
+}
+
+#[async_trait]
+impl Handler<GetBatchInternalDAMessage> for DAServerCelestiaActor {
+    async fn handle(
+        &mut self,
+        msg: GetBatchInternalDAMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<DABatchV0>> {
+        self.get_batch(msg).await
+    }
+}
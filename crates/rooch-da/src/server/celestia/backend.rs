@@ -14,6 +14,7 @@ pub struct Backend {
     client: Client,
 }
 
+#[derive(Clone, Debug)]
 pub struct SubmitBackendResult {
     pub segment_id: SegmentID,
     pub namespace: Namespace,
@@ -32,14 +33,13 @@ impl Backend {
         }
     }
 
-    // TODO return segment id, height, commitment
     pub async fn submit(&self, segment: Box<dyn Segment + Send>) -> Result<SubmitBackendResult> {
         let data = segment.to_bytes();
         let blob = Blob::new(self.namespace, data).unwrap();
         let segment_id = segment.get_id();
 
         // TODO tx manager
-        // TODO backoff retry
+        // failed segments are retried with backoff by DAActor
         match self
             .client
             .blob_submit(&[blob.clone()], SubmitOptions::default())
@@ -4,7 +4,11 @@
 use async_trait::async_trait;
 use coerce::actor::ActorRef;
 
-use crate::messages::PutBatchInternalDAMessage;
+use crate::chunk::DABatchV0;
+use crate::messages::{
+    FlushChunkInternalDAMessage, GetBatchInternalDAMessage, PutBatchInternalDAMessage,
+    PutBatchState,
+};
 use crate::server::celestia::actor::server::DAServerCelestiaActor;
 use crate::server::serverproxy::DAServerProxy;
 
@@ -18,14 +22,44 @@ impl DAServerCelestiaProxy {
         Self { actor }
     }
 
-    pub async fn submit_batch(&self, msg: PutBatchInternalDAMessage) -> anyhow::Result<()> {
+    pub async fn submit_batch(
+        &self,
+        msg: PutBatchInternalDAMessage,
+    ) -> anyhow::Result<PutBatchState> {
+        self.actor.send(msg).await?
+    }
+
+    pub async fn get_batch(
+        &self,
+        msg: GetBatchInternalDAMessage,
+    ) -> anyhow::Result<Option<DABatchV0>> {
+        self.actor.send(msg).await?
+    }
+
+    pub async fn flush_chunk(
+        &self,
+        msg: FlushChunkInternalDAMessage,
+    ) -> anyhow::Result<Option<u128>> {
         self.actor.send(msg).await?
     }
 }
 
 #[async_trait]
 impl DAServerProxy for DAServerCelestiaProxy {
-    async fn public_batch(&self, msg: PutBatchInternalDAMessage) -> anyhow::Result<()> {
+    async fn public_batch(&self, msg: PutBatchInternalDAMessage) -> anyhow::Result<PutBatchState> {
         self.submit_batch(msg).await
     }
+
+    async fn get_batch(&self, msg: GetBatchInternalDAMessage) -> anyhow::Result<Option<DABatchV0>> {
+        self.get_batch(msg).await
+    }
+
+    // celestia blobs could only be got by height & commitment, the batches are read from OpenDA
+    fn is_readable(&self) -> bool {
+        false
+    }
+
+    async fn flush_chunk(&self, msg: FlushChunkInternalDAMessage) -> anyhow::Result<Option<u128>> {
+        self.flush_chunk(msg).await
+    }
 }
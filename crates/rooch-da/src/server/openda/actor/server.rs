@@ -7,33 +7,44 @@ use coerce::actor::context::ActorContext;
 use coerce::actor::message::Handler;
 use coerce::actor::Actor;
 use opendal::layers::{LoggingLayer, RetryLayer};
-use opendal::{Operator, Scheme};
+use opendal::{ErrorKind, Operator, Scheme};
 use rooch_config::config::retrieve_map_config_value;
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
-use crate::chunk::DABatchV0;
+use crate::chunk::{
+    batches_from_raw_segments, find_batch, ChunkBuilder, ChunkV1, DABatchV0,
+    DEFAULT_MAX_CHUNK_AGE_SECS,
+};
+use moveos_types::h256::H256;
 use rooch_config::da_config::{DAServerOpenDAConfig, OpenDAScheme};
+use rooch_types::da::{DASegmentIndex, DASegmentLocation};
 
-use crate::messages::PutBatchInternalDAMessage;
-use crate::segment::{Segment, SegmentID, SegmentV0};
+use crate::messages::{
+    BatchQuery, FlushChunkInternalDAMessage, GetBatchInternalDAMessage, PutBatchInternalDAMessage,
+    PutBatchState,
+};
+use crate::segment::{segment_from_bytes, Segment, SegmentID, SegmentV2};
+use crate::segment_indexer::{build_chunk_index, now_millis, SegmentIndexer};
 
 pub struct DAServerOpenDAActor {
     max_segment_size: usize,
     operator: Operator,
+    chunk_builder: ChunkBuilder,
+    // FEC is disabled if it's 0
+    fec_parity_segments: usize,
+    indexer: Arc<dyn SegmentIndexer>,
 }
 
-// TODO get request and response
-// 1. get by block number
-// 2. get by batch hash
-// 3. pull by stream
-//
-
 impl Actor for DAServerOpenDAActor {}
 
-// TODO add FEC get for SDC protection (wrong response attacks)
 impl DAServerOpenDAActor {
-    pub async fn new(cfg: &DAServerOpenDAConfig) -> Result<DAServerOpenDAActor> {
+    pub async fn new(
+        cfg: &DAServerOpenDAConfig,
+        indexer: Arc<dyn SegmentIndexer>,
+    ) -> Result<DAServerOpenDAActor> {
         let mut config = cfg.clone();
 
         let op: Operator = match config.scheme {
@@ -110,63 +121,270 @@ impl DAServerOpenDAActor {
                 // After setting defaults, proceed with creating Operator
                 new_retry_operator(Scheme::Gcs, config.config, None).await?
             }
-            _ => Err(anyhow!("unsupported open-da scheme: {:?}", config.scheme))?,
+            OpenDAScheme::S3 => {
+                // If certain keys don't exist in the map, set them from environment
+                for (key, env_var) in [
+                    ("bucket", "OPENDA_S3_BUCKET"),
+                    ("root", "OPENDA_S3_ROOT"),
+                    ("endpoint", "OPENDA_S3_ENDPOINT"),
+                    ("access_key_id", "OPENDA_S3_ACCESS_KEY_ID"),
+                    ("secret_access_key", "OPENDA_S3_SECRET_ACCESS_KEY"),
+                ] {
+                    if !config.config.contains_key(key) {
+                        if let Ok(value) = std::env::var(env_var) {
+                            config.config.insert(key.to_string(), value);
+                        }
+                    }
+                }
+                // region is required for signing, S3-compatible services (e.g. MinIO) accept us-east-1
+                retrieve_map_config_value(
+                    &mut config.config,
+                    "region",
+                    Some("OPENDA_S3_REGION"),
+                    "us-east-1",
+                );
+
+                check_config_exist(OpenDAScheme::S3, &config.config, "bucket")?;
+                match (
+                    check_config_exist(OpenDAScheme::S3, &config.config, "access_key_id"),
+                    check_config_exist(OpenDAScheme::S3, &config.config, "secret_access_key"),
+                ) {
+                    (Ok(_), Ok(_)) => (),
+
+                    // credentials are loaded from environment by opendal (e.g. AWS_ACCESS_KEY_ID, IAM role)
+                    (Err(_), Err(_)) => (),
+
+                    _ => {
+                        return Err(anyhow!("'access_key_id' and 'secret_access_key' must exist together in config for scheme {:?}", OpenDAScheme::S3));
+                    }
+                }
+
+                new_retry_operator(Scheme::S3, config.config, None).await?
+            }
+            OpenDAScheme::Memory => new_retry_operator(Scheme::Memory, config.config, None).await?,
         };
 
         Ok(Self {
             max_segment_size: cfg.max_segment_size.unwrap_or(4 * 1024 * 1024) as usize,
             operator: op,
+            chunk_builder: ChunkBuilder::new(
+                cfg.compression.unwrap_or_default(),
+                cfg.max_chunk_batches.unwrap_or(1) as usize,
+                Duration::from_secs(cfg.max_chunk_age_secs.unwrap_or(DEFAULT_MAX_CHUNK_AGE_SECS)),
+            ),
+            fec_parity_segments: cfg.fec_parity_segments.unwrap_or(0) as usize,
+            indexer,
         })
     }
 
-    pub async fn pub_batch(&self, batch: PutBatchInternalDAMessage) -> Result<()> {
-        // TODO using chunk builder to make segments:
-        // 1. persist batch into buffer then return ok
-        // 2. collect batch for better compression ratio
-        // 3. split chunk into segments
-        // 4. submit segments to celestia node
-        // 5. record segment id in order
-        // 6. clean up batch buffer
-
-        // TODO more chunk version supports
-        let chunk = DABatchV0 {
-            version: 0,
-            block_number: batch.batch.block_number,
-            batch_hash: batch.batch.batch_hash,
-            data: batch.batch.data,
+    pub async fn pub_batch(&mut self, batch: PutBatchInternalDAMessage) -> Result<PutBatchState> {
+        // the buffered batches are kept in the DA outbox until the chunk is written, they are put again after restart
+        let batch = DABatchV0::new(
+            batch.batch.block_number,
+            batch.batch.batch_hash,
+            batch.batch.data,
+        );
+        let chunk = match self.chunk_builder.append(batch)? {
+            Some(chunk) => chunk,
+            None => return Ok(PutBatchState::Buffered),
+        };
+        // the buffer is kept if the chunk fails, the chunk is submitted again when the batch is retried
+        self.write_chunk(&chunk).await?;
+        self.chunk_builder.clear();
+        Ok(PutBatchState::Written)
+    }
+
+    /// Write the buffered batches if the first one is older than the max chunk age,
+    /// so the batches of a quiet chain are not kept in the buffer forever.
+    pub async fn flush_chunk(&mut self) -> Result<Option<u128>> {
+        let chunk = match self.chunk_builder.build_expired()? {
+            Some(chunk) => chunk,
+            None => return Ok(None),
         };
-        let chunk_bytes = bcs::to_bytes(&chunk).unwrap();
-        let segs = chunk_bytes.chunks(self.max_segment_size);
-        let total = segs.len();
-
-        // TODO explain why block number is a good idea: easy to get next block number for segments, then we could request chunk by block number
-        let chunk_id = batch.batch.block_number;
-        let segments = segs
-            .enumerate()
-            .map(|(i, data)| {
-                SegmentV0 {
-                    id: SegmentID {
-                        chunk_id,
-                        segment_number: i as u64,
+        self.write_chunk(&chunk).await?;
+        self.chunk_builder.clear();
+        Ok(chunk.batches.last().map(|batch| batch.block_number))
+    }
+
+    // chunk_id is the first block number in the chunk:
+    // easy to get next chunk id for segments, then we could request chunk by block number
+    async fn write_chunk(&self, chunk: &ChunkV1) -> Result<()> {
+        let chunk_id = chunk.chunk_id();
+        let mut segment_indexes = Vec::new();
+        for segment in chunk.make_segments(self.max_segment_size, self.fec_parity_segments)? {
+            let segment_id = segment.get_id();
+            let path = segment_id.to_string();
+            self.operator.write(&path, segment.to_bytes()).await?; // failed chunk is retried by DAActor
+            segment_indexes.push(DASegmentIndex {
+                segment_number: segment_id.segment_number,
+                is_last: segment.is_last(),
+                location: DASegmentLocation::OpenDA { path },
+                submitted_at: now_millis(),
+            });
+        }
+        self.indexer.index_chunk(build_chunk_index(
+            self.indexer.as_ref(),
+            chunk,
+            segment_indexes,
+        )?)?;
+        // indexes are written after all segments, so a batch found by them is complete
+        for batch in chunk.batches.iter() {
+            if batch.block_number != chunk_id {
+                self.operator
+                    .write(
+                        &block_chunk_path(batch.block_number),
+                        bcs::to_bytes(&chunk_id)?,
+                    )
+                    .await?;
+            }
+            self.operator
+                .write(
+                    &batch_hash_path(batch.batch_hash),
+                    bcs::to_bytes(&batch.block_number)?,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn get_batch(&self, msg: GetBatchInternalDAMessage) -> Result<Option<DABatchV0>> {
+        if let Some(batch) = self.chunk_builder.get_batch(&msg.query) {
+            return Ok(Some(batch));
+        }
+
+        // local index first, then the index objects in the storage
+        let block_number = match &msg.query {
+            BatchQuery::BlockNumber(block_number) => *block_number,
+            BatchQuery::BatchHash(batch_hash) => {
+                match self.indexer.get_block_number_by_batch_hash(*batch_hash)? {
+                    Some(block_number) => block_number,
+                    None => match self.read_object(&batch_hash_path(*batch_hash)).await? {
+                        Some(bytes) => bcs::from_bytes::<u128>(&bytes)?,
+                        None => return Ok(None),
                     },
-                    is_last: i == total - 1, // extra info overhead is much smaller than max_block_size - max_segment_size
-                    data_checksum: 0,
-                    checksum: 0,
-                    data: data.to_vec(),
                 }
-            })
-            .collect::<Vec<_>>();
+            }
+        };
+        if let Some(index) = self.indexer.get_chunk_index_by_block(block_number)? {
+            if index.is_complete() {
+                // missing segments are left to FEC
+                let mut raw_segments = Vec::with_capacity(index.segments.len());
+                for segment_index in index.segments.iter() {
+                    let path = match &segment_index.location {
+                        DASegmentLocation::OpenDA { path } => path,
+                        location => {
+                            return Err(anyhow!("unexpected segment location: {:?}", location))
+                        }
+                    };
+                    raw_segments.push(self.read_object(path).await?);
+                }
+                return find_batch_in_chunk(index.chunk_id, raw_segments, &msg.query);
+            }
+        }
 
-        for segment in segments {
-            let bytes = segment.to_bytes();
+        // a block without chunk index is the first block of its chunk
+        let chunk_id = match self.read_object(&block_chunk_path(block_number)).await? {
+            Some(bytes) => bcs::from_bytes::<u128>(&bytes)?,
+            None => block_number,
+        };
 
-            // TODO record ok segment in order
-            // TODO segment indexer trait (local file, db, etc)
-            self.operator.write(&segment.id.to_string(), bytes).await?; // TODO retry logic
+        // read segments in order until the last one,
+        // the segments count of FEC chunk is known from any valid segment: missing ones are skipped
+        let mut raw_segments: Vec<Option<Vec<u8>>> = Vec::new();
+        let mut fec_total_segments: Option<u64> = None;
+        loop {
+            let segment_number = raw_segments.len() as u64;
+            if fec_total_segments.map_or(false, |total| segment_number >= total) {
+                break;
+            }
+            let segment_id = SegmentID {
+                chunk_id,
+                segment_number,
+            };
+            let bytes = match self.read_object(&segment_id.to_string()).await? {
+                Some(bytes) => bytes,
+                None if fec_total_segments.is_some() => {
+                    raw_segments.push(None);
+                    continue;
+                }
+                None if raw_segments.is_empty() => match self.probe_fec_segment(chunk_id).await? {
+                    Some((segment_number, bytes)) => {
+                        raw_segments.resize(segment_number as usize, None);
+                        bytes
+                    }
+                    None => return Ok(None),
+                },
+                None => {
+                    return Err(anyhow!(
+                        "segment {} not found, chunk {} is incomplete",
+                        segment_id,
+                        chunk_id
+                    ))
+                }
+            };
+            if fec_total_segments.is_none() {
+                fec_total_segments = SegmentV2::from_bytes(&bytes)
+                    .ok()
+                    .map(|segment| segment.total_segments());
+            }
+            let is_last = segment_from_bytes(&bytes).map_or(false, |segment| segment.is_last());
+            raw_segments.push(Some(bytes));
+            if is_last {
+                break;
+            }
         }
 
-        Ok(())
+        find_batch_in_chunk(chunk_id, raw_segments, &msg.query)
+    }
+
+    // The first segment of the chunk is missing, probe the following segments for a FEC segment,
+    // its header carries the coding parameters of the chunk. A FEC chunk is only recoverable if no more than
+    // parity segments are missing, so a valid segment is found within them if the chunk is recoverable.
+    async fn probe_fec_segment(&self, chunk_id: u128) -> Result<Option<(u64, Vec<u8>)>> {
+        for segment_number in 1..=self.fec_parity_segments as u64 {
+            let segment_id = SegmentID {
+                chunk_id,
+                segment_number,
+            };
+            if let Some(bytes) = self.read_object(&segment_id.to_string()).await? {
+                match SegmentV2::from_bytes(&bytes) {
+                    Ok(segment) if segment.id == segment_id => {
+                        return Ok(Some((segment_number, bytes)))
+                    }
+                    _ => log::warn!("invalid FEC segment {}, ignored", segment_id),
+                }
+            }
+        }
+        Ok(None)
     }
+
+    async fn read_object(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        match self.operator.read(path).await {
+            Ok(buffer) => Ok(Some(buffer.to_vec())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+fn find_batch_in_chunk(
+    chunk_id: u128,
+    raw_segments: Vec<Option<Vec<u8>>>,
+    query: &BatchQuery,
+) -> Result<Option<DABatchV0>> {
+    let batches = batches_from_raw_segments(chunk_id, raw_segments)?;
+    match find_batch(batches, query) {
+        Some(batch) => Ok(Some(batch)),
+        None => Err(anyhow!("batch {:?} not found in chunk {}", query, chunk_id)),
+    }
+}
+
+fn batch_hash_path(batch_hash: H256) -> String {
+    format!("batch_hash/{:#x}", batch_hash)
+}
+
+fn block_chunk_path(block_number: u128) -> String {
+    format!("block_chunk/{}", block_number)
 }
 
 fn check_config_exist(
@@ -205,7 +423,313 @@ impl Handler<PutBatchInternalDAMessage> for DAServerOpenDAActor {
         &mut self,
         msg: PutBatchInternalDAMessage,
         _ctx: &mut ActorContext,
-    ) -> Result<()> {
+    ) -> Result<PutBatchState> {
         self.pub_batch(msg).await
     }
 }
+
+#[async_trait]
+impl Handler<FlushChunkInternalDAMessage> for DAServerOpenDAActor {
+    async fn handle(
+        &mut self,
+        _msg: FlushChunkInternalDAMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<u128>> {
+        self.flush_chunk().await
+    }
+}
+
+#[async_trait]
+impl Handler<GetBatchInternalDAMessage> for DAServerOpenDAActor {
+    async fn handle(
+        &mut self,
+        msg: GetBatchInternalDAMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<DABatchV0>> {
+        self.get_batch(msg).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::Batch;
+    use crate::segment_indexer::RocksDBSegmentIndexer;
+    use moveos_types::h256;
+    use rooch_config::da_config::DAChunkCompression;
+    use rooch_store::RoochStore;
+
+    #[tokio::test]
+    async fn test_memory_scheme_put_get_batch() {
+        let (rooch_store, _tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let cfg = DAServerOpenDAConfig {
+            scheme: OpenDAScheme::Memory,
+            max_segment_size: Some(64),
+            compression: Some(DAChunkCompression::Zstd),
+            max_chunk_batches: Some(2),
+            ..Default::default()
+        };
+        let indexer = Arc::new(RocksDBSegmentIndexer::new(
+            "DAServerOpenDA-memory".to_string(),
+            rooch_store,
+        ));
+        let mut server = DAServerOpenDAActor::new(&cfg, indexer).await.unwrap();
+
+        let mut batch_hashes = Vec::new();
+        for block_number in 0..3u128 {
+            let data = vec![block_number as u8; 200];
+            let batch_hash = h256::sha3_256_of(&data);
+            batch_hashes.push(batch_hash);
+            server
+                .pub_batch(PutBatchInternalDAMessage {
+                    batch: Batch {
+                        block_number,
+                        batch_hash,
+                        data,
+                    },
+                })
+                .await
+                .unwrap();
+        }
+
+        // block 0 and 1 are in the chunk 0, block 2 is still in the builder
+        for block_number in 0..3u128 {
+            let batch = server
+                .get_batch(GetBatchInternalDAMessage {
+                    query: BatchQuery::BlockNumber(block_number),
+                })
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(batch.block_number, block_number);
+        }
+        let batch = server
+            .get_batch(GetBatchInternalDAMessage {
+                query: BatchQuery::BatchHash(batch_hashes[1]),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(batch.block_number, 1);
+        assert!(server
+            .get_batch(GetBatchInternalDAMessage {
+                query: BatchQuery::BlockNumber(3),
+            })
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_flush_expired_chunk() {
+        let (rooch_store, _tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let cfg = DAServerOpenDAConfig {
+            scheme: OpenDAScheme::Memory,
+            max_chunk_batches: Some(4),
+            max_chunk_age_secs: Some(0),
+            ..Default::default()
+        };
+        let indexer = Arc::new(RocksDBSegmentIndexer::new(
+            "DAServerOpenDA-flush".to_string(),
+            rooch_store,
+        ));
+        let mut server = DAServerOpenDAActor::new(&cfg, indexer).await.unwrap();
+        assert_eq!(server.flush_chunk().await.unwrap(), None);
+
+        let data = vec![1u8; 100];
+        let batch_hash = h256::sha3_256_of(&data);
+        let state = server
+            .pub_batch(PutBatchInternalDAMessage {
+                batch: Batch {
+                    block_number: 0,
+                    batch_hash,
+                    data,
+                },
+            })
+            .await
+            .unwrap();
+        assert_eq!(state, PutBatchState::Buffered);
+
+        // the chunk is not full, it's written because the batch is buffered longer than the max age
+        assert_eq!(server.flush_chunk().await.unwrap(), Some(0));
+        assert!(server.chunk_builder.is_empty());
+        let batch = server
+            .get_batch(GetBatchInternalDAMessage {
+                query: BatchQuery::BatchHash(batch_hash),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(batch.block_number, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_fec_chunk_without_first_segment() {
+        let (rooch_store, tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let root = tmpdir.path().join("openda");
+        let mut config = HashMap::new();
+        config.insert("root".to_string(), root.to_string_lossy().to_string());
+        let cfg = DAServerOpenDAConfig {
+            scheme: OpenDAScheme::Fs,
+            config,
+            max_segment_size: Some(64),
+            compression: Some(DAChunkCompression::None),
+            max_chunk_batches: Some(2),
+            fec_parity_segments: Some(2),
+            ..Default::default()
+        };
+        let indexer = Arc::new(RocksDBSegmentIndexer::new(
+            "DAServerOpenDA-fec".to_string(),
+            rooch_store,
+        ));
+        let mut server = DAServerOpenDAActor::new(&cfg, indexer).await.unwrap();
+        let batches = (0..2u128)
+            .map(|block_number| {
+                let data = vec![block_number as u8; 100];
+                Batch {
+                    block_number,
+                    batch_hash: h256::sha3_256_of(&data),
+                    data,
+                }
+            })
+            .collect::<Vec<_>>();
+        for batch in batches.iter() {
+            server
+                .pub_batch(PutBatchInternalDAMessage {
+                    batch: batch.clone(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let first_segment = SegmentID {
+            chunk_id: 0,
+            segment_number: 0,
+        };
+        std::fs::remove_file(root.join(first_segment.to_string())).unwrap();
+
+        // the reader has no index of the chunk, the layout is learnt from the following FEC segments
+        let (reader_store, _reader_tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let reader_indexer = Arc::new(RocksDBSegmentIndexer::new(
+            "DAServerOpenDA-fec".to_string(),
+            reader_store,
+        ));
+        let reader = DAServerOpenDAActor::new(&cfg, reader_indexer)
+            .await
+            .unwrap();
+        for batch in batches {
+            let got = reader
+                .get_batch(GetBatchInternalDAMessage {
+                    query: BatchQuery::BlockNumber(batch.block_number),
+                })
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(got.data, batch.data);
+            let got = reader
+                .get_batch(GetBatchInternalDAMessage {
+                    query: BatchQuery::BatchHash(batch.batch_hash),
+                })
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(got.block_number, batch.block_number);
+        }
+        assert!(reader
+            .get_batch(GetBatchInternalDAMessage {
+                query: BatchQuery::BlockNumber(2),
+            })
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_s3_scheme_config_check() {
+        let (rooch_store, _tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let mut config = HashMap::new();
+        config.insert("bucket".to_string(), "rooch-da".to_string());
+        config.insert("access_key_id".to_string(), "minioadmin".to_string());
+        let cfg = DAServerOpenDAConfig {
+            scheme: OpenDAScheme::S3,
+            config,
+            ..Default::default()
+        };
+        let indexer = Arc::new(RocksDBSegmentIndexer::new(
+            "DAServerOpenDA-s3".to_string(),
+            rooch_store,
+        ));
+        // secret_access_key is missing
+        assert!(DAServerOpenDAActor::new(&cfg, indexer).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_retry_batch_after_write_failure() {
+        let (rooch_store, tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let root = tmpdir.path().join("openda");
+        let mut config = HashMap::new();
+        config.insert("root".to_string(), root.to_string_lossy().to_string());
+        let cfg = DAServerOpenDAConfig {
+            scheme: OpenDAScheme::Fs,
+            config,
+            max_chunk_batches: Some(2),
+            ..Default::default()
+        };
+        let indexer = Arc::new(RocksDBSegmentIndexer::new(
+            "DAServerOpenDA-retry".to_string(),
+            rooch_store,
+        ));
+        let mut server = DAServerOpenDAActor::new(&cfg, indexer).await.unwrap();
+
+        let batches = (0..2u128)
+            .map(|block_number| {
+                let data = vec![block_number as u8; 100];
+                Batch {
+                    block_number,
+                    batch_hash: h256::sha3_256_of(&data),
+                    data,
+                }
+            })
+            .collect::<Vec<_>>();
+        let state = server
+            .pub_batch(PutBatchInternalDAMessage {
+                batch: batches[0].clone(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(state, PutBatchState::Buffered);
+
+        // the root dir is replaced by a file, the chunk of block 0 and 1 fails to write
+        std::fs::remove_dir_all(&root).unwrap();
+        std::fs::write(&root, b"").unwrap();
+        assert!(server
+            .pub_batch(PutBatchInternalDAMessage {
+                batch: batches[1].clone(),
+            })
+            .await
+            .is_err());
+
+        // retry the same block after the storage recovers
+        std::fs::remove_file(&root).unwrap();
+        std::fs::create_dir_all(&root).unwrap();
+        let state = server
+            .pub_batch(PutBatchInternalDAMessage {
+                batch: batches[1].clone(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(state, PutBatchState::Written);
+        assert!(server.chunk_builder.is_empty());
+        for batch in batches {
+            let got = server
+                .get_batch(GetBatchInternalDAMessage {
+                    query: BatchQuery::BatchHash(batch.batch_hash),
+                })
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(got.block_number, batch.block_number);
+            assert_eq!(got.data, batch.data);
+        }
+    }
+}
@@ -1,7 +1,11 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::messages::PutBatchInternalDAMessage;
+use crate::chunk::DABatchV0;
+use crate::messages::{
+    FlushChunkInternalDAMessage, GetBatchInternalDAMessage, PutBatchInternalDAMessage,
+    PutBatchState,
+};
 use crate::server::openda::actor::server::DAServerOpenDAActor;
 use crate::server::serverproxy::DAServerProxy;
 use async_trait::async_trait;
@@ -17,14 +21,39 @@ impl DAServerOpenDAProxy {
         Self { actor }
     }
 
-    pub async fn submit_batch(&self, msg: PutBatchInternalDAMessage) -> anyhow::Result<()> {
+    pub async fn submit_batch(
+        &self,
+        msg: PutBatchInternalDAMessage,
+    ) -> anyhow::Result<PutBatchState> {
+        self.actor.send(msg).await?
+    }
+
+    pub async fn get_batch(
+        &self,
+        msg: GetBatchInternalDAMessage,
+    ) -> anyhow::Result<Option<DABatchV0>> {
+        self.actor.send(msg).await?
+    }
+
+    pub async fn flush_chunk(
+        &self,
+        msg: FlushChunkInternalDAMessage,
+    ) -> anyhow::Result<Option<u128>> {
         self.actor.send(msg).await?
     }
 }
 
 #[async_trait]
 impl DAServerProxy for DAServerOpenDAProxy {
-    async fn public_batch(&self, msg: PutBatchInternalDAMessage) -> anyhow::Result<()> {
+    async fn public_batch(&self, msg: PutBatchInternalDAMessage) -> anyhow::Result<PutBatchState> {
         self.submit_batch(msg).await
     }
+
+    async fn get_batch(&self, msg: GetBatchInternalDAMessage) -> anyhow::Result<Option<DABatchV0>> {
+        self.get_batch(msg).await
+    }
+
+    async fn flush_chunk(&self, msg: FlushChunkInternalDAMessage) -> anyhow::Result<Option<u128>> {
+        self.flush_chunk(msg).await
+    }
 }
@@ -4,11 +4,23 @@
 use anyhow::Result;
 use async_trait::async_trait;
 
-use crate::messages::PutBatchInternalDAMessage;
+use crate::chunk::DABatchV0;
+use crate::messages::{
+    FlushChunkInternalDAMessage, GetBatchInternalDAMessage, PutBatchInternalDAMessage,
+    PutBatchState,
+};
 
 #[async_trait]
 pub trait DAServerProxy: Sync + Send {
-    async fn public_batch(&self, request: PutBatchInternalDAMessage) -> Result<()>;
+    /// Put the batch into the server, the batches must be put in block number order.
+    async fn public_batch(&self, request: PutBatchInternalDAMessage) -> Result<PutBatchState>;
+    async fn get_batch(&self, request: GetBatchInternalDAMessage) -> Result<Option<DABatchV0>>;
+    /// Whether the batches could be got from the server, a write-only server is skipped by the reads.
+    fn is_readable(&self) -> bool {
+        true
+    }
+    /// Write the buffered batches which are older than the max chunk age, return the last block number written.
+    async fn flush_chunk(&self, request: FlushChunkInternalDAMessage) -> Result<Option<u128>>;
 }
 
 // DAServerNopProxy is a no-op implementation of DAServerProxy
@@ -16,7 +28,15 @@ pub struct DAServerNopProxy;
 
 #[async_trait]
 impl DAServerProxy for DAServerNopProxy {
-    async fn public_batch(&self, _request: PutBatchInternalDAMessage) -> Result<()> {
-        Ok(())
+    async fn public_batch(&self, _request: PutBatchInternalDAMessage) -> Result<PutBatchState> {
+        Ok(PutBatchState::Written)
+    }
+
+    async fn get_batch(&self, _request: GetBatchInternalDAMessage) -> Result<Option<DABatchV0>> {
+        Ok(None)
+    }
+
+    async fn flush_chunk(&self, _request: FlushChunkInternalDAMessage) -> Result<Option<u128>> {
+        Ok(None)
     }
 }
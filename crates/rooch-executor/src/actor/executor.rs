@@ -164,7 +164,49 @@ impl ExecutorActor {
         }
     }
 
-    pub fn validate_l2_tx(&self, mut tx: RoochTransaction) -> Result<VerifiedMoveOSTransaction> {
+    pub fn validate_l2_tx(&self, tx: RoochTransaction) -> Result<VerifiedMoveOSTransaction> {
+        self.l2_tx_validator().validate_l2_tx(tx)
+    }
+
+    pub fn validate_authenticator(
+        &self,
+        ctx: &TxContext,
+        authenticator: AuthenticatorInfo,
+    ) -> Result<ValidateAuthenticatorResult> {
+        self.l2_tx_validator()
+            .validate_authenticator(ctx, authenticator)
+    }
+
+    fn l2_tx_validator(&self) -> L2TxValidator<'_> {
+        L2TxValidator::new(&self.root, &self.moveos, &self.moveos_store)
+    }
+}
+
+/// L2TxValidator validates the L2 transactions against the root object,
+/// it is shared by the writer executor and the reader executor.
+pub(crate) struct L2TxValidator<'a> {
+    root: &'a ObjectMeta,
+    moveos: &'a MoveOS,
+    moveos_store: &'a MoveOSStore,
+}
+
+impl<'a> L2TxValidator<'a> {
+    pub(crate) fn new(
+        root: &'a ObjectMeta,
+        moveos: &'a MoveOS,
+        moveos_store: &'a MoveOSStore,
+    ) -> Self {
+        Self {
+            root,
+            moveos,
+            moveos_store,
+        }
+    }
+
+    pub(crate) fn validate_l2_tx(
+        &self,
+        mut tx: RoochTransaction,
+    ) -> Result<VerifiedMoveOSTransaction> {
         let sender = tx.sender();
         let tx_hash = tx.tx_hash();
 
@@ -199,7 +241,7 @@ impl ExecutorActor {
                     }
                 }
                 Err(e) => {
-                    let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
+                    let resolver = RootObjectResolver::new(self.root.clone(), self.moveos_store);
                     let status_view = explain_vm_status(&resolver, e.clone())?;
                     warn!(
                         "transaction validate vm error, tx_hash: {}, error:{:?}",
@@ -220,7 +262,7 @@ impl ExecutorActor {
         }
     }
 
-    pub fn validate_authenticator(
+    pub(crate) fn validate_authenticator(
         &self,
         ctx: &TxContext,
         authenticator: AuthenticatorInfo,
@@ -273,6 +315,14 @@ impl ExecutorActor {
     }
 }
 
+impl MoveFunctionCaller for L2TxValidator<'_> {
+    fn call_function(&self, ctx: &TxContext, call: FunctionCall) -> Result<FunctionResult> {
+        Ok(self
+            .moveos
+            .execute_readonly_function(self.root.clone(), ctx, call))
+    }
+}
+
 impl Actor for ExecutorActor {}
 
 #[async_trait]
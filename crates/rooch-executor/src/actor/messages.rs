@@ -11,8 +11,10 @@ use moveos_types::h256::H256;
 use moveos_types::moveos_std::event::{AnnotatedEvent, Event, EventID};
 use moveos_types::moveos_std::object::ObjectMeta;
 use moveos_types::state::{AnnotatedState, FieldKey, ObjectState};
+use moveos_types::state_proof::StateWithProof;
 use moveos_types::state_resolver::{AnnotatedStateKV, StateKV};
 use moveos_types::transaction::FunctionCall;
+use moveos_types::transaction::RawTransactionOutput;
 use moveos_types::transaction::TransactionExecutionInfo;
 use moveos_types::transaction::TransactionOutput;
 use moveos_types::transaction::VerifiedMoveOSTransaction;
@@ -62,24 +64,60 @@ impl Message for ExecuteTransactionMessage {
     type Result = Result<ExecuteTransactionResult>;
 }
 
+/// Validate and execute the L2 transaction on the latest state without applying the output
+#[derive(Debug)]
+pub struct DryRunTransactionMessage {
+    pub tx: RoochTransaction,
+}
+
+impl Message for DryRunTransactionMessage {
+    type Result = Result<RawTransactionOutput>;
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExecuteViewFunctionMessage {
     pub call: FunctionCall,
+    /// Read at the given root object, or the latest root object if None
+    pub root: Option<ObjectMeta>,
 }
 
 impl Message for ExecuteViewFunctionMessage {
     type Result = Result<AnnotatedFunctionResult, anyhow::Error>;
 }
 
+/// Get the root object after the transaction of the tx order is executed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetRootByTxOrderMessage {
+    pub tx_order: u64,
+}
+
+impl Message for GetRootByTxOrderMessage {
+    type Result = Result<ObjectMeta>;
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StatesMessage {
     pub access_path: AccessPath,
+    /// Read at the given root object, or the latest root object if None
+    pub root: Option<ObjectMeta>,
 }
 
 impl Message for StatesMessage {
     type Result = Result<Vec<Option<ObjectState>>>;
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatesWithProofMessage {
+    pub access_path: AccessPath,
+    /// Read at the given root object, or the latest root object if None
+    pub root: Option<ObjectMeta>,
+}
+
+impl Message for StatesWithProofMessage {
+    /// The state root of the proofs and the states with proofs
+    type Result = Result<(H256, Vec<StateWithProof>)>;
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ResolveMessage {
     pub address: MultiChainAddress,
@@ -92,6 +130,8 @@ impl Message for ResolveMessage {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AnnotatedStatesMessage {
     pub access_path: AccessPath,
+    /// Read at the given root object, or the latest root object if None
+    pub root: Option<ObjectMeta>,
 }
 
 impl Message for AnnotatedStatesMessage {
@@ -103,6 +143,8 @@ pub struct ListStatesMessage {
     pub access_path: AccessPath,
     pub cursor: Option<FieldKey>,
     pub limit: usize,
+    /// Read at the given root object, or the latest root object if None
+    pub root: Option<ObjectMeta>,
 }
 
 impl Message for ListStatesMessage {
@@ -114,6 +156,8 @@ pub struct ListAnnotatedStatesMessage {
     pub access_path: AccessPath,
     pub cursor: Option<FieldKey>,
     pub limit: usize,
+    /// Read at the given root object, or the latest root object if None
+    pub root: Option<ObjectMeta>,
 }
 
 impl Message for ListAnnotatedStatesMessage {
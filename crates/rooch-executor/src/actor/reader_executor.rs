@@ -1,17 +1,20 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use super::executor::L2TxValidator;
 use super::messages::{
-    AnnotatedStatesMessage, ExecuteViewFunctionMessage, GetAnnotatedEventsByEventHandleMessage,
-    GetEventsByEventHandleMessage, RefreshStateMessage, StatesMessage,
+    AnnotatedStatesMessage, DryRunTransactionMessage, ExecuteViewFunctionMessage,
+    GetAnnotatedEventsByEventHandleMessage, GetEventsByEventHandleMessage, GetRootByTxOrderMessage,
+    RefreshStateMessage, StatesMessage,
 };
 use crate::actor::messages::{
     GetEventsByEventIDsMessage, GetTxExecutionInfosByHashMessage, ListAnnotatedStatesMessage,
-    ListStatesMessage,
+    ListStatesMessage, StatesWithProofMessage,
 };
-use anyhow::Result;
+use anyhow::{anyhow, ensure, Result};
 use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor};
+use move_core_types::vm_status::StatusCode;
 use move_resource_viewer::MoveValueAnnotator;
 use moveos::moveos::MoveOS;
 use moveos::moveos::MoveOSConfig;
@@ -19,16 +22,19 @@ use moveos_store::transaction_store::TransactionStore;
 use moveos_store::MoveOSStore;
 use moveos_types::function_return_value::AnnotatedFunctionResult;
 use moveos_types::function_return_value::AnnotatedFunctionReturnValue;
+use moveos_types::h256::H256;
 use moveos_types::moveos_std::event::EventHandle;
 use moveos_types::moveos_std::event::{AnnotatedEvent, Event};
 use moveos_types::moveos_std::object::ObjectMeta;
 use moveos_types::state::{AnnotatedState, ObjectState};
+use moveos_types::state_proof::StateWithProof;
 use moveos_types::state_resolver::RootObjectResolver;
 use moveos_types::state_resolver::{AnnotatedStateKV, AnnotatedStateReader, StateKV, StateReader};
-use moveos_types::transaction::TransactionExecutionInfo;
+use moveos_types::transaction::{FunctionCall, RawTransactionOutput, TransactionExecutionInfo};
 use rooch_genesis::FrameworksGasParameters;
 use rooch_store::RoochStore;
 use rooch_types::framework::{system_post_execute_functions, system_pre_execute_functions};
+use rooch_types::transaction::RoochTransaction;
 
 pub struct ReaderExecutorActor {
     root: ObjectMeta,
@@ -43,16 +49,7 @@ impl ReaderExecutorActor {
         moveos_store: MoveOSStore,
         rooch_store: RoochStore,
     ) -> Result<Self> {
-        let resolver = RootObjectResolver::new(root.clone(), &moveos_store);
-        let gas_parameters = FrameworksGasParameters::load_from_chain(&resolver)?;
-        let moveos = MoveOS::new(
-            moveos_store.clone(),
-            gas_parameters.all_natives(),
-            MoveOSConfig::default(),
-            system_pre_execute_functions(),
-            system_post_execute_functions(),
-        )?;
-
+        let moveos = new_moveos(&root, &moveos_store)?;
         Ok(Self {
             root,
             moveos,
@@ -69,25 +66,92 @@ impl ReaderExecutorActor {
         &self.moveos
     }
 
-    pub fn refresh_state(&mut self, root: ObjectMeta, is_upgrade: bool) -> Result<()> {
-        self.root = root;
-        self.moveos.flush_module_cache(is_upgrade)
+    /// Get the root object to read from, the latest root object if `root` is None.
+    /// Return an error if the state tree of the `root` has been pruned.
+    fn resolve_root(&self, root: Option<ObjectMeta>) -> Result<ObjectMeta> {
+        match root {
+            Some(root) => {
+                let state_root = root.state_root();
+                ensure!(
+                    self.moveos_store
+                        .get_state_store()
+                        .contains_state_root(state_root)?,
+                    "The state root {:?} is not found, it may have been pruned",
+                    state_root
+                );
+                Ok(root)
+            }
+            None => Ok(self.root.clone()),
+        }
     }
-}
 
-impl Actor for ReaderExecutorActor {}
+    /// Validate and execute the transaction against the latest root, the output is not applied to the state.
+    /// The dry run is executed by the reader, so it does not block the execution of the writer.
+    pub fn dry_run(&self, tx: RoochTransaction) -> Result<RawTransactionOutput> {
+        let verified_tx =
+            L2TxValidator::new(&self.root, &self.moveos, &self.moveos_store).validate_l2_tx(tx)?;
+        let output = self.moveos.execute(verified_tx)?;
+        // The published modules of the dry run are not committed, the loaded modules should be reloaded
+        self.moveos.flush_module_cache(output.is_upgrade)?;
+        Ok(output)
+    }
 
-#[async_trait]
-impl Handler<ExecuteViewFunctionMessage> for ReaderExecutorActor {
-    async fn handle(
-        &mut self,
-        msg: ExecuteViewFunctionMessage,
-        _ctx: &mut ActorContext,
-    ) -> Result<AnnotatedFunctionResult, anyhow::Error> {
-        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
-        let function_result = self
-            .moveos()
-            .execute_view_function(self.root.clone(), msg.call);
+    /// The historical root is not the latest root, it is read without the caches of the reader.
+    fn is_historical(&self, root: &ObjectMeta) -> bool {
+        root.state_root() != self.root.state_root()
+    }
+
+    /// Read at the `root` by the `read`, or at the latest root object if `root` is None.
+    /// `resolve_root` only checks the top node of the state tree, the tree of a historical root may be
+    /// partially pruned, so the error of reading a historical root is reported as pruned.
+    fn read_at<T, F>(&self, root: Option<ObjectMeta>, read: F) -> Result<T>
+    where
+        F: FnOnce(ObjectMeta) -> Result<T>,
+    {
+        let root = self.resolve_root(root)?;
+        if !self.is_historical(&root) {
+            return read(root);
+        }
+        let state_root = root.state_root();
+        read(root).map_err(|e| {
+            e.context(format!(
+                "Failed to read the state root {:?}, it may have been pruned",
+                state_root
+            ))
+        })
+    }
+
+    /// Execute the view function at the `root`, or at the latest root object if `root` is None.
+    pub fn execute_view_function(
+        &self,
+        root: Option<ObjectMeta>,
+        call: FunctionCall,
+    ) -> Result<AnnotatedFunctionResult> {
+        self.read_at(root, |root| {
+            if !self.is_historical(&root) {
+                return self.annotated_view_function(&self.moveos, root, call);
+            }
+            // The modules and the gas schedule of a historical root are loaded by a new MoveOS,
+            // so they do not pollute the caches of the reader, which follow the latest root.
+            let moveos = new_moveos(&root, &self.moveos_store)?;
+            let result = self.annotated_view_function(&moveos, root, call)?;
+            ensure!(
+                result.vm_status.status_code() != StatusCode::STORAGE_ERROR,
+                "The view function failed with the storage error: {:?}",
+                result.vm_status
+            );
+            Ok(result)
+        })
+    }
+
+    fn annotated_view_function(
+        &self,
+        moveos: &MoveOS,
+        root: ObjectMeta,
+        call: FunctionCall,
+    ) -> Result<AnnotatedFunctionResult> {
+        let resolver = RootObjectResolver::new(root.clone(), &self.moveos_store);
+        let function_result = moveos.execute_view_function(root, call);
         Ok(AnnotatedFunctionResult {
             vm_status: function_result.vm_status,
             return_values: match function_result.return_values {
@@ -107,6 +171,79 @@ impl Handler<ExecuteViewFunctionMessage> for ReaderExecutorActor {
             },
         })
     }
+
+    /// Get the root object after the transaction of the `tx_order` is executed.
+    pub fn get_root_by_tx_order(&self, tx_order: u64) -> Result<ObjectMeta> {
+        let tx_hash = self
+            .rooch_store
+            .transaction_store
+            .get_tx_hashs(vec![tx_order])?
+            .pop()
+            .flatten()
+            .ok_or_else(|| anyhow!("Can not find transaction with tx_order: {}", tx_order))?;
+        let execution_info = self
+            .moveos_store
+            .get_tx_execution_info(tx_hash)?
+            .ok_or_else(|| {
+                anyhow!(
+                    "Can not find execution info of transaction with tx_order: {}",
+                    tx_order
+                )
+            })?;
+        Ok(execution_info.root_metadata())
+    }
+
+    pub fn refresh_state(&mut self, root: ObjectMeta, is_upgrade: bool) -> Result<()> {
+        self.root = root;
+        self.moveos.flush_module_cache(is_upgrade)
+    }
+}
+
+fn new_moveos(root: &ObjectMeta, moveos_store: &MoveOSStore) -> Result<MoveOS> {
+    let resolver = RootObjectResolver::new(root.clone(), moveos_store);
+    let gas_parameters = FrameworksGasParameters::load_from_chain(&resolver)?;
+    MoveOS::new(
+        moveos_store.clone(),
+        gas_parameters.all_natives(),
+        MoveOSConfig::default(),
+        system_pre_execute_functions(),
+        system_post_execute_functions(),
+    )
+}
+
+impl Actor for ReaderExecutorActor {}
+
+#[async_trait]
+impl Handler<ExecuteViewFunctionMessage> for ReaderExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: ExecuteViewFunctionMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<AnnotatedFunctionResult, anyhow::Error> {
+        self.execute_view_function(msg.root, msg.call)
+    }
+}
+
+#[async_trait]
+impl Handler<GetRootByTxOrderMessage> for ReaderExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: GetRootByTxOrderMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<ObjectMeta> {
+        self.get_root_by_tx_order(msg.tx_order)
+    }
+}
+
+#[async_trait]
+impl Handler<DryRunTransactionMessage> for ReaderExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: DryRunTransactionMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<RawTransactionOutput> {
+        self.dry_run(msg.tx)
+    }
 }
 
 #[async_trait]
@@ -116,8 +253,26 @@ impl Handler<StatesMessage> for ReaderExecutorActor {
         msg: StatesMessage,
         _ctx: &mut ActorContext,
     ) -> Result<Vec<Option<ObjectState>>, anyhow::Error> {
-        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
-        resolver.get_states(msg.access_path)
+        self.read_at(msg.root, |root| {
+            RootObjectResolver::new(root, &self.moveos_store).get_states(msg.access_path)
+        })
+    }
+}
+
+#[async_trait]
+impl Handler<StatesWithProofMessage> for ReaderExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: StatesWithProofMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<(H256, Vec<StateWithProof>), anyhow::Error> {
+        self.read_at(msg.root, |root| {
+            let states = self
+                .moveos_store
+                .get_state_store()
+                .get_states_with_proof(&root, msg.access_path)?;
+            Ok((root.state_root(), states))
+        })
     }
 }
 
@@ -128,8 +283,9 @@ impl Handler<AnnotatedStatesMessage> for ReaderExecutorActor {
         msg: AnnotatedStatesMessage,
         _ctx: &mut ActorContext,
     ) -> Result<Vec<Option<AnnotatedState>>, anyhow::Error> {
-        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
-        resolver.get_annotated_states(msg.access_path)
+        self.read_at(msg.root, |root| {
+            RootObjectResolver::new(root, &self.moveos_store).get_annotated_states(msg.access_path)
+        })
     }
 }
 
@@ -140,8 +296,13 @@ impl Handler<ListStatesMessage> for ReaderExecutorActor {
         msg: ListStatesMessage,
         _ctx: &mut ActorContext,
     ) -> Result<Vec<StateKV>, anyhow::Error> {
-        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
-        resolver.list_states(msg.access_path, msg.cursor, msg.limit)
+        self.read_at(msg.root, |root| {
+            RootObjectResolver::new(root, &self.moveos_store).list_states(
+                msg.access_path,
+                msg.cursor,
+                msg.limit,
+            )
+        })
     }
 }
 
@@ -152,8 +313,13 @@ impl Handler<ListAnnotatedStatesMessage> for ReaderExecutorActor {
         msg: ListAnnotatedStatesMessage,
         _ctx: &mut ActorContext,
     ) -> Result<Vec<AnnotatedStateKV>, anyhow::Error> {
-        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
-        resolver.list_annotated_states(msg.access_path, msg.cursor, msg.limit)
+        self.read_at(msg.root, |root| {
+            RootObjectResolver::new(root, &self.moveos_store).list_annotated_states(
+                msg.access_path,
+                msg.cursor,
+                msg.limit,
+            )
+        })
     }
 }
 
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::actor::messages::{
-    GetEventsByEventHandleMessage, GetEventsByEventIDsMessage, GetTxExecutionInfosByHashMessage,
-    ListAnnotatedStatesMessage, ListStatesMessage, RefreshStateMessage, ValidateL1BlockMessage,
+    DryRunTransactionMessage, GetEventsByEventHandleMessage, GetEventsByEventIDsMessage,
+    GetRootByTxOrderMessage, GetTxExecutionInfosByHashMessage, ListAnnotatedStatesMessage,
+    ListStatesMessage, RefreshStateMessage, StatesWithProofMessage, ValidateL1BlockMessage,
     ValidateL1TxMessage,
 };
 use crate::actor::reader_executor::ReaderExecutorActor;
@@ -26,8 +27,10 @@ use moveos_types::moveos_std::event::{Event, EventID};
 use moveos_types::moveos_std::object::ObjectMeta;
 use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::state::FieldKey;
+use moveos_types::state_proof::StateWithProof;
 use moveos_types::state_resolver::{AnnotatedStateKV, StateKV};
 use moveos_types::transaction::FunctionCall;
+use moveos_types::transaction::RawTransactionOutput;
 use moveos_types::transaction::TransactionExecutionInfo;
 use moveos_types::transaction::TransactionOutput;
 use moveos_types::{access_path::AccessPath, transaction::VerifiedMoveOSTransaction};
@@ -84,27 +87,79 @@ impl ExecutorProxy {
         Ok((result.output, result.transaction_info))
     }
 
+    /// Validate and execute the transaction on the reader executor, the output is not applied to the state.
+    pub async fn dry_run_transaction(&self, tx: RoochTransaction) -> Result<RawTransactionOutput> {
+        self.reader_actor
+            .send(DryRunTransactionMessage { tx })
+            .await?
+    }
+
     pub async fn execute_view_function(
         &self,
         call: FunctionCall,
+    ) -> Result<AnnotatedFunctionResult> {
+        self.execute_view_function_at(None, call).await
+    }
+
+    /// Execute the view function at the given root object, or the latest root object if None
+    pub async fn execute_view_function_at(
+        &self,
+        root: Option<ObjectMeta>,
+        call: FunctionCall,
     ) -> Result<AnnotatedFunctionResult> {
         self.reader_actor
-            .send(ExecuteViewFunctionMessage { call })
+            .send(ExecuteViewFunctionMessage { call, root })
+            .await?
+    }
+
+    /// Get the root object after the transaction of the `tx_order` is executed
+    pub async fn get_root_by_tx_order(&self, tx_order: u64) -> Result<ObjectMeta> {
+        self.reader_actor
+            .send(GetRootByTxOrderMessage { tx_order })
             .await?
     }
 
     pub async fn get_states(&self, access_path: AccessPath) -> Result<Vec<Option<ObjectState>>> {
+        self.get_states_at(None, access_path).await
+    }
+
+    /// Get the states at the given root object, or the latest root object if None
+    pub async fn get_states_at(
+        &self,
+        root: Option<ObjectMeta>,
+        access_path: AccessPath,
+    ) -> Result<Vec<Option<ObjectState>>> {
+        self.reader_actor
+            .send(StatesMessage { access_path, root })
+            .await?
+    }
+
+    /// Get the states with the proofs at the given root object, or the latest root object if None,
+    /// return the state root of the proofs and the states
+    pub async fn get_states_with_proof(
+        &self,
+        root: Option<ObjectMeta>,
+        access_path: AccessPath,
+    ) -> Result<(H256, Vec<StateWithProof>)> {
         self.reader_actor
-            .send(StatesMessage { access_path })
+            .send(StatesWithProofMessage { access_path, root })
             .await?
     }
 
     pub async fn get_annotated_states(
         &self,
         access_path: AccessPath,
+    ) -> Result<Vec<Option<AnnotatedState>>> {
+        self.get_annotated_states_at(None, access_path).await
+    }
+
+    pub async fn get_annotated_states_at(
+        &self,
+        root: Option<ObjectMeta>,
+        access_path: AccessPath,
     ) -> Result<Vec<Option<AnnotatedState>>> {
         self.reader_actor
-            .send(AnnotatedStatesMessage { access_path })
+            .send(AnnotatedStatesMessage { access_path, root })
             .await?
     }
 
@@ -113,12 +168,23 @@ impl ExecutorProxy {
         access_path: AccessPath,
         cursor: Option<FieldKey>,
         limit: usize,
+    ) -> Result<Vec<StateKV>> {
+        self.list_states_at(None, access_path, cursor, limit).await
+    }
+
+    pub async fn list_states_at(
+        &self,
+        root: Option<ObjectMeta>,
+        access_path: AccessPath,
+        cursor: Option<FieldKey>,
+        limit: usize,
     ) -> Result<Vec<StateKV>> {
         self.reader_actor
             .send(ListStatesMessage {
                 access_path,
                 cursor,
                 limit,
+                root,
             })
             .await?
     }
@@ -128,12 +194,24 @@ impl ExecutorProxy {
         access_path: AccessPath,
         cursor: Option<FieldKey>,
         limit: usize,
+    ) -> Result<Vec<AnnotatedStateKV>> {
+        self.list_annotated_states_at(None, access_path, cursor, limit)
+            .await
+    }
+
+    pub async fn list_annotated_states_at(
+        &self,
+        root: Option<ObjectMeta>,
+        access_path: AccessPath,
+        cursor: Option<FieldKey>,
+        limit: usize,
     ) -> Result<Vec<AnnotatedStateKV>> {
         self.reader_actor
             .send(ListAnnotatedStatesMessage {
                 access_path,
                 cursor,
                 limit,
+                root,
             })
             .await?
     }
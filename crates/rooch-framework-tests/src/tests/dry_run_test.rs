@@ -0,0 +1,46 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test;
+use move_core_types::vm_status::KeptVMStatus;
+use moveos_types::transaction::MoveAction;
+use rooch_key::keystore::account_keystore::AccountKeystore;
+use rooch_key::keystore::memory_keystore::InMemKeystore;
+use rooch_types::framework::empty::Empty;
+use rooch_types::transaction::rooch::RoochTransactionData;
+
+#[test]
+fn test_dry_run() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    let root = binding_test.root().clone();
+
+    let keystore = InMemKeystore::new_insecure_for_tests(1);
+    let sender = keystore.addresses()[0];
+    let action = MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![]);
+    let tx_data = RoochTransactionData::new_for_test(sender, 0, action);
+    let tx = keystore
+        .sign_transaction(&sender, tx_data.clone(), None)
+        .unwrap();
+
+    let output = binding_test.reader_executor.dry_run(tx).unwrap();
+    assert_eq!(output.status, KeptVMStatus::Executed);
+    assert!(output.gas_used > 0);
+    assert!(!output.changeset.changes.is_empty());
+
+    // The dry run output is not applied
+    assert_eq!(binding_test.root(), &root);
+    assert_eq!(
+        binding_test
+            .get_account_sequence_number(sender.into())
+            .unwrap(),
+        0
+    );
+
+    // The tx can be executed with the max gas amount estimated by the dry run
+    let mut tx_data = tx_data;
+    tx_data.max_gas_amount = output.gas_used * 120 / 100;
+    let tx = keystore.sign_transaction(&sender, tx_data, None).unwrap();
+    let result = binding_test.execute_as_result(tx).unwrap();
+    assert_eq!(result.transaction_info.status, KeptVMStatus::Executed);
+}
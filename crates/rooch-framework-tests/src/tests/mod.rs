@@ -5,6 +5,7 @@ mod bitcoin_test;
 mod bitcoin_validator_tests;
 mod brc20_test;
 mod chain_id_test;
+mod dry_run_test;
 mod empty_tests;
 mod ethereum_test;
 mod ord_test;
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::actor::messages::{
-    IndexerEventsMessage, IndexerStatesMessage, IndexerTransactionMessage, UpdateIndexerMessage,
+    FlushIndexerMessage, IndexerEventsMessage, IndexerStatesMessage, IndexerTransactionMessage,
+    UpdateIndexerMessage,
 };
 use crate::store::traits::IndexerStoreTrait;
 use crate::IndexerStore;
@@ -161,3 +162,11 @@ impl Handler<IndexerEventsMessage> for IndexerActor {
         Ok(())
     }
 }
+
+#[async_trait]
+impl Handler<FlushIndexerMessage> for IndexerActor {
+    async fn handle(&mut self, _msg: FlushIndexerMessage, _ctx: &mut ActorContext) -> Result<()> {
+        // The messages are handled in order, so the previous updates are written
+        Ok(())
+    }
+}
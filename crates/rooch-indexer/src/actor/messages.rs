@@ -41,6 +41,14 @@ impl Message for IndexerTransactionMessage {
     type Result = Result<()>;
 }
 
+/// Wait until the indexer updates sent before the message are written
+#[derive(Debug)]
+pub struct FlushIndexerMessage;
+
+impl Message for FlushIndexerMessage {
+    type Result = Result<()>;
+}
+
 /// Indexer Event write Message
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IndexerEventsMessage {
@@ -3,7 +3,7 @@
 
 use crate::actor::indexer::IndexerActor;
 use crate::actor::messages::{
-    IndexerEventsMessage, IndexerStatesMessage, IndexerTransactionMessage,
+    FlushIndexerMessage, IndexerEventsMessage, IndexerStatesMessage, IndexerTransactionMessage,
     QueryIndexerEventsMessage, QueryIndexerObjectIdsMessage, QueryIndexerObjectStatesMessage,
     QueryIndexerTransactionsMessage, UpdateIndexerMessage,
 };
@@ -54,6 +54,11 @@ impl IndexerProxy {
         Ok(())
     }
 
+    /// Wait until the updates sent by `update_indexer` before are written.
+    pub async fn flush(&self) -> Result<()> {
+        self.actor.send(FlushIndexerMessage).await?
+    }
+
     pub async fn indexer_states(
         &self,
         root: ObjectMeta,
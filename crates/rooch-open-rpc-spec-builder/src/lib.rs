@@ -10,6 +10,7 @@ use clap::Parser;
 use rooch_open_rpc::Project;
 use rooch_rpc_api::api::btc_api::BtcAPIOpenRpc;
 use rooch_rpc_api::api::rooch_api::RoochAPIOpenRpc;
+use rooch_rpc_api::api::rooch_subscription_api::RoochSubscriptionAPIOpenRpc;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
@@ -42,6 +43,7 @@ const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub fn build_rooch_rpc_spec() -> Project {
     let mut open_rpc = rooch_rpc_doc(VERSION);
     open_rpc.add_module(RoochAPIOpenRpc::module_doc());
+    open_rpc.add_module(RoochSubscriptionAPIOpenRpc::module_doc());
     //FIXME if add the EthAPIOpenRpc, the pnpm sdk gen raies error
     open_rpc.add_module(BtcAPIOpenRpc::module_doc());
     //open_rpc.add_examples(RpcExampleProvider::new().examples());
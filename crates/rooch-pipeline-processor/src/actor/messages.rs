@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
-use coerce::actor::message::Message;
+use coerce::actor::{message::Message, scheduler::timer::TimerTick};
 use rooch_types::transaction::{
     ExecuteTransactionResponse, L1BlockWithBody, L1Transaction, RoochTransaction,
 };
@@ -33,3 +33,13 @@ pub struct ExecuteL1TxMessage {
 impl Message for ExecuteL1TxMessage {
     type Result = Result<ExecuteTransactionResponse>;
 }
+
+/// Submit the proposed blocks to the on-chain State Commitment Chain
+#[derive(Clone)]
+pub struct SubmitBlockCommitments {}
+
+impl Message for SubmitBlockCommitments {
+    type Result = ();
+}
+
+impl TimerTick for SubmitBlockCommitments {}
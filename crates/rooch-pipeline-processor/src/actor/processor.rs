@@ -1,20 +1,57 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use super::messages::{ExecuteL1BlockMessage, ExecuteL1TxMessage, ExecuteL2TxMessage};
-use anyhow::Result;
+use super::messages::{
+    ExecuteL1BlockMessage, ExecuteL1TxMessage, ExecuteL2TxMessage, SubmitBlockCommitments,
+};
+use crate::notifier::{ExecutedTransaction, ExecutionNotifier};
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor};
+use move_core_types::vm_status::KeptVMStatus;
+use moveos_types::module_binding::MoveFunctionCaller;
+use moveos_types::moveos_std::gas_schedule::GasScheduleConfig;
 use moveos_types::transaction::VerifiedMoveOSTransaction;
 use rooch_executor::proxy::ExecutorProxy;
 use rooch_indexer::proxy::IndexerProxy;
 use rooch_proposer::proxy::ProposerProxy;
 use rooch_sequencer::proxy::SequencerProxy;
+use rooch_types::framework::gas_coin::GasCoinModule;
+use rooch_types::framework::state_commitment_chain::StateCommitmentChainModule;
+use rooch_types::framework::transaction_fee::TransactionFeeModule;
+use rooch_types::rooch_network::{CHAIN_ID_DEV, CHAIN_ID_LOCAL};
 use rooch_types::transaction::{
     ExecuteTransactionResponse, L1BlockWithBody, L1Transaction, LedgerTransaction, LedgerTxData,
     RoochTransaction,
 };
-use tracing::{debug, info};
+use std::fmt;
+use tracing::{debug, info, warn};
+
+/// The max number of block commitments submitted in one tick
+const MAX_BLOCK_COMMITMENTS_PER_TICK: u128 = 10;
+
+/// The error context of the transaction failed after it is sequenced,
+/// the transaction is already in the ledger and must not be submitted again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequencedTx {
+    pub tx_order: u64,
+}
+
+impl fmt::Display for SequencedTx {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The transaction is sequenced with tx order {}",
+            self.tx_order
+        )
+    }
+}
+
+/// The tx order if the error is raised after the transaction is sequenced,
+/// none if the transaction is not sequenced, for example, it fails to validate.
+pub fn sequenced_tx_order(err: &anyhow::Error) -> Option<u64> {
+    err.downcast_ref::<SequencedTx>().map(|tx| tx.tx_order)
+}
 
 /// PipelineProcessor aggregates the executor, sequencer, proposer, and indexer to process transactions.
 pub struct PipelineProcessorActor {
@@ -22,6 +59,7 @@ pub struct PipelineProcessorActor {
     pub(crate) sequencer: SequencerProxy,
     pub(crate) proposer: ProposerProxy,
     pub(crate) indexer: IndexerProxy,
+    pub(crate) notifier: ExecutionNotifier,
     pub(crate) data_import_flag: bool,
 }
 
@@ -31,6 +69,7 @@ impl PipelineProcessorActor {
         sequencer: SequencerProxy,
         proposer: ProposerProxy,
         indexer: IndexerProxy,
+        notifier: ExecutionNotifier,
         data_import_flag: bool,
     ) -> Self {
         Self {
@@ -38,6 +77,7 @@ impl PipelineProcessorActor {
             sequencer,
             proposer,
             indexer,
+            notifier,
             data_import_flag,
         }
     }
@@ -73,6 +113,8 @@ impl PipelineProcessorActor {
         if txs.is_empty() {
             return Ok(());
         }
+        // Execute the txs in the sequenced order
+        txs.reverse();
         info!(
             "Process sequenced but not executed transactions on startup, txs: {:?}",
             txs
@@ -85,9 +127,17 @@ impl PipelineProcessorActor {
                 .await?
                 .ok_or_else(|| anyhow::anyhow!("The tx with hash {} should exists", tx_hash))?;
             match &ledger_tx.data {
-                LedgerTxData::L1Block(_block) => {
-                    //TODO how to get the L1BlockWithBody
-                    unimplemented!("L1Block tx not support")
+                LedgerTxData::L1Block(block) => {
+                    debug!("process_sequenced_tx_on_startup l1_block: {:?}", block);
+                    let l1_block = self
+                        .sequencer
+                        .get_l1_block_body(tx_hash)
+                        .await?
+                        .ok_or_else(|| {
+                            anyhow::anyhow!("The body of L1 block tx {} should exists", tx_hash)
+                        })?;
+                    let moveos_tx = self.executor.validate_l1_block(l1_block).await?;
+                    self.execute_tx(ledger_tx.clone(), moveos_tx).await?;
                 }
                 LedgerTxData::L1Tx(l1_tx) => {
                     debug!("process_sequenced_tx_on_startup l1_tx: {:?}", l1_tx);
@@ -109,11 +159,8 @@ impl PipelineProcessorActor {
         l1_block: L1BlockWithBody,
     ) -> Result<ExecuteTransactionResponse> {
         let moveos_tx = self.executor.validate_l1_block(l1_block.clone()).await?;
-        let ledger_tx = self
-            .sequencer
-            .sequence_transaction(LedgerTxData::L1Block(l1_block.block))
-            .await?;
-        self.execute_tx(ledger_tx, moveos_tx).await
+        let ledger_tx = self.sequencer.sequence_l1_block(l1_block).await?;
+        self.execute_sequenced_tx(ledger_tx, moveos_tx).await
     }
 
     pub async fn execute_l1_tx(
@@ -125,7 +172,7 @@ impl PipelineProcessorActor {
             .sequencer
             .sequence_transaction(LedgerTxData::L1Tx(l1_tx))
             .await?;
-        self.execute_tx(ledger_tx, moveos_tx).await
+        self.execute_sequenced_tx(ledger_tx, moveos_tx).await
     }
 
     pub async fn execute_l2_tx(
@@ -138,7 +185,96 @@ impl PipelineProcessorActor {
             .sequencer
             .sequence_transaction(LedgerTxData::L2Tx(tx))
             .await?;
-        self.execute_tx(ledger_tx, moveos_tx).await
+        self.execute_sequenced_tx(ledger_tx, moveos_tx).await
+    }
+
+    /// Execute the sequenced transaction, the error is attached with the `SequencedTx` context
+    async fn execute_sequenced_tx(
+        &mut self,
+        tx: LedgerTransaction,
+        moveos_tx: VerifiedMoveOSTransaction,
+    ) -> Result<ExecuteTransactionResponse> {
+        let tx_order = tx.sequence_info.tx_order;
+        self.execute_tx(tx, moveos_tx)
+            .await
+            .context(SequencedTx { tx_order })
+    }
+
+    /// Submit the proposed but not committed blocks to the on-chain SCC,
+    /// the transactions are signed by the proposer and executed as L2 transactions.
+    /// The proposer account pays the gas and uses its sequence numbers, so it should be a dedicated and funded account,
+    /// the commitments are not submitted while its gas balance can not pay the gas deposit.
+    pub async fn submit_block_commitments(&mut self) -> Result<()> {
+        let latest_block_number = match self.proposer.latest_block().await? {
+            Some(block) => block.block_number,
+            None => return Ok(()),
+        };
+        let block_count = self
+            .executor
+            .as_module_binding::<StateCommitmentChainModule>()
+            .block_count()?;
+        if block_count > latest_block_number {
+            return Ok(());
+        }
+        let proposer_address = self.proposer.proposer_address().await?;
+        let scc_proposer = self
+            .executor
+            .as_module_binding::<StateCommitmentChainModule>()
+            .proposer()?;
+        if scc_proposer != proposer_address.into() {
+            // the commitments would be aborted, wait for the sequencer to set the proposer by `--set-scc-proposer`
+            warn!(
+                "The proposer {:?} is not the proposer {:?} of the on-chain SCC, skip submitting block commitments",
+                proposer_address, scc_proposer
+            );
+            return Ok(());
+        }
+        let chain_id = self.executor.chain_id().await?.id();
+        // the gas deposit is not checked in local or dev chain, the new accounts get the gas from the faucet
+        if chain_id != CHAIN_ID_LOCAL && chain_id != CHAIN_ID_DEV {
+            let gas_balance = self
+                .executor
+                .as_module_binding::<GasCoinModule>()
+                .balance(proposer_address.into())?;
+            let gas_deposit = self
+                .executor
+                .as_module_binding::<TransactionFeeModule>()
+                .calculate_gas(GasScheduleConfig::INITIAL_MAX_GAS_AMOUNT)?;
+            if gas_balance < gas_deposit {
+                warn!(
+                    "The gas balance {} of the proposer {:?} can not pay the gas deposit {}, fund it to submit block commitments",
+                    gas_balance, proposer_address, gas_deposit
+                );
+                return Ok(());
+            }
+        }
+        let end_block_number =
+            latest_block_number.min(block_count + MAX_BLOCK_COMMITMENTS_PER_TICK - 1);
+        for block_number in block_count..=end_block_number {
+            let sequence_number = self
+                .executor
+                .get_sequence_number(proposer_address.into())
+                .await?;
+            let tx = match self
+                .proposer
+                .sign_block_commitment(block_number, sequence_number, chain_id)
+                .await?
+            {
+                Some(tx) => tx,
+                None => break,
+            };
+            let response = self.execute_l2_tx(tx).await?;
+            if response.execution_info.status != KeptVMStatus::Executed {
+                // the status of the on-chain SCC is checked again in the next tick
+                warn!(
+                    "Submit block {} commitment to SCC failed: {:?}",
+                    block_number, response.execution_info.status
+                );
+                break;
+            }
+            debug!("Block {} commitment submitted to SCC", block_number);
+        }
+        Ok(())
     }
 
     pub async fn execute_tx(
@@ -150,6 +286,19 @@ impl PipelineProcessorActor {
         moveos_tx.ctx.add(tx.sequence_info.clone())?;
         // Then execute
         let (output, execution_info) = self.executor.execute_transaction(moveos_tx.clone()).await?;
+        // The execution info is persisted, the block body is not required to re-execute the block on startup
+        if let LedgerTxData::L1Block(_) = &tx.data {
+            if let Err(e) = self
+                .sequencer
+                .remove_l1_block_body(execution_info.tx_hash)
+                .await
+            {
+                warn!(
+                    "Remove the body of L1 block tx {:?} error: {:?}",
+                    execution_info.tx_hash, e
+                );
+            }
+        }
         self.proposer
             .propose_transaction(tx.clone(), execution_info.clone())
             .await?;
@@ -164,8 +313,15 @@ impl PipelineProcessorActor {
         let execution_info_clone = execution_info.clone();
         let output_clone = output.clone();
 
-        // If bitcoin block data import, don't write all indexer
+        // If bitcoin block data import, don't write all indexer and notify the subscribers
         if !self.data_import_flag {
+            self.notifier
+                .notify_with(sequence_info.tx_order, || ExecutedTransaction {
+                    tx: tx.clone(),
+                    ctx: moveos_tx.ctx.clone(),
+                    execution_info: execution_info.clone(),
+                    output: output.clone(),
+                });
             //The update_indexer is a notify call, do not block current task
             let result = indexer
                 .update_indexer(
@@ -231,3 +387,12 @@ impl Handler<ExecuteL1TxMessage> for PipelineProcessorActor {
         self.execute_l1_tx(msg.tx).await
     }
 }
+
+#[async_trait]
+impl Handler<SubmitBlockCommitments> for PipelineProcessorActor {
+    async fn handle(&mut self, _msg: SubmitBlockCommitments, _ctx: &mut ActorContext) {
+        if let Err(e) = self.submit_block_commitments().await {
+            log::error!("Submit block commitments error: {:?}", e);
+        }
+    }
+}
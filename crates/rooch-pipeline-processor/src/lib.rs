@@ -2,4 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod actor;
+pub mod notifier;
 pub mod proxy;
+pub mod tx_pool;
+
+#[cfg(test)]
+mod tests;
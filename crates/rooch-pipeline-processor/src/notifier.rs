@@ -0,0 +1,347 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Result};
+use moveos_types::moveos_std::tx_context::TxContext;
+use moveos_types::transaction::{TransactionExecutionInfo, TransactionOutput};
+use parking_lot::Mutex;
+use rooch_types::transaction::LedgerTransaction;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// The default number of the recent executed transactions kept in memory for the subscribers to resume
+pub const DEFAULT_NOTIFIER_HISTORY_SIZE: usize = 128;
+
+/// The transaction executed by the pipeline processor
+#[derive(Clone)]
+pub struct ExecutedTransaction {
+    pub tx: LedgerTransaction,
+    pub ctx: TxContext,
+    pub execution_info: TransactionExecutionInfo,
+    pub output: TransactionOutput,
+}
+
+impl ExecutedTransaction {
+    pub fn tx_order(&self) -> u64 {
+        self.tx.sequence_info.tx_order
+    }
+}
+
+struct NotifierInner {
+    sender: broadcast::Sender<Arc<ExecutedTransaction>>,
+    history: VecDeque<Arc<ExecutedTransaction>>,
+    // The tx order of the next executed transaction, the history ends before it
+    next_tx_order: u64,
+}
+
+impl NotifierInner {
+    fn oldest_tx_order(&self) -> u64 {
+        self.history
+            .front()
+            .map(|tx| tx.tx_order())
+            .unwrap_or(self.next_tx_order)
+    }
+}
+
+/// ExecutionNotifier publishes the executed transactions to the subscribers.
+/// The recent transactions are kept in memory whether there are subscribers or not, so a subscriber
+/// could resume after reconnecting, the older transactions should be read from the store,
+/// see `ExecutionSubscription::take_stored_range`.
+/// The channel is bounded, a subscriber lagging behind the window is closed and should resubscribe.
+#[derive(Clone)]
+pub struct ExecutionNotifier {
+    inner: Arc<Mutex<NotifierInner>>,
+    history_size: usize,
+}
+
+impl ExecutionNotifier {
+    /// The `next_tx_order` is the tx order of the first transaction executed after the notifier is created.
+    pub fn new(history_size: usize, next_tx_order: u64) -> Self {
+        let (sender, _) = broadcast::channel(history_size.max(1));
+        Self {
+            inner: Arc::new(Mutex::new(NotifierInner {
+                sender,
+                history: VecDeque::with_capacity(history_size),
+                next_tx_order,
+            })),
+            history_size,
+        }
+    }
+
+    pub fn notify(&self, tx: ExecutedTransaction) {
+        self.notify_with(tx.tx_order(), || tx)
+    }
+
+    /// Notify the executed transaction of the `tx_order`, the transaction is only built if it is kept
+    /// in the history or there are subscribers.
+    pub fn notify_with<F>(&self, tx_order: u64, build_tx: F)
+    where
+        F: FnOnce() -> ExecutedTransaction,
+    {
+        let mut inner = self.inner.lock();
+        inner.next_tx_order = tx_order + 1;
+        if self.history_size == 0 && inner.sender.receiver_count() == 0 {
+            return;
+        }
+        let tx = Arc::new(build_tx());
+        if self.history_size > 0 {
+            if inner.history.len() >= self.history_size {
+                inner.history.pop_front();
+            }
+            inner.history.push_back(tx.clone());
+        }
+        let _ = inner.sender.send(tx);
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.inner.lock().sender.receiver_count()
+    }
+
+    /// Subscribe the executed transactions from the `from_tx_order`(inclusive),
+    /// only the new transactions are published if `from_tx_order` is none.
+    /// The transactions before the in-memory window are not published, the subscriber reads them
+    /// from the store by the range of `ExecutionSubscription::take_stored_range`.
+    pub fn subscribe(&self, from_tx_order: Option<u64>) -> ExecutionSubscription {
+        let inner = self.inner.lock();
+        let (stored_range, backlog) = match from_tx_order {
+            Some(from_tx_order) => {
+                let oldest_tx_order = inner.oldest_tx_order();
+                let stored_range =
+                    (from_tx_order < oldest_tx_order).then_some(from_tx_order..oldest_tx_order);
+                let backlog = inner
+                    .history
+                    .iter()
+                    .filter(|tx| tx.tx_order() >= from_tx_order)
+                    .cloned()
+                    .collect();
+                (stored_range, backlog)
+            }
+            None => (None, VecDeque::new()),
+        };
+        // Subscribe under the lock, so no tx is missed between the backlog and the receiver
+        let receiver = inner.sender.subscribe();
+        ExecutionSubscription {
+            stored_range,
+            backlog,
+            receiver,
+            from_tx_order: from_tx_order.unwrap_or(inner.next_tx_order),
+            last_tx_order: None,
+        }
+    }
+}
+
+pub struct ExecutionSubscription {
+    stored_range: Option<Range<u64>>,
+    backlog: VecDeque<Arc<ExecutedTransaction>>,
+    receiver: broadcast::Receiver<Arc<ExecutedTransaction>>,
+    from_tx_order: u64,
+    last_tx_order: Option<u64>,
+}
+
+impl ExecutionSubscription {
+    /// The tx orders before the in-memory window, the subscriber should read them from the store
+    /// before calling `next`, the transactions published by `next` follow the range without a gap.
+    pub fn take_stored_range(&mut self) -> Option<Range<u64>> {
+        self.stored_range.take()
+    }
+
+    /// The next executed transaction in tx order, none if the notifier is dropped.
+    pub async fn next(&mut self) -> Result<Option<Arc<ExecutedTransaction>>> {
+        loop {
+            let tx = self.next_tx().await?;
+            match tx {
+                // Skip the txs before the subscribed tx order
+                Some(tx) if tx.tx_order() < self.from_tx_order => continue,
+                Some(tx) => {
+                    self.last_tx_order = Some(tx.tx_order());
+                    return Ok(Some(tx));
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+
+    async fn next_tx(&mut self) -> Result<Option<Arc<ExecutedTransaction>>> {
+        let tx = match self.backlog.pop_front() {
+            Some(tx) => tx,
+            None => match self.receiver.recv().await {
+                Ok(tx) => tx,
+                Err(RecvError::Lagged(skipped)) => {
+                    let resume_tx_order = self
+                        .last_tx_order
+                        .map(|last_tx_order| last_tx_order + 1)
+                        .unwrap_or(self.from_tx_order);
+                    return Err(anyhow!(
+                        "The subscriber lagged behind {} transactions, please resubscribe from tx order {}",
+                        skipped,
+                        resume_tx_order
+                    ));
+                }
+                Err(RecvError::Closed) => return Ok(None),
+            },
+        };
+        Ok(Some(tx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::vm_status::KeptVMStatus;
+    use moveos_types::h256::H256;
+    use rooch_types::test_utils::{random_ledger_transaction, random_state_change_set};
+
+    fn executed_tx(tx_order: u64) -> ExecutedTransaction {
+        let mut tx = random_ledger_transaction();
+        tx.sequence_info.tx_order = tx_order;
+        ExecutedTransaction {
+            tx,
+            ctx: TxContext::random_for_testing_only(),
+            execution_info: TransactionExecutionInfo::new(
+                H256::random(),
+                H256::random(),
+                0,
+                H256::random(),
+                0,
+                KeptVMStatus::Executed,
+            ),
+            output: TransactionOutput::new(
+                KeptVMStatus::Executed,
+                random_state_change_set(),
+                vec![],
+                0,
+                false,
+            ),
+        }
+    }
+
+    fn notify_range(notifier: &ExecutionNotifier, tx_orders: Range<u64>) {
+        for tx_order in tx_orders {
+            notifier.notify(executed_tx(tx_order));
+        }
+    }
+
+    async fn next_tx_order(subscription: &mut ExecutionSubscription) -> u64 {
+        subscription.next().await.unwrap().unwrap().tx_order()
+    }
+
+    #[tokio::test]
+    async fn test_resume_in_window() {
+        let notifier = ExecutionNotifier::new(4, 1);
+        let _live = notifier.subscribe(None);
+        notify_range(&notifier, 1..6);
+
+        let mut subscription = notifier.subscribe(Some(3));
+        assert_eq!(subscription.take_stored_range(), None);
+        for tx_order in 3..6 {
+            assert_eq!(next_tx_order(&mut subscription).await, tx_order);
+        }
+        notify_range(&notifier, 6..7);
+        assert_eq!(next_tx_order(&mut subscription).await, 6);
+    }
+
+    #[tokio::test]
+    async fn test_resume_out_of_window() {
+        let notifier = ExecutionNotifier::new(2, 1);
+        let _live = notifier.subscribe(None);
+        notify_range(&notifier, 1..6);
+
+        // The txs before the window are read from the store, the window follows them
+        let mut subscription = notifier.subscribe(Some(1));
+        assert_eq!(subscription.take_stored_range(), Some(1..4));
+        assert_eq!(subscription.take_stored_range(), None);
+        assert_eq!(next_tx_order(&mut subscription).await, 4);
+        assert_eq!(next_tx_order(&mut subscription).await, 5);
+    }
+
+    #[tokio::test]
+    async fn test_no_subscriber() {
+        let notifier = ExecutionNotifier::new(2, 1);
+        // The history is kept without subscribers
+        notify_range(&notifier, 1..4);
+
+        let mut subscription = notifier.subscribe(Some(1));
+        assert_eq!(subscription.take_stored_range(), Some(1..2));
+        assert_eq!(next_tx_order(&mut subscription).await, 2);
+        assert_eq!(next_tx_order(&mut subscription).await, 3);
+        notify_range(&notifier, 4..5);
+        assert_eq!(next_tx_order(&mut subscription).await, 4);
+        drop(subscription);
+
+        // Nothing is built or kept without the history and subscribers
+        let notifier = ExecutionNotifier::new(0, 1);
+        notifier.notify_with(1, || {
+            panic!("The tx should not be built without the history and subscribers")
+        });
+        let mut subscription = notifier.subscribe(Some(1));
+        assert_eq!(subscription.take_stored_range(), Some(1..2));
+
+        let mut subscription = notifier.subscribe(None);
+        assert_eq!(subscription.take_stored_range(), None);
+        notify_range(&notifier, 2..3);
+        assert_eq!(next_tx_order(&mut subscription).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_resubscribe_after_sole_subscriber_dropped() {
+        let notifier = ExecutionNotifier::new(DEFAULT_NOTIFIER_HISTORY_SIZE, 1);
+        let mut subscription = notifier.subscribe(None);
+        notify_range(&notifier, 1..2);
+        assert_eq!(next_tx_order(&mut subscription).await, 1);
+        drop(subscription);
+        assert_eq!(notifier.subscriber_count(), 0);
+
+        // The tx executed while there is no subscriber is resumed from the history
+        notify_range(&notifier, 2..3);
+        let mut subscription = notifier.subscribe(Some(2));
+        assert_eq!(subscription.take_stored_range(), None);
+        assert_eq!(next_tx_order(&mut subscription).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_no_gap_between_backlog_and_live() {
+        let notifier = ExecutionNotifier::new(1000, 1);
+        let _live = notifier.subscribe(None);
+        notify_range(&notifier, 1..50);
+
+        let notify_handle = {
+            let notifier = notifier.clone();
+            std::thread::spawn(move || notify_range(&notifier, 50..500))
+        };
+        let mut subscription = notifier.subscribe(Some(10));
+        let mut expected_tx_order = match subscription.take_stored_range() {
+            Some(stored_range) => stored_range.end,
+            None => 10,
+        };
+        while expected_tx_order < 500 {
+            assert_eq!(next_tx_order(&mut subscription).await, expected_tx_order);
+            expected_tx_order += 1;
+        }
+        notify_handle.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber() {
+        let notifier = ExecutionNotifier::new(2, 1);
+        let mut subscription = notifier.subscribe(None);
+        notify_range(&notifier, 1..2);
+        assert_eq!(next_tx_order(&mut subscription).await, 1);
+
+        notify_range(&notifier, 2..6);
+        let error = subscription.next().await.unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("please resubscribe from tx order 2"),
+            "unexpected error: {}",
+            error
+        );
+
+        let mut subscription = notifier.subscribe(Some(2));
+        assert_eq!(subscription.take_stored_range(), Some(2..4));
+        assert_eq!(next_tx_order(&mut subscription).await, 4);
+        assert_eq!(next_tx_order(&mut subscription).await, 5);
+    }
+}
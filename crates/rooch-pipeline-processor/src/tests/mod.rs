@@ -0,0 +1,119 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::actor::processor::PipelineProcessorActor;
+use crate::notifier::ExecutionNotifier;
+use crate::proxy::PipelineProcessorProxy;
+use anyhow::Result;
+use coerce::actor::{system::ActorSystem, IntoActor};
+use rooch_config::RoochOpt;
+use rooch_da::actor::da::DAActor;
+use rooch_da::proxy::DAProxy;
+use rooch_db::RoochDB;
+use rooch_executor::actor::executor::ExecutorActor;
+use rooch_executor::actor::reader_executor::ReaderExecutorActor;
+use rooch_executor::proxy::ExecutorProxy;
+use rooch_genesis::RoochGenesis;
+use rooch_indexer::actor::indexer::IndexerActor;
+use rooch_indexer::actor::reader_indexer::IndexerReaderActor;
+use rooch_indexer::proxy::IndexerProxy;
+use rooch_proposer::actor::proposer::ProposerActor;
+use rooch_proposer::proxy::ProposerProxy;
+use rooch_sequencer::actor::sequencer::SequencerActor;
+use rooch_sequencer::proxy::SequencerProxy;
+use rooch_types::crypto::RoochKeyPair;
+
+mod test_historical_state;
+mod test_processor;
+
+/// The actors of the pipeline processor started on a local chain store.
+/// Start it again with the same `opt` and `rooch_db` to simulate the restart of the server.
+pub struct PipelineTest {
+    pub executor: ExecutorProxy,
+    pub sequencer: SequencerProxy,
+    pub processor: PipelineProcessorProxy,
+    pub notifier: ExecutionNotifier,
+}
+
+pub fn init_rooch_db(opt: &RoochOpt) -> Result<RoochDB> {
+    let rooch_db = RoochDB::init_with_mock_metrics_for_test(opt.store_config())?;
+    RoochGenesis::load_or_init(opt.network(), &rooch_db)?;
+    Ok(rooch_db)
+}
+
+impl PipelineTest {
+    pub async fn start(opt: &RoochOpt, rooch_db: &RoochDB) -> Result<Self> {
+        let actor_system = ActorSystem::new();
+        let root = rooch_db
+            .latest_root()?
+            .ok_or_else(|| anyhow::anyhow!("The genesis is not initialized"))?;
+        let executor = ExecutorActor::new(
+            root.clone(),
+            rooch_db.moveos_store.clone(),
+            rooch_db.rooch_store.clone(),
+        )?
+        .into_actor(Some("Executor"), &actor_system)
+        .await?;
+        let reader_executor = ReaderExecutorActor::new(
+            root.clone(),
+            rooch_db.moveos_store.clone(),
+            rooch_db.rooch_store.clone(),
+        )?
+        .into_actor(Some("ReaderExecutor"), &actor_system)
+        .await?;
+        let executor = ExecutorProxy::new(executor.into(), reader_executor.into());
+
+        let sequencer = SequencerActor::new(
+            RoochKeyPair::generate_secp256k1(),
+            rooch_db.rooch_store.clone(),
+        )?
+        .into_actor(Some("Sequencer"), &actor_system)
+        .await?;
+        let sequencer = SequencerProxy::new(sequencer.into());
+
+        let da = DAActor::new(
+            opt.da_config().clone(),
+            &actor_system,
+            rooch_db.rooch_store.clone(),
+            &prometheus::Registry::new(),
+        )
+        .await?
+        .into_actor(Some("DAProxy"), &actor_system)
+        .await?;
+        let proposer = ProposerActor::new(
+            RoochKeyPair::generate_secp256k1(),
+            rooch_db.rooch_store.clone(),
+            DAProxy::new(da.into()),
+        )?
+        .into_actor(Some("Proposer"), &actor_system)
+        .await?;
+        let proposer = ProposerProxy::new(proposer.into());
+
+        let indexer = IndexerActor::new(root, rooch_db.indexer_store.clone())?
+            .into_actor(Some("Indexer"), &actor_system)
+            .await?;
+        let indexer_reader = IndexerReaderActor::new(rooch_db.indexer_reader.clone())?
+            .into_actor(Some("IndexerReader"), &actor_system)
+            .await?;
+        let indexer = IndexerProxy::new(indexer.into(), indexer_reader.into());
+
+        let last_sequenced_order = sequencer.get_sequencer_order().await?;
+        let notifier = ExecutionNotifier::new(16, last_sequenced_order + 1);
+        let processor = PipelineProcessorActor::new(
+            executor.clone(),
+            sequencer.clone(),
+            proposer,
+            indexer,
+            notifier.clone(),
+            false,
+        )
+        .into_actor(Some("PipelineProcessor"), &actor_system)
+        .await?;
+        Ok(Self {
+            executor,
+            sequencer,
+            processor: PipelineProcessorProxy::new(processor.into()),
+            notifier,
+        })
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{init_rooch_db, PipelineTest};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::ident_str;
+use move_core_types::language_storage::ModuleId;
+use move_core_types::vm_status::KeptVMStatus;
+use moveos_types::access_path::AccessPath;
+use moveos_types::addresses::MOVEOS_STD_ADDRESS;
+use moveos_types::h256::H256;
+use moveos_types::move_types::FunctionId;
+use moveos_types::moveos_std::account::Account;
+use moveos_types::moveos_std::object::ObjectMeta;
+use moveos_types::transaction::{FunctionCall, MoveAction};
+use rooch_config::RoochOpt;
+use rooch_executor::proxy::ExecutorProxy;
+use rooch_types::crypto::RoochKeyPair;
+use rooch_types::framework::empty::Empty;
+use rooch_types::transaction::rooch::RoochTransactionData;
+
+async fn view_sequence_number(
+    executor: &ExecutorProxy,
+    root: Option<ObjectMeta>,
+    address: AccountAddress,
+) -> u64 {
+    let call = FunctionCall::new(
+        FunctionId::new(
+            ModuleId::new(MOVEOS_STD_ADDRESS, ident_str!("account").to_owned()),
+            ident_str!("sequence_number").to_owned(),
+        ),
+        vec![],
+        vec![bcs::to_bytes(&address).unwrap()],
+    );
+    let result = executor.execute_view_function_at(root, call).await.unwrap();
+    let return_values = result
+        .return_values
+        .expect("The view function should succeed");
+    bcs::from_bytes(&return_values[0].value.value).unwrap()
+}
+
+async fn get_sequence_number(
+    executor: &ExecutorProxy,
+    root: Option<ObjectMeta>,
+    address: AccountAddress,
+) -> u64 {
+    executor
+        .get_states_at(
+            root,
+            AccessPath::object(Account::account_object_id(address)),
+        )
+        .await
+        .unwrap()
+        .pop()
+        .flatten()
+        .map(|state| {
+            state
+                .into_object::<Account>()
+                .unwrap()
+                .value
+                .sequence_number
+        })
+        .unwrap_or(0)
+}
+
+#[tokio::test]
+async fn test_read_at_tx_order() {
+    let opt = RoochOpt::new_with_temp_store().unwrap();
+    let rooch_db = init_rooch_db(&opt).unwrap();
+    let pipeline = PipelineTest::start(&opt, &rooch_db).await.unwrap();
+
+    let kp = RoochKeyPair::generate_secp256k1();
+    let sender = kp.public().rooch_address().unwrap();
+    let mut tx_orders = vec![];
+    for sequence_number in 0..2 {
+        let action = MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![]);
+        let tx = RoochTransactionData::new_for_test(sender, sequence_number, action).sign(&kp);
+        let response = pipeline.processor.execute_l2_tx(tx).await.unwrap();
+        assert_eq!(response.execution_info.status, KeptVMStatus::Executed);
+        tx_orders.push(response.sequence_info.tx_order);
+    }
+    assert!(tx_orders[0] > 0);
+
+    let executor = &pipeline.executor;
+    let address = AccountAddress::from(sender);
+    let root = executor.get_root_by_tx_order(tx_orders[0]).await.unwrap();
+    assert_eq!(
+        get_sequence_number(executor, Some(root.clone()), address).await,
+        1
+    );
+    assert_eq!(
+        view_sequence_number(executor, Some(root.clone()), address).await,
+        1
+    );
+    // The historical view call does not change the latest state read by the reader
+    assert_eq!(get_sequence_number(executor, None, address).await, 2);
+    assert_eq!(view_sequence_number(executor, None, address).await, 2);
+
+    let latest_root = executor.get_root_by_tx_order(tx_orders[1]).await.unwrap();
+    assert_eq!(
+        view_sequence_number(executor, Some(latest_root), address).await,
+        2
+    );
+
+    let unknown_root = ObjectMeta::root_metadata(H256::random(), 0);
+    let error = executor
+        .get_states_at(
+            Some(unknown_root),
+            AccessPath::object(Account::account_object_id(address)),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("it may have been pruned"),
+        "unexpected error: {}",
+        error
+    );
+}
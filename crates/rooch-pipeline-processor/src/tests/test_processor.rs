@@ -0,0 +1,93 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{init_rooch_db, PipelineTest};
+use crate::actor::messages::SubmitBlockCommitments;
+use bitcoin::consensus::deserialize;
+use bitcoin::hashes::Hash;
+use hex::FromHex;
+use move_core_types::vm_status::KeptVMStatus;
+use moveos_types::module_binding::MoveFunctionCaller;
+use moveos_types::moveos_std::timestamp::TimestampModule;
+use rooch_config::RoochOpt;
+use rooch_types::multichain_id::RoochMultiChainID;
+use rooch_types::transaction::{L1Block, L1BlockWithBody};
+
+/// Mainnet block 496, with the block time
+fn bitcoin_block() -> (L1BlockWithBody, u32) {
+    let block_hex = Vec::<u8>::from_hex("010000004ddccd549d28f385ab457e98d1b11ce80bfea2c5ab93015ade4973e400000000bf4473e53794beae34e64fccc471dace6ae544180816f89591894e0f417a914cd74d6e49ffff001d323b3a7b0201000000010000000000000000000000000000000000000000000000000000000000000000ffffffff0804ffff001d026e04ffffffff0100f2052a0100000043410446ef0102d1ec5240f0d061a4246c1bdef63fc3dbab7733052fbbf0ecd8f41fc26bf049ebb4f9527f374280259e7cfa99c48b0e3f39c51347a19a5819651503a5ac00000000010000000321f75f3139a013f50f315b23b0c9a2b6eac31e2bec98e5891c924664889942260000000049483045022100cb2c6b346a978ab8c61b18b5e9397755cbd17d6eb2fe0083ef32e067fa6c785a02206ce44e613f31d9a6b0517e46f3db1576e9812cc98d159bfdaf759a5014081b5c01ffffffff79cda0945903627c3da1f85fc95d0b8ee3e76ae0cfdc9a65d09744b1f8fc85430000000049483045022047957cdd957cfd0becd642f6b84d82f49b6cb4c51a91f49246908af7c3cfdf4a022100e96b46621f1bffcf5ea5982f88cef651e9354f5791602369bf5a82a6cd61a62501fffffffffe09f5fe3ffbf5ee97a54eb5e5069e9da6b4856ee86fc52938c2f979b0f38e82000000004847304402204165be9a4cbab8049e1af9723b96199bfd3e85f44c6b4c0177e3962686b26073022028f638da23fc003760861ad481ead4099312c60030d4cb57820ce4d33812a5ce01ffffffff01009d966b01000000434104ea1feff861b51fe3f5f8a3b12d0f4712db80e919548a80839fc47c6a21e66d957e9c5d8cd108c7a2d2324bad71f9904ac0ae7336507d785b17a2c115e427a32fac00000000").unwrap();
+    let block: bitcoin::Block = deserialize(&block_hex).unwrap();
+    let time = block.header.time;
+    let l1_block = L1BlockWithBody {
+        block: L1Block {
+            chain_id: RoochMultiChainID::Bitcoin.multichain_id(),
+            block_height: 496,
+            block_hash: block.header.block_hash().to_byte_array().to_vec(),
+        },
+        block_body: rooch_types::bitcoin::types::Block::from(block).encode(),
+    };
+    (l1_block, time)
+}
+
+#[tokio::test]
+async fn test_process_sequenced_l1_block_on_startup() {
+    let opt = RoochOpt::new_with_temp_store().unwrap();
+    let rooch_db = init_rooch_db(&opt).unwrap();
+    let (l1_block, time) = bitcoin_block();
+    let tx_hash = l1_block.block.tx_hash();
+
+    {
+        let pipeline = PipelineTest::start(&opt, &rooch_db).await.unwrap();
+        // Sequence the block without executing it, as if the server is stopped after sequencing
+        let ledger_tx = pipeline
+            .sequencer
+            .sequence_l1_block(l1_block.clone())
+            .await
+            .unwrap();
+        assert_eq!(ledger_tx.sequence_info.tx_order, 1);
+        let execution_info = pipeline
+            .executor
+            .get_transaction_execution_infos_by_hash(vec![tx_hash])
+            .await
+            .unwrap()
+            .pop()
+            .flatten();
+        assert!(execution_info.is_none());
+        assert_eq!(
+            pipeline.sequencer.get_l1_block_body(tx_hash).await.unwrap(),
+            Some(l1_block.clone())
+        );
+    }
+
+    // Restart, the sequenced block is executed with the saved body on startup
+    let pipeline = PipelineTest::start(&opt, &rooch_db).await.unwrap();
+    // The message is handled after the startup processing is finished
+    pipeline
+        .processor
+        .actor
+        .send(SubmitBlockCommitments {})
+        .await
+        .unwrap();
+    let execution_info = pipeline
+        .executor
+        .get_transaction_execution_infos_by_hash(vec![tx_hash])
+        .await
+        .unwrap()
+        .pop()
+        .flatten()
+        .unwrap();
+    assert_eq!(execution_info.status, KeptVMStatus::Executed);
+    let now_milliseconds = pipeline
+        .executor
+        .as_module_binding::<TimestampModule>()
+        .now_milliseconds()
+        .unwrap();
+    assert_eq!(now_milliseconds, time as u64 * 1000);
+
+    // The body is removed after the block is executed
+    assert_eq!(
+        pipeline.sequencer.get_l1_block_body(tx_hash).await.unwrap(),
+        None
+    );
+    assert_eq!(pipeline.sequencer.get_sequencer_order().await.unwrap(), 1);
+}
@@ -0,0 +1,508 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::actor::processor::sequenced_tx_order;
+use crate::proxy::PipelineProcessorProxy;
+use anyhow::{bail, ensure, Context, Result};
+use moveos_types::h256::H256;
+use parking_lot::Mutex;
+use rooch_executor::proxy::ExecutorProxy;
+use rooch_types::address::RoochAddress;
+use rooch_types::transaction::{RoochTransaction, TransactionStatus};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+/// The default max number of the queued transactions in the tx pool
+pub const DEFAULT_TX_POOL_CAPACITY: usize = 10000;
+/// The default max number of the queued transactions of one sender
+pub const DEFAULT_MAX_PENDING_TXS_PER_SENDER: usize = 32;
+/// The default number of the executed or dropped transactions whose status is kept in memory
+pub const DEFAULT_TX_STATUS_HISTORY_SIZE: usize = 10000;
+
+#[derive(Clone, Debug)]
+pub struct TxPoolConfig {
+    pub capacity: usize,
+    pub max_pending_txs_per_sender: usize,
+    pub status_history_size: usize,
+}
+
+impl Default for TxPoolConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_TX_POOL_CAPACITY,
+            max_pending_txs_per_sender: DEFAULT_MAX_PENDING_TXS_PER_SENDER,
+            status_history_size: DEFAULT_TX_STATUS_HISTORY_SIZE,
+        }
+    }
+}
+
+#[derive(Default)]
+struct TxPoolInner {
+    // The queued transactions of every sender, (sequence_number, tx_hash) in sequence number order
+    pending: HashMap<RoochAddress, VecDeque<(u64, H256)>>,
+    pending_count: usize,
+    statuses: HashMap<H256, TransactionStatus>,
+    // The executed or dropped transactions in finish order, the oldest status is evicted first
+    finished: VecDeque<H256>,
+}
+
+impl TxPoolInner {
+    fn is_pending(&self, tx_hash: &H256) -> bool {
+        matches!(self.statuses.get(tx_hash), Some(TransactionStatus::Queued))
+    }
+
+    fn last_pending_sequence_number(&self, sender: &RoochAddress) -> Option<u64> {
+        self.pending
+            .get(sender)
+            .and_then(|sender_pending| sender_pending.back().map(|(seq, _)| *seq))
+    }
+
+    fn check_submittable(
+        &self,
+        config: &TxPoolConfig,
+        tx_hash: H256,
+        sender: RoochAddress,
+        sequence_number: u64,
+    ) -> Result<()> {
+        match self.statuses.get(&tx_hash) {
+            Some(TransactionStatus::Queued) => {
+                bail!("The transaction {:?} is already queued", tx_hash)
+            }
+            Some(TransactionStatus::Executed { tx_order })
+            | Some(TransactionStatus::Failed { tx_order, .. }) => bail!(
+                "The transaction {:?} is already sequenced with tx order {}",
+                tx_hash,
+                tx_order
+            ),
+            // A dropped transaction can be submitted again
+            Some(TransactionStatus::Dropped { .. }) | None => {}
+        }
+        ensure!(
+            self.pending_count < config.capacity,
+            "The tx pool is full, the capacity is {}",
+            config.capacity
+        );
+        let sender_pending_count = self.pending.get(&sender).map_or(0, |p| p.len());
+        ensure!(
+            sender_pending_count < config.max_pending_txs_per_sender,
+            "The sender {} has {} queued transactions, the limit is {}, the transaction with sequence number {} is rejected",
+            sender,
+            sender_pending_count,
+            config.max_pending_txs_per_sender,
+            sequence_number
+        );
+        Ok(())
+    }
+
+    fn queue(
+        &mut self,
+        config: &TxPoolConfig,
+        tx_hash: H256,
+        sender: RoochAddress,
+        sequence_number: u64,
+    ) -> Result<()> {
+        self.check_submittable(config, tx_hash, sender, sequence_number)?;
+        if let Some(last_sequence_number) = self.last_pending_sequence_number(&sender) {
+            ensure!(
+                sequence_number == last_sequence_number + 1,
+                "The sequence number {} of the transaction does not follow the last queued transaction {} of the sender {}",
+                sequence_number,
+                last_sequence_number,
+                sender
+            );
+        }
+        self.pending
+            .entry(sender)
+            .or_default()
+            .push_back((sequence_number, tx_hash));
+        self.pending_count += 1;
+        self.statuses.insert(tx_hash, TransactionStatus::Queued);
+        Ok(())
+    }
+
+    fn finish(
+        &mut self,
+        sender: RoochAddress,
+        tx_hash: H256,
+        status: TransactionStatus,
+        history_size: usize,
+    ) {
+        if let Some(sender_pending) = self.pending.get_mut(&sender) {
+            let before = sender_pending.len();
+            sender_pending.retain(|(_, hash)| hash != &tx_hash);
+            self.pending_count -= before - sender_pending.len();
+            if sender_pending.is_empty() {
+                self.pending.remove(&sender);
+            }
+        }
+        self.statuses.insert(tx_hash, status);
+        self.finished.push_back(tx_hash);
+        while self.finished.len() > history_size {
+            if let Some(evicted) = self.finished.pop_front() {
+                // The status may be replaced by a resubmitted transaction
+                if !self.is_pending(&evicted) {
+                    self.statuses.remove(&evicted);
+                }
+            }
+        }
+    }
+
+    /// Drop the transaction, the following queued transactions of the sender are dropped too,
+    /// because their sequence numbers can not be valid anymore.
+    fn drop_sender_txs(
+        &mut self,
+        sender: RoochAddress,
+        tx_hash: H256,
+        reason: String,
+        history_size: usize,
+    ) {
+        let following = self
+            .pending
+            .get(&sender)
+            .map(|sender_pending| {
+                sender_pending
+                    .iter()
+                    .skip_while(|(_, hash)| hash != &tx_hash)
+                    .skip(1)
+                    .map(|(_, hash)| *hash)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        self.finish(
+            sender,
+            tx_hash,
+            TransactionStatus::Dropped { reason },
+            history_size,
+        );
+        for following_hash in following {
+            let status = TransactionStatus::Dropped {
+                reason: format!(
+                    "The previous transaction {:?} of the sender is dropped",
+                    tx_hash
+                ),
+            };
+            self.finish(sender, following_hash, status, history_size);
+        }
+    }
+}
+
+/// TxPool queues the submitted L2 transactions in front of the pipeline processor,
+/// so the submitter does not wait for the transaction to be sequenced and executed.
+/// The transactions are executed one by one in the submission order by a background worker.
+#[derive(Clone)]
+pub struct TxPool {
+    inner: Arc<Mutex<TxPoolInner>>,
+    sender: mpsc::UnboundedSender<RoochTransaction>,
+    executor: ExecutorProxy,
+    config: TxPoolConfig,
+}
+
+impl TxPool {
+    /// Create the tx pool and start the worker executing the queued transactions.
+    pub fn start(
+        executor: ExecutorProxy,
+        processor: PipelineProcessorProxy,
+        config: TxPoolConfig,
+    ) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let tx_pool = Self {
+            inner: Arc::new(Mutex::new(TxPoolInner::default())),
+            sender,
+            executor,
+            config,
+        };
+        tokio::spawn(tx_pool.clone().run(processor, receiver));
+        tx_pool
+    }
+
+    /// Validate the transaction and queue it, return the tx hash without waiting for the execution.
+    /// The authenticator is always verified without accessing the state. The transaction is fully
+    /// validated against the latest state if the sender has no queued transaction, otherwise the
+    /// sequence number must follow the sender's last queued transaction, and the transaction is
+    /// fully validated when it is executed.
+    pub async fn submit(&self, mut tx: RoochTransaction) -> Result<H256> {
+        let tx_hash = tx.tx_hash();
+        let sender = tx.sender();
+        let sequence_number = tx.sequence_number();
+        tx.verify_authenticator_stateless()
+            .with_context(|| format!("Invalid authenticator of the transaction {:?}", tx_hash))?;
+
+        let last_pending = {
+            let inner = self.inner.lock();
+            inner.check_submittable(&self.config, tx_hash, sender, sequence_number)?;
+            inner.last_pending_sequence_number(&sender)
+        };
+        if last_pending.is_none() {
+            self.executor.validate_l2_tx(tx.clone()).await?;
+        }
+
+        let mut inner = self.inner.lock();
+        // The pool may be changed by the concurrent submissions during the validation,
+        // the checks are done again when queueing
+        inner.queue(&self.config, tx_hash, sender, sequence_number)?;
+        if self.sender.send(tx).is_err() {
+            let status = TransactionStatus::Dropped {
+                reason: "The tx pool is stopped".to_string(),
+            };
+            inner.finish(sender, tx_hash, status, self.config.status_history_size);
+            bail!("The tx pool is stopped");
+        }
+        debug!("tx pool queued tx: {:?}, sender: {}", tx_hash, sender);
+        Ok(tx_hash)
+    }
+
+    /// The status of the transaction in the tx pool, none if the transaction is unknown or its status is evicted.
+    pub fn get_status(&self, tx_hash: &H256) -> Option<TransactionStatus> {
+        self.inner.lock().statuses.get(tx_hash).cloned()
+    }
+
+    /// The number of the queued transactions
+    pub fn pending_count(&self) -> usize {
+        self.inner.lock().pending_count
+    }
+
+    async fn run(
+        self,
+        processor: PipelineProcessorProxy,
+        mut receiver: mpsc::UnboundedReceiver<RoochTransaction>,
+    ) {
+        while let Some(mut tx) = receiver.recv().await {
+            let tx_hash = tx.tx_hash();
+            let sender = tx.sender();
+            // The transaction may be dropped with the previous transaction of the sender
+            if !self.inner.lock().is_pending(&tx_hash) {
+                continue;
+            }
+            match processor.execute_l2_tx(tx).await {
+                Ok(response) => {
+                    let status = TransactionStatus::Executed {
+                        tx_order: response.sequence_info.tx_order,
+                    };
+                    self.inner.lock().finish(
+                        sender,
+                        tx_hash,
+                        status,
+                        self.config.status_history_size,
+                    );
+                }
+                Err(e) => {
+                    let history_size = self.config.status_history_size;
+                    match sequenced_tx_order(&e) {
+                        Some(tx_order) => {
+                            // The transaction is in the ledger and its sequence number is used,
+                            // the following transactions of the sender are kept and validated when executed
+                            warn!(
+                                "tx pool tx: {:?} failed after sequenced with tx order {}, error: {:?}",
+                                tx_hash, tx_order, e
+                            );
+                            let status = TransactionStatus::Failed {
+                                tx_order,
+                                reason: e.to_string(),
+                            };
+                            self.inner
+                                .lock()
+                                .finish(sender, tx_hash, status, history_size);
+                        }
+                        None => {
+                            warn!("tx pool drop tx: {:?}, error: {:?}", tx_hash, e);
+                            self.inner.lock().drop_sender_txs(
+                                sender,
+                                tx_hash,
+                                e.to_string(),
+                                history_size,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rooch_types::address::RoochSupportedAddress;
+
+    fn test_config(capacity: usize, max_pending_txs_per_sender: usize) -> TxPoolConfig {
+        TxPoolConfig {
+            capacity,
+            max_pending_txs_per_sender,
+            status_history_size: DEFAULT_TX_STATUS_HISTORY_SIZE,
+        }
+    }
+
+    fn dropped(reason: &str) -> TransactionStatus {
+        TransactionStatus::Dropped {
+            reason: reason.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_dedup() {
+        let config = TxPoolConfig::default();
+        let mut inner = TxPoolInner::default();
+        let sender = RoochAddress::random();
+        let tx_hash = H256::random();
+
+        inner.queue(&config, tx_hash, sender, 0).unwrap();
+        assert!(inner.queue(&config, tx_hash, sender, 1).is_err());
+
+        let status = TransactionStatus::Executed { tx_order: 1 };
+        inner.finish(sender, tx_hash, status, config.status_history_size);
+        assert!(inner.queue(&config, tx_hash, sender, 0).is_err());
+
+        let status = TransactionStatus::Failed {
+            tx_order: 1,
+            reason: "execute error".to_string(),
+        };
+        inner.finish(sender, tx_hash, status, config.status_history_size);
+        assert!(inner.queue(&config, tx_hash, sender, 0).is_err());
+
+        // A dropped transaction can be submitted again
+        inner.finish(
+            sender,
+            tx_hash,
+            dropped("invalid"),
+            config.status_history_size,
+        );
+        inner.queue(&config, tx_hash, sender, 0).unwrap();
+        assert!(inner.is_pending(&tx_hash));
+        assert_eq!(inner.pending_count, 1);
+    }
+
+    #[test]
+    fn test_per_sender_limit() {
+        let config = test_config(10, 2);
+        let mut inner = TxPoolInner::default();
+        let sender = RoochAddress::random();
+
+        // The sequence number must follow the last queued transaction of the sender
+        inner.queue(&config, H256::random(), sender, 5).unwrap();
+        assert!(inner.queue(&config, H256::random(), sender, 5).is_err());
+        assert!(inner.queue(&config, H256::random(), sender, 7).is_err());
+        inner.queue(&config, H256::random(), sender, 6).unwrap();
+
+        assert!(inner.queue(&config, H256::random(), sender, 7).is_err());
+        assert_eq!(inner.last_pending_sequence_number(&sender), Some(6));
+
+        // The other senders are not limited
+        inner
+            .queue(&config, H256::random(), RoochAddress::random(), 0)
+            .unwrap();
+        assert_eq!(inner.pending_count, 3);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let config = test_config(2, 2);
+        let mut inner = TxPoolInner::default();
+        let first_sender = RoochAddress::random();
+        let first_tx_hash = H256::random();
+
+        inner
+            .queue(&config, first_tx_hash, first_sender, 0)
+            .unwrap();
+        inner
+            .queue(&config, H256::random(), RoochAddress::random(), 0)
+            .unwrap();
+        assert!(inner
+            .queue(&config, H256::random(), RoochAddress::random(), 0)
+            .is_err());
+
+        let status = TransactionStatus::Executed { tx_order: 1 };
+        inner.finish(
+            first_sender,
+            first_tx_hash,
+            status,
+            config.status_history_size,
+        );
+        inner
+            .queue(&config, H256::random(), RoochAddress::random(), 0)
+            .unwrap();
+        assert_eq!(inner.pending_count, 2);
+    }
+
+    #[test]
+    fn test_drop_cascade() {
+        let config = TxPoolConfig::default();
+        let mut inner = TxPoolInner::default();
+        let sender = RoochAddress::random();
+        let other_sender = RoochAddress::random();
+        let tx_hashes = (0..4).map(|_| H256::random()).collect::<Vec<_>>();
+        for (sequence_number, tx_hash) in tx_hashes.iter().enumerate() {
+            inner
+                .queue(&config, *tx_hash, sender, sequence_number as u64)
+                .unwrap();
+        }
+        let other_tx_hash = H256::random();
+        inner
+            .queue(&config, other_tx_hash, other_sender, 0)
+            .unwrap();
+
+        let status = TransactionStatus::Executed { tx_order: 1 };
+        inner.finish(sender, tx_hashes[0], status, config.status_history_size);
+        inner.drop_sender_txs(
+            sender,
+            tx_hashes[1],
+            "invalid".to_string(),
+            config.status_history_size,
+        );
+
+        assert_eq!(
+            inner.statuses.get(&tx_hashes[0]),
+            Some(&TransactionStatus::Executed { tx_order: 1 })
+        );
+        assert_eq!(inner.statuses.get(&tx_hashes[1]), Some(&dropped("invalid")));
+        let cascade_reason = format!(
+            "The previous transaction {:?} of the sender is dropped",
+            tx_hashes[1]
+        );
+        for tx_hash in &tx_hashes[2..] {
+            assert_eq!(inner.statuses.get(tx_hash), Some(&dropped(&cascade_reason)));
+        }
+        assert!(!inner.pending.contains_key(&sender));
+        assert!(inner.is_pending(&other_tx_hash));
+        assert_eq!(inner.pending_count, 1);
+
+        // The dropped sequence number can be used again
+        inner.queue(&config, H256::random(), sender, 1).unwrap();
+    }
+
+    #[test]
+    fn test_status_eviction() {
+        let history_size = 2;
+        let config = TxPoolConfig {
+            status_history_size: history_size,
+            ..TxPoolConfig::default()
+        };
+        let mut inner = TxPoolInner::default();
+        let sender = RoochAddress::random();
+        let tx_hashes = (0..3).map(|_| H256::random()).collect::<Vec<_>>();
+
+        // The dropped transaction is resubmitted, its old finished entry must not evict the queued status
+        let resubmitted = tx_hashes[0];
+        inner.queue(&config, resubmitted, sender, 0).unwrap();
+        inner.finish(sender, resubmitted, dropped("invalid"), history_size);
+        inner.queue(&config, resubmitted, sender, 0).unwrap();
+
+        for (i, tx_hash) in tx_hashes[1..].iter().enumerate() {
+            let other_sender = RoochAddress::random();
+            inner.queue(&config, *tx_hash, other_sender, 0).unwrap();
+            let status = TransactionStatus::Executed { tx_order: i as u64 };
+            inner.finish(other_sender, *tx_hash, status, history_size);
+        }
+        assert_eq!(inner.finished.len(), history_size);
+        assert!(inner.is_pending(&resubmitted));
+
+        let status = TransactionStatus::Executed { tx_order: 2 };
+        inner.finish(sender, resubmitted, status, history_size);
+        assert_eq!(inner.finished.len(), history_size);
+        assert!(!inner.statuses.contains_key(&tx_hashes[1]));
+        assert!(inner.statuses.contains_key(&tx_hashes[2]));
+        assert!(inner.statuses.contains_key(&resubmitted));
+        assert_eq!(inner.statuses.len(), history_size);
+    }
+}
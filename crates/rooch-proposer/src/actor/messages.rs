@@ -1,10 +1,14 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use accumulator::proof::AccumulatorProof;
 use anyhow::Result;
 use coerce::actor::{message::Message, scheduler::timer::TimerTick};
+use moveos_types::h256::H256;
 use moveos_types::transaction::TransactionExecutionInfo;
-use rooch_types::transaction::LedgerTransaction;
+use rooch_types::address::RoochAddress;
+use rooch_types::block::Block;
+use rooch_types::transaction::{LedgerTransaction, RoochTransaction};
 
 /// Transaction Propose Message
 #[derive(Debug)]
@@ -30,3 +34,52 @@ impl Message for ProposeBlock {
 }
 
 impl TimerTick for ProposeBlock {}
+
+/// Get block by block number Message
+#[derive(Debug)]
+pub struct GetBlockMessage {
+    pub block_number: u128,
+}
+
+impl Message for GetBlockMessage {
+    type Result = Result<Option<Block>>;
+}
+
+/// Get the latest block Message
+#[derive(Debug)]
+pub struct GetLatestBlockMessage {}
+
+impl Message for GetLatestBlockMessage {
+    type Result = Result<Option<Block>>;
+}
+
+/// Get the proposer account address Message
+#[derive(Debug)]
+pub struct GetProposerAddressMessage {}
+
+impl Message for GetProposerAddressMessage {
+    type Result = RoochAddress;
+}
+
+/// Sign the transaction which submits the block commitment to the on-chain SCC with the proposer key
+#[derive(Debug)]
+pub struct SignBlockCommitmentMessage {
+    pub block_number: u128,
+    pub sequence_number: u64,
+    pub chain_id: u64,
+}
+
+impl Message for SignBlockCommitmentMessage {
+    type Result = Result<Option<RoochTransaction>>;
+}
+
+/// Get the state root of the tx in the block with the proof Message
+#[derive(Debug)]
+pub struct GetStateRootProofMessage {
+    pub block_number: u128,
+    pub tx_index: u64,
+}
+
+impl Message for GetStateRootProofMessage {
+    type Result = Result<Option<(H256, AccumulatorProof)>>;
+}
@@ -1,28 +1,67 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use accumulator::proof::AccumulatorProof;
 use anyhow::Result;
 use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor};
 
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::gas_schedule::GasScheduleConfig;
 use rooch_da::proxy::DAProxy;
+use rooch_store::RoochStore;
+use rooch_types::address::RoochAddress;
+use rooch_types::block::{Block, PendingBlockTransaction};
 use rooch_types::crypto::RoochKeyPair;
+use rooch_types::framework::state_commitment_chain::StateCommitmentChainModule;
+use rooch_types::transaction::{RoochTransaction, RoochTransactionData};
 
 use crate::scc::StateCommitmentChain;
 
-use super::messages::{ProposeBlock, TransactionProposeMessage, TransactionProposeResult};
+use super::messages::{
+    GetBlockMessage, GetLatestBlockMessage, GetProposerAddressMessage, GetStateRootProofMessage,
+    ProposeBlock, SignBlockCommitmentMessage, TransactionProposeMessage, TransactionProposeResult,
+};
 
 pub struct ProposerActor {
     proposer_key: RoochKeyPair,
+    proposer_address: RoochAddress,
     scc: StateCommitmentChain,
 }
 
 impl ProposerActor {
-    pub fn new(proposer_key: RoochKeyPair, da_proxy: DAProxy) -> Self {
-        Self {
+    pub fn new(
+        proposer_key: RoochKeyPair,
+        rooch_store: RoochStore,
+        da_proxy: DAProxy,
+    ) -> Result<Self> {
+        let proposer_address = proposer_key.public().rooch_address()?;
+        Ok(Self {
             proposer_key,
-            scc: StateCommitmentChain::new(da_proxy),
-        }
+            proposer_address,
+            scc: StateCommitmentChain::new(rooch_store, da_proxy)?,
+        })
+    }
+
+    /// Sign the block summary with the proposer key, as a transaction to the on-chain SCC
+    pub fn sign_block_commitment(
+        &self,
+        block_number: u128,
+        sequence_number: u64,
+        chain_id: u64,
+    ) -> Result<Option<RoochTransaction>> {
+        let block = match self.scc.get_block(block_number)? {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+        let tx_data = RoochTransactionData::new(
+            self.proposer_address,
+            sequence_number,
+            chain_id,
+            GasScheduleConfig::INITIAL_MAX_GAS_AMOUNT,
+            StateCommitmentChainModule::create_submit_block_action(&block),
+        );
+        Ok(Some(tx_data.sign(&self.proposer_key)))
     }
 }
 
@@ -35,7 +74,8 @@ impl Handler<TransactionProposeMessage> for ProposerActor {
         msg: TransactionProposeMessage,
         _ctx: &mut ActorContext,
     ) -> Result<TransactionProposeResult> {
-        self.scc.append_transaction(msg);
+        self.scc
+            .append_transaction(PendingBlockTransaction::new(msg.tx, msg.tx_execution_info))?;
         Ok(TransactionProposeResult {})
     }
 }
@@ -45,18 +85,75 @@ impl Handler<ProposeBlock> for ProposerActor {
     async fn handle(&mut self, _message: ProposeBlock, _ctx: &mut ActorContext) {
         let block = self.scc.propose_block().await;
         match block {
-            Some(block) => {
+            Ok(Some(block)) => {
                 log::info!(
                     "[ProposeBlock] block_number: {}, batch_size: {:?}",
                     block.block_number,
                     block.batch_size
                 );
             }
-            None => {
+            Ok(None) => {
                 log::debug!("[ProposeBlock] no transaction to propose block");
             }
+            Err(e) => {
+                log::error!("[ProposeBlock] propose block failed: {:?}", e);
+            }
         };
-        //TODO submit to the on-chain SCC contract use the proposer key
-        let _proposer_key = &self.proposer_key;
+    }
+}
+
+#[async_trait]
+impl Handler<GetBlockMessage> for ProposerActor {
+    async fn handle(
+        &mut self,
+        msg: GetBlockMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<Block>> {
+        self.scc.get_block(msg.block_number)
+    }
+}
+
+#[async_trait]
+impl Handler<GetLatestBlockMessage> for ProposerActor {
+    async fn handle(
+        &mut self,
+        _msg: GetLatestBlockMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<Block>> {
+        Ok(self.scc.last_block().cloned())
+    }
+}
+
+#[async_trait]
+impl Handler<GetStateRootProofMessage> for ProposerActor {
+    async fn handle(
+        &mut self,
+        msg: GetStateRootProofMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<(H256, AccumulatorProof)>> {
+        self.scc
+            .get_state_root_proof(msg.block_number, msg.tx_index)
+    }
+}
+
+#[async_trait]
+impl Handler<GetProposerAddressMessage> for ProposerActor {
+    async fn handle(
+        &mut self,
+        _msg: GetProposerAddressMessage,
+        _ctx: &mut ActorContext,
+    ) -> RoochAddress {
+        self.proposer_address
+    }
+}
+
+#[async_trait]
+impl Handler<SignBlockCommitmentMessage> for ProposerActor {
+    async fn handle(
+        &mut self,
+        msg: SignBlockCommitmentMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<RoochTransaction>> {
+        self.sign_block_commitment(msg.block_number, msg.sequence_number, msg.chain_id)
     }
 }
@@ -2,13 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::actor::{
-    messages::{TransactionProposeMessage, TransactionProposeResult},
+    messages::{
+        GetBlockMessage, GetLatestBlockMessage, GetProposerAddressMessage,
+        GetStateRootProofMessage, SignBlockCommitmentMessage, TransactionProposeMessage,
+        TransactionProposeResult,
+    },
     proposer::ProposerActor,
 };
+use accumulator::proof::AccumulatorProof;
 use anyhow::Result;
 use coerce::actor::ActorRef;
+use moveos_types::h256::H256;
 use moveos_types::transaction::TransactionExecutionInfo;
-use rooch_types::transaction::LedgerTransaction;
+use rooch_types::address::RoochAddress;
+use rooch_types::block::Block;
+use rooch_types::transaction::{LedgerTransaction, RoochTransaction};
 
 #[derive(Clone)]
 pub struct ProposerProxy {
@@ -32,4 +40,47 @@ impl ProposerProxy {
             })
             .await?
     }
+
+    pub async fn get_block(&self, block_number: u128) -> Result<Option<Block>> {
+        self.actor.send(GetBlockMessage { block_number }).await?
+    }
+
+    pub async fn latest_block(&self) -> Result<Option<Block>> {
+        self.actor.send(GetLatestBlockMessage {}).await?
+    }
+
+    /// Get the state root of the tx at `tx_index` in the block, with the proof of inclusion
+    pub async fn get_state_root_proof(
+        &self,
+        block_number: u128,
+        tx_index: u64,
+    ) -> Result<Option<(H256, AccumulatorProof)>> {
+        self.actor
+            .send(GetStateRootProofMessage {
+                block_number,
+                tx_index,
+            })
+            .await?
+    }
+
+    pub async fn proposer_address(&self) -> Result<RoochAddress> {
+        Ok(self.actor.send(GetProposerAddressMessage {}).await?)
+    }
+
+    /// Get the signed transaction which submits the block commitment to the on-chain SCC,
+    /// returns None if the block has not been proposed yet
+    pub async fn sign_block_commitment(
+        &self,
+        block_number: u128,
+        sequence_number: u64,
+        chain_id: u64,
+    ) -> Result<Option<RoochTransaction>> {
+        self.actor
+            .send(SignBlockCommitmentMessage {
+                block_number,
+                sequence_number,
+                chain_id,
+            })
+            .await?
+    }
 }
@@ -1,62 +1,122 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::BTreeMap;
-
-use crate::actor::messages::TransactionProposeMessage;
+use accumulator::proof::AccumulatorProof;
+use anyhow::Result;
 use moveos_types::h256;
 use moveos_types::h256::H256;
 use rooch_da::messages::Batch;
 use rooch_da::proxy::DAProxy;
-use rooch_types::block::Block;
+use rooch_store::proposer_store::ProposerStore;
+use rooch_store::RoochStore;
+use rooch_types::block::{Block, PendingBlockTransaction};
+use rooch_types::framework::state_commitment_chain::StateCommitmentChainModule;
+use rooch_types::transaction::LedgerTransaction;
 
 /// State Commitment Chain(SCC) is a chain of transaction state root
 /// This SCC is a mirror of the on-chain SCC
 pub struct StateCommitmentChain {
-    //TODO save to the storage
-    blocks: BTreeMap<u128, Block>,
-    buffer: Vec<TransactionProposeMessage>,
+    last_block: Option<Block>,
+    buffer: Vec<PendingBlockTransaction>,
     da: DAProxy,
+    rooch_store: RoochStore,
 }
 
 impl StateCommitmentChain {
-    /// Create a new SCC
-    pub fn new(da_proxy: DAProxy) -> Self {
-        Self {
-            blocks: BTreeMap::new(),
-            buffer: Vec::new(),
-            da: da_proxy,
+    /// Create a new SCC, continue from the last persisted block and unproposed transactions
+    pub fn new(rooch_store: RoochStore, da_proxy: DAProxy) -> Result<Self> {
+        let last_block = rooch_store.get_last_block()?;
+        let mut buffer = rooch_store.get_pending_transactions()?;
+
+        // The block may have been saved while the pending transactions were not cleaned up,
+        // drop the transactions which have been included in the last block.
+        if let Some(block) = &last_block {
+            if let Some(pos) = buffer
+                .iter()
+                .position(|tx| tx.tx.sequence_info.tx_accumulator_root == block.tx_accumulator_root)
+            {
+                let proposed = buffer.drain(..=pos).map(|tx| tx.tx_order()).collect();
+                rooch_store.remove_pending_transactions(proposed)?;
+            }
         }
+
+        if let Some(block) = &last_block {
+            log::info!(
+                "[StateCommitmentChain] continue from block_number: {}, pending transactions: {}",
+                block.block_number,
+                buffer.len()
+            );
+        }
+
+        Ok(Self {
+            last_block,
+            buffer,
+            da: da_proxy,
+            rooch_store,
+        })
     }
 
-    pub fn append_transaction(&mut self, tx: TransactionProposeMessage) {
+    pub fn append_transaction(&mut self, tx: PendingBlockTransaction) -> Result<()> {
+        self.rooch_store.save_pending_transaction(tx.clone())?;
         self.buffer.push(tx);
+        Ok(())
     }
 
     /// Append a new block to the SCC
-    fn append_block(&mut self, block: Block) {
-        self.blocks.insert(block.block_number, block);
+    fn append_block(&mut self, block: Block, state_roots: Vec<H256>) -> Result<()> {
+        self.rooch_store.save_block(block.clone(), state_roots)?;
+        self.last_block = Some(block);
+        Ok(())
+    }
+
+    /// Get the block of the SCC by block number
+    pub fn get_block(&self, block_number: u128) -> Result<Option<Block>> {
+        self.rooch_store.get_block(block_number)
+    }
+
+    /// Prove the state root of the tx at `tx_index` is included in the block,
+    /// returns the state root and the proof
+    pub fn get_state_root_proof(
+        &self,
+        block_number: u128,
+        tx_index: u64,
+    ) -> Result<Option<(H256, AccumulatorProof)>> {
+        let state_roots = match self.rooch_store.get_block_state_roots(block_number)? {
+            Some(state_roots) => state_roots,
+            None => return Ok(None),
+        };
+        let proof = Block::state_root_proof(&state_roots, tx_index)?;
+        Ok(Some((state_roots[tx_index as usize], proof)))
     }
 
     /// Get the last block of the SCC
     pub fn last_block(&self) -> Option<&Block> {
-        self.blocks.values().last()
+        self.last_block.as_ref()
     }
 
     /// Get the last block number of the SCC
     pub fn last_block_number(&self) -> Option<u128> {
-        self.blocks.keys().last().copied()
+        self.last_block.as_ref().map(|block| block.block_number)
     }
 
     /// Trigger the proposer to propose a new block
-    pub async fn propose_block(&mut self) -> Option<&Block> {
+    pub async fn propose_block(&mut self) -> Result<Option<&Block>> {
         if self.buffer.is_empty() {
-            return None;
+            return Ok(None);
+        }
+        // The block commitments are submitted for the proposed blocks, a block of them only would
+        // be committed by another commitment, so they wait for the other transactions to propose a block.
+        if self
+            .buffer
+            .iter()
+            .all(|tx| StateCommitmentChainModule::is_submit_block_tx(&tx.tx))
+        {
+            return Ok(None);
         }
         // construct a new block from buffer
         let latest_transaction = self.buffer.last().expect("buffer must not empty");
         let tx_accumulator_root = latest_transaction.tx.sequence_info.tx_accumulator_root;
-        let state_roots = self
+        let state_roots: Vec<H256> = self
             .buffer
             .iter()
             .map(|tx| tx.tx_execution_info.state_root)
@@ -79,7 +139,9 @@ impl StateCommitmentChain {
 
         // submit batch to DA server
         // TODO move batch submit out of proposer
-        let batch_data: Vec<u8> = self.buffer.iter().flat_map(|tx| tx.tx.encode()).collect();
+        // encode the tx list as a whole, so the batch could be decoded back into transactions
+        let txs: Vec<LedgerTransaction> = self.buffer.iter().map(|tx| tx.tx.clone()).collect();
+        let batch_data = bcs::to_bytes(&txs)?;
         // regard batch(tx list) as a blob: easy to check integrity
         let batch_hash = h256::sha3_256_of(&batch_data);
         if let Err(e) = self
@@ -92,7 +154,7 @@ impl StateCommitmentChain {
             .await
         {
             log::error!("submit batch to DA server failed: {}", e);
-            return None;
+            return Ok(None);
         }
 
         let new_block = Block::new(
@@ -100,10 +162,11 @@ impl StateCommitmentChain {
             batch_size,
             prev_tx_accumulator_root,
             tx_accumulator_root,
-            state_roots,
+            state_roots.clone(),
         );
-        self.append_block(new_block);
-        self.buffer.clear();
-        self.last_block()
+        self.append_block(new_block, state_roots)?;
+        let proposed = self.buffer.drain(..).map(|tx| tx.tx_order()).collect();
+        self.rooch_store.remove_pending_transactions(proposed)?;
+        Ok(self.last_block())
     }
 }
@@ -0,0 +1,240 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::bitcoin_relayer::{get_ready_l1_txs, new_l1_block_with_body};
+use super::messages::{
+    GetL1TipHeightMessage, GetReadyL1BlockMessage, GetReadyL1TxsMessage, SyncTick,
+};
+use super::relayer_proxy::RelayerProxy;
+use super::relayer_registry::{RelayerContext, RelayerFactory};
+use crate::bitcoin_block_file::BlockFileIndex;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use bitcoin::BlockHash;
+use coerce::actor::{context::ActorContext, message::Handler, Actor, ActorRef};
+use moveos_types::module_binding::MoveFunctionCaller;
+use rooch_config::{BitcoinBlockFileRelayerConfig, RelayerFailurePolicy};
+use rooch_executor::proxy::ExecutorProxy;
+use rooch_types::bitcoin::types::BlockHeightHash;
+use rooch_types::bitcoin::{pending_block::PendingBlockModule, BitcoinModule};
+use rooch_types::into_address::FromAddress;
+use rooch_types::transaction::{L1BlockWithBody, L1Transaction};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::{error, info};
+
+/// Relay the Bitcoin blocks from the local block files instead of the Bitcoin RPC.
+/// The blocks are relayed in height order from the next block of the best block in Rooch,
+/// so the import can be stopped and resumed.
+pub struct BitcoinBlockFileRelayer {
+    block_dir: PathBuf,
+    genesis_block: BlockHeightHash,
+    end_block_height: Option<u64>,
+    move_caller: ExecutorProxy,
+    // The index is built at the first sync
+    index: Option<BlockFileIndex>,
+    // The main chain in the block files, start from the Rooch genesis block or the best block in Rooch at the first sync
+    chain: Vec<BlockHash>,
+    chain_start_height: u64,
+    chain_positions: HashMap<BlockHash, usize>,
+    buffer: Vec<L1BlockWithBody>,
+    batch_size: usize,
+}
+
+impl BitcoinBlockFileRelayer {
+    pub fn new(config: BitcoinBlockFileRelayerConfig, executor: ExecutorProxy) -> Result<Self> {
+        let bitcoin_module = executor.as_module_binding::<BitcoinModule>();
+        let genesis_block = bitcoin_module.get_genesis_block()?;
+        Ok(Self {
+            block_dir: config.btc_block_dir,
+            genesis_block,
+            end_block_height: config.btc_end_block_height,
+            move_caller: executor,
+            index: None,
+            chain: vec![],
+            chain_start_height: 0,
+            chain_positions: HashMap::new(),
+            buffer: vec![],
+            batch_size: 10,
+        })
+    }
+
+    fn build_chain(&mut self, best_block_in_rooch: Option<&BlockHeightHash>) -> Result<()> {
+        let index = BlockFileIndex::build(&self.block_dir)?;
+        let anchor = best_block_in_rooch.unwrap_or(&self.genesis_block);
+        let anchor_hash = BlockHash::from_address(anchor.block_hash);
+        if !index.contains(&anchor_hash) {
+            return Err(anyhow!(
+                "The block {} at height {} is not in the block files of {:?}",
+                anchor_hash,
+                anchor.block_height,
+                self.block_dir
+            ));
+        }
+        let mut chain = vec![anchor_hash];
+        chain.extend(index.main_chain_after(anchor_hash));
+        self.chain_positions = chain
+            .iter()
+            .enumerate()
+            .map(|(position, block_hash)| (*block_hash, position))
+            .collect();
+        self.chain_start_height = anchor.block_height;
+        self.chain = chain;
+        self.index = Some(index);
+        info!(
+            "BitcoinBlockFileRelayer build the main chain from height {} to {}",
+            self.chain_start_height,
+            self.l1_tip_height().unwrap_or_default()
+        );
+        Ok(())
+    }
+
+    fn l1_tip_height(&self) -> Option<u64> {
+        if self.chain.is_empty() {
+            None
+        } else {
+            Some(self.chain_start_height + self.chain.len() as u64 - 1)
+        }
+    }
+
+    fn sync_block(&mut self) -> Result<()> {
+        if !self.buffer.is_empty() {
+            return Ok(());
+        }
+        let pending_block_module = self.move_caller.as_module_binding::<PendingBlockModule>();
+        let best_block_in_rooch = pending_block_module.get_best_block()?;
+        if self.index.is_none() {
+            self.build_chain(best_block_in_rooch.as_ref())?;
+        }
+
+        // The start block is included
+        let start_position = match &best_block_in_rooch {
+            Some(best_block_in_rooch) => {
+                let best_block_hash = BlockHash::from_address(best_block_in_rooch.block_hash);
+                let position = self.chain_positions.get(&best_block_hash).ok_or_else(|| {
+                    anyhow!(
+                        "The best block {} at height {} in rooch is not in the main chain of the block files",
+                        best_block_hash,
+                        best_block_in_rooch.block_height
+                    )
+                })?;
+                position + 1
+            }
+            None => 0,
+        };
+        let index = self
+            .index
+            .as_ref()
+            .expect("The block file index should be built");
+        for (position, block_hash) in self
+            .chain
+            .iter()
+            .enumerate()
+            .skip(start_position)
+            .take(self.batch_size)
+        {
+            let block_height = self.chain_start_height + position as u64;
+            if let Some(end_block_height) = self.end_block_height {
+                if block_height > end_block_height {
+                    info!(
+                        "BitcoinBlockFileRelayer reach end block height {}, stop sync",
+                        end_block_height
+                    );
+                    break;
+                }
+            }
+            let block = index.read_block(block_hash)?;
+            info!(
+                "BitcoinBlockFileRelayer process block, height: {}, hash: {}, tx_size: {}, time: {}",
+                block_height,
+                block_hash,
+                block.txdata.len(),
+                block.header.time
+            );
+            self.buffer
+                .push(new_l1_block_with_body(block_height, block));
+        }
+        Ok(())
+    }
+
+    fn pop_buffer(&mut self) -> Option<L1BlockWithBody> {
+        if self.buffer.is_empty() {
+            None
+        } else {
+            Some(self.buffer.remove(0))
+        }
+    }
+}
+
+#[async_trait]
+impl Actor for BitcoinBlockFileRelayer {
+    async fn started(&mut self, _ctx: &mut ActorContext) {}
+}
+
+#[async_trait]
+impl Handler<SyncTick> for BitcoinBlockFileRelayer {
+    async fn handle(&mut self, _message: SyncTick, _ctx: &mut ActorContext) {
+        if let Err(e) = self.sync_block() {
+            error!("BitcoinBlockFileRelayer sync block error: {:?}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<GetReadyL1BlockMessage> for BitcoinBlockFileRelayer {
+    async fn handle(
+        &mut self,
+        _message: GetReadyL1BlockMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<L1BlockWithBody>> {
+        Ok(self.pop_buffer())
+    }
+}
+
+#[async_trait]
+impl Handler<GetReadyL1TxsMessage> for BitcoinBlockFileRelayer {
+    async fn handle(
+        &mut self,
+        _message: GetReadyL1TxsMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Vec<L1Transaction>> {
+        get_ready_l1_txs(&self.move_caller)
+    }
+}
+
+#[async_trait]
+impl Handler<GetL1TipHeightMessage> for BitcoinBlockFileRelayer {
+    async fn handle(
+        &mut self,
+        _message: GetL1TipHeightMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<u64>> {
+        Ok(self.l1_tip_height())
+    }
+}
+
+pub struct BitcoinBlockFileRelayerFactory {
+    pub config: BitcoinBlockFileRelayerConfig,
+}
+
+#[async_trait]
+impl RelayerFactory for BitcoinBlockFileRelayerFactory {
+    fn failure_policy(&self) -> RelayerFailurePolicy {
+        self.config.failure_policy
+    }
+
+    async fn start(
+        &self,
+        ctx: &mut ActorContext,
+        relayer_ctx: &RelayerContext,
+    ) -> Result<RelayerProxy> {
+        let relayer =
+            BitcoinBlockFileRelayer::new(self.config.clone(), relayer_ctx.executor.clone())?;
+        let relayer_actor_ref = ctx
+            .spawn("bitcoin_block_file_relayer".into(), relayer)
+            .await?;
+        Ok(RelayerProxy::new(
+            ActorRef::<BitcoinBlockFileRelayer>::from(relayer_actor_ref),
+        ))
+    }
+}
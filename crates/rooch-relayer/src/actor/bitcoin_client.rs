@@ -10,7 +10,7 @@ use bitcoincore_rpc::{json, Auth, Client, RpcApi};
 use coerce::actor::{context::ActorContext, message::Handler, Actor};
 use rooch_config::BitcoinRelayerConfig;
 
-use super::messages::GetChainTipsMessage;
+use super::messages::{GetChainTipsMessage, GetRawMempoolMessage, GetRawTransactionMessage};
 
 pub struct BitcoinClientActor {
     rpc_client: Client,
@@ -90,3 +90,27 @@ impl Handler<GetChainTipsMessage> for BitcoinClientActor {
         Ok(self.rpc_client.get_chain_tips()?)
     }
 }
+
+#[async_trait]
+impl Handler<GetRawMempoolMessage> for BitcoinClientActor {
+    async fn handle(
+        &mut self,
+        _msg: GetRawMempoolMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Vec<bitcoin::Txid>> {
+        Ok(self.rpc_client.get_raw_mempool()?)
+    }
+}
+
+#[async_trait]
+impl Handler<GetRawTransactionMessage> for BitcoinClientActor {
+    async fn handle(
+        &mut self,
+        msg: GetRawTransactionMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<bitcoin::Transaction> {
+        let GetRawTransactionMessage { txid } = msg;
+
+        Ok(self.rpc_client.get_raw_transaction(&txid, None)?)
+    }
+}
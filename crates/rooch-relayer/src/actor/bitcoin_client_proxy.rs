@@ -9,7 +9,7 @@ use anyhow::Result;
 use bitcoincore_rpc::json;
 use coerce::actor::ActorRef;
 
-use super::messages::GetChainTipsMessage;
+use super::messages::{GetChainTipsMessage, GetRawMempoolMessage, GetRawTransactionMessage};
 
 #[derive(Clone)]
 pub struct BitcoinClientProxy {
@@ -43,4 +43,12 @@ impl BitcoinClientProxy {
     pub async fn get_chain_tips(&self) -> Result<json::GetChainTipsResult> {
         self.actor.send(GetChainTipsMessage {}).await?
     }
+
+    pub async fn get_raw_mempool(&self) -> Result<Vec<bitcoin::Txid>> {
+        self.actor.send(GetRawMempoolMessage {}).await?
+    }
+
+    pub async fn get_raw_transaction(&self, txid: bitcoin::Txid) -> Result<bitcoin::Transaction> {
+        self.actor.send(GetRawTransactionMessage { txid }).await?
+    }
 }
@@ -0,0 +1,633 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::messages::{MempoolTick, QueryPendingTxsMessage};
+use crate::actor::bitcoin_client_proxy::BitcoinClientProxy;
+use anyhow::Result;
+use async_trait::async_trait;
+use bitcoin_move::natives::ord::envelope::ParsedEnvelope;
+use coerce::actor::{context::ActorContext, message::Handler, Actor};
+use move_core_types::account_address::AccountAddress;
+use moveos_types::access_path::AccessPath;
+use moveos_types::moveos_std::object::ObjectID;
+use moveos_types::state::{MoveStructType, ObjectState};
+use rooch_executor::proxy::ExecutorProxy;
+use rooch_types::bitcoin::mempool::{
+    self, PendingCreatedUTXO, PendingInscription, PendingSpentUTXO, PendingTx,
+};
+use rooch_types::bitcoin::ord::{
+    self, Envelope, Inscription, InscriptionID, InscriptionRecord, SatPoint,
+};
+use rooch_types::bitcoin::types::{OutPoint, Transaction};
+use rooch_types::bitcoin::utxo::{self, UTXO};
+use rooch_types::into_address::{FromAddress, IntoAddress};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, warn};
+
+/// The source of the Bitcoin mempool transactions
+#[async_trait]
+pub trait MempoolSource: Send + Sync {
+    async fn get_raw_mempool(&self) -> Result<Vec<bitcoin::Txid>>;
+    async fn get_raw_transaction(&self, txid: bitcoin::Txid) -> Result<bitcoin::Transaction>;
+}
+
+#[async_trait]
+impl MempoolSource for BitcoinClientProxy {
+    async fn get_raw_mempool(&self) -> Result<Vec<bitcoin::Txid>> {
+        BitcoinClientProxy::get_raw_mempool(self).await
+    }
+
+    async fn get_raw_transaction(&self, txid: bitcoin::Txid) -> Result<bitcoin::Transaction> {
+        BitcoinClientProxy::get_raw_transaction(self, txid).await
+    }
+}
+
+/// The reader of the confirmed UTXO and Inscription states
+#[async_trait]
+pub trait MempoolStateReader: Send + Sync {
+    async fn get_states(&self, access_path: AccessPath) -> Result<Vec<Option<ObjectState>>>;
+}
+
+#[async_trait]
+impl MempoolStateReader for ExecutorProxy {
+    async fn get_states(&self, access_path: AccessPath) -> Result<Vec<Option<ObjectState>>> {
+        ExecutorProxy::get_states(self, access_path).await
+    }
+}
+
+/// Poll the Bitcoin mempool and preview the UTXO and Inscription changes of the pending transactions.
+/// The previews are kept in memory, the pending transactions are never written to the state.
+pub struct BitcoinMempoolActor {
+    rpc_client: Box<dyn MempoolSource>,
+    executor: Box<dyn MempoolStateReader>,
+    pending_txs: HashMap<AccountAddress, PendingTx>,
+    batch_size: usize,
+}
+
+/// The spent UTXO info: (value, unconfirmed, inscriptions with the offset in the UTXO)
+type SpentUTXOInfo = (Option<u64>, bool, Vec<(InscriptionID, u64)>);
+
+impl BitcoinMempoolActor {
+    pub fn new(rpc_client: BitcoinClientProxy, executor: ExecutorProxy) -> Self {
+        Self {
+            rpc_client: Box::new(rpc_client),
+            executor: Box::new(executor),
+            pending_txs: HashMap::new(),
+            batch_size: 100,
+        }
+    }
+
+    async fn sync_mempool(&mut self) -> Result<()> {
+        let mempool = self
+            .rpc_client
+            .get_raw_mempool()
+            .await?
+            .into_iter()
+            .map(|txid| txid.into_address())
+            .collect::<HashSet<_>>();
+        // The transactions confirmed or evicted are removed from the preview
+        self.pending_txs.retain(|txid, _| mempool.contains(txid));
+
+        let new_txids = mempool
+            .into_iter()
+            .filter(|txid| !self.pending_txs.contains_key(txid))
+            .take(self.batch_size)
+            .collect::<Vec<_>>();
+        let txs = self.get_txs(new_txids).await;
+        self.preview_txs(txs).await;
+
+        // The transactions previewed before their parent transactions are previewed again
+        let unresolved_txids = self
+            .pending_txs
+            .values()
+            .filter(|tx| !tx.resolved && self.has_previewed_parent(tx))
+            .map(|tx| tx.txid)
+            .take(self.batch_size)
+            .collect::<Vec<_>>();
+        let txs = self.get_txs(unresolved_txids).await;
+        self.preview_txs(txs).await;
+        Ok(())
+    }
+
+    async fn get_txs(&self, txids: Vec<AccountAddress>) -> Vec<bitcoin::Transaction> {
+        let mut txs = vec![];
+        for txid in txids {
+            let bitcoin_txid = bitcoin::Txid::from_address(txid);
+            match self.rpc_client.get_raw_transaction(bitcoin_txid).await {
+                Ok(tx) => txs.push(tx),
+                Err(e) => {
+                    // The transaction may be removed from the mempool after the `getrawmempool`
+                    debug!("Get mempool tx {} error: {:?}", bitcoin_txid, e);
+                }
+            }
+        }
+        txs
+    }
+
+    /// Preview the transactions in ancestry order, the transaction failed to preview is skipped
+    async fn preview_txs(&mut self, txs: Vec<bitcoin::Transaction>) {
+        for tx in sort_by_ancestry(txs) {
+            let txid = tx.txid().into_address();
+            match self.preview_tx(tx).await {
+                Ok(mut pending_tx) => {
+                    if let Some(previous) = self.pending_txs.get(&txid) {
+                        pending_tx.first_seen = previous.first_seen;
+                    }
+                    self.pending_txs.insert(txid, pending_tx);
+                }
+                Err(e) => {
+                    warn!("Preview mempool tx {} error: {:?}", txid, e);
+                }
+            }
+        }
+    }
+
+    /// Whether some unknown spent UTXOs of the transaction are created by the previewed transactions
+    fn has_previewed_parent(&self, tx: &PendingTx) -> bool {
+        tx.spent_utxos.iter().any(|utxo| {
+            utxo.value.is_none()
+                && self
+                    .pending_txs
+                    .get(&utxo.outpoint.txid)
+                    .map_or(false, |parent| {
+                        parent
+                            .created_utxos
+                            .iter()
+                            .any(|created| created.outpoint == utxo.outpoint)
+                    })
+        })
+    }
+
+    async fn preview_tx(&self, bitcoin_tx: bitcoin::Transaction) -> Result<PendingTx> {
+        let envelopes = ParsedEnvelope::from_transaction(&bitcoin_tx)
+            .into_iter()
+            .map(Envelope::<InscriptionRecord>::from)
+            .collect::<Vec<_>>();
+        let tx = Transaction::from(bitcoin_tx);
+
+        let mut spent_utxos = vec![];
+        let mut spent_inscriptions = vec![];
+        for (input_index, input) in tx.input.iter().enumerate() {
+            let outpoint = input.previous_output.clone();
+            let (value, unconfirmed, inscriptions) = self.get_spent_utxo(&outpoint).await?;
+            spent_inscriptions.extend(
+                inscriptions
+                    .into_iter()
+                    .map(|(inscription_id, offset)| (input_index, inscription_id, offset)),
+            );
+            spent_utxos.push(PendingSpentUTXO {
+                object_id: utxo::derive_utxo_id(&outpoint),
+                outpoint,
+                value,
+                unconfirmed,
+            });
+        }
+        let input_values = spent_utxos
+            .iter()
+            .map(|utxo| utxo.value)
+            .collect::<Option<Vec<_>>>();
+
+        let created_utxos = tx
+            .output
+            .iter()
+            .enumerate()
+            .map(|(vout, output)| {
+                let outpoint = OutPoint::new(tx.id, vout as u32);
+                PendingCreatedUTXO {
+                    object_id: utxo::derive_utxo_id(&outpoint),
+                    outpoint,
+                    value: output.value,
+                    recipient_address: output.recipient_address.clone(),
+                }
+            })
+            .collect();
+
+        let mut inscriptions = vec![];
+        for (input_index, inscription_id, offset) in spent_inscriptions {
+            let sat_point = input_values.as_ref().and_then(|input_values| {
+                let input_offset = mempool::input_offset(input_values, input_index, offset);
+                mempool::match_output(&tx, input_offset).map(|(vout, offset)| SatPoint {
+                    outpoint: OutPoint::new(tx.id, vout),
+                    offset,
+                })
+            });
+            inscriptions.push(PendingInscription {
+                object_id: ord::derive_inscription_id(&inscription_id),
+                inscription_id,
+                is_new: false,
+                sat_point,
+            });
+        }
+
+        let envelopes_len = envelopes.len();
+        for (index, envelope) in envelopes.into_iter().enumerate() {
+            let inscription_id = InscriptionID::new(tx.id, index as u32);
+            // Same as the `ord::from_transaction`, the pointer is ignored if it exceeds the input value
+            let sat_point = input_values.as_ref().and_then(|input_values| {
+                let input_index = envelope.input as usize;
+                let input_value = input_values.get(input_index).copied().unwrap_or_default();
+                let pointer = Option::<u64>::from(envelope.payload.pointer)
+                    .filter(|pointer| *pointer < input_value)
+                    .unwrap_or_default();
+                let inscription_offset = mempool::input_offset(input_values, input_index, pointer);
+                mempool::new_inscription_sat_point(&tx, index, envelopes_len, inscription_offset)
+            });
+            inscriptions.push(PendingInscription {
+                object_id: ord::derive_inscription_id(&inscription_id),
+                inscription_id,
+                is_new: true,
+                sat_point,
+            });
+        }
+
+        Ok(PendingTx {
+            txid: tx.id,
+            spent_utxos,
+            created_utxos,
+            inscriptions,
+            resolved: input_values.is_some(),
+            first_seen: chrono::Utc::now().timestamp() as u64,
+        })
+    }
+
+    /// Get the spent UTXO from the state, or from the outputs of the previewed pending transactions
+    async fn get_spent_utxo(&self, outpoint: &OutPoint) -> Result<SpentUTXOInfo> {
+        let object_id = utxo::derive_utxo_id(outpoint);
+        let utxo_state = self
+            .executor
+            .get_states(AccessPath::object(object_id))
+            .await?
+            .pop()
+            .flatten();
+        if let Some(utxo_state) = utxo_state {
+            let utxo = utxo_state.value_as::<UTXO>()?;
+            let inscriptions = self.get_sealed_inscriptions(&utxo).await?;
+            return Ok((Some(utxo.value), false, inscriptions));
+        }
+
+        match self.pending_txs.get(&outpoint.txid) {
+            Some(parent_tx) => {
+                let value = parent_tx
+                    .created_utxos
+                    .iter()
+                    .find(|utxo| &utxo.outpoint == outpoint)
+                    .map(|utxo| utxo.value);
+                let inscriptions = parent_tx
+                    .inscriptions
+                    .iter()
+                    .filter_map(|inscription| {
+                        inscription
+                            .sat_point
+                            .as_ref()
+                            .filter(|sat_point| &sat_point.outpoint == outpoint)
+                            .map(|sat_point| (inscription.inscription_id.clone(), sat_point.offset))
+                    })
+                    .collect();
+                Ok((value, true, inscriptions))
+            }
+            None => Ok((None, false, vec![])),
+        }
+    }
+
+    async fn get_sealed_inscriptions(&self, utxo: &UTXO) -> Result<Vec<(InscriptionID, u64)>> {
+        let protocol = Inscription::struct_tag().to_canonical_string();
+        let object_ids: Vec<ObjectID> = utxo
+            .seals
+            .data
+            .iter()
+            .filter(|element| element.key.as_bytes() == protocol.as_bytes())
+            .flat_map(|element| element.value.clone())
+            .collect();
+        if object_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let inscriptions = self
+            .executor
+            .get_states(AccessPath::objects(object_ids))
+            .await?
+            .into_iter()
+            .flatten()
+            .map(|state| {
+                state.value_as::<Inscription>().map(|inscription| {
+                    (
+                        InscriptionID::new(inscription.txid, inscription.index),
+                        inscription.offset,
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(inscriptions)
+    }
+}
+
+/// Sort the transactions so that a parent transaction is always previewed before its children,
+/// the child can only be resolved with the outputs of the previewed parent.
+fn sort_by_ancestry(txs: Vec<bitcoin::Transaction>) -> Vec<bitcoin::Transaction> {
+    fn visit(
+        txid: bitcoin::Txid,
+        txs: &mut HashMap<bitcoin::Txid, bitcoin::Transaction>,
+        sorted: &mut Vec<bitcoin::Transaction>,
+    ) {
+        // The transaction is removed once visited, so every transaction is sorted only once
+        if let Some(tx) = txs.remove(&txid) {
+            for input in tx.input.iter() {
+                visit(input.previous_output.txid, txs, sorted);
+            }
+            sorted.push(tx);
+        }
+    }
+
+    let txids = txs.iter().map(|tx| tx.txid()).collect::<Vec<_>>();
+    let mut txs = txs
+        .into_iter()
+        .map(|tx| (tx.txid(), tx))
+        .collect::<HashMap<_, _>>();
+    let mut sorted = Vec::with_capacity(txids.len());
+    for txid in txids {
+        visit(txid, &mut txs, &mut sorted);
+    }
+    sorted
+}
+
+impl Actor for BitcoinMempoolActor {}
+
+#[async_trait]
+impl Handler<MempoolTick> for BitcoinMempoolActor {
+    async fn handle(&mut self, _message: MempoolTick, _ctx: &mut ActorContext) {
+        if let Err(err) = self.sync_mempool().await {
+            warn!("Sync bitcoin mempool error: {:?}", err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<QueryPendingTxsMessage> for BitcoinMempoolActor {
+    async fn handle(
+        &mut self,
+        msg: QueryPendingTxsMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Vec<PendingTx>> {
+        let QueryPendingTxsMessage { filter, limit } = msg;
+        let mut pending_txs = self
+            .pending_txs
+            .values()
+            .filter(|tx| filter.matches(tx))
+            .cloned()
+            .collect::<Vec<_>>();
+        // The latest seen transactions first
+        pending_txs.sort_by(|a, b| b.first_seen.cmp(&a.first_seen));
+        pending_txs.truncate(limit);
+        Ok(pending_txs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::absolute::LockTime;
+    use bitcoin::transaction::Version;
+    use bitcoin::{Amount, ScriptBuf, Sequence, TxIn, TxOut, Witness};
+    use coerce::actor::system::ActorSystem;
+    use coerce::actor::IntoActor;
+    use moveos_types::moveos_std::object::ObjectMeta;
+    use moveos_types::moveos_std::simple_multimap::SimpleMultiMap;
+    use moveos_types::state::MoveType;
+    use parking_lot::Mutex;
+    use rooch_types::bitcoin::mempool::PendingTxFilter;
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct MockMempool {
+        txs: Arc<Mutex<Vec<bitcoin::Transaction>>>,
+    }
+
+    impl MockMempool {
+        fn set_txs(&self, txs: Vec<bitcoin::Transaction>) {
+            *self.txs.lock() = txs;
+        }
+    }
+
+    #[async_trait]
+    impl MempoolSource for MockMempool {
+        async fn get_raw_mempool(&self) -> Result<Vec<bitcoin::Txid>> {
+            Ok(self.txs.lock().iter().map(|tx| tx.txid()).collect())
+        }
+
+        async fn get_raw_transaction(&self, txid: bitcoin::Txid) -> Result<bitcoin::Transaction> {
+            self.txs
+                .lock()
+                .iter()
+                .find(|tx| tx.txid() == txid)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("tx {} not found", txid))
+        }
+    }
+
+    /// The confirmed UTXOs, reading the `broken` UTXOs returns an error
+    #[derive(Default)]
+    struct MockState {
+        utxos: HashMap<ObjectID, ObjectState>,
+        broken: HashSet<ObjectID>,
+    }
+
+    impl MockState {
+        fn add_utxo(&mut self, outpoint: &bitcoin::OutPoint, value: u64) {
+            let object_id = utxo::derive_utxo_id(&(*outpoint).into());
+            let utxo = UTXO::new(
+                outpoint.txid.into_address(),
+                outpoint.vout,
+                value,
+                SimpleMultiMap::create(),
+            );
+            let metadata = ObjectMeta::genesis_meta(object_id.clone(), UTXO::type_tag());
+            self.utxos.insert(
+                object_id,
+                ObjectState::new_with_struct(metadata, utxo).unwrap(),
+            );
+        }
+    }
+
+    #[async_trait]
+    impl MempoolStateReader for MockState {
+        async fn get_states(&self, access_path: AccessPath) -> Result<Vec<Option<ObjectState>>> {
+            access_path
+                .into_state_query()
+                .into_fields_query()?
+                .into_iter()
+                .map(|(parent, field_key)| {
+                    let object_id = parent.child_id(field_key);
+                    if self.broken.contains(&object_id) {
+                        anyhow::bail!("read object {} error", object_id);
+                    }
+                    Ok(self.utxos.get(&object_id).cloned())
+                })
+                .collect()
+        }
+    }
+
+    fn new_tx(inputs: Vec<bitcoin::OutPoint>, output_values: Vec<u64>) -> bitcoin::Transaction {
+        bitcoin::Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: inputs
+                .into_iter()
+                .map(|previous_output| TxIn {
+                    previous_output,
+                    script_sig: ScriptBuf::new(),
+                    sequence: Sequence::MAX,
+                    witness: Witness::new(),
+                })
+                .collect(),
+            output: output_values
+                .into_iter()
+                .map(|value| TxOut {
+                    value: Amount::from_sat(value),
+                    script_pubkey: ScriptBuf::new(),
+                })
+                .collect(),
+        }
+    }
+
+    fn confirmed_outpoint(n: u8) -> bitcoin::OutPoint {
+        let tx = new_tx(vec![], vec![n as u64]);
+        bitcoin::OutPoint::new(tx.txid(), 0)
+    }
+
+    fn new_actor(mempool: MockMempool, state: MockState) -> BitcoinMempoolActor {
+        BitcoinMempoolActor {
+            rpc_client: Box::new(mempool),
+            executor: Box::new(state),
+            pending_txs: HashMap::new(),
+            batch_size: 100,
+        }
+    }
+
+    fn pending_tx(actor: &BitcoinMempoolActor, tx: &bitcoin::Transaction) -> PendingTx {
+        actor.pending_txs[&tx.txid().into_address()].clone()
+    }
+
+    #[tokio::test]
+    async fn test_preview_parent_before_child() {
+        let confirmed = confirmed_outpoint(1);
+        let mut state = MockState::default();
+        state.add_utxo(&confirmed, 1000);
+        let parent = new_tx(vec![confirmed], vec![600, 300]);
+        let child = new_tx(vec![bitcoin::OutPoint::new(parent.txid(), 0)], vec![500]);
+        let grandchild = new_tx(vec![bitcoin::OutPoint::new(child.txid(), 0)], vec![400]);
+        let mempool = MockMempool::default();
+        // The descendants come first
+        mempool.set_txs(vec![grandchild.clone(), child.clone(), parent.clone()]);
+
+        let actor = new_actor(mempool, state)
+            .into_actor(Some("BitcoinMempoolTest"), &ActorSystem::new())
+            .await
+            .unwrap();
+        actor.send(MempoolTick {}).await.unwrap();
+        let pending_txs = actor
+            .send(QueryPendingTxsMessage {
+                filter: PendingTxFilter::All,
+                limit: 10,
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(pending_txs.len(), 3);
+        assert!(pending_txs.iter().all(|tx| tx.resolved));
+
+        let child_tx = actor
+            .send(QueryPendingTxsMessage {
+                filter: PendingTxFilter::Txid(child.txid().into_address()),
+                limit: 10,
+            })
+            .await
+            .unwrap()
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(child_tx.spent_utxos[0].value, Some(600));
+        assert!(child_tx.spent_utxos[0].unconfirmed);
+    }
+
+    #[tokio::test]
+    async fn test_re_preview_unresolved_child() {
+        let confirmed = confirmed_outpoint(1);
+        let mut state = MockState::default();
+        state.add_utxo(&confirmed, 1000);
+        let parent = new_tx(vec![confirmed], vec![600]);
+        let child = new_tx(vec![bitcoin::OutPoint::new(parent.txid(), 0)], vec![500]);
+        let mempool = MockMempool::default();
+        let mut actor = new_actor(mempool.clone(), state);
+
+        // The child is seen before its parent
+        mempool.set_txs(vec![child.clone()]);
+        actor.sync_mempool().await.unwrap();
+        let unresolved = pending_tx(&actor, &child);
+        assert!(!unresolved.resolved);
+        assert_eq!(unresolved.spent_utxos[0].value, None);
+
+        mempool.set_txs(vec![child.clone(), parent.clone()]);
+        actor.sync_mempool().await.unwrap();
+        assert!(pending_tx(&actor, &parent).resolved);
+        let resolved = pending_tx(&actor, &child);
+        assert!(resolved.resolved);
+        assert_eq!(resolved.spent_utxos[0].value, Some(600));
+        assert_eq!(resolved.first_seen, unresolved.first_seen);
+
+        // The confirmed or evicted transactions are removed
+        mempool.set_txs(vec![child.clone()]);
+        actor.sync_mempool().await.unwrap();
+        assert_eq!(actor.pending_txs.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_skip_preview_error() {
+        let confirmed = confirmed_outpoint(1);
+        let broken = confirmed_outpoint(2);
+        let mut state = MockState::default();
+        state.add_utxo(&confirmed, 1000);
+        state.broken.insert(utxo::derive_utxo_id(&broken.into()));
+        let tx = new_tx(vec![confirmed], vec![600]);
+        let broken_tx = new_tx(vec![broken], vec![600]);
+        let mempool = MockMempool::default();
+        mempool.set_txs(vec![broken_tx.clone(), tx.clone()]);
+        let mut actor = new_actor(mempool, state);
+
+        actor.sync_mempool().await.unwrap();
+        assert!(pending_tx(&actor, &tx).resolved);
+        assert!(!actor
+            .pending_txs
+            .contains_key(&broken_tx.txid().into_address()));
+    }
+
+    #[test]
+    fn test_sort_by_ancestry() {
+        let parent = new_tx(vec![confirmed_outpoint(1)], vec![600, 300]);
+        let child1 = new_tx(vec![bitcoin::OutPoint::new(parent.txid(), 0)], vec![500]);
+        let child2 = new_tx(vec![bitcoin::OutPoint::new(parent.txid(), 1)], vec![200]);
+        let grandchild = new_tx(
+            vec![
+                bitcoin::OutPoint::new(child2.txid(), 0),
+                bitcoin::OutPoint::new(child1.txid(), 0),
+            ],
+            vec![600],
+        );
+        let sorted = sort_by_ancestry(vec![
+            grandchild.clone(),
+            child1.clone(),
+            child2.clone(),
+            parent.clone(),
+        ])
+        .into_iter()
+        .map(|tx| tx.txid())
+        .collect::<Vec<_>>();
+        assert_eq!(
+            sorted,
+            vec![
+                parent.txid(),
+                child2.txid(),
+                child1.txid(),
+                grandchild.txid()
+            ]
+        );
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::actor::bitcoin_mempool::BitcoinMempoolActor;
+use crate::actor::messages::QueryPendingTxsMessage;
+use anyhow::Result;
+use coerce::actor::ActorRef;
+use rooch_types::bitcoin::mempool::{PendingTx, PendingTxFilter};
+
+#[derive(Clone)]
+pub struct BitcoinMempoolProxy {
+    pub actor: ActorRef<BitcoinMempoolActor>,
+}
+
+impl BitcoinMempoolProxy {
+    pub fn new(actor: ActorRef<BitcoinMempoolActor>) -> Self {
+        Self { actor }
+    }
+
+    pub async fn query_pending_txs(
+        &self,
+        filter: PendingTxFilter,
+        limit: usize,
+    ) -> Result<Vec<PendingTx>> {
+        self.actor
+            .send(QueryPendingTxsMessage { filter, limit })
+            .await?
+    }
+}
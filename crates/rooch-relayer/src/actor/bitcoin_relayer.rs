@@ -1,8 +1,11 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use super::messages::{GetReadyL1BlockMessage, GetReadyL1TxsMessage, SyncTick};
+use super::messages::{
+    GetL1TipHeightMessage, GetReadyL1BlockMessage, GetReadyL1TxsMessage, SyncTick,
+};
 use crate::actor::bitcoin_client_proxy::BitcoinClientProxy;
+use crate::actor::relayer_proxy::RelayerHalted;
 use anyhow::Result;
 use async_trait::async_trait;
 use bitcoin::hashes::Hash;
@@ -12,6 +15,8 @@ use coerce::actor::{context::ActorContext, message::Handler, Actor};
 use moveos_types::module_binding::MoveFunctionCaller;
 use rooch_config::BitcoinRelayerConfig;
 use rooch_executor::proxy::ExecutorProxy;
+use rooch_store::RoochStore;
+use rooch_types::bitcoin::pending_block::DeepReorgCheckpoint;
 use rooch_types::bitcoin::types::BlockHeightHash;
 use rooch_types::into_address::{FromAddress, IntoAddress};
 use rooch_types::{
@@ -27,11 +32,15 @@ pub struct BitcoinRelayer {
     end_block_height: Option<u64>,
     rpc_client: BitcoinClientProxy,
     move_caller: ExecutorProxy,
+    rooch_store: RoochStore,
+    // The relayer halts when a reorg deeper than the pending block window is detected
+    reorg_checkpoint: Option<DeepReorgCheckpoint>,
     buffer: Vec<BlockResult>,
     sync_block_interval: u64,
     latest_sync_timestamp: u64,
     sync_to_latest: bool,
     batch_size: usize,
+    l1_tip_height: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -45,25 +54,42 @@ impl BitcoinRelayer {
         config: BitcoinRelayerConfig,
         rpc_client: BitcoinClientProxy,
         executor: ExecutorProxy,
+        rooch_store: RoochStore,
     ) -> Result<Self> {
         let bitcoin_module = executor.as_module_binding::<BitcoinModule>();
         let genesis_block = bitcoin_module.get_genesis_block()?;
         let sync_block_interval = config.btc_sync_block_interval.unwrap_or(60u64);
+        let reorg_checkpoint = rooch_store
+            .get_meta_store()
+            .get_bitcoin_reorg_checkpoint()?;
+        if let Some(checkpoint) = &reorg_checkpoint {
+            error!(
+                "BitcoinRelayer is halted by the deep reorg at height {}, please rewind the state with `rooch statedb rewind` first. checkpoint: {:?}",
+                checkpoint.fork_height, checkpoint
+            );
+        }
 
         Ok(Self {
             genesis_block,
             end_block_height: config.btc_end_block_height,
             rpc_client,
             move_caller: executor,
+            rooch_store,
+            reorg_checkpoint,
             buffer: vec![],
             sync_block_interval,
             latest_sync_timestamp: 0u64,
             sync_to_latest: false,
             batch_size: 10,
+            l1_tip_height: None,
         })
     }
 
     async fn sync_block(&mut self) -> Result<()> {
+        // The error is already reported when the deep reorg is detected
+        if self.reorg_checkpoint.is_some() {
+            return Ok(());
+        }
         if !self.buffer.is_empty() {
             return Ok(());
         }
@@ -79,6 +105,11 @@ impl BitcoinRelayer {
         let pending_block_module = self.move_caller.as_module_binding::<PendingBlockModule>();
         let best_block_in_rooch = pending_block_module.get_best_block()?;
         let best_block_hash_in_bitcoin = self.rpc_client.get_best_block_hash().await?;
+        let best_block_header_in_bitcoin = self
+            .rpc_client
+            .get_block_header_info(best_block_hash_in_bitcoin)
+            .await?;
+        self.l1_tip_height = Some(best_block_header_in_bitcoin.height as u64);
 
         //The start block is included
         let start_block_hash = match best_block_in_rooch {
@@ -107,6 +138,7 @@ impl BitcoinRelayer {
                         .get_block_header_info(previous_block_hash)
                         .await?;
                 }
+                self.check_reorg_depth(&best_block_in_rooch, &best_block_header_info)?;
                 best_block_header_info.next_block_hash
             }
             None => {
@@ -141,7 +173,67 @@ impl BitcoinRelayer {
         Ok(())
     }
 
+    /// The blocks within the `reorg_block_count` of the best block are pending and can be reorged by the pending block,
+    /// the blocks before them may already be executed, so a reorg reaching them can not be handled.
+    /// The relayer records a checkpoint and halts instead of relaying the blocks of the new chain.
+    fn check_reorg_depth(
+        &mut self,
+        best_block_in_rooch: &BlockHeightHash,
+        common_ancestor_header_info: &GetBlockHeaderResult,
+    ) -> Result<()> {
+        let ancestor_height = common_ancestor_header_info.height as u64;
+        let fork_height = ancestor_height + 1;
+        if fork_height > best_block_in_rooch.block_height {
+            return Ok(());
+        }
+        let pending_block_module = self.move_caller.as_module_binding::<PendingBlockModule>();
+        let reorg_block_count = pending_block_module.get_reorg_block_count()?;
+        let common_ancestor = BlockHeightHash {
+            block_height: ancestor_height,
+            block_hash: common_ancestor_header_info.hash.into_address(),
+        };
+        let checkpoint = match deep_reorg_checkpoint(
+            best_block_in_rooch,
+            common_ancestor,
+            reorg_block_count,
+            chrono::Utc::now().timestamp() as u64,
+        ) {
+            Some(checkpoint) => checkpoint,
+            None => {
+                info!(
+                    "BitcoinRelayer detect reorg at height {}, best block height in rooch: {}",
+                    fork_height, best_block_in_rooch.block_height
+                );
+                return Ok(());
+            }
+        };
+        self.rooch_store
+            .get_meta_store()
+            .save_bitcoin_reorg_checkpoint(checkpoint.clone())?;
+        self.reorg_checkpoint = Some(checkpoint);
+        Err(anyhow::anyhow!(
+            "BitcoinRelayer detect deep reorg at height {}, the reorg block count is {}, best block height in rooch: {}. The relayer is halted, please rewind the state with `rooch statedb rewind`",
+            fork_height,
+            reorg_block_count,
+            best_block_in_rooch.block_height
+        ))
+    }
+
+    /// The halted relayer reports the deep reorg checkpoint, so the `RelayerActor` pauses it until the state is rewound
+    fn ensure_not_halted(&self) -> Result<()> {
+        match &self.reorg_checkpoint {
+            Some(checkpoint) => Err(anyhow::anyhow!(
+                "BitcoinRelayer is halted by the deep reorg at height {}, please rewind the state with `rooch statedb rewind` and restart. checkpoint: {:?}",
+                checkpoint.fork_height,
+                checkpoint
+            )
+            .context(RelayerHalted)),
+            None => Ok(()),
+        }
+    }
+
     fn pop_buffer(&mut self) -> Result<Option<L1BlockWithBody>> {
+        self.ensure_not_halted()?;
         if self.buffer.is_empty() {
             Ok(None)
         } else {
@@ -156,47 +248,82 @@ impl BitcoinRelayer {
             );
             debug!("GetBlockHeaderResult: {:?}", block_result);
 
-            let block_height = block_result.header_info.height;
-            let block_body = rooch_types::bitcoin::types::Block::from(block_result.block);
-
-            Ok(Some(L1BlockWithBody {
-                block: L1Block {
-                    chain_id: RoochMultiChainID::Bitcoin.multichain_id(),
-                    block_height: block_height as u64,
-                    block_hash: block_hash.to_byte_array().to_vec(),
-                },
-                block_body: block_body.encode(),
-            }))
+            Ok(Some(new_l1_block_with_body(
+                block_result.header_info.height as u64,
+                block_result.block,
+            )))
         }
     }
 
     fn get_ready_l1_txs(&mut self) -> Result<Vec<L1Transaction>> {
-        let pending_block_module = self.move_caller.as_module_binding::<PendingBlockModule>();
-        let pending_txs = pending_block_module.get_ready_pending_txs()?;
-        match pending_txs {
-            Some(pending_txs) => {
-                let block_hash = pending_txs.block_hash;
-                let mut txs = pending_txs.txs;
-                if txs.len() > 1 {
-                    // move coinbase tx to the end
-                    let coinbase_tx = txs.remove(0);
-                    txs.push(coinbase_tx);
-                }
-                let l1_txs = txs
-                    .into_iter()
-                    .map(|txid| {
-                        L1Transaction::new(
-                            RoochMultiChainID::Bitcoin.multichain_id(),
-                            block_hash.to_vec(),
-                            txid.to_vec(),
-                        )
-                    })
-                    .collect();
-                Ok(l1_txs)
+        self.ensure_not_halted()?;
+        get_ready_l1_txs(&self.move_caller)
+    }
+}
+
+/// Convert the Bitcoin block at `block_height` to the L1 block which is sequenced and executed by Rooch
+pub(crate) fn new_l1_block_with_body(block_height: u64, block: Block) -> L1BlockWithBody {
+    let block_hash = block.block_hash();
+    let block_body = rooch_types::bitcoin::types::Block::from(block);
+    L1BlockWithBody {
+        block: L1Block {
+            chain_id: RoochMultiChainID::Bitcoin.multichain_id(),
+            block_height,
+            block_hash: block_hash.to_byte_array().to_vec(),
+        },
+        block_body: block_body.encode(),
+    }
+}
+
+/// Get the txs of the pending block which is ready to be executed, the coinbase tx is moved to the end
+pub(crate) fn get_ready_l1_txs(move_caller: &ExecutorProxy) -> Result<Vec<L1Transaction>> {
+    let pending_block_module = move_caller.as_module_binding::<PendingBlockModule>();
+    let pending_txs = pending_block_module.get_ready_pending_txs()?;
+    match pending_txs {
+        Some(pending_txs) => {
+            let block_hash = pending_txs.block_hash;
+            let mut txs = pending_txs.txs;
+            if txs.len() > 1 {
+                // move coinbase tx to the end
+                let coinbase_tx = txs.remove(0);
+                txs.push(coinbase_tx);
             }
-            None => Ok(vec![]),
+            let l1_txs = txs
+                .into_iter()
+                .map(|txid| {
+                    L1Transaction::new(
+                        RoochMultiChainID::Bitcoin.multichain_id(),
+                        block_hash.to_vec(),
+                        txid.to_vec(),
+                    )
+                })
+                .collect();
+            Ok(l1_txs)
         }
+        None => Ok(vec![]),
+    }
+}
+
+/// The checkpoint of the reorg forking after the `common_ancestor`, none if the reorg can be handled by the pending block.
+/// The reorg is too deep if the fork height is not greater than `best_block.block_height - reorg_block_count`.
+fn deep_reorg_checkpoint(
+    best_block: &BlockHeightHash,
+    common_ancestor: BlockHeightHash,
+    reorg_block_count: u64,
+    detected_at: u64,
+) -> Option<DeepReorgCheckpoint> {
+    let fork_height = common_ancestor.block_height + 1;
+    let executed_height = best_block.block_height.saturating_sub(reorg_block_count);
+    if fork_height > best_block.block_height || fork_height > executed_height {
+        return None;
     }
+    Some(DeepReorgCheckpoint {
+        fork_height,
+        common_ancestor,
+        best_block: best_block.clone(),
+        reorg_block_count,
+        detected_at,
+    })
 }
 
 #[async_trait]
@@ -234,3 +361,56 @@ impl Handler<GetReadyL1TxsMessage> for BitcoinRelayer {
         self.get_ready_l1_txs()
     }
 }
+
+#[async_trait]
+impl Handler<GetL1TipHeightMessage> for BitcoinRelayer {
+    async fn handle(
+        &mut self,
+        _message: GetL1TipHeightMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<u64>> {
+        Ok(self.l1_tip_height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::account_address::AccountAddress;
+
+    fn block(block_height: u64) -> BlockHeightHash {
+        BlockHeightHash {
+            block_height,
+            block_hash: AccountAddress::random(),
+        }
+    }
+
+    #[test]
+    fn test_deep_reorg_checkpoint() {
+        let best_block = block(100);
+        // The blocks after 100 - 6 are pending, the reorg forking at them can be handled
+        assert!(deep_reorg_checkpoint(&best_block, block(99), 6, 0).is_none());
+        assert!(deep_reorg_checkpoint(&best_block, block(94), 6, 0).is_none());
+        // The new chain extends the best block
+        assert!(deep_reorg_checkpoint(&best_block, block(100), 6, 0).is_none());
+
+        let common_ancestor = block(93);
+        let checkpoint =
+            deep_reorg_checkpoint(&best_block, common_ancestor.clone(), 6, 42).unwrap();
+        assert_eq!(checkpoint.fork_height, 94);
+        assert_eq!(checkpoint.common_ancestor.block_height, 93);
+        assert_eq!(
+            checkpoint.common_ancestor.block_hash,
+            common_ancestor.block_hash
+        );
+        assert_eq!(checkpoint.best_block.block_hash, best_block.block_hash);
+        assert_eq!(checkpoint.reorg_block_count, 6);
+        assert_eq!(checkpoint.detected_at, 42);
+
+        assert!(deep_reorg_checkpoint(&best_block, block(0), 6, 0).is_some());
+        // All the blocks are executed if the reorg block count is zero
+        assert!(deep_reorg_checkpoint(&best_block, block(99), 0, 0).is_some());
+        // The best block is lower than the reorg block count
+        assert!(deep_reorg_checkpoint(&block(3), block(0), 6, 0).is_none());
+    }
+}
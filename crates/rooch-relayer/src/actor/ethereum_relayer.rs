@@ -5,68 +5,226 @@ use anyhow::Result;
 use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor};
 use ethers::prelude::*;
+use moveos_types::module_binding::MoveFunctionCaller;
 use rooch_config::EthereumRelayerConfig;
+use rooch_executor::proxy::ExecutorProxy;
 use rooch_types::{
-    framework::ethereum::BlockHeader,
+    framework::ethereum::{BlockHeader, EthereumModule},
     multichain_id::RoochMultiChainID,
     transaction::{L1Block, L1BlockWithBody, L1Transaction},
 };
-use std::collections::HashSet;
-use tracing::info;
+use tracing::{debug, error, info, warn};
 
-use super::messages::{GetReadyL1BlockMessage, GetReadyL1TxsMessage, SyncTick};
+use super::messages::{
+    GetL1TipHeightMessage, GetReadyL1BlockMessage, GetReadyL1TxsMessage, SyncTick,
+};
+
+/// The max depth to look back for the common ancestor when the chain is reorganized
+const MAX_REORG_DEPTH: u64 = 64;
+/// The default max number of blocks fetched in one sync tick
+const DEFAULT_BATCH_SIZE: usize = 10;
+
+/// The source of the Ethereum main chain blocks
+#[async_trait]
+pub trait EthereumBlockSource: Send + Sync {
+    async fn get_block_header(&self, block_number: u64) -> Result<Option<BlockHeader>>;
+}
+
+#[async_trait]
+impl EthereumBlockSource for Provider<Http> {
+    async fn get_block_header(&self, block_number: u64) -> Result<Option<BlockHeader>> {
+        let block = self
+            .get_block(BlockId::Number(BlockNumber::Number(block_number.into())))
+            .await?;
+        block.as_ref().map(BlockHeader::try_from).transpose()
+    }
+}
+
+/// Find the last block in rooch which is still on the Ethereum main chain,
+/// walk back from the best block in rooch via the parent hash.
+async fn find_common_ancestor<S, F>(
+    source: &S,
+    get_block_in_rooch: F,
+    best_block_in_rooch: BlockHeader,
+) -> Result<BlockHeader>
+where
+    S: EthereumBlockSource + ?Sized,
+    F: Fn(u64) -> Result<Option<BlockHeader>>,
+{
+    let mut block_in_rooch = best_block_in_rooch;
+    for _ in 0..MAX_REORG_DEPTH {
+        let block_number = block_in_rooch.number;
+        match source.get_block_header(block_number).await? {
+            Some(block) if block.hash == block_in_rooch.hash => return Ok(block_in_rooch),
+            _ => {
+                if block_number == 0 {
+                    break;
+                }
+                warn!(
+                    "EthereumRelayer block {} in rooch is not on the main chain, hash: {}",
+                    block_number,
+                    hex::encode(&block_in_rooch.hash)
+                );
+                block_in_rooch = get_block_in_rooch(block_number - 1)?.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "EthereumRelayer can not find the common ancestor, block {} is not in rooch",
+                        block_number - 1
+                    )
+                })?;
+            }
+        }
+    }
+    Err(anyhow::anyhow!(
+        "EthereumRelayer can not find the common ancestor of block {} in {} blocks",
+        block_in_rooch.number,
+        MAX_REORG_DEPTH
+    ))
+}
+
+/// Fetch at most `batch_size` blocks from `start_block_number` to `end_block_number`,
+/// every block must be the child of the previous one, otherwise the chain is reorganized
+/// while fetching and no block is returned.
+async fn fetch_linked_blocks<S>(
+    source: &S,
+    start_block_number: u64,
+    end_block_number: u64,
+    mut parent_hash: Option<Vec<u8>>,
+    batch_size: usize,
+) -> Result<Vec<BlockHeader>>
+where
+    S: EthereumBlockSource + ?Sized,
+{
+    let mut blocks = vec![];
+    for block_number in start_block_number..=end_block_number {
+        let block_header = match source.get_block_header(block_number).await? {
+            Some(block_header) => block_header,
+            None => break,
+        };
+        if let Some(parent_hash) = &parent_hash {
+            if &block_header.parent_hash != parent_hash {
+                // the chain is reorganized while syncing, retry in the next tick
+                warn!(
+                    "EthereumRelayer block {} parent hash mismatch, the chain may be reorganized",
+                    block_number
+                );
+                return Ok(vec![]);
+            }
+        }
+        parent_hash = Some(block_header.hash.clone());
+        blocks.push(block_header);
+        if blocks.len() >= batch_size {
+            break;
+        }
+    }
+    Ok(blocks)
+}
 
 pub struct EthereumRelayer {
     rpc_client: Provider<Http>,
-    processed_blocks: HashSet<H256>,
+    move_caller: ExecutorProxy,
+    // the block to start from if no block has been relayed, default is the latest block
+    start_block_height: Option<u64>,
+    // only for data import
+    end_block_height: Option<u64>,
+    buffer: Vec<BlockHeader>,
+    batch_size: usize,
+    l1_tip_height: Option<u64>,
 }
 
 impl EthereumRelayer {
-    pub fn new(config: EthereumRelayerConfig) -> Result<Self> {
+    pub fn new(config: EthereumRelayerConfig, executor: ExecutorProxy) -> Result<Self> {
         let rpc_client = Provider::<Http>::try_from(config.eth_rpc_url)?;
         Ok(Self {
             rpc_client,
-            //TODO load processed block from Move state
-            processed_blocks: HashSet::new(),
+            move_caller: executor,
+            start_block_height: config.eth_start_block_height,
+            end_block_height: config.eth_end_block_height,
+            buffer: vec![],
+            batch_size: config.eth_relay_batch_size.unwrap_or(DEFAULT_BATCH_SIZE),
+            l1_tip_height: None,
         })
     }
 
-    async fn relay_ethereum(&mut self) -> Result<Option<L1BlockWithBody>> {
-        let block = self
-            .rpc_client
-            .get_block(BlockId::Number(BlockNumber::Latest))
-            .await?;
-        match block {
-            Some(block) => {
-                let block_hash = block
-                    .hash
-                    .ok_or_else(|| anyhow::format_err!("The block is a pending block"))?;
-                if self.processed_blocks.contains(&block_hash) {
-                    info!("The block {} has already been processed", block_hash);
-                    return Ok(None);
-                }
-                let block_header = BlockHeader::try_from(&block)?;
-                info!(
-                    "EthereumRelayer process block, hash: {}, number: {}, timestamp: {}",
-                    block_hash, block_header.number, block_header.timestamp
-                );
-                let l1_block = L1BlockWithBody {
-                    block: L1Block {
-                        chain_id: RoochMultiChainID::Ether.multichain_id(),
-                        block_height: block_header.number,
-                        block_hash: block_hash.as_bytes().to_vec(),
-                    },
-                    block_body: block_header.encode(),
+    async fn sync_block(&mut self) -> Result<()> {
+        if !self.buffer.is_empty() {
+            return Ok(());
+        }
+        let ethereum_module = self.move_caller.as_module_binding::<EthereumModule>();
+        let best_block_in_rooch = ethereum_module.get_best_block()?;
+        let latest_block_number = self.rpc_client.get_block_number().await?.as_u64();
+        self.l1_tip_height = Some(latest_block_number);
+
+        //The start block is included
+        let (start_block_number, parent_hash) = match best_block_in_rooch {
+            Some(best_block_in_rooch) => {
+                let best_block_in_rooch = if best_block_in_rooch.number > latest_block_number {
+                    // the rpc node is behind rooch, or the chain is reorganized to a shorter one
+                    match ethereum_module.get_block_option(latest_block_number)? {
+                        Some(block) => block,
+                        None => {
+                            warn!(
+                                "EthereumRelayer the latest block {} is not in rooch, the best block in rooch is {}, wait for the rpc node",
+                                latest_block_number, best_block_in_rooch.number
+                            );
+                            return Ok(());
+                        }
+                    }
+                } else {
+                    best_block_in_rooch
                 };
-                self.processed_blocks.insert(block_hash);
-                Ok(Some(l1_block))
-            }
-            None => {
-                info!("The RPC returned no block");
-                Ok(None)
+                let common_ancestor = find_common_ancestor(
+                    &self.rpc_client,
+                    |block_number| ethereum_module.get_block_option(block_number),
+                    best_block_in_rooch,
+                )
+                .await?;
+                (common_ancestor.number + 1, Some(common_ancestor.hash))
             }
+            None => (self.start_block_height.unwrap_or(latest_block_number), None),
+        };
+
+        let end_block_number = match self.end_block_height {
+            Some(end_block_height) => latest_block_number.min(end_block_height),
+            None => latest_block_number,
+        };
+
+        self.buffer = fetch_linked_blocks(
+            &self.rpc_client,
+            start_block_number,
+            end_block_number,
+            parent_hash,
+            self.batch_size,
+        )
+        .await?;
+        if self.end_block_height.is_some() && start_block_number > end_block_number {
+            info!(
+                "EthereumRelayer process should exit at height {}, end_block_height is {:?}",
+                start_block_number, self.end_block_height
+            );
         }
-        //TODO clean up processed block
+        Ok(())
+    }
+
+    fn pop_buffer(&mut self) -> Result<Option<L1BlockWithBody>> {
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+        let block_header = self.buffer.remove(0);
+        info!(
+            "EthereumRelayer process block, hash: {}, number: {}, timestamp: {}",
+            hex::encode(&block_header.hash),
+            block_header.number,
+            block_header.timestamp
+        );
+        debug!("BlockHeader: {:?}", block_header);
+        Ok(Some(L1BlockWithBody {
+            block: L1Block {
+                chain_id: RoochMultiChainID::Ether.multichain_id(),
+                block_height: block_header.number,
+                block_hash: block_header.hash.clone(),
+            },
+            block_body: block_header.encode(),
+        }))
     }
 }
 
@@ -78,7 +236,9 @@ impl Actor for EthereumRelayer {
 #[async_trait]
 impl Handler<SyncTick> for EthereumRelayer {
     async fn handle(&mut self, _message: SyncTick, _ctx: &mut ActorContext) {
-        //TODO support buffer block
+        if let Err(e) = self.sync_block().await {
+            error!("EthereumRelayer sync block error: {:?}", e);
+        }
     }
 }
 
@@ -89,7 +249,7 @@ impl Handler<GetReadyL1BlockMessage> for EthereumRelayer {
         _message: GetReadyL1BlockMessage,
         _ctx: &mut ActorContext,
     ) -> Result<Option<L1BlockWithBody>> {
-        self.relay_ethereum().await
+        self.pop_buffer()
     }
 }
 
@@ -104,3 +264,166 @@ impl Handler<GetReadyL1TxsMessage> for EthereumRelayer {
         Ok(vec![])
     }
 }
+
+#[async_trait]
+impl Handler<GetL1TipHeightMessage> for EthereumRelayer {
+    async fn handle(
+        &mut self,
+        _message: GetL1TipHeightMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<u64>> {
+        Ok(self.l1_tip_height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MockBlockSource {
+        blocks: HashMap<u64, BlockHeader>,
+    }
+
+    impl MockBlockSource {
+        fn new(blocks: Vec<BlockHeader>) -> Self {
+            Self {
+                blocks: blocks.into_iter().map(|b| (b.number, b)).collect(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EthereumBlockSource for MockBlockSource {
+        async fn get_block_header(&self, block_number: u64) -> Result<Option<BlockHeader>> {
+            Ok(self.blocks.get(&block_number).cloned())
+        }
+    }
+
+    fn new_block(number: u64, hash: u8, parent_hash: u8) -> BlockHeader {
+        BlockHeader {
+            hash: vec![hash],
+            parent_hash: vec![parent_hash],
+            uncles_hash: vec![],
+            author: H160::zero().into(),
+            state_root: vec![],
+            transactions_root: vec![],
+            receipts_root: vec![],
+            logs_bloom: vec![],
+            difficulty: 0u64.into(),
+            number,
+            gas_limit: 0u64.into(),
+            gas_used: 0u64.into(),
+            timestamp: number.into(),
+            extra_data: vec![],
+        }
+    }
+
+    // The blocks from `from` to `to`, the hash of block n is `n + fork`
+    fn new_chain(from: u64, to: u64, fork: u8) -> Vec<BlockHeader> {
+        (from..=to)
+            .map(|n| new_block(n, n as u8 + fork, (n - 1) as u8 + fork))
+            .collect()
+    }
+
+    fn rooch_lookup(blocks: &[BlockHeader]) -> impl Fn(u64) -> Result<Option<BlockHeader>> + '_ {
+        move |number| Ok(blocks.iter().find(|b| b.number == number).cloned())
+    }
+
+    #[tokio::test]
+    async fn test_find_common_ancestor_without_reorg() {
+        let chain = new_chain(1, 10, 0);
+        let source = MockBlockSource::new(chain.clone());
+        let ancestor = find_common_ancestor(&source, rooch_lookup(&chain), chain[9].clone())
+            .await
+            .unwrap();
+        assert_eq!(ancestor.number, 10);
+    }
+
+    #[tokio::test]
+    async fn test_find_common_ancestor_with_reorg() {
+        // rooch has 1..=10, the main chain forks after block 7
+        let rooch_blocks = new_chain(1, 10, 0);
+        let mut main_chain = new_chain(1, 7, 0);
+        let mut fork = new_chain(8, 12, 100);
+        fork[0].parent_hash = main_chain[6].hash.clone();
+        main_chain.extend(fork);
+        let source = MockBlockSource::new(main_chain);
+        let ancestor = find_common_ancestor(
+            &source,
+            rooch_lookup(&rooch_blocks),
+            rooch_blocks[9].clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(ancestor.number, 7);
+        assert_eq!(ancestor.hash, rooch_blocks[6].hash);
+    }
+
+    #[tokio::test]
+    async fn test_find_common_ancestor_block_not_in_rooch() {
+        // rooch only has 5..=6, the main chain forks before block 5
+        let rooch_blocks = new_chain(5, 6, 0);
+        let source = MockBlockSource::new(new_chain(1, 10, 100));
+        let result = find_common_ancestor(
+            &source,
+            rooch_lookup(&rooch_blocks),
+            rooch_blocks[1].clone(),
+        )
+        .await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("block 4 is not in rooch"));
+    }
+
+    #[tokio::test]
+    async fn test_find_common_ancestor_too_deep() {
+        let depth = MAX_REORG_DEPTH + 10;
+        let rooch_blocks = new_chain(1, depth, 0);
+        let source = MockBlockSource::new(new_chain(1, depth, 1));
+        let result = find_common_ancestor(
+            &source,
+            rooch_lookup(&rooch_blocks),
+            rooch_blocks.last().cloned().unwrap(),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_linked_blocks() {
+        let source = MockBlockSource::new(new_chain(1, 10, 0));
+        let blocks = fetch_linked_blocks(&source, 3, 10, Some(vec![2]), 5)
+            .await
+            .unwrap();
+        assert_eq!(
+            blocks.iter().map(|b| b.number).collect::<Vec<_>>(),
+            vec![3, 4, 5, 6, 7]
+        );
+
+        // stop at the end block and at the missing block
+        let blocks = fetch_linked_blocks(&source, 8, 9, None, 5).await.unwrap();
+        assert_eq!(blocks.len(), 2);
+        let blocks = fetch_linked_blocks(&source, 9, 20, None, 5).await.unwrap();
+        assert_eq!(blocks.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_linked_blocks_parent_hash_mismatch() {
+        let mut chain = new_chain(1, 10, 0);
+        chain[5].parent_hash = vec![0xff];
+        let source = MockBlockSource::new(chain);
+        // the parent of the start block is not the common ancestor
+        let blocks = fetch_linked_blocks(&source, 3, 10, Some(vec![0xff]), 10)
+            .await
+            .unwrap();
+        assert!(blocks.is_empty());
+        // the chain is reorganized while fetching
+        let blocks = fetch_linked_blocks(&source, 3, 10, Some(vec![2]), 10)
+            .await
+            .unwrap();
+        assert!(blocks.is_empty());
+    }
+}
@@ -4,6 +4,8 @@
 use anyhow::Result;
 use bitcoincore_rpc::json;
 use coerce::actor::{message::Message, scheduler::timer::TimerTick};
+use rooch_types::bitcoin::mempool::{PendingTx, PendingTxFilter};
+use rooch_types::relayer::RelayerStatus;
 use rooch_types::transaction::{L1BlockWithBody, L1Transaction};
 use serde::{Deserialize, Serialize};
 
@@ -57,6 +59,22 @@ impl Message for GetChainTipsMessage {
     type Result = Result<json::GetChainTipsResult>;
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetRawMempoolMessage {}
+
+impl Message for GetRawMempoolMessage {
+    type Result = Result<Vec<bitcoin::Txid>>;
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetRawTransactionMessage {
+    pub txid: bitcoin::Txid,
+}
+
+impl Message for GetRawTransactionMessage {
+    type Result = Result<bitcoin::Transaction>;
+}
+
 pub struct GetReadyL1BlockMessage {}
 
 impl Message for GetReadyL1BlockMessage {
@@ -69,9 +87,49 @@ impl Message for GetReadyL1TxsMessage {
     type Result = Result<Vec<L1Transaction>>;
 }
 
+/// Get the latest block height of the L1 chain seen by the relayer
+pub struct GetL1TipHeightMessage {}
+
+impl Message for GetL1TipHeightMessage {
+    type Result = Result<Option<u64>>;
+}
+
+pub struct GetRelayerStatusMessage {}
+
+impl Message for GetRelayerStatusMessage {
+    type Result = Result<Vec<RelayerStatus>>;
+}
+
+/// Resume the relayer paused by the failure policy
+pub struct ResumeRelayerMessage {
+    pub name: String,
+}
+
+impl Message for ResumeRelayerMessage {
+    type Result = Result<RelayerStatus>;
+}
+
 #[derive(Clone)]
 pub struct SyncTick {}
 
 impl Message for SyncTick {
     type Result = ();
 }
+
+#[derive(Clone)]
+pub struct MempoolTick {}
+
+impl Message for MempoolTick {
+    type Result = ();
+}
+
+impl TimerTick for MempoolTick {}
+
+pub struct QueryPendingTxsMessage {
+    pub filter: PendingTxFilter,
+    pub limit: usize,
+}
+
+impl Message for QueryPendingTxsMessage {
+    type Result = Result<Vec<PendingTx>>;
+}
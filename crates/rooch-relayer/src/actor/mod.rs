@@ -1,10 +1,15 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod bitcoin_block_file_relayer;
 pub mod bitcoin_client;
 pub mod bitcoin_client_proxy;
+pub mod bitcoin_mempool;
+pub mod bitcoin_mempool_proxy;
 pub mod bitcoin_relayer;
 pub mod ethereum_relayer;
 pub mod messages;
 pub mod relayer;
+pub mod relayer_actor_proxy;
 pub mod relayer_proxy;
+pub mod relayer_registry;
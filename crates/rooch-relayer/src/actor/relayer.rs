@@ -1,74 +1,117 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use super::bitcoin_relayer::BitcoinRelayer;
-use super::ethereum_relayer::EthereumRelayer;
-use super::messages::RelayTick;
-use crate::actor::bitcoin_client::BitcoinClientActor;
-use crate::actor::bitcoin_client_proxy::BitcoinClientProxy;
-use crate::actor::relayer_proxy::RelayerProxy;
-use anyhow::Result;
+use super::messages::{GetRelayerStatusMessage, RelayTick, ResumeRelayerMessage};
+use crate::actor::relayer_proxy::{is_relayer_halted, RelayerProxy};
+use crate::actor::relayer_registry::{RelayerContext, RelayerRegistry};
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor};
 use move_core_types::vm_status::KeptVMStatus;
-use rooch_config::{BitcoinRelayerConfig, EthereumRelayerConfig};
+use rooch_config::RelayerFailurePolicy;
 use rooch_executor::proxy::ExecutorProxy;
+use rooch_pipeline_processor::actor::processor::{sequenced_tx_order, SequencedTx};
 use rooch_pipeline_processor::proxy::PipelineProcessorProxy;
+use rooch_store::RoochStore;
+use rooch_types::relayer::RelayerStatus;
 use rooch_types::transaction::{L1BlockWithBody, L1Transaction};
 use tracing::{error, info, warn};
 
+struct RelayerState {
+    proxy: RelayerProxy,
+    failure_policy: RelayerFailurePolicy,
+    status: RelayerStatus,
+    /// The item failed before it is sequenced when the relayer is paused, it is relayed again after resuming
+    pending: Option<RelayItem>,
+}
+
+#[derive(Clone)]
+enum RelayItem {
+    Block(L1BlockWithBody),
+    Tx(L1Transaction),
+}
+
+/// What the relayer does after a relayed item fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FailureAction {
+    Retry,
+    Skip,
+    Pause,
+}
+
+/// Only the item failed before it is sequenced can be retried,
+/// the sequenced item is already in the ledger, submitting it again would sequence it twice.
+fn failure_action(
+    failure_policy: RelayerFailurePolicy,
+    sequenced: bool,
+    retries: u32,
+) -> FailureAction {
+    match failure_policy {
+        RelayerFailurePolicy::Continue => FailureAction::Skip,
+        RelayerFailurePolicy::Retry(max_retries) if !sequenced && retries < max_retries => {
+            FailureAction::Retry
+        }
+        RelayerFailurePolicy::Retry(_) | RelayerFailurePolicy::Pause => FailureAction::Pause,
+    }
+}
+
+fn record_error(status: &mut RelayerStatus, err: &anyhow::Error) {
+    status.last_error = Some(format!("{:?}", err));
+    status.last_error_at = Some(chrono::Utc::now().timestamp() as u64);
+}
+
 pub struct RelayerActor {
-    relayers: Vec<RelayerProxy>,
+    relayers: Vec<RelayerState>,
     executor: ExecutorProxy,
     processor: PipelineProcessorProxy,
-    ethereum_config: Option<EthereumRelayerConfig>,
-    bitcoin_config: Option<BitcoinRelayerConfig>,
+    rooch_store: RoochStore,
+    registry: RelayerRegistry,
 }
 
 impl RelayerActor {
     pub async fn new(
         executor: ExecutorProxy,
         processor: PipelineProcessorProxy,
-        ethereum_config: Option<EthereumRelayerConfig>,
-        bitcoin_config: Option<BitcoinRelayerConfig>,
+        rooch_store: RoochStore,
+        registry: RelayerRegistry,
     ) -> Result<Self> {
         Ok(Self {
             relayers: vec![],
             executor,
             processor,
-            ethereum_config,
-            bitcoin_config,
+            rooch_store,
+            registry,
         })
     }
 
     async fn init_relayer(&mut self, ctx: &mut ActorContext) -> Result<()> {
-        if let Some(ethereum_config) = &self.ethereum_config {
-            let eth_relayer = EthereumRelayer::new(ethereum_config.clone())?;
-            let eth_relayer_actor_ref = ctx.spawn("eth_relayer".into(), eth_relayer).await?;
-            self.relayers
-                .push(RelayerProxy::ethereum(eth_relayer_actor_ref.into()));
-            info!("EthereumRelayer started")
-        }
-
-        if let Some(bitcoin_config) = &self.bitcoin_config {
-            let bitcoin_client = BitcoinClientActor::new(bitcoin_config.clone())?;
-            let bitcoin_client_actor_ref =
-                ctx.spawn("bitcoin_client".into(), bitcoin_client).await?;
-            let bitcoin_client_proxy = BitcoinClientProxy::new(bitcoin_client_actor_ref.into());
-            let bitcoin_relayer = BitcoinRelayer::new(
-                bitcoin_config.clone(),
-                bitcoin_client_proxy,
-                self.executor.clone(),
-            )?;
-            let bitcoin_relayer_actor_ref =
-                ctx.spawn("bitcoin_relayer".into(), bitcoin_relayer).await?;
-            self.relayers
-                .push(RelayerProxy::bitcoin(bitcoin_relayer_actor_ref.into()));
-            info!("BitcoinRelayer started")
+        let relayer_ctx = RelayerContext {
+            executor: self.executor.clone(),
+            rooch_store: self.rooch_store.clone(),
+        };
+        let registry = std::mem::take(&mut self.registry);
+        for factory in registry.factories() {
+            let relayer = factory.start(ctx, &relayer_ctx).await?;
+            info!("Relayer {} started", relayer.name());
+            self.add_relayer(relayer, factory.failure_policy());
         }
         Ok(())
     }
 
+    fn add_relayer(&mut self, proxy: RelayerProxy, failure_policy: RelayerFailurePolicy) {
+        let status = RelayerStatus {
+            name: proxy.name(),
+            failure_policy: failure_policy.to_string(),
+            ..Default::default()
+        };
+        self.relayers.push(RelayerState {
+            proxy,
+            failure_policy,
+            status,
+            pending: None,
+        });
+    }
+
     async fn handle_l1_block(&mut self, l1_block: L1BlockWithBody) -> Result<()> {
         let block_hash = hex::encode(&l1_block.block.block_hash);
         let block_height = l1_block.block.block_height;
@@ -80,16 +123,18 @@ impl RelayerActor {
                     "Relayer execute relay block(hash: {}, height: {}) success",
                     block_hash, block_height
                 );
+                Ok(())
             }
-            _ => {
-                //TODO should we stop the service if the relayer failed
-                error!(
-                    "Relayer execute relay block(hash: {}, height: {}) failed, status: {:?}",
-                    block_hash, block_height, result.execution_info.status
-                );
-            }
+            status => Err(anyhow!(
+                "Relayer execute relay block(hash: {}, height: {}) failed, status: {:?}",
+                block_hash,
+                block_height,
+                status
+            )
+            .context(SequencedTx {
+                tx_order: result.sequence_info.tx_order,
+            })),
         }
-        Ok(())
     }
 
     async fn handle_l1_tx(&mut self, l1_tx: L1Transaction) -> Result<()> {
@@ -99,57 +144,157 @@ impl RelayerActor {
         match result.execution_info.status {
             KeptVMStatus::Executed => {
                 info!("Relayer execute relay tx(txid: {}) success", txid);
+                Ok(())
             }
-            _ => {
-                error!(
-                    "Relayer execute relay tx(txid: {}) failed, status: {:?}",
-                    txid, result.execution_info.status
-                );
+            status => Err(anyhow!(
+                "Relayer execute relay tx(txid: {}) failed, status: {:?}",
+                txid,
+                status
+            )
+            .context(SequencedTx {
+                tx_order: result.sequence_info.tx_order,
+            })),
+        }
+    }
+
+    /// Execute the relayed L1 block or transaction with the failure policy of the relayer,
+    /// return false if the relayer is paused.
+    async fn relay(&mut self, index: usize, item: RelayItem) -> bool {
+        let failure_policy = self.relayers[index].failure_policy;
+        let mut retries = 0u32;
+        loop {
+            let result = match &item {
+                RelayItem::Block(l1_block) => self.handle_l1_block(l1_block.clone()).await,
+                RelayItem::Tx(l1_tx) => self.handle_l1_tx(l1_tx.clone()).await,
+            };
+            let relayer = &mut self.relayers[index];
+            let status = &mut relayer.status;
+            let err = match result {
+                Ok(()) => {
+                    if let RelayItem::Block(l1_block) = &item {
+                        status.last_relayed_height = Some(l1_block.block.block_height);
+                        status.last_relayed_hash = Some(l1_block.block.block_hash.clone());
+                    }
+                    return true;
+                }
+                Err(err) => err,
+            };
+            error!("Relayer {} error: {:?}", status.name, err);
+            record_error(status, &err);
+            let sequenced = sequenced_tx_order(&err).is_some();
+            match failure_action(failure_policy, sequenced, retries) {
+                FailureAction::Skip => return true,
+                FailureAction::Retry => {
+                    retries += 1;
+                    warn!(
+                        "Relayer {} retry {} times, the failure policy is {}",
+                        status.name, retries, failure_policy
+                    );
+                }
+                FailureAction::Pause => {
+                    status.paused = true;
+                    error!(
+                        "Relayer {} is paused by the failure policy {}, resume it by the rooch_resumeRelayer RPC",
+                        status.name, failure_policy
+                    );
+                    if !sequenced {
+                        relayer.pending = Some(item);
+                    }
+                    return false;
+                }
             }
         }
-        Ok(())
     }
 
-    async fn sync(&mut self) {
-        let relayers = self.relayers.clone();
-        for relayer in relayers {
-            let relayer_name = relayer.name();
-            if let Err(e) = relayer.sync().await {
-                warn!("Relayer {} sync error: {:?}", relayer_name, e);
+    /// Resume the paused relayer, the item failed before it is sequenced is relayed again in the next tick
+    fn resume_relayer(&mut self, name: &str) -> Result<RelayerStatus> {
+        let relayer = self
+            .relayers
+            .iter_mut()
+            .find(|relayer| relayer.status.name == name)
+            .ok_or_else(|| anyhow!("Relayer {} is not found", name))?;
+        if relayer.status.paused {
+            relayer.status.paused = false;
+            info!("Relayer {} is resumed", name);
+        }
+        Ok(relayer.status.clone())
+    }
+
+    /// Record the error of getting the ready items, the halted relayer is paused.
+    /// Return false if the relayer is paused.
+    fn handle_relayer_error(&mut self, index: usize, err: &anyhow::Error) -> bool {
+        let status = &mut self.relayers[index].status;
+        record_error(status, err);
+        if is_relayer_halted(err) {
+            status.paused = true;
+            error!("Relayer {} is paused: {:?}", status.name, err);
+            return false;
+        }
+        warn!("Relayer {} error: {:?}", status.name, err);
+        true
+    }
+
+    async fn sync_relayer(&mut self, index: usize) {
+        if let Some(item) = self.relayers[index].pending.take() {
+            if !self.relay(index, item).await {
+                return;
+            }
+        }
+        let relayer = self.relayers[index].proxy.clone();
+        let relayer_name = relayer.name();
+        if let Err(e) = relayer.sync().await {
+            warn!("Relayer {} sync error: {:?}", relayer_name, e);
+        }
+        match relayer.get_l1_tip_height().await {
+            Ok(Some(l1_tip_height)) => {
+                self.relayers[index].status.l1_tip_height = Some(l1_tip_height)
             }
+            Ok(None) => {}
+            Err(err) => warn!("Relayer {} error: {:?}", relayer_name, err),
+        }
 
-            loop {
-                match relayer.get_ready_l1_txs().await {
-                    Ok(txs) => {
-                        for tx in txs {
-                            if let Err(err) = self.handle_l1_tx(tx).await {
-                                warn!("Relayer {} error: {:?}", relayer_name, err);
-                            }
+        loop {
+            match relayer.get_ready_l1_txs().await {
+                Ok(txs) => {
+                    for tx in txs {
+                        if !self.relay(index, RelayItem::Tx(tx)).await {
+                            return;
                         }
                     }
-                    Err(err) => {
-                        warn!("Relayer {} error: {:?}", relayer_name, err);
-                        break;
-                    }
                 }
-                match relayer.get_ready_l1_block().await {
-                    Ok(Some(l1_block)) => {
-                        if let Err(err) = self.handle_l1_block(l1_block).await {
-                            warn!("Relayer {} error: {:?}", relayer_name, err);
-                        }
+                Err(err) => {
+                    if !self.handle_relayer_error(index, &err) {
+                        return;
                     }
-                    Ok(None) => {
-                        //skip
-                        break;
-                    }
-                    Err(err) => {
-                        warn!("Relayer {} error: {:?}", relayer_name, err);
-                        break;
+                    break;
+                }
+            }
+            match relayer.get_ready_l1_block().await {
+                Ok(Some(l1_block)) => {
+                    if !self.relay(index, RelayItem::Block(l1_block)).await {
+                        return;
                     }
                 }
+                Ok(None) => {
+                    //skip
+                    break;
+                }
+                Err(err) => {
+                    self.handle_relayer_error(index, &err);
+                    break;
+                }
             }
         }
     }
+
+    async fn sync(&mut self) {
+        for index in 0..self.relayers.len() {
+            if self.relayers[index].status.paused {
+                continue;
+            }
+            self.sync_relayer(index).await;
+        }
+    }
 }
 
 #[async_trait]
@@ -167,3 +312,410 @@ impl Handler<RelayTick> for RelayerActor {
         self.sync().await
     }
 }
+
+#[async_trait]
+impl Handler<GetRelayerStatusMessage> for RelayerActor {
+    async fn handle(
+        &mut self,
+        _message: GetRelayerStatusMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Vec<RelayerStatus>> {
+        Ok(self
+            .relayers
+            .iter()
+            .map(|relayer| relayer.status.clone())
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Handler<ResumeRelayerMessage> for RelayerActor {
+    async fn handle(
+        &mut self,
+        message: ResumeRelayerMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<RelayerStatus> {
+        self.resume_relayer(&message.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actor::relayer_proxy::{Relayer, RelayerHalted};
+    use crate::actor::relayer_registry::RelayerFactory;
+    use bitcoin::consensus::deserialize;
+    use bitcoin::hashes::Hash;
+    use coerce::actor::{system::ActorSystem, ActorRef, IntoActor};
+    use hex::FromHex;
+    use parking_lot::Mutex;
+    use rooch_config::RoochOpt;
+    use rooch_da::actor::da::DAActor;
+    use rooch_da::proxy::DAProxy;
+    use rooch_db::RoochDB;
+    use rooch_executor::actor::executor::ExecutorActor;
+    use rooch_executor::actor::reader_executor::ReaderExecutorActor;
+    use rooch_genesis::RoochGenesis;
+    use rooch_indexer::actor::indexer::IndexerActor;
+    use rooch_indexer::actor::reader_indexer::IndexerReaderActor;
+    use rooch_indexer::proxy::IndexerProxy;
+    use rooch_pipeline_processor::actor::processor::PipelineProcessorActor;
+    use rooch_pipeline_processor::notifier::ExecutionNotifier;
+    use rooch_proposer::actor::proposer::ProposerActor;
+    use rooch_proposer::proxy::ProposerProxy;
+    use rooch_sequencer::actor::sequencer::SequencerActor;
+    use rooch_sequencer::proxy::SequencerProxy;
+    use rooch_types::crypto::RoochKeyPair;
+    use rooch_types::multichain_id::{MultiChainID, RoochMultiChainID};
+    use rooch_types::transaction::L1Block;
+    use std::collections::VecDeque;
+    use std::sync::Arc;
+
+    const MOCK_RELAYER: &str = "mock_relayer";
+
+    #[derive(Clone, Default)]
+    struct MockRelayer {
+        blocks: Arc<Mutex<VecDeque<L1BlockWithBody>>>,
+        halted: Arc<Mutex<bool>>,
+    }
+
+    #[async_trait]
+    impl Relayer for MockRelayer {
+        fn name(&self) -> String {
+            MOCK_RELAYER.to_string()
+        }
+
+        async fn sync(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_ready_l1_block(&self) -> Result<Option<L1BlockWithBody>> {
+            Ok(self.blocks.lock().pop_front())
+        }
+
+        async fn get_ready_l1_txs(&self) -> Result<Vec<L1Transaction>> {
+            if *self.halted.lock() {
+                return Err(anyhow!("mock relayer is halted").context(RelayerHalted));
+            }
+            Ok(vec![])
+        }
+    }
+
+    struct MockRelayerFactory {
+        relayer: MockRelayer,
+        failure_policy: RelayerFailurePolicy,
+    }
+
+    #[async_trait]
+    impl RelayerFactory for MockRelayerFactory {
+        fn failure_policy(&self) -> RelayerFailurePolicy {
+            self.failure_policy
+        }
+
+        async fn start(
+            &self,
+            _ctx: &mut ActorContext,
+            _relayer_ctx: &RelayerContext,
+        ) -> Result<RelayerProxy> {
+            Ok(RelayerProxy::new(self.relayer.clone()))
+        }
+    }
+
+    /// Mainnet block 496, executed successfully on a new local chain
+    fn good_block() -> L1BlockWithBody {
+        let block_hex = Vec::<u8>::from_hex("010000004ddccd549d28f385ab457e98d1b11ce80bfea2c5ab93015ade4973e400000000bf4473e53794beae34e64fccc471dace6ae544180816f89591894e0f417a914cd74d6e49ffff001d323b3a7b0201000000010000000000000000000000000000000000000000000000000000000000000000ffffffff0804ffff001d026e04ffffffff0100f2052a0100000043410446ef0102d1ec5240f0d061a4246c1bdef63fc3dbab7733052fbbf0ecd8f41fc26bf049ebb4f9527f374280259e7cfa99c48b0e3f39c51347a19a5819651503a5ac00000000010000000321f75f3139a013f50f315b23b0c9a2b6eac31e2bec98e5891c924664889942260000000049483045022100cb2c6b346a978ab8c61b18b5e9397755cbd17d6eb2fe0083ef32e067fa6c785a02206ce44e613f31d9a6b0517e46f3db1576e9812cc98d159bfdaf759a5014081b5c01ffffffff79cda0945903627c3da1f85fc95d0b8ee3e76ae0cfdc9a65d09744b1f8fc85430000000049483045022047957cdd957cfd0becd642f6b84d82f49b6cb4c51a91f49246908af7c3cfdf4a022100e96b46621f1bffcf5ea5982f88cef651e9354f5791602369bf5a82a6cd61a62501fffffffffe09f5fe3ffbf5ee97a54eb5e5069e9da6b4856ee86fc52938c2f979b0f38e82000000004847304402204165be9a4cbab8049e1af9723b96199bfd3e85f44c6b4c0177e3962686b26073022028f638da23fc003760861ad481ead4099312c60030d4cb57820ce4d33812a5ce01ffffffff01009d966b01000000434104ea1feff861b51fe3f5f8a3b12d0f4712db80e919548a80839fc47c6a21e66d957e9c5d8cd108c7a2d2324bad71f9904ac0ae7336507d785b17a2c115e427a32fac00000000").unwrap();
+        let block: bitcoin::Block = deserialize(&block_hex).unwrap();
+        L1BlockWithBody {
+            block: L1Block {
+                chain_id: RoochMultiChainID::Bitcoin.multichain_id(),
+                block_height: 496,
+                block_hash: block.header.block_hash().to_byte_array().to_vec(),
+            },
+            block_body: rooch_types::bitcoin::types::Block::from(block).encode(),
+        }
+    }
+
+    /// The block of an invalid chain fails to validate, before it is sequenced
+    fn invalid_block() -> L1BlockWithBody {
+        L1BlockWithBody {
+            block: L1Block {
+                chain_id: MultiChainID::from(u64::MAX),
+                block_height: 1,
+                block_hash: vec![1u8; 32],
+            },
+            block_body: vec![],
+        }
+    }
+
+    /// The block body can not be decoded, the block is sequenced and aborted in Move
+    fn aborted_block() -> L1BlockWithBody {
+        L1BlockWithBody {
+            block: L1Block {
+                chain_id: RoochMultiChainID::Bitcoin.multichain_id(),
+                block_height: 495,
+                block_hash: vec![2u8; 32],
+            },
+            block_body: vec![0u8],
+        }
+    }
+
+    struct RelayerTest {
+        // Keep the temp store until the test is finished
+        _opt: RoochOpt,
+        relayer: ActorRef<RelayerActor>,
+        sequencer: SequencerProxy,
+        mock_relayer: MockRelayer,
+    }
+
+    impl RelayerTest {
+        async fn start(failure_policy: RelayerFailurePolicy, blocks: Vec<L1BlockWithBody>) -> Self {
+            let opt = RoochOpt::new_with_temp_store().unwrap();
+            let rooch_db = RoochDB::init_with_mock_metrics_for_test(opt.store_config()).unwrap();
+            let genesis = RoochGenesis::load_or_init(opt.network(), &rooch_db).unwrap();
+            let root = genesis.genesis_root().clone();
+            let actor_system = ActorSystem::new();
+
+            let executor = ExecutorActor::new(
+                root.clone(),
+                rooch_db.moveos_store.clone(),
+                rooch_db.rooch_store.clone(),
+            )
+            .unwrap()
+            .into_actor(Some("Executor"), &actor_system)
+            .await
+            .unwrap();
+            let reader_executor = ReaderExecutorActor::new(
+                root.clone(),
+                rooch_db.moveos_store.clone(),
+                rooch_db.rooch_store.clone(),
+            )
+            .unwrap()
+            .into_actor(Some("ReaderExecutor"), &actor_system)
+            .await
+            .unwrap();
+            let executor = ExecutorProxy::new(executor.into(), reader_executor.into());
+            let sequencer = SequencerActor::new(
+                RoochKeyPair::generate_secp256k1(),
+                rooch_db.rooch_store.clone(),
+            )
+            .unwrap()
+            .into_actor(Some("Sequencer"), &actor_system)
+            .await
+            .unwrap();
+            let sequencer = SequencerProxy::new(sequencer.into());
+            let da = DAActor::new(
+                opt.da_config().clone(),
+                &actor_system,
+                rooch_db.rooch_store.clone(),
+                &prometheus::Registry::new(),
+            )
+            .await
+            .unwrap()
+            .into_actor(Some("DAProxy"), &actor_system)
+            .await
+            .unwrap();
+            let proposer = ProposerActor::new(
+                RoochKeyPair::generate_secp256k1(),
+                rooch_db.rooch_store.clone(),
+                DAProxy::new(da.into()),
+            )
+            .unwrap()
+            .into_actor(Some("Proposer"), &actor_system)
+            .await
+            .unwrap();
+            let indexer = IndexerActor::new(root, rooch_db.indexer_store.clone())
+                .unwrap()
+                .into_actor(Some("Indexer"), &actor_system)
+                .await
+                .unwrap();
+            let indexer_reader = IndexerReaderActor::new(rooch_db.indexer_reader.clone())
+                .unwrap()
+                .into_actor(Some("IndexerReader"), &actor_system)
+                .await
+                .unwrap();
+            let processor = PipelineProcessorActor::new(
+                executor.clone(),
+                sequencer.clone(),
+                ProposerProxy::new(proposer.into()),
+                IndexerProxy::new(indexer.into(), indexer_reader.into()),
+                ExecutionNotifier::new(16, 1),
+                false,
+            )
+            .into_actor(Some("PipelineProcessor"), &actor_system)
+            .await
+            .unwrap();
+
+            let mock_relayer = MockRelayer::default();
+            mock_relayer.blocks.lock().extend(blocks);
+            let mut registry = RelayerRegistry::new();
+            registry.register(MockRelayerFactory {
+                relayer: mock_relayer.clone(),
+                failure_policy,
+            });
+            let relayer = RelayerActor::new(
+                executor,
+                PipelineProcessorProxy::new(processor.into()),
+                rooch_db.rooch_store.clone(),
+                registry,
+            )
+            .await
+            .unwrap()
+            .into_actor(Some("Relayer"), &actor_system)
+            .await
+            .unwrap();
+            Self {
+                _opt: opt,
+                relayer,
+                sequencer,
+                mock_relayer,
+            }
+        }
+
+        async fn tick(&self) -> RelayerStatus {
+            self.relayer.send(RelayTick {}).await.unwrap();
+            self.status().await
+        }
+
+        async fn status(&self) -> RelayerStatus {
+            let mut status = self
+                .relayer
+                .send(GetRelayerStatusMessage {})
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(status.len(), 1);
+            status.pop().unwrap()
+        }
+
+        async fn resume(&self) -> RelayerStatus {
+            self.relayer
+                .send(ResumeRelayerMessage {
+                    name: MOCK_RELAYER.to_string(),
+                })
+                .await
+                .unwrap()
+                .unwrap()
+        }
+
+        async fn sequencer_order(&self) -> u64 {
+            self.sequencer.get_sequencer_order().await.unwrap()
+        }
+
+        fn remaining_blocks(&self) -> usize {
+            self.mock_relayer.blocks.lock().len()
+        }
+    }
+
+    #[test]
+    fn test_failure_action() {
+        use RelayerFailurePolicy::*;
+        assert_eq!(failure_action(Continue, false, 0), FailureAction::Skip);
+        assert_eq!(failure_action(Continue, true, 0), FailureAction::Skip);
+        assert_eq!(failure_action(Retry(2), false, 0), FailureAction::Retry);
+        assert_eq!(failure_action(Retry(2), false, 1), FailureAction::Retry);
+        assert_eq!(failure_action(Retry(2), false, 2), FailureAction::Pause);
+        assert_eq!(failure_action(Retry(2), true, 0), FailureAction::Pause);
+        assert_eq!(failure_action(Pause, false, 0), FailureAction::Pause);
+        assert_eq!(failure_action(Pause, true, 0), FailureAction::Pause);
+    }
+
+    #[tokio::test]
+    async fn test_continue_policy() {
+        let test = RelayerTest::start(
+            RelayerFailurePolicy::Continue,
+            vec![invalid_block(), aborted_block(), good_block()],
+        )
+        .await;
+        let status = test.tick().await;
+        assert!(!status.paused);
+        assert_eq!(status.last_relayed_height, Some(496));
+        assert!(status.last_error.is_some());
+        // The invalid block is skipped, the aborted block and the good block are sequenced
+        assert_eq!(test.sequencer_order().await, 2);
+        assert_eq!(test.remaining_blocks(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_retry_policy_not_retry_sequenced() {
+        let test = RelayerTest::start(
+            RelayerFailurePolicy::Retry(3),
+            vec![aborted_block(), good_block()],
+        )
+        .await;
+        let status = test.tick().await;
+        // The aborted block is sequenced once and not retried
+        assert!(status.paused);
+        assert_eq!(status.last_relayed_height, None);
+        assert_eq!(test.sequencer_order().await, 1);
+        assert_eq!(test.remaining_blocks(), 1);
+
+        // The paused relayer does not relay
+        let status = test.tick().await;
+        assert!(status.paused);
+        assert_eq!(test.sequencer_order().await, 1);
+
+        // The sequenced block is not relayed again after resuming
+        assert!(!test.resume().await.paused);
+        let status = test.tick().await;
+        assert!(!status.paused);
+        assert_eq!(status.last_relayed_height, Some(496));
+        assert_eq!(test.sequencer_order().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_and_pause_policy_before_sequenced() {
+        for failure_policy in [RelayerFailurePolicy::Retry(2), RelayerFailurePolicy::Pause] {
+            let test =
+                RelayerTest::start(failure_policy, vec![invalid_block(), good_block()]).await;
+            let status = test.tick().await;
+            assert!(status.paused);
+            assert!(status.last_error.unwrap().contains("is invalid"));
+            assert_eq!(test.sequencer_order().await, 0);
+            assert_eq!(test.remaining_blocks(), 1);
+
+            // The failed block is kept and relayed again after resuming, before the next blocks
+            test.resume().await;
+            let status = test.tick().await;
+            assert!(status.paused);
+            assert_eq!(test.sequencer_order().await, 0);
+            assert_eq!(test.remaining_blocks(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_halted_relayer_is_paused() {
+        let test = RelayerTest::start(RelayerFailurePolicy::Continue, vec![good_block()]).await;
+        *test.mock_relayer.halted.lock() = true;
+        let status = test.tick().await;
+        assert!(status.paused);
+        assert!(status
+            .last_error
+            .unwrap()
+            .contains("mock relayer is halted"));
+        assert_eq!(test.sequencer_order().await, 0);
+
+        // The relayer is paused again while it is halted
+        test.resume().await;
+        assert!(test.tick().await.paused);
+        assert_eq!(test.remaining_blocks(), 1);
+
+        *test.mock_relayer.halted.lock() = false;
+        test.resume().await;
+        let status = test.tick().await;
+        assert!(!status.paused);
+        assert_eq!(status.last_relayed_height, Some(496));
+    }
+
+    #[tokio::test]
+    async fn test_resume_unknown_relayer() {
+        let test = RelayerTest::start(RelayerFailurePolicy::Pause, vec![]).await;
+        let result = test
+            .relayer
+            .send(ResumeRelayerMessage {
+                name: "unknown".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(result.is_err());
+    }
+}
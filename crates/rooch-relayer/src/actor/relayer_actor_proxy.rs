@@ -0,0 +1,27 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::actor::messages::{GetRelayerStatusMessage, ResumeRelayerMessage};
+use crate::actor::relayer::RelayerActor;
+use anyhow::Result;
+use coerce::actor::ActorRef;
+use rooch_types::relayer::RelayerStatus;
+
+#[derive(Clone)]
+pub struct RelayerActorProxy {
+    pub actor: ActorRef<RelayerActor>,
+}
+
+impl RelayerActorProxy {
+    pub fn new(actor: ActorRef<RelayerActor>) -> Self {
+        Self { actor }
+    }
+
+    pub async fn get_relayer_status(&self) -> Result<Vec<RelayerStatus>> {
+        self.actor.send(GetRelayerStatusMessage {}).await?
+    }
+
+    pub async fn resume_relayer(&self, name: String) -> Result<RelayerStatus> {
+        self.actor.send(ResumeRelayerMessage { name }).await?
+    }
+}
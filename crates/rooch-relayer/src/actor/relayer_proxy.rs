@@ -1,53 +1,236 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{
-    bitcoin_relayer::BitcoinRelayer,
-    ethereum_relayer::EthereumRelayer,
-    messages::{GetReadyL1BlockMessage, GetReadyL1TxsMessage, SyncTick},
+use super::messages::{
+    GetL1TipHeightMessage, GetReadyL1BlockMessage, GetReadyL1TxsMessage, SyncTick,
 };
 use anyhow::Result;
-use coerce::actor::{ActorRef, ActorRefErr};
+use async_trait::async_trait;
+use coerce::actor::{message::Handler, Actor, ActorRef};
 use rooch_types::transaction::{L1BlockWithBody, L1Transaction};
+use std::fmt;
+use std::sync::Arc;
+
+/// The error context of the relayer halted until the operator fixes it, for example by rewinding the state,
+/// the `RelayerActor` pauses the relayer and reports the error in the relayer status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelayerHalted;
+
+impl fmt::Display for RelayerHalted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The relayer is halted")
+    }
+}
+
+/// Whether the error is raised by a halted relayer
+pub fn is_relayer_halted(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<RelayerHalted>().is_some()
+}
+
+/// The L1 relayer syncs the blocks and transactions from the L1 chain,
+/// and provides the ready ones to the `RelayerActor` to execute.
+#[async_trait]
+pub trait Relayer: Send + Sync {
+    fn name(&self) -> String;
+
+    /// Trigger the relayer to sync from the L1 chain, it should not block until the sync is finished.
+    async fn sync(&self) -> Result<()>;
+
+    async fn get_ready_l1_block(&self) -> Result<Option<L1BlockWithBody>>;
+
+    async fn get_ready_l1_txs(&self) -> Result<Vec<L1Transaction>>;
+
+    /// The latest block height of the L1 chain seen by the relayer
+    async fn get_l1_tip_height(&self) -> Result<Option<u64>> {
+        Ok(None)
+    }
+}
+
+/// Every actor handling the relayer messages is a `Relayer`
+#[async_trait]
+impl<A> Relayer for ActorRef<A>
+where
+    A: Actor
+        + Handler<SyncTick>
+        + Handler<GetReadyL1BlockMessage>
+        + Handler<GetReadyL1TxsMessage>
+        + Handler<GetL1TipHeightMessage>,
+{
+    fn name(&self) -> String {
+        self.actor_id().to_string()
+    }
+
+    async fn sync(&self) -> Result<()> {
+        Ok(self.notify(SyncTick {}).await?)
+    }
+
+    async fn get_ready_l1_block(&self) -> Result<Option<L1BlockWithBody>> {
+        self.send(GetReadyL1BlockMessage {}).await?
+    }
+
+    async fn get_ready_l1_txs(&self) -> Result<Vec<L1Transaction>> {
+        self.send(GetReadyL1TxsMessage {}).await?
+    }
+
+    async fn get_l1_tip_height(&self) -> Result<Option<u64>> {
+        self.send(GetL1TipHeightMessage {}).await?
+    }
+}
 
 #[derive(Clone)]
-pub enum RelayerProxy {
-    Bitcoin(ActorRef<BitcoinRelayer>),
-    Ethereum(ActorRef<EthereumRelayer>),
+pub struct RelayerProxy {
+    relayer: Arc<dyn Relayer>,
 }
 
 impl RelayerProxy {
-    pub fn bitcoin(actor: ActorRef<BitcoinRelayer>) -> Self {
-        Self::Bitcoin(actor)
-    }
-    pub fn ethereum(actor: ActorRef<EthereumRelayer>) -> Self {
-        Self::Ethereum(actor)
+    pub fn new<R: Relayer + 'static>(relayer: R) -> Self {
+        Self {
+            relayer: Arc::new(relayer),
+        }
     }
 
     pub fn name(&self) -> String {
-        match self {
-            Self::Bitcoin(actor) => actor.actor_id().to_string(),
-            Self::Ethereum(actor) => actor.actor_id().to_string(),
+        self.relayer.name()
+    }
+
+    pub async fn sync(&self) -> Result<()> {
+        self.relayer.sync().await
+    }
+
+    pub async fn get_ready_l1_block(&self) -> Result<Option<L1BlockWithBody>> {
+        self.relayer.get_ready_l1_block().await
+    }
+
+    pub async fn get_ready_l1_txs(&self) -> Result<Vec<L1Transaction>> {
+        self.relayer.get_ready_l1_txs().await
+    }
+
+    pub async fn get_l1_tip_height(&self) -> Result<Option<u64>> {
+        self.relayer.get_l1_tip_height().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coerce::actor::context::ActorContext;
+    use coerce::actor::system::ActorSystem;
+    use coerce::actor::IntoActor;
+    use rooch_types::multichain_id::RoochMultiChainID;
+    use rooch_types::transaction::L1Block;
+
+    /// The relayer actor serving the blocks, every sync tick relays one more block
+    #[derive(Default)]
+    struct MockRelayerActor {
+        synced: u64,
+        blocks: Vec<L1BlockWithBody>,
+    }
+
+    impl Actor for MockRelayerActor {}
+
+    #[async_trait]
+    impl Handler<SyncTick> for MockRelayerActor {
+        async fn handle(&mut self, _msg: SyncTick, _ctx: &mut ActorContext) {
+            self.synced += 1;
+            self.blocks.push(L1BlockWithBody {
+                block: L1Block {
+                    chain_id: RoochMultiChainID::Bitcoin.multichain_id(),
+                    block_height: self.synced,
+                    block_hash: vec![self.synced as u8; 32],
+                },
+                block_body: vec![],
+            });
         }
     }
-    pub async fn sync(&self) -> Result<(), ActorRefErr> {
-        match self {
-            Self::Bitcoin(actor) => actor.notify(SyncTick {}).await,
-            Self::Ethereum(actor) => actor.notify(SyncTick {}).await,
+
+    #[async_trait]
+    impl Handler<GetReadyL1BlockMessage> for MockRelayerActor {
+        async fn handle(
+            &mut self,
+            _msg: GetReadyL1BlockMessage,
+            _ctx: &mut ActorContext,
+        ) -> Result<Option<L1BlockWithBody>> {
+            if self.blocks.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(self.blocks.remove(0)))
+            }
         }
     }
 
-    pub async fn get_ready_l1_block(&self) -> Result<Option<L1BlockWithBody>> {
-        match self {
-            Self::Bitcoin(actor) => actor.send(GetReadyL1BlockMessage {}).await?,
-            Self::Ethereum(actor) => actor.send(GetReadyL1BlockMessage {}).await?,
+    #[async_trait]
+    impl Handler<GetReadyL1TxsMessage> for MockRelayerActor {
+        async fn handle(
+            &mut self,
+            _msg: GetReadyL1TxsMessage,
+            _ctx: &mut ActorContext,
+        ) -> Result<Vec<L1Transaction>> {
+            Err(anyhow::anyhow!("the mock relayer does not relay txs"))
         }
     }
 
-    pub async fn get_ready_l1_txs(&self) -> Result<Vec<L1Transaction>> {
-        match self {
-            Self::Bitcoin(actor) => actor.send(GetReadyL1TxsMessage {}).await?,
-            Self::Ethereum(actor) => actor.send(GetReadyL1TxsMessage {}).await?,
+    #[async_trait]
+    impl Handler<GetL1TipHeightMessage> for MockRelayerActor {
+        async fn handle(
+            &mut self,
+            _msg: GetL1TipHeightMessage,
+            _ctx: &mut ActorContext,
+        ) -> Result<Option<u64>> {
+            Ok(Some(self.synced))
         }
     }
+
+    #[tokio::test]
+    async fn test_actor_relayer() {
+        let actor = MockRelayerActor::default()
+            .into_actor(Some("mock_relayer"), &ActorSystem::new())
+            .await
+            .unwrap();
+        let proxy = RelayerProxy::new(actor);
+        assert_eq!(proxy.name(), "mock_relayer");
+        assert_eq!(proxy.get_l1_tip_height().await.unwrap(), Some(0));
+        assert_eq!(proxy.get_ready_l1_block().await.unwrap(), None);
+
+        proxy.sync().await.unwrap();
+        proxy.sync().await.unwrap();
+        // The sync is a notification, the following messages are handled after it
+        assert_eq!(proxy.get_l1_tip_height().await.unwrap(), Some(2));
+        for block_height in 1..=2 {
+            let block = proxy.get_ready_l1_block().await.unwrap().unwrap();
+            assert_eq!(block.block.block_height, block_height);
+        }
+        assert_eq!(proxy.get_ready_l1_block().await.unwrap(), None);
+
+        // The error of the relayer actor is returned by the proxy
+        assert!(proxy.get_ready_l1_txs().await.is_err());
+    }
+
+    struct DefaultTipRelayer;
+
+    #[async_trait]
+    impl Relayer for DefaultTipRelayer {
+        fn name(&self) -> String {
+            "default_tip_relayer".to_string()
+        }
+
+        async fn sync(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_ready_l1_block(&self) -> Result<Option<L1BlockWithBody>> {
+            Ok(None)
+        }
+
+        async fn get_ready_l1_txs(&self) -> Result<Vec<L1Transaction>> {
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_relayer_default_tip_height() {
+        let proxy = RelayerProxy::new(DefaultTipRelayer);
+        assert_eq!(proxy.name(), "default_tip_relayer");
+        assert_eq!(proxy.get_l1_tip_height().await.unwrap(), None);
+        assert!(proxy.get_ready_l1_txs().await.unwrap().is_empty());
+    }
 }
@@ -0,0 +1,220 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::bitcoin_client::BitcoinClientActor;
+use super::bitcoin_client_proxy::BitcoinClientProxy;
+use super::bitcoin_relayer::BitcoinRelayer;
+use super::ethereum_relayer::EthereumRelayer;
+use super::relayer_proxy::RelayerProxy;
+use anyhow::Result;
+use async_trait::async_trait;
+use coerce::actor::{context::ActorContext, ActorRef};
+use rooch_config::{BitcoinRelayerConfig, EthereumRelayerConfig, RelayerFailurePolicy};
+use rooch_executor::proxy::ExecutorProxy;
+use rooch_store::RoochStore;
+
+/// The shared components to start a relayer
+#[derive(Clone)]
+pub struct RelayerContext {
+    pub executor: ExecutorProxy,
+    pub rooch_store: RoochStore,
+}
+
+/// Start a relayer for a L1 chain or another block source.
+/// Register the factory to the `RelayerRegistry` to plug in a new relayer.
+#[async_trait]
+pub trait RelayerFactory: Send + Sync {
+    fn failure_policy(&self) -> RelayerFailurePolicy;
+
+    /// Start the relayer, the relayer actor is spawned as a child of the `RelayerActor`
+    async fn start(
+        &self,
+        ctx: &mut ActorContext,
+        relayer_ctx: &RelayerContext,
+    ) -> Result<RelayerProxy>;
+}
+
+#[derive(Default)]
+pub struct RelayerRegistry {
+    factories: Vec<Box<dyn RelayerFactory>>,
+}
+
+impl RelayerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_config(
+        ethereum_config: Option<EthereumRelayerConfig>,
+        bitcoin_config: Option<BitcoinRelayerConfig>,
+    ) -> Self {
+        let mut registry = Self::new();
+        if let Some(config) = ethereum_config {
+            registry.register(EthereumRelayerFactory { config });
+        }
+        if let Some(config) = bitcoin_config {
+            registry.register(BitcoinRelayerFactory { config });
+        }
+        registry
+    }
+
+    pub fn register<F: RelayerFactory + 'static>(&mut self, factory: F) {
+        self.factories.push(Box::new(factory));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.factories.is_empty()
+    }
+
+    pub fn factories(&self) -> &[Box<dyn RelayerFactory>] {
+        &self.factories
+    }
+}
+
+pub struct EthereumRelayerFactory {
+    pub config: EthereumRelayerConfig,
+}
+
+#[async_trait]
+impl RelayerFactory for EthereumRelayerFactory {
+    fn failure_policy(&self) -> RelayerFailurePolicy {
+        self.config.failure_policy
+    }
+
+    async fn start(
+        &self,
+        ctx: &mut ActorContext,
+        relayer_ctx: &RelayerContext,
+    ) -> Result<RelayerProxy> {
+        let eth_relayer = EthereumRelayer::new(self.config.clone(), relayer_ctx.executor.clone())?;
+        let eth_relayer_actor_ref = ctx.spawn("eth_relayer".into(), eth_relayer).await?;
+        Ok(RelayerProxy::new(ActorRef::<EthereumRelayer>::from(
+            eth_relayer_actor_ref,
+        )))
+    }
+}
+
+pub struct BitcoinRelayerFactory {
+    pub config: BitcoinRelayerConfig,
+}
+
+#[async_trait]
+impl RelayerFactory for BitcoinRelayerFactory {
+    fn failure_policy(&self) -> RelayerFailurePolicy {
+        self.config.failure_policy
+    }
+
+    async fn start(
+        &self,
+        ctx: &mut ActorContext,
+        relayer_ctx: &RelayerContext,
+    ) -> Result<RelayerProxy> {
+        let bitcoin_client = BitcoinClientActor::new(self.config.clone())?;
+        let bitcoin_client_actor_ref = ctx.spawn("bitcoin_client".into(), bitcoin_client).await?;
+        let bitcoin_client_proxy = BitcoinClientProxy::new(bitcoin_client_actor_ref.into());
+        let bitcoin_relayer = BitcoinRelayer::new(
+            self.config.clone(),
+            bitcoin_client_proxy,
+            relayer_ctx.executor.clone(),
+            relayer_ctx.rooch_store.clone(),
+        )?;
+        let bitcoin_relayer_actor_ref =
+            ctx.spawn("bitcoin_relayer".into(), bitcoin_relayer).await?;
+        Ok(RelayerProxy::new(ActorRef::<BitcoinRelayer>::from(
+            bitcoin_relayer_actor_ref,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ethereum_config(failure_policy: RelayerFailurePolicy) -> EthereumRelayerConfig {
+        EthereumRelayerConfig {
+            eth_rpc_url: "http://127.0.0.1:8545".to_string(),
+            eth_start_block_height: None,
+            eth_end_block_height: None,
+            eth_relay_batch_size: None,
+            failure_policy,
+        }
+    }
+
+    fn bitcoin_config(failure_policy: RelayerFailurePolicy) -> BitcoinRelayerConfig {
+        BitcoinRelayerConfig {
+            btc_rpc_url: "http://127.0.0.1:18443".to_string(),
+            btc_rpc_user_name: "roochuser".to_string(),
+            btc_rpc_password: "roochpass".to_string(),
+            btc_end_block_height: None,
+            btc_sync_block_interval: None,
+            btc_mempool_preview: false,
+            btc_mempool_poll_interval: None,
+            failure_policy,
+        }
+    }
+
+    struct MockRelayerFactory;
+
+    #[async_trait]
+    impl RelayerFactory for MockRelayerFactory {
+        fn failure_policy(&self) -> RelayerFailurePolicy {
+            RelayerFailurePolicy::Retry(1)
+        }
+
+        async fn start(
+            &self,
+            _ctx: &mut ActorContext,
+            _relayer_ctx: &RelayerContext,
+        ) -> Result<RelayerProxy> {
+            // the registered factories are started by the RelayerActor, see its tests
+            Err(anyhow::anyhow!(
+                "the mock relayer factory does not start a relayer"
+            ))
+        }
+    }
+
+    fn failure_policies(registry: &RelayerRegistry) -> Vec<RelayerFailurePolicy> {
+        registry
+            .factories()
+            .iter()
+            .map(|factory| factory.failure_policy())
+            .collect()
+    }
+
+    #[test]
+    fn test_registry_from_config() {
+        assert!(RelayerRegistry::from_config(None, None).is_empty());
+
+        let registry =
+            RelayerRegistry::from_config(None, Some(bitcoin_config(RelayerFailurePolicy::Pause)));
+        assert_eq!(
+            failure_policies(&registry),
+            vec![RelayerFailurePolicy::Pause]
+        );
+
+        let registry = RelayerRegistry::from_config(
+            Some(ethereum_config(RelayerFailurePolicy::Continue)),
+            Some(bitcoin_config(RelayerFailurePolicy::Pause)),
+        );
+        assert_eq!(
+            failure_policies(&registry),
+            vec![RelayerFailurePolicy::Continue, RelayerFailurePolicy::Pause]
+        );
+    }
+
+    #[test]
+    fn test_register_factory() {
+        let mut registry =
+            RelayerRegistry::from_config(Some(ethereum_config(RelayerFailurePolicy::Pause)), None);
+        registry.register(MockRelayerFactory);
+        // The factories are started in the registration order
+        assert_eq!(
+            failure_policies(&registry),
+            vec![RelayerFailurePolicy::Pause, RelayerFailurePolicy::Retry(1)]
+        );
+        // The registry is taken by the `RelayerActor` when it starts
+        let taken = std::mem::take(&mut registry);
+        assert!(registry.is_empty());
+        assert_eq!(taken.factories().len(), 2);
+    }
+}
@@ -0,0 +1,351 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+//! Read the Bitcoin blocks from the local block files, so the history can be imported without a bitcoind.
+//! Two layouts are supported in the block dir:
+//! 1. The `blk*.dat` files of bitcoind, every record is `magic(4 bytes) | size(4 bytes, little endian) | block`,
+//!    the files may be obfuscated by the key in `xor.dat`.
+//! 2. The `*.block` files, every file is a raw serialized block.
+//!
+//! The blocks in the files are out of order and may include the stale blocks,
+//! so the main chain is rebuilt by the previous block hash, the longest branch wins.
+
+use anyhow::{anyhow, bail, Result};
+use bitcoin::block::Header;
+use bitcoin::{Block, BlockHash};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+pub const BLOCK_FILE_PREFIX: &str = "blk";
+pub const BLOCK_FILE_EXTENSION: &str = "dat";
+pub const RAW_BLOCK_FILE_EXTENSION: &str = "block";
+pub const XOR_KEY_FILE_NAME: &str = "xor.dat";
+
+const BLOCK_HEADER_SIZE: usize = 80;
+const RECORD_HEADER_SIZE: usize = 8;
+
+#[derive(Debug, Clone)]
+struct BlockLocation {
+    file_index: usize,
+    offset: u64,
+    size: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct BlockFileIndex {
+    files: Vec<PathBuf>,
+    xor_key: Vec<u8>,
+    blocks: HashMap<BlockHash, BlockLocation>,
+    children: HashMap<BlockHash, Vec<BlockHash>>,
+}
+
+impl BlockFileIndex {
+    /// Index the blocks in the `block_dir`, only the block headers are read.
+    pub fn build(block_dir: &Path) -> Result<Self> {
+        if !block_dir.is_dir() {
+            bail!("The block dir {:?} is not a directory", block_dir);
+        }
+        let xor_key_path = block_dir.join(XOR_KEY_FILE_NAME);
+        let xor_key = if xor_key_path.exists() {
+            std::fs::read(&xor_key_path)?
+        } else {
+            vec![]
+        };
+        let mut index = BlockFileIndex {
+            // A zero key means the block files are not obfuscated
+            xor_key: if xor_key.iter().all(|b| *b == 0) {
+                vec![]
+            } else {
+                xor_key
+            },
+            ..Default::default()
+        };
+
+        let mut paths = std::fs::read_dir(block_dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        paths.sort();
+        for path in paths {
+            if !path.is_file() {
+                continue;
+            }
+            let file_name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            let extension = path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .unwrap_or_default();
+            if file_name.starts_with(BLOCK_FILE_PREFIX) && extension == BLOCK_FILE_EXTENSION {
+                index.index_block_file(path)?;
+            } else if extension == RAW_BLOCK_FILE_EXTENSION {
+                index.index_raw_block_file(path)?;
+            }
+        }
+        info!(
+            "BlockFileIndex indexed {} blocks in {} files from {:?}",
+            index.blocks.len(),
+            index.files.len(),
+            block_dir
+        );
+        Ok(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    pub fn contains(&self, block_hash: &BlockHash) -> bool {
+        self.blocks.contains_key(block_hash)
+    }
+
+    /// The main chain after the `anchor` block, the `anchor` is not included.
+    /// When the chain forks, the longest branch is chosen.
+    pub fn main_chain_after(&self, anchor: BlockHash) -> Vec<BlockHash> {
+        // The length of the longest branch after every block
+        let mut depths: HashMap<BlockHash, u64> = HashMap::new();
+        let mut stack = vec![(anchor, false)];
+        while let Some((block_hash, visited)) = stack.pop() {
+            let children = self.children_of(&block_hash);
+            if visited {
+                let depth = children
+                    .iter()
+                    .map(|child| depths[child] + 1)
+                    .max()
+                    .unwrap_or(0);
+                depths.insert(block_hash, depth);
+            } else {
+                stack.push((block_hash, true));
+                stack.extend(children.iter().map(|child| (*child, false)));
+            }
+        }
+
+        let mut chain = vec![];
+        let mut current = anchor;
+        while let Some(next) = self
+            .children_of(&current)
+            .iter()
+            .max_by_key(|child| depths[*child])
+        {
+            chain.push(*next);
+            current = *next;
+        }
+        chain
+    }
+
+    pub fn read_block(&self, block_hash: &BlockHash) -> Result<Block> {
+        let location = self
+            .blocks
+            .get(block_hash)
+            .ok_or_else(|| anyhow!("The block {} is not in the block files", block_hash))?;
+        let mut file = File::open(&self.files[location.file_index])?;
+        let bytes = self.read_at(&mut file, location.offset, location.size)?;
+        let block = bitcoin::consensus::deserialize::<Block>(&bytes)?;
+        if &block.block_hash() != block_hash {
+            bail!(
+                "The block hash {} in {:?} does not match the index {}",
+                block.block_hash(),
+                self.files[location.file_index],
+                block_hash
+            );
+        }
+        Ok(block)
+    }
+
+    fn children_of(&self, block_hash: &BlockHash) -> &[BlockHash] {
+        self.children
+            .get(block_hash)
+            .map(|children| children.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn index_block_file(&mut self, path: PathBuf) -> Result<()> {
+        let file_index = self.files.len();
+        let mut file = File::open(&path)?;
+        let file_size = file.metadata()?.len();
+        let mut offset = 0u64;
+        while offset + (RECORD_HEADER_SIZE as u64) <= file_size {
+            let record_header = self.read_at(&mut file, offset, RECORD_HEADER_SIZE)?;
+            // The block file is preallocated, the rest of the file is zero padding
+            if record_header[0..4] == [0u8; 4] {
+                break;
+            }
+            let size = u32::from_le_bytes(record_header[4..8].try_into()?) as usize;
+            let block_offset = offset + RECORD_HEADER_SIZE as u64;
+            if size < BLOCK_HEADER_SIZE || block_offset + size as u64 > file_size {
+                warn!(
+                    "BlockFileIndex skip the truncated block at offset {} in {:?}",
+                    offset, path
+                );
+                break;
+            }
+            self.index_block(&mut file, file_index, block_offset, size)?;
+            offset = block_offset + size as u64;
+        }
+        self.files.push(path);
+        Ok(())
+    }
+
+    fn index_raw_block_file(&mut self, path: PathBuf) -> Result<()> {
+        let file_index = self.files.len();
+        let mut file = File::open(&path)?;
+        let size = file.metadata()?.len() as usize;
+        if size < BLOCK_HEADER_SIZE {
+            bail!("The block file {:?} is too small", path);
+        }
+        self.index_block(&mut file, file_index, 0, size)?;
+        self.files.push(path);
+        Ok(())
+    }
+
+    fn index_block(
+        &mut self,
+        file: &mut File,
+        file_index: usize,
+        offset: u64,
+        size: usize,
+    ) -> Result<()> {
+        let header_bytes = self.read_at(file, offset, BLOCK_HEADER_SIZE)?;
+        let header = bitcoin::consensus::deserialize::<Header>(&header_bytes)?;
+        let block_hash = header.block_hash();
+        if self.blocks.contains_key(&block_hash) {
+            return Ok(());
+        }
+        self.blocks.insert(
+            block_hash,
+            BlockLocation {
+                file_index,
+                offset,
+                size,
+            },
+        );
+        self.children
+            .entry(header.prev_blockhash)
+            .or_default()
+            .push(block_hash);
+        Ok(())
+    }
+
+    fn read_at(&self, file: &mut File, offset: u64, size: usize) -> Result<Vec<u8>> {
+        file.seek(SeekFrom::Start(offset))?;
+        let mut bytes = vec![0u8; size];
+        file.read_exact(&mut bytes)?;
+        if !self.xor_key.is_empty() {
+            let key_len = self.xor_key.len();
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte ^= self.xor_key[(offset as usize + i) % key_len];
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::block::Version;
+    use bitcoin::hashes::Hash;
+    use bitcoin::{CompactTarget, TxMerkleNode};
+    use std::io::Write;
+
+    fn new_block(prev_blockhash: BlockHash, nonce: u32) -> Block {
+        Block {
+            header: Header {
+                version: Version::ONE,
+                prev_blockhash,
+                merkle_root: TxMerkleNode::all_zeros(),
+                time: nonce,
+                bits: CompactTarget::from_consensus(0x207fffff),
+                nonce,
+            },
+            txdata: vec![],
+        }
+    }
+
+    fn write_block_file(path: &Path, blocks: &[&Block], xor_key: &[u8]) {
+        let mut bytes = vec![];
+        for block in blocks {
+            let block_bytes = bitcoin::consensus::serialize(*block);
+            bytes.extend_from_slice(&bitcoin::Network::Regtest.magic().to_bytes());
+            bytes.extend_from_slice(&(block_bytes.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&block_bytes);
+        }
+        // The zero padding of the preallocated file
+        bytes.extend_from_slice(&[0u8; 16]);
+        if !xor_key.is_empty() {
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte ^= xor_key[i % xor_key.len()];
+            }
+        }
+        File::create(path).unwrap().write_all(&bytes).unwrap();
+    }
+
+    #[test]
+    fn test_block_file_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let xor_key = vec![1u8, 2, 3, 4, 5, 6, 7, 8];
+        std::fs::write(dir.path().join(XOR_KEY_FILE_NAME), &xor_key).unwrap();
+
+        let genesis = new_block(BlockHash::all_zeros(), 0);
+        let block1 = new_block(genesis.block_hash(), 1);
+        let block2 = new_block(block1.block_hash(), 2);
+        // A stale block at height 2
+        let stale_block2 = new_block(block1.block_hash(), 22);
+        let block3 = new_block(block2.block_hash(), 3);
+
+        // The blocks are out of order in the files
+        write_block_file(
+            &dir.path().join("blk00000.dat"),
+            &[&genesis, &block2, &stale_block2],
+            &xor_key,
+        );
+        write_block_file(
+            &dir.path().join("blk00001.dat"),
+            &[&block3, &block1],
+            &xor_key,
+        );
+
+        let index = BlockFileIndex::build(dir.path()).unwrap();
+        assert_eq!(index.len(), 5);
+        let chain = index.main_chain_after(genesis.block_hash());
+        assert_eq!(
+            chain,
+            vec![
+                block1.block_hash(),
+                block2.block_hash(),
+                block3.block_hash()
+            ]
+        );
+        assert_eq!(index.read_block(&block2.block_hash()).unwrap(), block2);
+        assert!(index.main_chain_after(block3.block_hash()).is_empty());
+    }
+
+    #[test]
+    fn test_raw_block_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let genesis = new_block(BlockHash::all_zeros(), 0);
+        let block1 = new_block(genesis.block_hash(), 1);
+        for (height, block) in [&genesis, &block1].iter().enumerate() {
+            std::fs::write(
+                dir.path()
+                    .join(format!("{}.{}", height, RAW_BLOCK_FILE_EXTENSION)),
+                bitcoin::consensus::serialize(*block),
+            )
+            .unwrap();
+        }
+        let index = BlockFileIndex::build(dir.path()).unwrap();
+        assert_eq!(
+            index.main_chain_after(genesis.block_hash()),
+            vec![block1.block_hash()]
+        );
+        assert_eq!(index.read_block(&block1.block_hash()).unwrap(), block1);
+    }
+}
@@ -1,9 +1,15 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::jsonrpc_types::btc::mempool::{PendingTxFilterView, PendingTxView};
 use crate::jsonrpc_types::btc::ord::InscriptionFilterView;
+use crate::jsonrpc_types::btc::runes::{RuneBalancesFilterView, RuneFilterView};
+use crate::jsonrpc_types::btc::sat::SatView;
 use crate::jsonrpc_types::btc::utxo::UTXOFilterView;
-use crate::jsonrpc_types::{IndexerStateIDView, InscriptionPageView, StrView, UTXOPageView};
+use crate::jsonrpc_types::{
+    IndexerStateIDView, InscriptionPageView, RuneBalancesPageView, RunePageView, StrView,
+    UTXOPageView,
+};
 use crate::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use rooch_open_rpc_macros::open_rpc;
@@ -33,4 +39,39 @@ pub trait BtcAPI {
         limit: Option<StrView<u64>>,
         descending_order: Option<bool>,
     ) -> RpcResult<InscriptionPageView>;
+
+    /// Query the Rune via global index by Rune filter
+    #[method(name = "queryRunes")]
+    async fn query_runes(
+        &self,
+        filter: RuneFilterView,
+        // exclusive cursor if `Some`, otherwise start from the beginning
+        cursor: Option<IndexerStateIDView>,
+        limit: Option<StrView<u64>>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<RunePageView>;
+
+    /// Query the Rune balances of the UTXOs via global index by RuneBalances filter
+    #[method(name = "queryRuneBalances")]
+    async fn query_rune_balances(
+        &self,
+        filter: RuneBalancesFilterView,
+        // exclusive cursor if `Some`, otherwise start from the beginning
+        cursor: Option<IndexerStateIDView>,
+        limit: Option<StrView<u64>>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<RuneBalancesPageView>;
+
+    /// Get the sat number info, include the height, rarity and the location of the sat
+    #[method(name = "getSat")]
+    async fn get_sat(&self, sat: StrView<u64>) -> RpcResult<SatView>;
+
+    /// Query the preview of the pending transactions in the Bitcoin mempool, the latest seen first.
+    /// The preview shows the UTXO and Inscription changes if the transactions are confirmed, it is not written to the state.
+    #[method(name = "queryPendingTxs")]
+    async fn query_pending_txs(
+        &self,
+        filter: PendingTxFilterView,
+        limit: Option<StrView<u64>>,
+    ) -> RpcResult<Vec<PendingTxView>>;
 }
@@ -5,6 +5,7 @@ use jsonrpsee::RpcModule;
 
 pub mod btc_api;
 pub mod rooch_api;
+pub mod rooch_subscription_api;
 
 pub const DEFAULT_RESULT_LIMIT: u64 = 50;
 pub const DEFAULT_RESULT_LIMIT_USIZE: usize = DEFAULT_RESULT_LIMIT as usize;
@@ -4,14 +4,17 @@
 use crate::jsonrpc_types::account_view::BalanceInfoView;
 use crate::jsonrpc_types::address::RoochOrBitcoinAddressView;
 use crate::jsonrpc_types::event_view::{EventFilterView, IndexerEventIDView};
-use crate::jsonrpc_types::transaction_view::{TransactionFilterView, TransactionWithInfoView};
+use crate::jsonrpc_types::transaction_view::{
+    TransactionFilterView, TransactionStatusView, TransactionWithInfoView,
+};
 use crate::jsonrpc_types::{
-    AccessPathView, AnnotatedFunctionResultView, BalanceInfoPageView, BytesView, EventOptions,
-    EventPageView, ExecuteTransactionResponseView, FieldKeyView, FunctionCallView, H256View,
-    IndexerEventPageView, IndexerObjectStatePageView, IndexerStateIDView, ModuleABIView,
-    ObjectIDVecView, ObjectIDView, ObjectStateFilterView, ObjectStateView, QueryOptions,
-    RoochAddressView, StateOptions, StatePageView, StrView, StructTagView,
-    TransactionWithInfoPageView, TxOptions,
+    AccessPathView, AnnotatedFunctionResultView, BalanceInfoPageView, BytesView,
+    DryRunTransactionResponseView, EventOptions, EventPageView, ExecuteTransactionResponseView,
+    FieldKeyView, FunctionCallView, H256View, IndexerEventPageView, IndexerObjectStatePageView,
+    IndexerStateIDView, ModuleABIView, ObjectIDVecView, ObjectIDView, ObjectStateFilterView,
+    ObjectStateView, QueryOptions, RelayerStatusView, RoochAddressView, StateOptions,
+    StatePageView, StatesWithProofView, StrView, StructTagView, TransactionWithInfoPageView,
+    TxOptions,
 };
 use crate::RpcResult;
 use jsonrpsee::proc_macros::rpc;
@@ -27,6 +30,7 @@ pub trait RoochAPI {
 
     /// Send the signed transaction in bcs hex format
     /// This method does not block waiting for the transaction to be executed.
+    /// The transaction is queued in the tx pool, use getTransactionStatus to query the status.
     #[method(name = "sendRawTransaction")]
     async fn send_raw_transaction(&self, tx_bcs_hex: BytesView) -> RpcResult<H256View>;
 
@@ -39,12 +43,22 @@ pub trait RoochAPI {
         tx_option: Option<TxOptions>,
     ) -> RpcResult<ExecuteTransactionResponseView>;
 
+    /// Dry run the signed transaction in bcs hex format
+    /// The transaction is validated and executed against the latest state, but it is not sequenced and the output is not committed.
+    #[method(name = "dryRunRawTransaction")]
+    async fn dry_run_raw_transaction(
+        &self,
+        tx_bcs_hex: BytesView,
+    ) -> RpcResult<DryRunTransactionResponseView>;
+
     /// Execute a read-only function call
     /// The function do not change the state of Application
+    /// If the StateOptions.state_root or StateOptions.tx_order is set, the function is executed at that historical state.
     #[method(name = "executeViewFunction")]
     async fn execute_view_function(
         &self,
         function_call: FunctionCallView,
+        state_option: Option<StateOptions>,
     ) -> RpcResult<AnnotatedFunctionResultView>;
 
     /// Get the states by access_path
@@ -56,6 +70,17 @@ pub trait RoochAPI {
         state_option: Option<StateOptions>,
     ) -> RpcResult<Vec<Option<ObjectStateView>>>;
 
+    /// Get the states by access_path with the merkle proofs from the root object
+    /// The proofs can be verified against the returned state root, which should match a trusted state root,
+    /// such as the state root of a block or a transaction execution info.
+    /// The states are read at the StateOptions.state_root or StateOptions.tx_order if specified, otherwise at the latest state.
+    #[method(name = "getStatesWithProof")]
+    async fn get_states_with_proof(
+        &self,
+        access_path: AccessPathView,
+        state_option: Option<StateOptions>,
+    ) -> RpcResult<StatesWithProofView>;
+
     /// List the states by access_path
     /// If the StateOptions.decode is true, the state is decoded and the decoded value is returned in the response.
     #[method(name = "listStates")]
@@ -129,12 +154,22 @@ pub trait RoochAPI {
         descending_order: Option<bool>,
     ) -> RpcResult<TransactionWithInfoPageView>;
 
+    /// Get the status of the transaction submitted by `sendRawTransaction`
+    /// Return null if the transaction is unknown, or it is dropped too long ago to keep the status.
+    #[method(name = "getTransactionStatus")]
+    async fn get_transaction_status(
+        &self,
+        tx_hash: H256View,
+    ) -> RpcResult<Option<TransactionStatusView>>;
+
     /// get account balance by RoochAddress and CoinType
+    /// If the StateOptions.state_root or StateOptions.tx_order is set, the balance is read at that historical state.
     #[method(name = "getBalance")]
     async fn get_balance(
         &self,
         account_addr: RoochOrBitcoinAddressView,
         coin_type: StructTagView,
+        state_option: Option<StateOptions>,
     ) -> RpcResult<BalanceInfoView>;
 
     /// get account balances by RoochAddress
@@ -186,4 +221,12 @@ pub trait RoochAPI {
         limit: Option<StrView<u64>>,
         query_option: Option<QueryOptions>,
     ) -> RpcResult<IndexerObjectStatePageView>;
+
+    /// Get the status of the L1 relayers, for monitoring the relayer is stuck or paused
+    #[method(name = "getRelayerStatus")]
+    async fn get_relayer_status(&self) -> RpcResult<Vec<RelayerStatusView>>;
+
+    /// Resume the relayer paused by the failure policy, the failed block or transaction is relayed again if it is not sequenced
+    #[method(name = "resumeRelayer")]
+    async fn resume_relayer(&self, name: String) -> RpcResult<RelayerStatusView>;
 }
@@ -0,0 +1,53 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::event_view::{EventFilterView, IndexerEventView};
+use crate::jsonrpc_types::transaction_view::TransactionWithInfoView;
+use crate::jsonrpc_types::{ObjectChangeFilterView, ObjectChangeNotificationView, StrView};
+use jsonrpsee::core::SubscriptionResult;
+use jsonrpsee::proc_macros::rpc;
+use rooch_open_rpc_macros::open_rpc;
+
+/// The subscriptions are only available via the WebSocket.
+/// Every subscription can resume from a tx order by `from_tx_order`(inclusive), the transactions and events
+/// before the recent window are read from the store, the object changes can only resume from the recent window.
+/// The subscription is closed with an error if the subscriber lags behind.
+#[open_rpc(namespace = "rooch")]
+#[rpc(server, client, namespace = "rooch")]
+#[async_trait]
+pub trait RoochSubscriptionAPI {
+    /// Subscribe the executed transactions
+    #[subscription(
+        name = "subscribeTransactions",
+        unsubscribe = "unsubscribeTransactions",
+        item = TransactionWithInfoView
+    )]
+    async fn subscribe_transactions(
+        &self,
+        from_tx_order: Option<StrView<u64>>,
+    ) -> SubscriptionResult;
+
+    /// Subscribe the events emitted by the executed transactions
+    #[subscription(
+        name = "subscribeEvents",
+        unsubscribe = "unsubscribeEvents",
+        item = IndexerEventView
+    )]
+    async fn subscribe_events(
+        &self,
+        filter: EventFilterView,
+        from_tx_order: Option<StrView<u64>>,
+    ) -> SubscriptionResult;
+
+    /// Subscribe the changes of the objects by object id or object type
+    #[subscription(
+        name = "subscribeObjectChanges",
+        unsubscribe = "unsubscribeObjectChanges",
+        item = ObjectChangeNotificationView
+    )]
+    async fn subscribe_object_changes(
+        &self,
+        filter: ObjectChangeFilterView,
+        from_tx_order: Option<StrView<u64>>,
+    ) -> SubscriptionResult;
+}
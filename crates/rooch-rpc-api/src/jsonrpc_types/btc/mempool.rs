@@ -0,0 +1,163 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::btc::ord::BitcoinInscriptionIDView;
+use crate::jsonrpc_types::btc::transaction::{hex_to_txid, TxidView};
+use crate::jsonrpc_types::{BitcoinAddressView, StrView};
+use anyhow::Result;
+use bitcoin::hashes::Hash;
+use bitcoin::Txid;
+use move_core_types::account_address::AccountAddress;
+use moveos_types::moveos_std::object::ObjectID;
+use rooch_types::bitcoin::mempool::{
+    PendingCreatedUTXO, PendingInscription, PendingSpentUTXO, PendingTx, PendingTxFilter,
+};
+use rooch_types::bitcoin::types::OutPoint;
+use rooch_types::into_address::IntoAddress;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+fn to_txid_view(txid: AccountAddress) -> TxidView {
+    Txid::from_byte_array(txid.into_bytes()).into()
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingTxFilterView {
+    /// Query by bitcoin txid
+    Txid(String),
+    /// Query the pending transaction which spends the UTXO, represent by bitcoin txid and vout
+    #[serde(rename = "spent_utxo")]
+    SpentUTXO { txid: String, vout: u32 },
+    /// Query the pending transactions which have an output to the bitcoin address
+    Recipient(BitcoinAddressView),
+    /// Query all.
+    All,
+}
+
+impl PendingTxFilterView {
+    pub fn into_pending_tx_filter(filter: PendingTxFilterView) -> Result<PendingTxFilter> {
+        Ok(match filter {
+            PendingTxFilterView::Txid(txid) => {
+                PendingTxFilter::Txid(hex_to_txid(txid.as_str())?.into_address())
+            }
+            PendingTxFilterView::SpentUTXO { txid, vout } => {
+                let txid = hex_to_txid(txid.as_str())?;
+                PendingTxFilter::SpentUTXO(OutPoint::new(txid.into_address(), vout))
+            }
+            PendingTxFilterView::Recipient(address) => PendingTxFilter::Recipient(address.0),
+            PendingTxFilterView::All => PendingTxFilter::All,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct PendingSpentUTXOView {
+    pub bitcoin_txid: TxidView,
+    pub vout: u32,
+    pub object_id: ObjectID,
+    /// The value of the UTXO, none if the UTXO is neither in the state nor created by a pending transaction
+    pub value: Option<StrView<u64>>,
+    /// Whether the UTXO is created by another pending transaction
+    pub unconfirmed: bool,
+}
+
+impl From<PendingSpentUTXO> for PendingSpentUTXOView {
+    fn from(utxo: PendingSpentUTXO) -> Self {
+        PendingSpentUTXOView {
+            bitcoin_txid: to_txid_view(utxo.outpoint.txid),
+            vout: utxo.outpoint.vout,
+            object_id: utxo.object_id,
+            value: utxo.value.map(Into::into),
+            unconfirmed: utxo.unconfirmed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct PendingCreatedUTXOView {
+    pub vout: u32,
+    pub object_id: ObjectID,
+    pub value: StrView<u64>,
+    /// The bitcoin address of the recipient, none if the output script is not a standard address
+    pub recipient_address: Option<String>,
+}
+
+impl PendingCreatedUTXOView {
+    pub fn new(utxo: PendingCreatedUTXO, bitcoin_network: u8) -> Self {
+        let recipient_address = if utxo.recipient_address.is_empty() {
+            None
+        } else {
+            utxo.recipient_address.format(bitcoin_network).ok()
+        };
+        PendingCreatedUTXOView {
+            vout: utxo.outpoint.vout,
+            object_id: utxo.object_id,
+            value: utxo.value.into(),
+            recipient_address,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct PendingInscriptionView {
+    pub bitcoin_inscription_id: BitcoinInscriptionIDView,
+    pub object_id: ObjectID,
+    /// Whether the inscription is created by the pending transaction
+    pub is_new: bool,
+    /// The output of the pending transaction which the inscription goes to,
+    /// none if the inscription is paid to the miner as fee or the input values are unresolved
+    pub vout: Option<u32>,
+    /// The offset of the inscription in the output
+    pub offset: Option<StrView<u64>>,
+}
+
+impl From<PendingInscription> for PendingInscriptionView {
+    fn from(inscription: PendingInscription) -> Self {
+        PendingInscriptionView {
+            bitcoin_inscription_id: BitcoinInscriptionIDView {
+                txid: to_txid_view(inscription.inscription_id.txid),
+                index: inscription.inscription_id.index,
+            },
+            object_id: inscription.object_id,
+            is_new: inscription.is_new,
+            vout: inscription
+                .sat_point
+                .as_ref()
+                .map(|sat_point| sat_point.outpoint.vout),
+            offset: inscription
+                .sat_point
+                .map(|sat_point| sat_point.offset.into()),
+        }
+    }
+}
+
+/// The preview of a pending transaction in the Bitcoin mempool, it is not written to the state
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct PendingTxView {
+    pub bitcoin_txid: TxidView,
+    pub spent_utxos: Vec<PendingSpentUTXOView>,
+    pub created_utxos: Vec<PendingCreatedUTXOView>,
+    pub inscriptions: Vec<PendingInscriptionView>,
+    /// Whether the values of all the spent UTXOs are resolved
+    pub resolved: bool,
+    /// The timestamp in seconds when the transaction is first seen in the mempool
+    pub first_seen: StrView<u64>,
+}
+
+impl PendingTxView {
+    pub fn new(tx: PendingTx, bitcoin_network: u8) -> Self {
+        PendingTxView {
+            bitcoin_txid: to_txid_view(tx.txid),
+            spent_utxos: tx.spent_utxos.into_iter().map(Into::into).collect(),
+            created_utxos: tx
+                .created_utxos
+                .into_iter()
+                .map(|utxo| PendingCreatedUTXOView::new(utxo, bitcoin_network))
+                .collect(),
+            inscriptions: tx.inscriptions.into_iter().map(Into::into).collect(),
+            resolved: tx.resolved,
+            first_seen: tx.first_seen.into(),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod mempool;
 pub mod ord;
+pub mod runes;
+pub mod sat;
 pub mod transaction;
 pub mod utxo;
@@ -14,6 +14,7 @@ use moveos_types::state::MoveState;
 use moveos_types::{moveos_std::object::ObjectID, state::MoveStructType};
 use rooch_types::bitcoin::ord;
 use rooch_types::bitcoin::ord::{BitcoinInscriptionID, Inscription, InscriptionID};
+use rooch_types::bitcoin::sat::Sat;
 use rooch_types::indexer::state::ObjectStateFilter;
 use rooch_types::into_address::IntoAddress;
 use schemars::JsonSchema;
@@ -90,6 +91,17 @@ pub struct InscriptionView {
     pub metaprotocol: Option<MoveStringView>,
     pub parents: ObjectIDVecView,
     pub pointer: Option<StrView<u64>>,
+    /// The sat which the inscription is inscribed on, only available when the sat tracking is enabled
+    pub sat: Option<StrView<u64>>,
+    /// The rarity of the sat
+    pub rarity: Option<String>,
+}
+
+impl InscriptionView {
+    pub fn set_sat(&mut self, sat: Option<u64>) {
+        self.sat = sat.map(StrView);
+        self.rarity = sat.map(|sat| Sat(sat).rarity().to_string());
+    }
 }
 
 impl From<Inscription> for InscriptionView {
@@ -109,6 +121,8 @@ impl From<Inscription> for InscriptionView {
             metaprotocol: Option::<MoveString>::from(inscription.metaprotocol).map(StrView),
             parents: inscription.parents.into(),
             pointer: Option::<u64>::from(inscription.pointer).map(StrView),
+            sat: None,
+            rarity: None,
         }
     }
 }
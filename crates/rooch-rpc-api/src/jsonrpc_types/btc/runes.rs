@@ -0,0 +1,227 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::btc::transaction::{hex_to_txid, TxidView};
+use crate::jsonrpc_types::{
+    IndexerObjectStateView, IndexerStateIDView, ObjectMetaView, RoochOrBitcoinAddressView, StrView,
+};
+use anyhow::{bail, Result};
+use bitcoin::hashes::Hash;
+use bitcoin::Txid;
+use moveos_types::moveos_std::object::ObjectID;
+use moveos_types::state::{MoveState, MoveStructType};
+use rooch_types::bitcoin::runes::{self, RuneBalance, RuneBalances, RuneEntry, RuneId, Terms};
+use rooch_types::bitcoin::types::OutPoint;
+use rooch_types::indexer::state::ObjectStateFilter;
+use rooch_types::into_address::IntoAddress;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RuneFilterView {
+    /// Query by rune id, represent by `block:tx`
+    RuneId(String),
+    /// Query by rune name, the spacers `•` or `.` are allowed, e.g. `UNCOMMON•GOODS`
+    Rune(String),
+    /// Query by object id.
+    ObjectId(ObjectID),
+    /// Query all.
+    All,
+}
+
+impl RuneFilterView {
+    /// The `Rune` filter should be resolved to `RuneId` via the on-chain rune name index before call this function
+    pub fn into_global_state_filter(filter: RuneFilterView) -> Result<ObjectStateFilter> {
+        Ok(match filter {
+            RuneFilterView::RuneId(id) => {
+                let rune_id = RuneId::from_str(id.as_str())?;
+                ObjectStateFilter::ObjectId(vec![runes::derive_rune_entry_id(&rune_id)])
+            }
+            RuneFilterView::Rune(rune) => {
+                bail!("The rune filter {} should be resolved to a rune id", rune)
+            }
+            RuneFilterView::ObjectId(object_id) => ObjectStateFilter::ObjectId(vec![object_id]),
+            RuneFilterView::All => ObjectStateFilter::ObjectType(RuneEntry::struct_tag()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RuneBalancesFilterView {
+    /// Query by owner, support rooch address and bitcoin address
+    Owner(RoochOrBitcoinAddressView),
+    /// Query by bitcoin outpoint, represent by bitcoin txid and vout
+    OutPoint { txid: String, vout: u32 },
+    /// Query by object id.
+    ObjectId(ObjectID),
+    /// Query all.
+    All,
+}
+
+impl RuneBalancesFilterView {
+    pub fn into_global_state_filter(filter: RuneBalancesFilterView) -> Result<ObjectStateFilter> {
+        Ok(match filter {
+            RuneBalancesFilterView::Owner(owner) => ObjectStateFilter::ObjectTypeWithOwner {
+                object_type: RuneBalances::struct_tag(),
+                owner: owner.0.rooch_address,
+            },
+            RuneBalancesFilterView::OutPoint { txid, vout } => {
+                let txid = hex_to_txid(txid.as_str())?;
+                let outpoint = OutPoint::new(txid.into_address(), vout);
+                ObjectStateFilter::ObjectId(vec![runes::derive_rune_balances_id(&outpoint)])
+            }
+            RuneBalancesFilterView::ObjectId(object_id) => {
+                ObjectStateFilter::ObjectId(vec![object_id])
+            }
+            RuneBalancesFilterView::All => {
+                ObjectStateFilter::ObjectType(RuneBalances::struct_tag())
+            }
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TermsView {
+    pub amount: Option<StrView<u128>>,
+    pub cap: Option<StrView<u128>>,
+    pub height_start: Option<StrView<u64>>,
+    pub height_end: Option<StrView<u64>>,
+    pub offset_start: Option<StrView<u64>>,
+    pub offset_end: Option<StrView<u64>>,
+}
+
+impl From<Terms> for TermsView {
+    fn from(terms: Terms) -> Self {
+        TermsView {
+            amount: Option::<u128>::from(terms.amount).map(Into::into),
+            cap: Option::<u128>::from(terms.cap).map(Into::into),
+            height_start: Option::<u64>::from(terms.height_start).map(Into::into),
+            height_end: Option::<u64>::from(terms.height_end).map(Into::into),
+            offset_start: Option::<u64>::from(terms.offset_start).map(Into::into),
+            offset_end: Option::<u64>::from(terms.offset_end).map(Into::into),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RuneEntryView {
+    /// The rune id, represent by `block:tx`
+    pub id: String,
+    /// The rune name with spacers
+    pub rune: String,
+    pub number: StrView<u64>,
+    /// The etching transaction id
+    pub etching: TxidView,
+    pub divisibility: u8,
+    /// The currency symbol
+    pub symbol: Option<String>,
+    pub premine: StrView<u128>,
+    pub terms: Option<TermsView>,
+    pub turbo: bool,
+    pub mints: StrView<u128>,
+    pub burned: StrView<u128>,
+    pub timestamp: u32,
+}
+
+impl From<RuneEntry> for RuneEntryView {
+    fn from(entry: RuneEntry) -> Self {
+        RuneEntryView {
+            id: entry.id.to_string(),
+            rune: entry.spaced_rune().to_string(),
+            number: entry.number.into(),
+            etching: Txid::from_byte_array(entry.etching.into_bytes()).into(),
+            divisibility: entry.divisibility,
+            symbol: Option::<u32>::from(entry.symbol)
+                .and_then(char::from_u32)
+                .map(|c| c.to_string()),
+            premine: entry.premine.into(),
+            terms: Option::<Terms>::from(entry.terms).map(Into::into),
+            turbo: entry.turbo,
+            mints: entry.mints.into(),
+            burned: entry.burned.into(),
+            timestamp: entry.timestamp,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RuneEntryStateView {
+    #[serde(flatten)]
+    pub metadata: ObjectMetaView,
+    pub value: RuneEntryView,
+    #[serde(flatten)]
+    pub indexer_id: IndexerStateIDView,
+}
+
+impl TryFrom<IndexerObjectStateView> for RuneEntryStateView {
+    type Error = anyhow::Error;
+
+    fn try_from(state: IndexerObjectStateView) -> Result<Self, Self::Error> {
+        let entry = RuneEntry::from_bytes(&state.value.0)?;
+        Ok(RuneEntryStateView {
+            metadata: state.metadata,
+            value: entry.into(),
+            indexer_id: state.indexer_id,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RuneBalanceView {
+    /// The rune id, represent by `block:tx`
+    pub id: String,
+    pub amount: StrView<u128>,
+}
+
+impl From<RuneBalance> for RuneBalanceView {
+    fn from(balance: RuneBalance) -> Self {
+        RuneBalanceView {
+            id: balance.id.to_string(),
+            amount: balance.amount.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RuneBalancesView {
+    /// The txid of the output
+    pub bitcoin_txid: TxidView,
+    /// The vout of the output
+    pub vout: u32,
+    pub balances: Vec<RuneBalanceView>,
+}
+
+impl From<RuneBalances> for RuneBalancesView {
+    fn from(rune_balances: RuneBalances) -> Self {
+        RuneBalancesView {
+            bitcoin_txid: Txid::from_byte_array(rune_balances.outpoint.txid.into_bytes()).into(),
+            vout: rune_balances.outpoint.vout,
+            balances: rune_balances.balances.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RuneBalancesStateView {
+    #[serde(flatten)]
+    pub metadata: ObjectMetaView,
+    pub value: RuneBalancesView,
+    #[serde(flatten)]
+    pub indexer_id: IndexerStateIDView,
+}
+
+impl TryFrom<IndexerObjectStateView> for RuneBalancesStateView {
+    type Error = anyhow::Error;
+
+    fn try_from(state: IndexerObjectStateView) -> Result<Self, Self::Error> {
+        let rune_balances = RuneBalances::from_bytes(&state.value.0)?;
+        Ok(RuneBalancesStateView {
+            metadata: state.metadata,
+            value: rune_balances.into(),
+            indexer_id: state.indexer_id,
+        })
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::btc::transaction::TxidView;
+use crate::jsonrpc_types::StrView;
+use bitcoin::hashes::Hash;
+use bitcoin::Txid;
+use rooch_types::bitcoin::sat::{Sat, SatLocation};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SatLocationView {
+    /// The txid of the output which holds the sat
+    pub bitcoin_txid: TxidView,
+    /// The vout of the output which holds the sat
+    pub vout: u32,
+    /// The position of the sat in the output
+    pub offset: StrView<u64>,
+}
+
+impl From<SatLocation> for SatLocationView {
+    fn from(location: SatLocation) -> Self {
+        SatLocationView {
+            bitcoin_txid: Txid::from_byte_array(location.outpoint.txid.into_bytes()).into(),
+            vout: location.outpoint.vout,
+            offset: location.offset.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SatView {
+    pub sat: StrView<u64>,
+    /// The height of the block which mined the sat
+    pub height: StrView<u64>,
+    /// The halving epoch of the sat
+    pub epoch: StrView<u64>,
+    /// The rarity of the sat: common, uncommon, rare, epic, legendary or mythic
+    pub rarity: String,
+    /// The location of the sat, none if the sat is lost or not tracked, the sats are tracked when the sat tracking is enabled
+    pub location: Option<SatLocationView>,
+}
+
+impl SatView {
+    pub fn new(sat: Sat, location: Option<SatLocation>) -> Self {
+        SatView {
+            sat: sat.0.into(),
+            height: sat.height().into(),
+            epoch: sat.epoch().into(),
+            rarity: sat.rarity().to_string(),
+            location: location.map(Into::into),
+        }
+    }
+}
@@ -3,11 +3,11 @@
 
 use super::BytesView;
 use super::{ModuleIdView, StateChangeSetView, StrView};
-use crate::jsonrpc_types::event_view::EventView;
+use crate::jsonrpc_types::event_view::{EventView, TransactionEventView};
 use crate::jsonrpc_types::H256View;
 use move_core_types::vm_status::{AbortLocation, KeptVMStatus};
 use moveos_types::transaction::TransactionExecutionInfo;
-use moveos_types::transaction::TransactionOutput;
+use moveos_types::transaction::{RawTransactionOutput, TransactionOutput};
 use rooch_types::transaction::ExecuteTransactionResponse;
 use rooch_types::transaction::{authenticator::Authenticator, TransactionSequenceInfo};
 use schemars::JsonSchema;
@@ -186,3 +186,25 @@ impl From<ExecuteTransactionResponse> for ExecuteTransactionResponseView {
         }
     }
 }
+
+/// The output of the dry run transaction, the output is not applied to the state
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DryRunTransactionResponseView {
+    pub status: KeptVMStatusView,
+    pub gas_used: StrView<u64>,
+    pub events: Vec<TransactionEventView>,
+    pub changeset: StateChangeSetView,
+    pub is_upgrade: bool,
+}
+
+impl From<RawTransactionOutput> for DryRunTransactionResponseView {
+    fn from(output: RawTransactionOutput) -> Self {
+        Self {
+            status: output.status.into(),
+            gas_used: output.gas_used.into(),
+            events: output.events.into_iter().map(Into::into).collect(),
+            changeset: output.changeset.into(),
+            is_upgrade: output.is_upgrade,
+        }
+    }
+}
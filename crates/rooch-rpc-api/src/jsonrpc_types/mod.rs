@@ -8,6 +8,7 @@ mod execute_tx_response;
 mod function_return_value_view;
 mod module_abi_view;
 mod move_types;
+mod relayer_view;
 mod rooch_types;
 mod rpc_options;
 mod state_view;
@@ -29,6 +30,7 @@ pub use execute_tx_response::*;
 pub use function_return_value_view::*;
 pub use module_abi_view::*;
 pub use move_types::*;
+pub use relayer_view::*;
 pub use rpc_options::*;
 pub use state_view::*;
 pub use str_view::*;
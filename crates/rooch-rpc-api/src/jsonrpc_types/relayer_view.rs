@@ -0,0 +1,42 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::{BytesView, StrView};
+use rooch_types::relayer::RelayerStatus;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RelayerStatusView {
+    pub name: String,
+    /// The failure policy of the relayer, `continue`, `pause` or `retry:<times>`
+    pub failure_policy: String,
+    /// The height of the last L1 block successfully relayed since the node started
+    pub last_relayed_height: Option<StrView<u64>>,
+    /// The hash of the last L1 block successfully relayed since the node started
+    pub last_relayed_hash: Option<BytesView>,
+    /// The latest block height of the L1 chain seen by the relayer
+    pub l1_tip_height: Option<StrView<u64>>,
+    /// How many L1 blocks the relayer is behind the L1 tip
+    pub lag: Option<StrView<u64>>,
+    pub last_error: Option<String>,
+    /// The timestamp in seconds of the last error
+    pub last_error_at: Option<StrView<u64>>,
+    pub paused: bool,
+}
+
+impl From<RelayerStatus> for RelayerStatusView {
+    fn from(status: RelayerStatus) -> Self {
+        RelayerStatusView {
+            lag: status.lag().map(Into::into),
+            name: status.name,
+            failure_policy: status.failure_policy,
+            last_relayed_height: status.last_relayed_height.map(Into::into),
+            last_relayed_hash: status.last_relayed_hash.map(Into::into),
+            l1_tip_height: status.l1_tip_height.map(Into::into),
+            last_error: status.last_error,
+            last_error_at: status.last_error_at.map(Into::into),
+            paused: status.paused,
+        }
+    }
+}
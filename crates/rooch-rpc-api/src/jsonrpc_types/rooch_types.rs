@@ -5,6 +5,7 @@ use super::event_view::IndexerEventIDView;
 use super::{HumanReadableDisplay, IndexerStateIDView};
 use crate::jsonrpc_types::account_view::BalanceInfoView;
 use crate::jsonrpc_types::btc::ord::InscriptionStateView;
+use crate::jsonrpc_types::btc::runes::{RuneBalancesStateView, RuneEntryStateView};
 use crate::jsonrpc_types::btc::utxo::UTXOStateView;
 use crate::jsonrpc_types::event_view::{EventView, IndexerEventView};
 use crate::jsonrpc_types::transaction_view::TransactionWithInfoView;
@@ -29,6 +30,8 @@ pub type IndexerObjectStatePageView = PageView<IndexerObjectStateView, IndexerSt
 
 pub type UTXOPageView = PageView<UTXOStateView, IndexerStateIDView>;
 pub type InscriptionPageView = PageView<InscriptionStateView, IndexerStateIDView>;
+pub type RunePageView = PageView<RuneEntryStateView, IndexerStateIDView>;
+pub type RuneBalancesPageView = PageView<RuneBalancesStateView, IndexerStateIDView>;
 
 /// `next_cursor` points to the last item in the page;
 /// Reading with `next_cursor` will start from the next item after `next_cursor` if
@@ -1,6 +1,7 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::jsonrpc_types::{H256View, StrView};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +12,11 @@ pub struct StateOptions {
     pub decode: bool,
     /// If true, result with display rendered is returned
     pub show_display: bool,
+    /// If set, the states are read at this historical state root instead of the latest one.
+    pub state_root: Option<H256View>,
+    /// If set, the states are read at the state root after the transaction with this order is executed.
+    /// Only one of `state_root` and `tx_order` can be set.
+    pub tx_order: Option<StrView<u64>>,
 }
 
 impl StateOptions {
@@ -27,6 +33,16 @@ impl StateOptions {
         self.show_display = show_display;
         self
     }
+
+    pub fn state_root(mut self, state_root: H256View) -> Self {
+        self.state_root = Some(state_root);
+        self
+    }
+
+    pub fn tx_order(mut self, tx_order: u64) -> Self {
+        self.tx_order = Some(tx_order.into());
+        self
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Default)]
@@ -3,18 +3,20 @@
 
 use super::{
     AnnotatedMoveStructView, BytesView, H256View, HumanReadableDisplay, ObjectIDVecView,
-    RoochAddressView, RoochOrBitcoinAddressView, StrView, StructTagView, TypeTagView,
+    ObjectIDView, RoochAddressView, RoochOrBitcoinAddressView, StrView, StructTagView, TypeTagView,
 };
 use anyhow::Result;
 use move_core_types::effects::Op;
 use moveos_types::moveos_std::object::ObjectMeta;
 use moveos_types::state::{FieldKey, ObjectChange};
+use moveos_types::state_proof::{SparseMerkleProof, StateProof, StateWithProof};
 use moveos_types::state_resolver::StateKV;
 use moveos_types::{
     moveos_std::object::{human_readable_flag, ObjectID},
     state::{AnnotatedState, ObjectState, StateChangeSet},
 };
 use rooch_types::indexer::state::{IndexerStateID, ObjectStateFilter};
+use rooch_types::subscription::ObjectChangeFilter;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -174,6 +176,32 @@ impl From<ObjectChange> for ObjectChangeView {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectChangeFilterView {
+    /// Subscribe the changes of the object by object id.
+    ObjectId(ObjectIDView),
+    /// Subscribe the changes of the objects by object value type.
+    ObjectType(StructTagView),
+}
+
+impl From<ObjectChangeFilterView> for ObjectChangeFilter {
+    fn from(filter: ObjectChangeFilterView) -> Self {
+        match filter {
+            ObjectChangeFilterView::ObjectId(object_id) => Self::ObjectId(object_id.0),
+            ObjectChangeFilterView::ObjectType(object_type) => Self::ObjectType(object_type.into()),
+        }
+    }
+}
+
+/// The object change in an executed transaction. Used as the item of `subscribeObjectChanges`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ObjectChangeNotificationView {
+    pub tx_order: StrView<u64>,
+    pub tx_hash: H256View,
+    pub change: ObjectChangeView,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct IndexerStateIDView {
     pub tx_order: StrView<u64>,
@@ -426,3 +454,107 @@ where
             .join("\n")
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SparseMerkleLeafView {
+    pub key_hash: H256View,
+    pub value_hash: H256View,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SparseMerkleProofView {
+    /// The leaf in the proof, none means the subtree is empty
+    pub leaf: Option<SparseMerkleLeafView>,
+    /// The siblings from the bottom level to the root level
+    pub siblings: Vec<H256View>,
+}
+
+impl From<SparseMerkleProof> for SparseMerkleProofView {
+    fn from(proof: SparseMerkleProof) -> Self {
+        Self {
+            leaf: proof
+                .leaf
+                .map(|(key_hash, value_hash)| SparseMerkleLeafView {
+                    key_hash: key_hash.into(),
+                    value_hash: value_hash.into(),
+                }),
+            siblings: proof.siblings.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<SparseMerkleProofView> for SparseMerkleProof {
+    fn from(proof: SparseMerkleProofView) -> Self {
+        SparseMerkleProof::new(
+            proof.leaf.map(|leaf| (leaf.key_hash.0, leaf.value_hash.0)),
+            proof
+                .siblings
+                .into_iter()
+                .map(|sibling| sibling.0)
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StateProofView {
+    /// The objects on the path from the child of the root object to the field's object
+    pub ancestors: Vec<ObjectStateView>,
+    /// The SMT proofs from the root object's tree to the field's object tree
+    pub proofs: Vec<SparseMerkleProofView>,
+}
+
+impl From<StateProof> for StateProofView {
+    fn from(proof: StateProof) -> Self {
+        Self {
+            ancestors: proof.ancestors.into_iter().map(Into::into).collect(),
+            proofs: proof.proofs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<StateProofView> for StateProof {
+    fn from(proof: StateProofView) -> Self {
+        StateProof::new(
+            proof.ancestors.into_iter().map(Into::into).collect(),
+            proof.proofs.into_iter().map(Into::into).collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StateWithProofView {
+    pub object_id: ObjectIDView,
+    pub field_key: FieldKeyView,
+    pub state: Option<ObjectStateView>,
+    pub proof: StateProofView,
+}
+
+impl From<StateWithProof> for StateWithProofView {
+    fn from(state: StateWithProof) -> Self {
+        Self {
+            object_id: state.object_id.into(),
+            field_key: state.field_key.into(),
+            state: state.state.map(Into::into),
+            proof: state.proof.into(),
+        }
+    }
+}
+
+impl From<StateWithProofView> for StateWithProof {
+    fn from(state: StateWithProofView) -> Self {
+        Self {
+            object_id: state.object_id.0,
+            field_key: state.field_key.0,
+            state: state.state.map(Into::into),
+            proof: state.proof.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StatesWithProofView {
+    /// The state root which the proofs are generated against
+    pub state_root: H256View,
+    pub states: Vec<StateWithProofView>,
+}
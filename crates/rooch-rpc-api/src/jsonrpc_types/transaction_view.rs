@@ -9,7 +9,7 @@ use crate::jsonrpc_types::{
 use bitcoin::hashes::Hash;
 use rooch_types::indexer::transaction::TransactionFilter;
 use rooch_types::transaction::{
-    L1Block, L1Transaction, LedgerTransaction, LedgerTxData, TransactionWithInfo,
+    L1Block, L1Transaction, LedgerTransaction, LedgerTxData, TransactionStatus, TransactionWithInfo,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -177,3 +177,35 @@ impl From<TransactionFilterView> for TransactionFilter {
         }
     }
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TransactionStatusView {
+    /// The transaction is waiting in the tx pool to be sequenced and executed.
+    Queued,
+    /// The transaction is sequenced and executed.
+    Executed { tx_order: StrView<u64> },
+    /// The transaction is sequenced but failed to execute, it will be executed again when the node restarts.
+    Failed {
+        tx_order: StrView<u64>,
+        reason: String,
+    },
+    /// The transaction is removed from the tx pool without being sequenced.
+    Dropped { reason: String },
+}
+
+impl From<TransactionStatus> for TransactionStatusView {
+    fn from(status: TransactionStatus) -> Self {
+        match status {
+            TransactionStatus::Queued => Self::Queued,
+            TransactionStatus::Executed { tx_order } => Self::Executed {
+                tx_order: tx_order.into(),
+            },
+            TransactionStatus::Failed { tx_order, reason } => Self::Failed {
+                tx_order: tx_order.into(),
+                reason,
+            },
+            TransactionStatus::Dropped { reason } => Self::Dropped { reason },
+        }
+    }
+}
@@ -8,13 +8,17 @@ use moveos_types::moveos_std::account::Account;
 use moveos_types::{access_path::AccessPath, state::ObjectState, transaction::FunctionCall};
 use rooch_rpc_api::api::rooch_api::RoochAPIClient;
 use rooch_rpc_api::jsonrpc_types::{
-    account_view::BalanceInfoView, transaction_view::TransactionWithInfoView,
+    account_view::BalanceInfoView,
+    transaction_view::{TransactionStatusView, TransactionWithInfoView},
 };
 use rooch_rpc_api::jsonrpc_types::{
     AccessPathView, AnnotatedFunctionResultView, BalanceInfoPageView, EventOptions, EventPageView,
-    ObjectIDView, RoochAddressView, StateOptions, StatePageView, StructTagView,
+    ObjectIDView, RoochAddressView, StateOptions, StatePageView, StatesWithProofView,
+    StructTagView,
+};
+use rooch_rpc_api::jsonrpc_types::{
+    DryRunTransactionResponseView, ExecuteTransactionResponseView, ObjectStateView,
 };
-use rooch_rpc_api::jsonrpc_types::{ExecuteTransactionResponseView, ObjectStateView};
 use rooch_rpc_api::jsonrpc_types::{
     IndexerObjectStatePageView, ObjectStateFilterView, QueryOptions,
 };
@@ -52,12 +56,20 @@ impl RoochRpcClient {
             .map_err(|e| anyhow::anyhow!(e))
     }
 
+    pub async fn dry_run_tx(&self, tx: RoochTransaction) -> Result<DryRunTransactionResponseView> {
+        let tx_payload = bcs::to_bytes(&tx)?;
+        self.http
+            .dry_run_raw_transaction(tx_payload.into())
+            .await
+            .map_err(|e| anyhow::anyhow!(e))
+    }
+
     pub async fn execute_view_function(
         &self,
         function_call: FunctionCall,
     ) -> Result<AnnotatedFunctionResultView> {
         self.http
-            .execute_view_function(function_call.into())
+            .execute_view_function(function_call.into(), None)
             .await
             .map_err(|e| anyhow::anyhow!(e))
     }
@@ -69,6 +81,18 @@ impl RoochRpcClient {
         Ok(self.http.get_states(access_path.into(), None).await?)
     }
 
+    /// Get the states with the proofs, read at the `state_option.state_root` or `state_option.tx_order` if specified
+    pub async fn get_states_with_proof(
+        &self,
+        access_path: AccessPath,
+        state_option: Option<StateOptions>,
+    ) -> Result<StatesWithProofView> {
+        Ok(self
+            .http
+            .get_states_with_proof(access_path.into(), state_option)
+            .await?)
+    }
+
     pub async fn get_decoded_states(
         &self,
         access_path: AccessPath,
@@ -121,6 +145,13 @@ impl RoochRpcClient {
             .await?)
     }
 
+    pub async fn get_transaction_status(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<TransactionStatusView>> {
+        Ok(self.http.get_transaction_status(tx_hash.into()).await?)
+    }
+
     pub async fn get_sequence_number(&self, sender: RoochAddress) -> Result<u64> {
         Ok(self
             .get_states(AccessPath::object(Account::account_object_id(
@@ -207,7 +238,7 @@ impl RoochRpcClient {
     ) -> Result<BalanceInfoView> {
         Ok(self
             .http
-            .get_balance(account_addr.into(), coin_type)
+            .get_balance(account_addr.into(), coin_type, None)
             .await?)
     }
 
@@ -0,0 +1,83 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use moveos_types::h256::H256;
+use moveos_types::state_proof::StateWithProof;
+use rooch_rpc_api::jsonrpc_types::StatesWithProofView;
+
+/// Verify the states returned by `rooch_getStatesWithProof` against a trusted state root,
+/// such as the `state_root` of a `Block` or a transaction's execution info.
+/// Return the verified states, so the caller does not need to trust the node.
+pub fn verify_states_with_proof(
+    trusted_state_root: H256,
+    states: StatesWithProofView,
+) -> Result<Vec<StateWithProof>> {
+    ensure!(
+        states.state_root.0 == trusted_state_root,
+        "The state root {:#x} of the proofs does not match the trusted state root {:#x}",
+        states.state_root.0,
+        trusted_state_root
+    );
+    states
+        .states
+        .into_iter()
+        .map(|state| {
+            let state = StateWithProof::from(state);
+            state.verify(trusted_state_root)?;
+            Ok(state)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use moveos_store::MoveOSStore;
+    use moveos_types::access_path::AccessPath;
+    use moveos_types::moveos_std::object::ObjectID;
+    use moveos_types::test_utils::random_state_change_set;
+    use rooch_rpc_api::jsonrpc_types::StateWithProofView;
+
+    #[tokio::test]
+    async fn test_verify_states_with_proof() -> Result<()> {
+        let (moveos_store, _) = MoveOSStore::mock_moveos_store()?;
+        let state_store = moveos_store.get_state_store();
+        let mut change_set = random_state_change_set();
+        state_store.apply_change_set(&mut change_set)?;
+        let root = change_set.root_metadata();
+        let state_root = root.state_root();
+
+        let mut object_ids = change_set
+            .changes
+            .values()
+            .map(|change| change.metadata.id.clone())
+            .collect::<Vec<_>>();
+        object_ids.push(ObjectID::random());
+        let object_count = object_ids.len();
+
+        // The view returned by the server, and passed through the JSON of the RPC
+        let states = state_store.get_states_with_proof(&root, AccessPath::objects(object_ids))?;
+        let view = StatesWithProofView {
+            state_root: state_root.into(),
+            states: states.into_iter().map(StateWithProofView::from).collect(),
+        };
+        let view: StatesWithProofView = serde_json::from_str(&serde_json::to_string(&view)?)?;
+
+        let verified = verify_states_with_proof(state_root, view.clone())?;
+        assert_eq!(verified.len(), object_count);
+        assert!(verified[..object_count - 1]
+            .iter()
+            .all(|state| state.state.is_some()));
+        assert!(verified[object_count - 1].state.is_none());
+
+        // The proofs do not match another state root
+        assert!(verify_states_with_proof(H256::random(), view.clone()).is_err());
+
+        // The state is tampered
+        let mut tampered = view;
+        tampered.states[0].state = None;
+        assert!(verify_states_with_proof(state_root, tampered).is_err());
+        Ok(())
+    }
+}
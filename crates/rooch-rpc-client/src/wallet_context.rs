@@ -24,6 +24,9 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
 
+/// The safety margin added to the gas used of the dry run, in percent
+pub const DRY_RUN_GAS_SAFETY_MARGIN_PERCENT: u64 = 20;
+
 pub struct WalletContext {
     client: Arc<RwLock<Option<Client>>>,
     pub client_config: PersistedConfig<ClientConfig>,
@@ -139,7 +142,7 @@ impl WalletContext {
             .await
             .map_err(RoochError::from)?;
         log::debug!("use sequence_number: {}", sequence_number);
-        //TODO max gas amount from cli option or dry run estimate
+        // The max gas amount is estimated by the dry run when signing if it is not provided
         let tx_data = RoochTransactionData::new(
             sender,
             sequence_number,
@@ -157,9 +160,58 @@ impl WalletContext {
         password: Option<String>,
         max_gas_amount: Option<u64>,
     ) -> RoochResult<RoochTransaction> {
-        let tx_data = self.build_tx_data(sender, action, max_gas_amount).await?;
-        let tx = self.keystore.sign_transaction(&sender, tx_data, password)?;
-        Ok(tx)
+        let mut tx_data = self.build_tx_data(sender, action, max_gas_amount).await?;
+        let tx = self
+            .keystore
+            .sign_transaction(&sender, tx_data.clone(), password.clone())?;
+        if max_gas_amount.is_some() {
+            return Ok(tx);
+        }
+        match self.estimate_max_gas_amount(tx.clone()).await? {
+            Some(estimated_max_gas_amount) => {
+                tx_data.max_gas_amount = estimated_max_gas_amount;
+                let tx = self.keystore.sign_transaction(&sender, tx_data, password)?;
+                Ok(tx)
+            }
+            None => Ok(tx),
+        }
+    }
+
+    /// Estimate the max gas amount of the transaction by the dry run, with a safety margin.
+    /// Return none if the dry run fails or is not executed successfully, for example the server does not
+    /// support the dry run, then the default max gas amount is used and the error is reported by the execution.
+    pub async fn estimate_max_gas_amount(&self, tx: RoochTransaction) -> RoochResult<Option<u64>> {
+        let client = self.get_client().await?;
+        let output = match client.rooch.dry_run_tx(tx).await {
+            Ok(output) => output,
+            Err(e) => {
+                log::debug!(
+                    "dry run transaction error: {:?}, use the default max gas amount",
+                    e
+                );
+                return Ok(None);
+            }
+        };
+        if output.status != KeptVMStatusView::Executed {
+            log::debug!(
+                "dry run transaction failed: {:?}, use the default max gas amount",
+                output.status
+            );
+            return Ok(None);
+        }
+        let max_gas_amount = output
+            .gas_used
+            .0
+            .saturating_mul(100 + DRY_RUN_GAS_SAFETY_MARGIN_PERCENT)
+            / 100;
+        log::debug!(
+            "dry run gas used: {}, use max_gas_amount: {}",
+            output.gas_used.0,
+            max_gas_amount
+        );
+        Ok(Some(
+            max_gas_amount.min(GasScheduleConfig::INITIAL_MAX_GAS_AMOUNT),
+        ))
     }
 
     pub async fn execute(
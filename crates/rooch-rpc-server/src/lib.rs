@@ -4,6 +4,7 @@
 use crate::metrics_server::start_basic_prometheus_server;
 use crate::server::btc_server::BtcServer;
 use crate::server::rooch_server::RoochServer;
+use crate::server::rooch_subscription_server::RoochSubscriptionServer;
 use crate::service::aggregate_service::AggregateService;
 use crate::service::rpc_logger::RpcLogger;
 use crate::service::rpc_service::RpcService;
@@ -14,11 +15,16 @@ use coerce::actor::{system::ActorSystem, IntoActor};
 use jsonrpsee::server::middleware::rpc::RpcServiceBuilder;
 use jsonrpsee::server::ServerBuilder;
 use jsonrpsee::RpcModule;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::vm_status::KeptVMStatus;
+use moveos_types::module_binding::MoveFunctionCaller;
+use moveos_types::moveos_std::gas_schedule::GasScheduleConfig;
 use raw_store::errors::RawStoreError;
 use raw_store::metrics::DBMetrics;
 use rooch_config::server_config::ServerConfig;
 use rooch_config::{RoochOpt, ServerOpt};
 use rooch_da::actor::da::DAActor;
+use rooch_da::messages::RetryPendingBatches;
 use rooch_da::proxy::DAProxy;
 use rooch_db::RoochDB;
 use rooch_executor::actor::executor::ExecutorActor;
@@ -28,20 +34,33 @@ use rooch_genesis::RoochGenesis;
 use rooch_indexer::actor::indexer::IndexerActor;
 use rooch_indexer::actor::reader_indexer::IndexerReaderActor;
 use rooch_indexer::proxy::IndexerProxy;
+use rooch_pipeline_processor::actor::messages::SubmitBlockCommitments;
 use rooch_pipeline_processor::actor::processor::PipelineProcessorActor;
+use rooch_pipeline_processor::notifier::{ExecutionNotifier, DEFAULT_NOTIFIER_HISTORY_SIZE};
 use rooch_pipeline_processor::proxy::PipelineProcessorProxy;
+use rooch_pipeline_processor::tx_pool::{TxPool, TxPoolConfig};
 use rooch_proposer::actor::messages::ProposeBlock;
 use rooch_proposer::actor::proposer::ProposerActor;
 use rooch_proposer::proxy::ProposerProxy;
-use rooch_relayer::actor::messages::RelayTick;
+use rooch_relayer::actor::bitcoin_block_file_relayer::BitcoinBlockFileRelayerFactory;
+use rooch_relayer::actor::bitcoin_client::BitcoinClientActor;
+use rooch_relayer::actor::bitcoin_client_proxy::BitcoinClientProxy;
+use rooch_relayer::actor::bitcoin_mempool::BitcoinMempoolActor;
+use rooch_relayer::actor::bitcoin_mempool_proxy::BitcoinMempoolProxy;
+use rooch_relayer::actor::messages::{MempoolTick, RelayTick};
 use rooch_relayer::actor::relayer::RelayerActor;
+use rooch_relayer::actor::relayer_actor_proxy::RelayerActorProxy;
+use rooch_relayer::actor::relayer_registry::RelayerRegistry;
 use rooch_rpc_api::api::RoochRpcModule;
 use rooch_rpc_api::RpcError;
 use rooch_sequencer::actor::sequencer::SequencerActor;
 use rooch_sequencer::proxy::SequencerProxy;
 use rooch_types::address::RoochAddress;
+use rooch_types::crypto::RoochKeyPair;
 use rooch_types::error::{GenesisError, RoochError};
+use rooch_types::framework::state_commitment_chain::StateCommitmentChainModule;
 use rooch_types::rooch_network::BuiltinChainID;
+use rooch_types::transaction::RoochTransactionData;
 use serde_json::json;
 use std::fmt::Debug;
 use std::net::SocketAddr;
@@ -207,6 +226,7 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
     let sequencer_bitcoin_address = sequencer_keypair.public().bitcoin_address()?;
 
     let data_import_flag = opt.data_import_flag;
+    let set_scc_proposer_flag = opt.set_scc_proposer;
     let mut network = opt.network();
     if network.chain_id == BuiltinChainID::Local.chain_id() {
         // local chain use current active account as sequencer account
@@ -245,7 +265,7 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
 
     // Init sequencer
     info!("RPC Server sequencer address: {:?}", sequencer_account);
-    let sequencer = SequencerActor::new(sequencer_keypair.copy(), rooch_store)?
+    let sequencer = SequencerActor::new(sequencer_keypair.copy(), rooch_store.clone())?
         .into_actor(Some("Sequencer"), &actor_system)
         .await?;
     let sequencer_proxy = SequencerProxy::new(sequencer.into());
@@ -253,19 +273,22 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
     // Init DA
     let da_config = opt.da_config().clone();
 
-    let da_proxy = DAProxy::new(
-        DAActor::new(da_config, &actor_system)
-            .await?
-            .into_actor(Some("DAProxy"), &actor_system)
-            .await?
-            .into(),
-    );
+    let da_actor = DAActor::new(
+        da_config,
+        &actor_system,
+        rooch_store.clone(),
+        &prometheus_registry,
+    )
+    .await?
+    .into_actor(Some("DAProxy"), &actor_system)
+    .await?;
+    let da_proxy = DAProxy::new(da_actor.clone().into());
 
     // Init proposer
     let proposer_keypair = server_opt.proposer_keypair.unwrap();
     let proposer_account: RoochAddress = proposer_keypair.public().rooch_address()?;
     info!("RPC Server proposer address: {:?}", proposer_account);
-    let proposer = ProposerActor::new(proposer_keypair, da_proxy)
+    let proposer = ProposerActor::new(proposer_keypair, rooch_store.clone(), da_proxy)?
         .into_actor(Some("Proposer"), &actor_system)
         .await?;
     let proposer_proxy = ProposerProxy::new(proposer.clone().into());
@@ -278,6 +301,14 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
         ProposeBlock {},
     );
     timers.push(proposer_timer);
+    // retry the batches which are not confirmed by all the DA servers
+    let da_retry_interval_in_seconds: u64 = 1;
+    let da_timer = Timer::start(
+        da_actor,
+        Duration::from_secs(da_retry_interval_in_seconds),
+        RetryPendingBatches {},
+    );
+    timers.push(da_timer);
 
     // Init indexer
     let indexer_executor = IndexerActor::new(root, indexer_store)?
@@ -288,48 +319,121 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
         .await?;
     let indexer_proxy = IndexerProxy::new(indexer_executor.into(), indexer_reader_executor.into());
 
+    // The txs executed after the server started can be resumed by the subscribers
+    let last_sequenced_order = sequencer_proxy.get_sequencer_order().await?;
+    let execution_notifier =
+        ExecutionNotifier::new(DEFAULT_NOTIFIER_HISTORY_SIZE, last_sequenced_order + 1);
     let processor = PipelineProcessorActor::new(
         executor_proxy.clone(),
         sequencer_proxy.clone(),
         proposer_proxy.clone(),
         indexer_proxy.clone(),
+        execution_notifier.clone(),
         data_import_flag,
     )
     .into_actor(Some("PipelineProcessor"), &actor_system)
     .await?;
-    let processor_proxy = PipelineProcessorProxy::new(processor.into());
+    let processor_proxy = PipelineProcessorProxy::new(processor.clone().into());
+    if set_scc_proposer_flag {
+        if let Err(e) = set_scc_proposer(
+            &executor_proxy,
+            &processor_proxy,
+            &sequencer_keypair,
+            proposer_account,
+        )
+        .await
+        {
+            error!("Set the proposer of the on-chain SCC failed: {:?}", e);
+        }
+    }
+    // submit the proposed blocks to the on-chain SCC
+    let scc_submit_interval_in_seconds: u64 = block_propose_duration_in_seconds;
+    let scc_timer = Timer::start(
+        processor,
+        Duration::from_secs(scc_submit_interval_in_seconds),
+        SubmitBlockCommitments {},
+    );
+    timers.push(scc_timer);
 
-    let rpc_service = RpcService::new(
-        network.chain_id.id,
-        network.genesis_config.bitcoin_network,
+    // The txs submitted by sendRawTransaction are queued and executed in background
+    let tx_pool = TxPool::start(
         executor_proxy.clone(),
-        sequencer_proxy,
-        indexer_proxy,
         processor_proxy.clone(),
+        TxPoolConfig::default(),
     );
-    let aggregate_service = AggregateService::new(rpc_service.clone());
 
     let ethereum_relayer_config = opt.ethereum_relayer_config();
     let bitcoin_relayer_config = opt.bitcoin_relayer_config();
 
-    if ethereum_relayer_config.is_some() || bitcoin_relayer_config.is_some() {
+    // The mempool preview is read-only, it does not relay the pending transactions to the state
+    let bitcoin_mempool_proxy = match &bitcoin_relayer_config {
+        Some(bitcoin_config) if bitcoin_config.btc_mempool_preview => {
+            let bitcoin_client = BitcoinClientActor::new(bitcoin_config.clone())?
+                .into_actor(Some("BitcoinMempoolClient"), &actor_system)
+                .await?;
+            let bitcoin_mempool = BitcoinMempoolActor::new(
+                BitcoinClientProxy::new(bitcoin_client.into()),
+                executor_proxy.clone(),
+            )
+            .into_actor(Some("BitcoinMempool"), &actor_system)
+            .await?;
+            let mempool_poll_interval_in_seconds =
+                bitcoin_config.btc_mempool_poll_interval.unwrap_or(10u64);
+            let mempool_timer = Timer::start(
+                bitcoin_mempool.clone(),
+                Duration::from_secs(mempool_poll_interval_in_seconds),
+                MempoolTick {},
+            );
+            timers.push(mempool_timer);
+            info!("BitcoinMempool preview started");
+            Some(BitcoinMempoolProxy::new(bitcoin_mempool.into()))
+        }
+        _ => None,
+    };
+
+    let mut relayer_registry =
+        RelayerRegistry::from_config(ethereum_relayer_config, bitcoin_relayer_config);
+    if let Some(config) = opt.bitcoin_block_file_relayer_config() {
+        info!(
+            "Relay the Bitcoin blocks from the block files in {:?}",
+            config.btc_block_dir
+        );
+        relayer_registry.register(BitcoinBlockFileRelayerFactory { config });
+    }
+    let relayer_proxy = if !relayer_registry.is_empty() {
         let relayer = RelayerActor::new(
-            executor_proxy,
+            executor_proxy.clone(),
             processor_proxy.clone(),
-            ethereum_relayer_config,
-            bitcoin_relayer_config,
+            rooch_store,
+            relayer_registry,
         )
         .await?
         .into_actor(Some("Relayer"), &actor_system)
         .await?;
         let relay_tick_in_seconds: u64 = 1;
         let relayer_timer = Timer::start(
-            relayer,
+            relayer.clone(),
             Duration::from_secs(relay_tick_in_seconds),
             RelayTick {},
         );
         timers.push(relayer_timer);
-    }
+        Some(RelayerActorProxy::new(relayer))
+    } else {
+        None
+    };
+
+    let rpc_service = RpcService::new(
+        network.chain_id.id,
+        network.genesis_config.bitcoin_network,
+        executor_proxy,
+        sequencer_proxy,
+        indexer_proxy,
+        processor_proxy.clone(),
+        tx_pool,
+        bitcoin_mempool_proxy,
+        relayer_proxy,
+    );
+    let aggregate_service = AggregateService::new(rpc_service.clone());
 
     let acl = match env::var("ACCESS_CONTROL_ALLOW_ORIGIN") {
         Ok(value) => {
@@ -371,6 +475,10 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
         aggregate_service.clone(),
     ))?;
     rpc_module_builder.register_module(BtcServer::new(rpc_service.clone()).await?)?;
+    rpc_module_builder.register_module(RoochSubscriptionServer::new(
+        rpc_service.clone(),
+        execution_notifier,
+    ))?;
     rpc_module_builder
         .module
         .register_method("rpc.discover", move |_, _, _| {
@@ -383,7 +491,10 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
     let methods_names = rpc_module_builder.module.method_names().collect::<Vec<_>>();
     let handle = server.start(rpc_module_builder.module);
 
-    info!("JSON-RPC HTTP Server start listening {:?}", addr);
+    info!(
+        "JSON-RPC HTTP and WebSocket Server start listening {:?}",
+        addr
+    );
     info!("Available JSON-RPC methods : {:?}", methods_names);
 
     Ok(ServerHandle {
@@ -394,6 +505,48 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
     })
 }
 
+/// The on-chain SCC only accepts the block commitments from its proposer, which is the sequencer by default.
+/// If `--set-scc-proposer` is set and the on-chain proposer is not the proposer account, the sequencer sets it,
+/// nothing is submitted if it is already set.
+async fn set_scc_proposer(
+    executor_proxy: &ExecutorProxy,
+    processor_proxy: &PipelineProcessorProxy,
+    sequencer_keypair: &RoochKeyPair,
+    proposer_account: RoochAddress,
+) -> Result<()> {
+    let onchain_proposer = executor_proxy
+        .as_module_binding::<StateCommitmentChainModule>()
+        .proposer()?;
+    if onchain_proposer == AccountAddress::from(proposer_account) {
+        return Ok(());
+    }
+    let sequencer_account = sequencer_keypair.public().rooch_address()?;
+    let sequence_number = executor_proxy
+        .get_sequence_number(sequencer_account.into())
+        .await?;
+    let chain_id = executor_proxy.chain_id().await?.id();
+    let tx = RoochTransactionData::new(
+        sequencer_account,
+        sequence_number,
+        chain_id,
+        GasScheduleConfig::INITIAL_MAX_GAS_AMOUNT,
+        StateCommitmentChainModule::create_set_proposer_action(proposer_account.into()),
+    )
+    .sign(sequencer_keypair);
+    let response = processor_proxy.execute_l2_tx(tx).await?;
+    ensure!(
+        response.execution_info.status == KeptVMStatus::Executed,
+        "Set the SCC proposer to {:?} failed: {:?}",
+        proposer_account,
+        response.execution_info.status
+    );
+    info!(
+        "The proposer of the on-chain SCC is set to {:?}",
+        proposer_account
+    );
+    Ok(())
+}
+
 fn _build_rpc_api<M: Send + Sync + 'static>(mut rpc_module: RpcModule<M>) -> RpcModule<M> {
     let mut available_methods = rpc_module.method_names().collect::<Vec<_>>();
     available_methods.sort();
@@ -6,13 +6,22 @@ use anyhow::Result;
 use jsonrpsee::{core::async_trait, RpcModule};
 use rooch_rpc_api::api::btc_api::BtcAPIServer;
 use rooch_rpc_api::api::{RoochRpcModule, DEFAULT_RESULT_LIMIT_USIZE, MAX_RESULT_LIMIT_USIZE};
+use rooch_rpc_api::jsonrpc_types::btc::mempool::{PendingTxFilterView, PendingTxView};
 use rooch_rpc_api::jsonrpc_types::btc::ord::{InscriptionFilterView, InscriptionStateView};
+use rooch_rpc_api::jsonrpc_types::btc::runes::{
+    RuneBalancesFilterView, RuneBalancesStateView, RuneEntryStateView, RuneFilterView,
+};
+use rooch_rpc_api::jsonrpc_types::btc::sat::SatView;
 use rooch_rpc_api::jsonrpc_types::btc::utxo::{UTXOFilterView, UTXOStateView};
 use rooch_rpc_api::jsonrpc_types::{
-    IndexerStateIDView, InscriptionPageView, StrView, UTXOPageView,
+    IndexerStateIDView, InscriptionPageView, RuneBalancesPageView, RunePageView, StrView,
+    UTXOPageView,
 };
 use rooch_rpc_api::RpcResult;
+use rooch_types::bitcoin::runes::SpacedRune;
+use rooch_types::bitcoin::sat::Sat;
 use std::cmp::min;
+use std::str::FromStr;
 
 pub struct BtcServer {
     rpc_service: RpcService,
@@ -103,6 +112,13 @@ impl BtcAPIServer for BtcServer {
 
         let has_next_page = data.len() > limit_of;
         data.truncate(limit_of);
+        let sats = self
+            .rpc_service
+            .get_inscription_sats(data.iter().map(|state| state.metadata.id.clone()).collect())
+            .await?;
+        for (state, sat) in data.iter_mut().zip(sats) {
+            state.value.set_sat(sat);
+        }
         let next_cursor = data.last().cloned().map_or(cursor, |t| Some(t.indexer_id));
 
         Ok(InscriptionPageView {
@@ -111,6 +127,134 @@ impl BtcAPIServer for BtcServer {
             has_next_page,
         })
     }
+
+    async fn query_runes(
+        &self,
+        filter: RuneFilterView,
+        // exclusive cursor if `Some`, otherwise start from the beginning
+        cursor: Option<IndexerStateIDView>,
+        limit: Option<StrView<u64>>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<RunePageView> {
+        let limit_of = min(
+            limit.map(Into::into).unwrap_or(DEFAULT_RESULT_LIMIT_USIZE),
+            MAX_RESULT_LIMIT_USIZE,
+        );
+        let descending_order = descending_order.unwrap_or(true);
+
+        // The rune name is resolved to the RuneId via the RuneStore
+        let filter = match filter {
+            RuneFilterView::Rune(rune) => {
+                let spaced_rune = SpacedRune::from_str(rune.as_str())?;
+                match self.rpc_service.get_rune_id(&spaced_rune.rune).await? {
+                    Some(rune_id) => RuneFilterView::RuneId(rune_id.to_string()),
+                    None => {
+                        return Ok(RunePageView {
+                            data: vec![],
+                            next_cursor: cursor,
+                            has_next_page: false,
+                        })
+                    }
+                }
+            }
+            filter => filter,
+        };
+
+        let global_state_filter = RuneFilterView::into_global_state_filter(filter)?;
+        let object_states = self
+            .rpc_service
+            .query_object_states(
+                global_state_filter,
+                cursor.map(Into::into),
+                limit_of + 1,
+                descending_order,
+                false,
+                false,
+            )
+            .await?;
+
+        let mut data = object_states
+            .into_iter()
+            .map(RuneEntryStateView::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let has_next_page = data.len() > limit_of;
+        data.truncate(limit_of);
+        let next_cursor = data.last().cloned().map_or(cursor, |t| Some(t.indexer_id));
+
+        Ok(RunePageView {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    async fn query_rune_balances(
+        &self,
+        filter: RuneBalancesFilterView,
+        // exclusive cursor if `Some`, otherwise start from the beginning
+        cursor: Option<IndexerStateIDView>,
+        limit: Option<StrView<u64>>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<RuneBalancesPageView> {
+        let limit_of = min(
+            limit.map(Into::into).unwrap_or(DEFAULT_RESULT_LIMIT_USIZE),
+            MAX_RESULT_LIMIT_USIZE,
+        );
+        let descending_order = descending_order.unwrap_or(true);
+
+        let global_state_filter = RuneBalancesFilterView::into_global_state_filter(filter)?;
+        let object_states = self
+            .rpc_service
+            .query_object_states(
+                global_state_filter,
+                cursor.map(Into::into),
+                limit_of + 1,
+                descending_order,
+                false,
+                false,
+            )
+            .await?;
+
+        let mut data = object_states
+            .into_iter()
+            .map(RuneBalancesStateView::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let has_next_page = data.len() > limit_of;
+        data.truncate(limit_of);
+        let next_cursor = data.last().cloned().map_or(cursor, |t| Some(t.indexer_id));
+
+        Ok(RuneBalancesPageView {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    async fn get_sat(&self, sat: StrView<u64>) -> RpcResult<SatView> {
+        let sat = Sat::new(sat.0)?;
+        let location = self.rpc_service.get_sat_location(sat).await?;
+        Ok(SatView::new(sat, location))
+    }
+
+    async fn query_pending_txs(
+        &self,
+        filter: PendingTxFilterView,
+        limit: Option<StrView<u64>>,
+    ) -> RpcResult<Vec<PendingTxView>> {
+        let limit_of = min(
+            limit.map(Into::into).unwrap_or(DEFAULT_RESULT_LIMIT_USIZE),
+            MAX_RESULT_LIMIT_USIZE,
+        );
+        let filter = PendingTxFilterView::into_pending_tx_filter(filter)?;
+        let bitcoin_network = self.rpc_service.get_bitcoin_network();
+        let pending_txs = self.rpc_service.query_pending_txs(filter, limit_of).await?;
+        Ok(pending_txs
+            .into_iter()
+            .map(|tx| PendingTxView::new(tx, bitcoin_network))
+            .collect())
+    }
 }
 
 impl RoochRpcModule for BtcServer {
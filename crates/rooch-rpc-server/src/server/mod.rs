@@ -3,3 +3,4 @@
 
 pub mod btc_server;
 pub mod rooch_server;
+pub mod rooch_subscription_server;
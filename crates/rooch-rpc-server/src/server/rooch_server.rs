@@ -18,13 +18,13 @@ use moveos_types::{
 use rooch_rpc_api::jsonrpc_types::{
     account_view::BalanceInfoView,
     event_view::{EventFilterView, EventView, IndexerEventIDView, IndexerEventView},
-    transaction_view::{TransactionFilterView, TransactionWithInfoView},
-    AccessPathView, BalanceInfoPageView, EventOptions, EventPageView,
-    ExecuteTransactionResponseView, FunctionCallView, H256View, IndexerEventPageView,
-    IndexerObjectStatePageView, IndexerStateIDView, ModuleABIView, ObjectIDVecView,
-    ObjectStateFilterView, ObjectStateView, QueryOptions, RoochAddressView,
-    RoochOrBitcoinAddressView, StateKVView, StateOptions, StatePageView, StrView, StructTagView,
-    TransactionWithInfoPageView, TxOptions,
+    transaction_view::{TransactionFilterView, TransactionStatusView, TransactionWithInfoView},
+    AccessPathView, BalanceInfoPageView, DryRunTransactionResponseView, EventOptions,
+    EventPageView, ExecuteTransactionResponseView, FunctionCallView, H256View,
+    IndexerEventPageView, IndexerObjectStatePageView, IndexerStateIDView, ModuleABIView,
+    ObjectIDVecView, ObjectStateFilterView, ObjectStateView, QueryOptions, RelayerStatusView,
+    RoochAddressView, RoochOrBitcoinAddressView, StateKVView, StateOptions, StatePageView, StrView,
+    StructTagView, TransactionWithInfoPageView, TxOptions,
 };
 use rooch_rpc_api::{
     api::rooch_api::RoochAPIServer,
@@ -97,11 +97,10 @@ impl RoochAPIServer for RoochServer {
 
     async fn send_raw_transaction(&self, payload: BytesView) -> RpcResult<H256View> {
         info!("send_raw_transaction payload: {:?}", payload);
-        let mut tx = bcs::from_bytes::<RoochTransaction>(&payload.0)?;
+        let tx = bcs::from_bytes::<RoochTransaction>(&payload.0)?;
         info!("send_raw_transaction tx: {:?}", tx);
 
-        let hash = tx.tx_hash();
-        self.rpc_service.queue_tx(tx).await?;
+        let hash = self.rpc_service.queue_tx(tx).await?;
         Ok(hash.into())
     }
 
@@ -122,13 +121,26 @@ impl RoochAPIServer for RoochServer {
         Ok(result)
     }
 
+    async fn dry_run_raw_transaction(
+        &self,
+        payload: BytesView,
+    ) -> RpcResult<DryRunTransactionResponseView> {
+        let tx = bcs::from_bytes::<RoochTransaction>(&payload.0)?;
+        Ok(self.rpc_service.dry_run_tx(tx).await?.into())
+    }
+
     async fn execute_view_function(
         &self,
         function_call: FunctionCallView,
+        state_option: Option<StateOptions>,
     ) -> RpcResult<AnnotatedFunctionResultView> {
+        let root = self
+            .rpc_service
+            .resolve_root(&state_option.unwrap_or_default())
+            .await?;
         Ok(self
             .rpc_service
-            .execute_view_function(function_call.into())
+            .execute_view_function(root, function_call.into())
             .await?
             .into())
     }
@@ -141,11 +153,12 @@ impl RoochAPIServer for RoochServer {
         let state_option = state_option.unwrap_or_default();
         let show_display =
             state_option.show_display && (access_path.0.is_object() || access_path.0.is_resource());
+        let root = self.rpc_service.resolve_root(&state_option).await?;
 
         let state_views = if state_option.decode || show_display {
             let states = self
                 .rpc_service
-                .get_annotated_states(access_path.into())
+                .get_annotated_states_at(root, access_path.into())
                 .await?;
 
             if show_display {
@@ -176,7 +189,7 @@ impl RoochAPIServer for RoochServer {
             }
         } else {
             self.rpc_service
-                .get_states(access_path.into())
+                .get_states_at(root, access_path.into())
                 .await?
                 .into_iter()
                 .map(|s| s.map(ObjectStateView::from))
@@ -185,6 +198,23 @@ impl RoochAPIServer for RoochServer {
         Ok(state_views)
     }
 
+    async fn get_states_with_proof(
+        &self,
+        access_path: AccessPathView,
+        state_option: Option<StateOptions>,
+    ) -> RpcResult<StatesWithProofView> {
+        let state_option = state_option.unwrap_or_default();
+        let root = self.rpc_service.resolve_root(&state_option).await?;
+        let (state_root, states) = self
+            .rpc_service
+            .get_states_with_proof(root, access_path.into())
+            .await?;
+        Ok(StatesWithProofView {
+            state_root: state_root.into(),
+            states: states.into_iter().map(Into::into).collect(),
+        })
+    }
+
     async fn list_states(
         &self,
         access_path: AccessPathView,
@@ -195,6 +225,7 @@ impl RoochAPIServer for RoochServer {
         let state_option = state_option.unwrap_or_default();
         let show_display =
             state_option.show_display && (access_path.0.is_object() || access_path.0.is_resource());
+        let root = self.rpc_service.resolve_root(&state_option).await?;
 
         let limit_of = min(
             limit.map(Into::into).unwrap_or(DEFAULT_RESULT_LIMIT_USIZE),
@@ -207,7 +238,7 @@ impl RoochAPIServer for RoochServer {
         let mut data: Vec<StateKVView> = if state_option.decode || show_display {
             let (key_states, states): (Vec<FieldKey>, Vec<AnnotatedState>) = self
                 .rpc_service
-                .list_annotated_states(access_path.into(), cursor_of, limit_of + 1)
+                .list_annotated_states(root, access_path.into(), cursor_of, limit_of + 1)
                 .await?
                 .into_iter()
                 .unzip();
@@ -237,7 +268,7 @@ impl RoochAPIServer for RoochServer {
             }
         } else {
             self.rpc_service
-                .list_states(access_path.into(), cursor_of, limit_of + 1)
+                .list_states(root, access_path.into(), cursor_of, limit_of + 1)
                 .await?
                 .into_iter()
                 .map(|(key, state)| StateKVView::new(key.into(), state.into()))
@@ -267,10 +298,13 @@ impl RoochAPIServer for RoochServer {
         let state_option = state_option.unwrap_or_default();
         let decode = state_option.decode;
         let show_display = state_option.show_display;
+        let root = self.rpc_service.resolve_root(&state_option).await?;
 
         let mut objects_view = if decode || show_display {
-            let states: Vec<Option<AnnotatedState>> =
-                self.rpc_service.get_annotated_states(access_path).await?;
+            let states: Vec<Option<AnnotatedState>> = self
+                .rpc_service
+                .get_annotated_states_at(root, access_path)
+                .await?;
 
             let mut valid_display_field_views = if show_display {
                 let valid_states = states.iter().filter_map(|s| s.as_ref()).collect::<Vec<_>>();
@@ -308,7 +342,7 @@ impl RoochAPIServer for RoochServer {
             }
         } else {
             self.rpc_service
-                .get_states(access_path)
+                .get_states_at(root, access_path)
                 .await?
                 .into_iter()
                 .map(|s| s.map(Into::into))
@@ -502,14 +536,30 @@ impl RoochAPIServer for RoochServer {
         })
     }
 
+    async fn get_transaction_status(
+        &self,
+        tx_hash: H256View,
+    ) -> RpcResult<Option<TransactionStatusView>> {
+        Ok(self
+            .rpc_service
+            .get_transaction_status(tx_hash.into())
+            .await?
+            .map(Into::into))
+    }
+
     async fn get_balance(
         &self,
         account_addr: RoochOrBitcoinAddressView,
         coin_type: StructTagView,
+        state_option: Option<StateOptions>,
     ) -> RpcResult<BalanceInfoView> {
+        let root = self
+            .rpc_service
+            .resolve_root(&state_option.unwrap_or_default())
+            .await?;
         Ok(self
             .aggregate_service
-            .get_balance(account_addr.into(), coin_type.into())
+            .get_balance(root, account_addr.into(), coin_type.into())
             .await
             .map(Into::into)?)
     }
@@ -704,6 +754,15 @@ impl RoochAPIServer for RoochServer {
             has_next_page,
         })
     }
+
+    async fn get_relayer_status(&self) -> RpcResult<Vec<RelayerStatusView>> {
+        let status = self.rpc_service.get_relayer_status().await?;
+        Ok(status.into_iter().map(Into::into).collect())
+    }
+
+    async fn resume_relayer(&self, name: String) -> RpcResult<RelayerStatusView> {
+        Ok(self.rpc_service.resume_relayer(name).await?.into())
+    }
 }
 
 impl RoochRpcModule for RoochServer {
@@ -0,0 +1,304 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::service::rpc_service::RpcService;
+use anyhow::{anyhow, Result};
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::core::{async_trait, SubscriptionResult};
+use jsonrpsee::{PendingSubscriptionSink, RpcModule, SubscriptionMessage};
+use rooch_pipeline_processor::notifier::{ExecutedTransaction, ExecutionNotifier};
+use rooch_rpc_api::api::rooch_subscription_api::RoochSubscriptionAPIServer;
+use rooch_rpc_api::api::RoochRpcModule;
+use rooch_rpc_api::jsonrpc_types::event_view::{EventFilterView, IndexerEventView};
+use rooch_rpc_api::jsonrpc_types::transaction_view::TransactionWithInfoView;
+use rooch_rpc_api::jsonrpc_types::{
+    ObjectChangeFilterView, ObjectChangeNotificationView, ObjectChangeView, StrView,
+};
+use rooch_rpc_api::RpcError;
+use rooch_types::indexer::event::{EventFilter, IndexerEvent};
+use rooch_types::indexer::Filter;
+use rooch_types::subscription::ObjectChangeFilter;
+use rooch_types::transaction::TransactionWithInfo;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// The max number of the transactions read from the store at once when a subscriber resumes
+/// from a tx order before the in-memory window of the notifier
+const STORED_TXS_BATCH_SIZE: u64 = 100;
+/// The max number of the events queried from the indexer at once
+const STORED_EVENTS_QUERY_LIMIT: usize = 1000;
+
+/// Read the subscribed items of the transactions in the tx order range from the store
+type StoredItemsLoader<T> = Box<dyn FnMut(Range<u64>) -> BoxFuture<'static, Result<Vec<T>>> + Send>;
+
+pub struct RoochSubscriptionServer {
+    rpc_service: RpcService,
+    notifier: ExecutionNotifier,
+}
+
+impl RoochSubscriptionServer {
+    pub fn new(rpc_service: RpcService, notifier: ExecutionNotifier) -> Self {
+        Self {
+            rpc_service,
+            notifier,
+        }
+    }
+
+    /// Send the items of the executed transactions to the subscriber until the subscriber is closed.
+    /// The transactions before the in-memory window of the notifier are read from the store by the
+    /// `load_stored`, the subscription is rejected if the items can not be read from the store.
+    /// The send waits for the buffer of the connection, a slow subscriber does not block the pipeline,
+    /// it lags behind the notifier window and is closed with an error.
+    async fn pipe_to_sink<T, F, Fut>(
+        &self,
+        pending: PendingSubscriptionSink,
+        from_tx_order: Option<StrView<u64>>,
+        load_stored: Option<StoredItemsLoader<T>>,
+        mut to_items: F,
+    ) -> SubscriptionResult
+    where
+        T: Serialize,
+        F: FnMut(Arc<ExecutedTransaction>) -> Fut,
+        Fut: Future<Output = Result<Vec<T>>>,
+    {
+        let mut subscription = self.notifier.subscribe(from_tx_order.map(|order| order.0));
+        let stored = match (subscription.take_stored_range(), load_stored) {
+            (Some(stored_range), Some(load_stored)) => Some((stored_range, load_stored)),
+            (Some(stored_range), None) => {
+                let error = anyhow!(
+                    "The tx order {} is out of the resumable window, the oldest tx order is {}",
+                    stored_range.start,
+                    stored_range.end
+                );
+                pending.reject(RpcError::from(error)).await;
+                return Ok(());
+            }
+            (None, _) => None,
+        };
+        let sink = pending.accept().await?;
+        if let Some((stored_range, mut load_stored)) = stored {
+            let mut from = stored_range.start;
+            while from < stored_range.end {
+                let to = (from + STORED_TXS_BATCH_SIZE).min(stored_range.end);
+                let items = tokio::select! {
+                    _ = sink.closed() => return Ok(()),
+                    items = load_stored(from..to) => items?,
+                };
+                for item in items {
+                    let message = SubscriptionMessage::from_json(&item)?;
+                    if sink.send(message).await.is_err() {
+                        return Ok(());
+                    }
+                }
+                from = to;
+            }
+        }
+        loop {
+            let tx = tokio::select! {
+                _ = sink.closed() => return Ok(()),
+                tx = subscription.next() => tx?,
+            };
+            let tx = match tx {
+                Some(tx) => tx,
+                None => return Ok(()),
+            };
+            for item in to_items(tx).await? {
+                let message = SubscriptionMessage::from_json(&item)?;
+                if sink.send(message).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+async fn transaction_views(
+    rpc_service: &RpcService,
+    txs: Vec<TransactionWithInfo>,
+) -> Result<Vec<TransactionWithInfoView>> {
+    let bitcoin_network = rpc_service.get_bitcoin_network();
+    let senders = txs
+        .iter()
+        .filter_map(|tx| tx.transaction.sender())
+        .collect::<Vec<_>>();
+    let bitcoin_addresses = if senders.is_empty() {
+        HashMap::new()
+    } else {
+        rpc_service.get_bitcoin_addresses(senders).await?
+    };
+    txs.into_iter()
+        .map(|tx| -> Result<TransactionWithInfoView> {
+            let sender_bitcoin_address = match tx.transaction.sender() {
+                Some(sender) => bitcoin_addresses
+                    .get(&sender)
+                    .cloned()
+                    .flatten()
+                    .map(|address| address.format(bitcoin_network))
+                    .transpose()?,
+                None => None,
+            };
+            Ok(TransactionWithInfoView::new_from_transaction_with_info(
+                tx,
+                sender_bitcoin_address,
+            ))
+        })
+        .collect()
+}
+
+async fn load_stored_transactions(
+    rpc_service: RpcService,
+    tx_orders: Range<u64>,
+) -> Result<Vec<TransactionWithInfoView>> {
+    let tx_hashes = rpc_service
+        .get_tx_hashs(tx_orders.clone().collect())
+        .await?
+        .into_iter()
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| {
+            anyhow!(
+                "The transactions from tx order {} to {} are not found in the store",
+                tx_orders.start,
+                tx_orders.end
+            )
+        })?;
+    let txs = rpc_service
+        .get_transactions_by_hash(tx_hashes.clone())
+        .await?;
+    let execution_infos = rpc_service
+        .get_transaction_execution_infos_by_hash(tx_hashes)
+        .await?;
+    let txs = txs
+        .into_iter()
+        .zip(execution_infos)
+        .zip(tx_orders.clone())
+        .map(|((tx, execution_info), tx_order)| {
+            let transaction =
+                tx.ok_or_else(|| anyhow!("The transaction of tx order {} is not found", tx_order))?;
+            Ok(TransactionWithInfo {
+                transaction,
+                execution_info,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    transaction_views(&rpc_service, txs).await
+}
+
+async fn load_stored_events(
+    rpc_service: RpcService,
+    filter: EventFilter,
+    tx_orders: Range<u64>,
+) -> Result<Vec<IndexerEventView>> {
+    // The indexer is updated asynchronously, wait for the executed transactions to be written
+    rpc_service.flush_indexer().await?;
+    let tx_order_filter = EventFilter::TxOrderRange {
+        from_order: tx_orders.start,
+        to_order: tx_orders.end,
+    };
+    let mut events = vec![];
+    let mut cursor = None;
+    loop {
+        let page = rpc_service
+            .query_events(
+                tx_order_filter.clone(),
+                cursor,
+                STORED_EVENTS_QUERY_LIMIT,
+                false,
+            )
+            .await?;
+        let has_next_page = page.len() == STORED_EVENTS_QUERY_LIMIT;
+        cursor = page.last().map(|event| event.indexer_event_id);
+        events.extend(
+            page.into_iter()
+                .filter(|event| filter.matches(event))
+                .map(IndexerEventView::from),
+        );
+        if !has_next_page {
+            return Ok(events);
+        }
+    }
+}
+
+#[async_trait]
+impl RoochSubscriptionAPIServer for RoochSubscriptionServer {
+    async fn subscribe_transactions(
+        &self,
+        pending: PendingSubscriptionSink,
+        from_tx_order: Option<StrView<u64>>,
+    ) -> SubscriptionResult {
+        let rpc_service = self.rpc_service.clone();
+        let stored_rpc_service = self.rpc_service.clone();
+        let load_stored: StoredItemsLoader<TransactionWithInfoView> = Box::new(move |tx_orders| {
+            load_stored_transactions(stored_rpc_service.clone(), tx_orders).boxed()
+        });
+        self.pipe_to_sink(pending, from_tx_order, Some(load_stored), move |tx| {
+            let rpc_service = rpc_service.clone();
+            async move {
+                let tx_with_info = TransactionWithInfo {
+                    transaction: tx.tx.clone(),
+                    execution_info: Some(tx.execution_info.clone()),
+                };
+                transaction_views(&rpc_service, vec![tx_with_info]).await
+            }
+        })
+        .await
+    }
+
+    async fn subscribe_events(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: EventFilterView,
+        from_tx_order: Option<StrView<u64>>,
+    ) -> SubscriptionResult {
+        let filter = EventFilter::from(filter);
+        let stored_rpc_service = self.rpc_service.clone();
+        let stored_filter = filter.clone();
+        let load_stored: StoredItemsLoader<IndexerEventView> = Box::new(move |tx_orders| {
+            load_stored_events(stored_rpc_service.clone(), stored_filter.clone(), tx_orders).boxed()
+        });
+        self.pipe_to_sink(pending, from_tx_order, Some(load_stored), move |tx| {
+            let events = tx
+                .output
+                .events
+                .iter()
+                .map(|event| IndexerEvent::new(event.clone(), tx.tx.clone(), tx.ctx.clone()))
+                .filter(|event| filter.matches(event))
+                .map(IndexerEventView::from)
+                .collect::<Vec<_>>();
+            async move { Ok(events) }
+        })
+        .await
+    }
+
+    async fn subscribe_object_changes(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: ObjectChangeFilterView,
+        from_tx_order: Option<StrView<u64>>,
+    ) -> SubscriptionResult {
+        let filter = ObjectChangeFilter::from(filter);
+        // The change sets are not kept in the store, so the object changes can only be resumed in the window
+        self.pipe_to_sink(pending, from_tx_order, None, move |tx| {
+            let tx_hash = tx.execution_info.tx_hash;
+            let changes = filter
+                .filter_changes(&tx.output.changeset)
+                .into_iter()
+                .map(|change| ObjectChangeNotificationView {
+                    tx_order: tx.tx_order().into(),
+                    tx_hash: tx_hash.into(),
+                    change: ObjectChangeView::from(change),
+                })
+                .collect::<Vec<_>>();
+            async move { Ok(changes) }
+        })
+        .await
+    }
+}
+
+impl RoochRpcModule for RoochSubscriptionServer {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+}
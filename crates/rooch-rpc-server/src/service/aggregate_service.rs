@@ -6,7 +6,7 @@ use anyhow::Result;
 use move_core_types::language_storage::StructTag;
 use moveos_types::access_path::AccessPath;
 use moveos_types::h256::H256;
-use moveos_types::moveos_std::object::ObjectID;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
 use moveos_types::state::PlaceholderStruct;
 use rooch_rpc_api::jsonrpc_types::account_view::BalanceInfoView;
 use rooch_rpc_api::jsonrpc_types::CoinInfoView;
@@ -34,6 +34,7 @@ impl AggregateService {
 impl AggregateService {
     pub async fn get_coin_infos(
         &self,
+        root: Option<ObjectMeta>,
         coin_types: Vec<StructTag>,
     ) -> Result<HashMap<StructTag, Option<CoinInfoView>>> {
         let access_path = AccessPath::objects(
@@ -44,7 +45,7 @@ impl AggregateService {
                 .collect(),
         );
         self.rpc_service
-            .get_states(access_path)
+            .get_states_at(root, access_path)
             .await?
             .into_iter()
             .zip(coin_types)
@@ -67,24 +68,27 @@ impl AggregateService {
 
     pub async fn get_coin_stores(
         &self,
+        root: Option<ObjectMeta>,
         coin_store_ids: Vec<ObjectID>,
     ) -> Result<Vec<Option<CoinStoreInfo>>> {
         let access_path = AccessPath::objects(coin_store_ids);
         self.rpc_service
-            .get_states(access_path)
+            .get_states_at(root, access_path)
             .await?
             .into_iter()
             .map(|state_opt| state_opt.map(CoinStoreInfo::try_from).transpose())
             .collect::<Result<Vec<_>>>()
     }
 
+    /// Get the balance at the given root object, or the latest root object if None
     pub async fn get_balance(
         &self,
+        root: Option<ObjectMeta>,
         account_addr: RoochAddress,
         coin_type: StructTag,
     ) -> Result<BalanceInfoView> {
         let coin_info = self
-            .get_coin_infos(vec![coin_type.clone()])
+            .get_coin_infos(root.clone(), vec![coin_type.clone()])
             .await?
             .into_values()
             .flatten()
@@ -96,7 +100,7 @@ impl AggregateService {
         let coin_store_id =
             AccountCoinStoreModule::account_coin_store_id(account_addr.into(), coin_type);
         let balance = self
-            .get_coin_stores(vec![coin_store_id])
+            .get_coin_stores(root, vec![coin_store_id])
             .await?
             .pop()
             .flatten()
@@ -138,7 +142,7 @@ impl AggregateService {
             .map(|m| m.metadata.id.clone())
             .collect::<Vec<_>>();
 
-        let coin_stores = self.get_coin_stores(coin_store_ids.clone()).await?;
+        let coin_stores = self.get_coin_stores(None, coin_store_ids.clone()).await?;
 
         let coin_types = coin_stores
             .iter()
@@ -146,7 +150,7 @@ impl AggregateService {
             .map(|coin_store| coin_store.coin_type())
             .collect::<Vec<_>>();
 
-        let coin_info_map = self.get_coin_infos(coin_types).await?;
+        let coin_info_map = self.get_coin_infos(None, coin_types).await?;
 
         let mut result = vec![];
         for (indexer_coin_store, coin_store) in indexer_coin_stores.into_iter().zip(coin_stores) {
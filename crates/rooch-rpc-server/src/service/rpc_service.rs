@@ -9,21 +9,36 @@ use moveos_types::function_return_value::AnnotatedFunctionResult;
 use moveos_types::h256::H256;
 use moveos_types::moveos_std::display::{get_object_display_id, RawDisplay};
 use moveos_types::moveos_std::event::{AnnotatedEvent, Event, EventID};
-use moveos_types::moveos_std::object::ObjectID;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
 use moveos_types::state::{AnnotatedState, FieldKey, ObjectState};
+use moveos_types::state_proof::StateWithProof;
 use moveos_types::state_resolver::{AnnotatedStateKV, StateKV};
-use moveos_types::transaction::{FunctionCall, TransactionExecutionInfo};
+use moveos_types::transaction::{FunctionCall, RawTransactionOutput, TransactionExecutionInfo};
 use rooch_executor::proxy::ExecutorProxy;
 use rooch_indexer::proxy::IndexerProxy;
 use rooch_pipeline_processor::proxy::PipelineProcessorProxy;
-use rooch_rpc_api::jsonrpc_types::{DisplayFieldsView, IndexerObjectStateView, ObjectMetaView};
+use rooch_pipeline_processor::tx_pool::TxPool;
+use rooch_relayer::actor::bitcoin_mempool_proxy::BitcoinMempoolProxy;
+use rooch_relayer::actor::relayer_actor_proxy::RelayerActorProxy;
+use rooch_rpc_api::jsonrpc_types::{
+    DisplayFieldsView, IndexerObjectStateView, ObjectMetaView, StateOptions,
+};
 use rooch_sequencer::proxy::SequencerProxy;
 use rooch_types::address::{BitcoinAddress, RoochAddress};
+use rooch_types::bitcoin::mempool::{PendingTx, PendingTxFilter};
+use rooch_types::bitcoin::ord::INSCRIPTION_SAT_FIELD;
+use rooch_types::bitcoin::runes::{Rune, RuneId, RuneStore};
+use rooch_types::bitcoin::sat::{
+    height_starting_sat, Sat, SatLocation, SatRangeLocation, SatStore,
+};
 use rooch_types::framework::address_mapping::RoochToBitcoinAddressMapping;
 use rooch_types::indexer::event::{EventFilter, IndexerEvent, IndexerEventID};
 use rooch_types::indexer::state::{IndexerStateID, ObjectStateFilter};
 use rooch_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
-use rooch_types::transaction::{ExecuteTransactionResponse, LedgerTransaction, RoochTransaction};
+use rooch_types::relayer::RelayerStatus;
+use rooch_types::transaction::{
+    ExecuteTransactionResponse, LedgerTransaction, RoochTransaction, TransactionStatus,
+};
 use std::collections::{BTreeMap, HashMap};
 
 /// RpcService is the implementation of the RPC service.
@@ -37,6 +52,9 @@ pub struct RpcService {
     pub(crate) sequencer: SequencerProxy,
     pub(crate) indexer: IndexerProxy,
     pub(crate) pipeline_processor: PipelineProcessorProxy,
+    pub(crate) tx_pool: TxPool,
+    pub(crate) bitcoin_mempool: Option<BitcoinMempoolProxy>,
+    pub(crate) relayer: Option<RelayerActorProxy>,
 }
 
 impl RpcService {
@@ -47,6 +65,9 @@ impl RpcService {
         sequencer: SequencerProxy,
         indexer: IndexerProxy,
         pipeline_processor: PipelineProcessorProxy,
+        tx_pool: TxPool,
+        bitcoin_mempool: Option<BitcoinMempoolProxy>,
+        relayer: Option<RelayerActorProxy>,
     ) -> Self {
         Self {
             chain_id,
@@ -55,6 +76,9 @@ impl RpcService {
             sequencer,
             indexer,
             pipeline_processor,
+            tx_pool,
+            bitcoin_mempool,
+            relayer,
         }
     }
 }
@@ -68,26 +92,63 @@ impl RpcService {
         self.bitcoin_network
     }
 
-    pub async fn queue_tx(&self, tx: RoochTransaction) -> Result<()> {
-        //TODO implement queue tx and do not wait to execute
-        let _ = self.execute_tx(tx).await?;
-        Ok(())
+    /// Queue the transaction in the tx pool, return the tx hash without waiting for the execution.
+    pub async fn queue_tx(&self, tx: RoochTransaction) -> Result<H256> {
+        self.tx_pool.submit(tx).await
+    }
+
+    /// Get the status of the transaction in the tx pool,
+    /// the transaction executed before the tx pool keeps its status is looked up in the sequencer.
+    pub async fn get_transaction_status(&self, tx_hash: H256) -> Result<Option<TransactionStatus>> {
+        if let Some(status) = self.tx_pool.get_status(&tx_hash) {
+            return Ok(Some(status));
+        }
+        Ok(self
+            .get_transaction_by_hash(tx_hash)
+            .await?
+            .map(|ledger_tx| TransactionStatus::Executed {
+                tx_order: ledger_tx.sequence_info.tx_order,
+            }))
     }
 
     pub async fn execute_tx(&self, tx: RoochTransaction) -> Result<ExecuteTransactionResponse> {
         self.pipeline_processor.execute_l2_tx(tx).await
     }
 
+    pub async fn dry_run_tx(&self, tx: RoochTransaction) -> Result<RawTransactionOutput> {
+        self.executor.dry_run_transaction(tx).await
+    }
+
+    /// Resolve the root object of a historical state by the `state_root` or the `tx_order` of the StateOptions,
+    /// return None to read the latest state if both of them are None.
+    pub async fn resolve_root(&self, state_option: &StateOptions) -> Result<Option<ObjectMeta>> {
+        let state_root = state_option.state_root.map(|state_root| state_root.0);
+        let tx_order = state_option.tx_order.map(|tx_order| tx_order.0);
+        match (state_root, tx_order) {
+            (Some(_), Some(_)) => Err(anyhow::anyhow!(
+                "Only one of state_root and tx_order can be specified"
+            )),
+            // The size of the root object can not be derived from the state root, so it is zero here.
+            (Some(state_root), None) => Ok(Some(ObjectMeta::root_metadata(state_root, 0))),
+            (None, Some(tx_order)) => Ok(Some(self.executor.get_root_by_tx_order(tx_order).await?)),
+            (None, None) => Ok(None),
+        }
+    }
+
     pub async fn execute_view_function(
         &self,
+        root: Option<ObjectMeta>,
         function_call: FunctionCall,
     ) -> Result<AnnotatedFunctionResult> {
         let module_id = function_call.function_id.module_id.clone();
-        if !self.exists_module(module_id.clone()).await? {
+        if !self.exists_module(root.clone(), module_id.clone()).await? {
             return Err(anyhow::anyhow!("Module does not exist: {}", module_id));
         }
 
-        let resp = self.executor.execute_view_function(function_call).await?;
+        let resp = self
+            .executor
+            .execute_view_function_at(root, function_call)
+            .await?;
         Ok(resp)
     }
 
@@ -95,11 +156,35 @@ impl RpcService {
         self.executor.get_states(access_path).await
     }
 
-    pub async fn exists_module(&self, module_id: ModuleId) -> Result<bool> {
-        let mut resp = self.get_states(AccessPath::module(&module_id)).await?;
+    /// Get the states at the given root object, or the latest root object if None
+    pub async fn get_states_at(
+        &self,
+        root: Option<ObjectMeta>,
+        access_path: AccessPath,
+    ) -> Result<Vec<Option<ObjectState>>> {
+        self.executor.get_states_at(root, access_path).await
+    }
+
+    pub async fn exists_module(
+        &self,
+        root: Option<ObjectMeta>,
+        module_id: ModuleId,
+    ) -> Result<bool> {
+        let mut resp = self
+            .get_states_at(root, AccessPath::module(&module_id))
+            .await?;
         Ok(resp.pop().flatten().is_some())
     }
 
+    /// Get the states with the proofs at the given root object, or the latest root object if None
+    pub async fn get_states_with_proof(
+        &self,
+        root: Option<ObjectMeta>,
+        access_path: AccessPath,
+    ) -> Result<(H256, Vec<StateWithProof>)> {
+        self.executor.get_states_with_proof(root, access_path).await
+    }
+
     pub async fn get_annotated_states(
         &self,
         access_path: AccessPath,
@@ -107,23 +192,37 @@ impl RpcService {
         self.executor.get_annotated_states(access_path).await
     }
 
+    pub async fn get_annotated_states_at(
+        &self,
+        root: Option<ObjectMeta>,
+        access_path: AccessPath,
+    ) -> Result<Vec<Option<AnnotatedState>>> {
+        self.executor
+            .get_annotated_states_at(root, access_path)
+            .await
+    }
+
     pub async fn list_states(
         &self,
+        root: Option<ObjectMeta>,
         access_path: AccessPath,
         cursor: Option<FieldKey>,
         limit: usize,
     ) -> Result<Vec<StateKV>> {
-        self.executor.list_states(access_path, cursor, limit).await
+        self.executor
+            .list_states_at(root, access_path, cursor, limit)
+            .await
     }
 
     pub async fn list_annotated_states(
         &self,
+        root: Option<ObjectMeta>,
         access_path: AccessPath,
         cursor: Option<FieldKey>,
         limit: usize,
     ) -> Result<Vec<AnnotatedStateKV>> {
         self.executor
-            .list_annotated_states(access_path, cursor, limit)
+            .list_annotated_states_at(root, access_path, cursor, limit)
             .await
     }
 
@@ -234,6 +333,11 @@ impl RpcService {
         Ok(resp)
     }
 
+    /// Wait until the indexer writes the executed transactions sent to it before.
+    pub async fn flush_indexer(&self) -> Result<()> {
+        self.indexer.flush().await
+    }
+
     pub async fn query_object_states(
         &self,
         filter: ObjectStateFilter,
@@ -376,6 +480,99 @@ impl RpcService {
         Ok(address_mapping)
     }
 
+    /// Get the RuneId by the rune name from the RuneStore fields
+    pub async fn get_rune_id(&self, rune: &Rune) -> Result<Option<RuneId>> {
+        let access_path =
+            AccessPath::fields(RuneStore::object_id(), vec![FieldKey::derive(&rune.0)?]);
+        let rune_id = self
+            .get_states(access_path)
+            .await?
+            .pop()
+            .flatten()
+            .map(|state| state.value_as_df::<u128, RuneId>().map(|df| df.value))
+            .transpose()?;
+        Ok(rune_id)
+    }
+
+    /// Get the location of the sat via the SatStore index, return none if the sat is lost or not tracked.
+    /// The indexed sat ranges of a block are contiguous from the first sat of the block,
+    /// so walk the ranges from the first sat of the block to the range containing the sat, as `sat::get_sat_location` does.
+    pub async fn get_sat_location(&self, sat: Sat) -> Result<Option<SatLocation>> {
+        let mut start = height_starting_sat(sat.height());
+        loop {
+            let access_path =
+                AccessPath::fields(SatStore::object_id(), vec![FieldKey::derive(&start)?]);
+            let range_location = match self.get_states(access_path).await?.pop().flatten() {
+                Some(state) => state.value_as_df::<u64, SatRangeLocation>()?.value,
+                None => return Ok(None),
+            };
+            if sat.0 < range_location.end {
+                let location: Option<SatLocation> = range_location.location.into();
+                return Ok(location.map(|mut location| {
+                    location.offset += sat.0 - start;
+                    location
+                }));
+            }
+            start = range_location.end;
+        }
+    }
+
+    /// Get the sats of the inscriptions in one batch, the sat is none if it is not tracked
+    pub async fn get_inscription_sats(
+        &self,
+        inscription_object_ids: Vec<ObjectID>,
+    ) -> Result<Vec<Option<u64>>> {
+        if inscription_object_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let field_key = FieldKey::derive(&INSCRIPTION_SAT_FIELD.to_vec())?;
+        let access_path = AccessPath::objects(
+            inscription_object_ids
+                .into_iter()
+                .map(|object_id| object_id.child_id(field_key))
+                .collect(),
+        );
+        self.get_states(access_path)
+            .await?
+            .into_iter()
+            .map(|state_opt| {
+                state_opt
+                    .map(|state| state.value_as_df::<Vec<u8>, u64>().map(|df| df.value))
+                    .transpose()
+            })
+            .collect()
+    }
+
+    /// Query the preview of the pending transactions in the Bitcoin mempool
+    pub async fn query_pending_txs(
+        &self,
+        filter: PendingTxFilter,
+        limit: usize,
+    ) -> Result<Vec<PendingTx>> {
+        match &self.bitcoin_mempool {
+            Some(bitcoin_mempool) => bitcoin_mempool.query_pending_txs(filter, limit).await,
+            None => Err(anyhow::anyhow!(
+                "The Bitcoin mempool preview is not enabled, start the server with --btc-mempool-preview"
+            )),
+        }
+    }
+
+    /// Get the status of the L1 relayers, empty if the relayer is not started
+    pub async fn get_relayer_status(&self) -> Result<Vec<RelayerStatus>> {
+        match &self.relayer {
+            Some(relayer) => relayer.get_relayer_status().await,
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Resume the relayer paused by the failure policy
+    pub async fn resume_relayer(&self, name: String) -> Result<RelayerStatus> {
+        match &self.relayer {
+            Some(relayer) => relayer.resume_relayer(name).await,
+            None => Err(anyhow::anyhow!("The relayer is not started")),
+        }
+    }
+
     pub async fn get_display_fields_and_render(
         &self,
         states: &[&AnnotatedState],
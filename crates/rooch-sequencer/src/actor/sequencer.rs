@@ -4,8 +4,9 @@
 use std::time::SystemTime;
 
 use crate::messages::{
-    GetSequencerOrderMessage, GetTransactionByHashMessage, GetTransactionsByHashMessage,
-    GetTxHashsMessage, TransactionSequenceMessage,
+    GetL1BlockBodyMessage, GetSequencerOrderMessage, GetTransactionByHashMessage,
+    GetTransactionsByHashMessage, GetTxHashsMessage, L1BlockSequenceMessage,
+    RemoveL1BlockBodyMessage, TransactionSequenceMessage,
 };
 use accumulator::{Accumulator, MerkleAccumulator};
 use anyhow::Result;
@@ -17,7 +18,7 @@ use rooch_store::transaction_store::TransactionStore;
 use rooch_store::RoochStore;
 use rooch_types::crypto::{RoochKeyPair, Signature};
 use rooch_types::sequencer::SequencerInfo;
-use rooch_types::transaction::{LedgerTransaction, LedgerTxData};
+use rooch_types::transaction::{L1BlockWithBody, LedgerTransaction, LedgerTxData};
 use tracing::info;
 
 pub struct SequencerActor {
@@ -95,6 +96,13 @@ impl SequencerActor {
         self.last_sequencer_info = sequencer_info;
         Ok(tx)
     }
+
+    /// Save the block body before sequencing, so the sequenced but not executed block can be re-executed on startup.
+    pub fn sequence_l1_block(&mut self, block: L1BlockWithBody) -> Result<LedgerTransaction> {
+        let l1_block = block.block.clone();
+        self.rooch_store.save_l1_block_body(block)?;
+        self.sequence(LedgerTxData::L1Block(l1_block))
+    }
 }
 
 impl Actor for SequencerActor {}
@@ -110,6 +118,17 @@ impl Handler<TransactionSequenceMessage> for SequencerActor {
     }
 }
 
+#[async_trait]
+impl Handler<L1BlockSequenceMessage> for SequencerActor {
+    async fn handle(
+        &mut self,
+        msg: L1BlockSequenceMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<LedgerTransaction> {
+        self.sequence_l1_block(msg.block)
+    }
+}
+
 #[async_trait]
 impl Handler<GetTransactionByHashMessage> for SequencerActor {
     async fn handle(
@@ -154,3 +173,25 @@ impl Handler<GetSequencerOrderMessage> for SequencerActor {
         Ok(self.last_sequencer_info.last_order)
     }
 }
+
+#[async_trait]
+impl Handler<GetL1BlockBodyMessage> for SequencerActor {
+    async fn handle(
+        &mut self,
+        msg: GetL1BlockBodyMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<L1BlockWithBody>> {
+        self.rooch_store.get_l1_block_body(msg.tx_hash)
+    }
+}
+
+#[async_trait]
+impl Handler<RemoveL1BlockBodyMessage> for SequencerActor {
+    async fn handle(
+        &mut self,
+        msg: RemoveL1BlockBodyMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<()> {
+        self.rooch_store.remove_l1_block_body(msg.tx_hash)
+    }
+}
@@ -4,7 +4,7 @@
 use anyhow::Result;
 use coerce::actor::message::Message;
 use moveos_types::h256::H256;
-use rooch_types::transaction::{LedgerTransaction, LedgerTxData};
+use rooch_types::transaction::{L1BlockWithBody, LedgerTransaction, LedgerTxData};
 use serde::{Deserialize, Serialize};
 
 /// Transaction Sequence Message
@@ -17,6 +17,16 @@ impl Message for TransactionSequenceMessage {
     type Result = Result<LedgerTransaction>;
 }
 
+/// L1 Block Sequence Message, the block body is saved before the block is sequenced
+#[derive(Debug, Serialize, Deserialize)]
+pub struct L1BlockSequenceMessage {
+    pub block: L1BlockWithBody,
+}
+
+impl Message for L1BlockSequenceMessage {
+    type Result = Result<LedgerTransaction>;
+}
+
 /// Get Transaction By Hash Message
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GetTransactionByHashMessage {
@@ -52,3 +62,23 @@ pub struct GetSequencerOrderMessage {}
 impl Message for GetSequencerOrderMessage {
     type Result = Result<u64>;
 }
+
+/// Get the L1 block with body by the hash of the L1Block ledger transaction
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetL1BlockBodyMessage {
+    pub tx_hash: H256,
+}
+
+impl Message for GetL1BlockBodyMessage {
+    type Result = Result<Option<L1BlockWithBody>>;
+}
+
+/// Remove the L1 block body by the hash of the L1Block ledger transaction, after the block is executed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoveL1BlockBodyMessage {
+    pub tx_hash: H256,
+}
+
+impl Message for RemoveL1BlockBodyMessage {
+    type Result = Result<()>;
+}
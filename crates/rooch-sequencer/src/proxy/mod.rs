@@ -2,14 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::messages::{
-    GetSequencerOrderMessage, GetTransactionByHashMessage, GetTransactionsByHashMessage,
-    GetTxHashsMessage,
+    GetL1BlockBodyMessage, GetSequencerOrderMessage, GetTransactionByHashMessage,
+    GetTransactionsByHashMessage, GetTxHashsMessage, L1BlockSequenceMessage,
+    RemoveL1BlockBodyMessage,
 };
 use crate::{actor::sequencer::SequencerActor, messages::TransactionSequenceMessage};
 use anyhow::Result;
 use coerce::actor::ActorRef;
 use moveos_types::h256::H256;
-use rooch_types::transaction::{LedgerTransaction, LedgerTxData};
+use rooch_types::transaction::{L1BlockWithBody, LedgerTransaction, LedgerTxData};
 
 #[derive(Clone)]
 pub struct SequencerProxy {
@@ -25,6 +26,10 @@ impl SequencerProxy {
         self.actor.send(TransactionSequenceMessage { tx }).await?
     }
 
+    pub async fn sequence_l1_block(&self, block: L1BlockWithBody) -> Result<LedgerTransaction> {
+        self.actor.send(L1BlockSequenceMessage { block }).await?
+    }
+
     pub async fn get_transaction_by_hash(&self, hash: H256) -> Result<Option<LedgerTransaction>> {
         self.actor
             .send(GetTransactionByHashMessage { hash })
@@ -47,4 +52,14 @@ impl SequencerProxy {
     pub async fn get_sequencer_order(&self) -> Result<u64> {
         self.actor.send(GetSequencerOrderMessage {}).await?
     }
+
+    pub async fn get_l1_block_body(&self, tx_hash: H256) -> Result<Option<L1BlockWithBody>> {
+        self.actor.send(GetL1BlockBodyMessage { tx_hash }).await?
+    }
+
+    pub async fn remove_l1_block_body(&self, tx_hash: H256) -> Result<()> {
+        self.actor
+            .send(RemoveL1BlockBodyMessage { tx_hash })
+            .await?
+    }
 }
@@ -0,0 +1,168 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    DA_BACKEND_CONFIRMED_COLUMN_FAMILY_NAME, DA_BATCH_HASH_COLUMN_FAMILY_NAME,
+    DA_BLOCK_CHUNK_COLUMN_FAMILY_NAME, DA_CHUNK_INDEX_COLUMN_FAMILY_NAME,
+    DA_PENDING_BATCH_COLUMN_FAMILY_NAME,
+};
+use anyhow::Result;
+use moveos_types::h256::H256;
+use raw_store::{derive_store, CodecKVStore, StoreInstance};
+use rooch_types::da::{DAChunkIndex, PendingDABatch};
+
+derive_store!(
+    PendingDABatchStore,
+    u128,
+    PendingDABatch,
+    DA_PENDING_BATCH_COLUMN_FAMILY_NAME
+);
+
+derive_store!(
+    DABackendConfirmedStore,
+    String,
+    u128,
+    DA_BACKEND_CONFIRMED_COLUMN_FAMILY_NAME
+);
+
+// (backend, chunk_id) -> chunk index
+derive_store!(
+    DAChunkIndexStore,
+    (String, u128),
+    DAChunkIndex,
+    DA_CHUNK_INDEX_COLUMN_FAMILY_NAME
+);
+
+// (backend, block_number) -> chunk_id
+derive_store!(
+    DABlockChunkStore,
+    (String, u128),
+    u128,
+    DA_BLOCK_CHUNK_COLUMN_FAMILY_NAME
+);
+
+// batch_hash -> block_number
+derive_store!(
+    DABatchHashStore,
+    H256,
+    u128,
+    DA_BATCH_HASH_COLUMN_FAMILY_NAME
+);
+
+pub trait DAStore {
+    fn save_pending_da_batch(&self, batch: PendingDABatch) -> Result<()>;
+
+    fn get_pending_da_batches(&self) -> Result<Vec<PendingDABatch>>;
+
+    fn remove_pending_da_batches(&self, block_numbers: Vec<u128>) -> Result<()>;
+
+    fn get_da_backend_confirmed(&self, backend: String) -> Result<Option<u128>>;
+
+    fn save_da_backend_confirmed(&self, backend: String, block_number: u128) -> Result<()>;
+
+    fn save_da_chunk_index(&self, backend: String, index: DAChunkIndex) -> Result<()>;
+
+    fn get_da_chunk_index(&self, backend: String, chunk_id: u128) -> Result<Option<DAChunkIndex>>;
+
+    fn get_da_chunk_index_by_block(
+        &self,
+        backend: String,
+        block_number: u128,
+    ) -> Result<Option<DAChunkIndex>>;
+
+    fn get_da_block_number_by_batch_hash(&self, batch_hash: H256) -> Result<Option<u128>>;
+}
+
+/// DA submission outbox: batches not confirmed by all the DA servers yet,
+/// and the last confirmed block number of each DA server.
+/// Segment index: where the chunks of each DA server are stored.
+#[derive(Clone)]
+pub struct DADBStore {
+    pending_batch_store: PendingDABatchStore,
+    backend_confirmed_store: DABackendConfirmedStore,
+    chunk_index_store: DAChunkIndexStore,
+    block_chunk_store: DABlockChunkStore,
+    batch_hash_store: DABatchHashStore,
+}
+
+impl DADBStore {
+    pub fn new(instance: StoreInstance) -> Self {
+        DADBStore {
+            pending_batch_store: PendingDABatchStore::new(instance.clone()),
+            backend_confirmed_store: DABackendConfirmedStore::new(instance.clone()),
+            chunk_index_store: DAChunkIndexStore::new(instance.clone()),
+            block_chunk_store: DABlockChunkStore::new(instance.clone()),
+            batch_hash_store: DABatchHashStore::new(instance),
+        }
+    }
+
+    pub fn save_pending_da_batch(&self, batch: PendingDABatch) -> Result<()> {
+        self.pending_batch_store.put_sync(batch.block_number, batch)
+    }
+
+    /// Get all the pending batches, ordered by block_number
+    pub fn get_pending_da_batches(&self) -> Result<Vec<PendingDABatch>> {
+        let mut iter = self.pending_batch_store.iter()?;
+        iter.seek_to_first();
+        let mut batches = iter
+            .map(|item| item.map(|(_, batch)| batch))
+            .collect::<Result<Vec<_>>>()?;
+        // the key is encoded in little endian, so the iterator order is not the block_number
+        batches.sort_by_key(|batch| batch.block_number);
+        Ok(batches)
+    }
+
+    pub fn remove_pending_da_batches(&self, block_numbers: Vec<u128>) -> Result<()> {
+        self.pending_batch_store.delete_all(block_numbers)
+    }
+
+    pub fn get_da_backend_confirmed(&self, backend: String) -> Result<Option<u128>> {
+        self.backend_confirmed_store.kv_get(backend)
+    }
+
+    pub fn save_da_backend_confirmed(&self, backend: String, block_number: u128) -> Result<()> {
+        self.backend_confirmed_store.put_sync(backend, block_number)
+    }
+
+    /// Save the chunk index, and the block number -> chunk id, batch hash -> block number mappings
+    pub fn save_da_chunk_index(&self, backend: String, index: DAChunkIndex) -> Result<()> {
+        // mappings first, the chunk index is the commit point
+        for (i, batch_hash) in index.batch_hashes.iter().enumerate() {
+            let block_number = index.start_block_number + i as u128;
+            self.block_chunk_store
+                .put_sync((backend.clone(), block_number), index.chunk_id)?;
+            self.batch_hash_store.put_sync(*batch_hash, block_number)?;
+        }
+        self.chunk_index_store
+            .put_sync((backend, index.chunk_id), index)
+    }
+
+    pub fn get_da_chunk_index(
+        &self,
+        backend: String,
+        chunk_id: u128,
+    ) -> Result<Option<DAChunkIndex>> {
+        self.chunk_index_store.kv_get((backend, chunk_id))
+    }
+
+    pub fn get_da_chunk_index_by_block(
+        &self,
+        backend: String,
+        block_number: u128,
+    ) -> Result<Option<DAChunkIndex>> {
+        let chunk_id = match self
+            .block_chunk_store
+            .kv_get((backend.clone(), block_number))?
+        {
+            Some(chunk_id) => chunk_id,
+            None => return Ok(None),
+        };
+        Ok(self
+            .get_da_chunk_index(backend, chunk_id)?
+            .filter(|index| index.contains_block(block_number)))
+    }
+
+    pub fn get_da_block_number_by_batch_hash(&self, batch_hash: H256) -> Result<Option<u128>> {
+        self.batch_hash_store.kv_get(batch_hash)
+    }
+}
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::accumulator_store::{AccumulatorStore, TransactionAccumulatorStore};
+use crate::da_store::{DADBStore, DAStore};
 use crate::meta_store::{MetaDBStore, MetaStore};
+use crate::proposer_store::{ProposerDBStore, ProposerStore};
 use crate::transaction_store::{TransactionDBStore, TransactionStore};
 use accumulator::AccumulatorTreeStore;
 use anyhow::Result;
@@ -13,14 +15,18 @@ use once_cell::sync::Lazy;
 use raw_store::metrics::DBMetrics;
 use raw_store::rocks::RocksDB;
 use raw_store::{ColumnFamilyName, StoreInstance};
+use rooch_types::block::{Block, PendingBlockTransaction};
+use rooch_types::da::{DAChunkIndex, PendingDABatch};
 use rooch_types::sequencer::SequencerInfo;
-use rooch_types::transaction::LedgerTransaction;
+use rooch_types::transaction::{L1BlockWithBody, LedgerTransaction};
 use std::fmt::{Debug, Display, Formatter};
 use std::path::Path;
 use std::sync::Arc;
 
 pub mod accumulator_store;
+pub mod da_store;
 pub mod meta_store;
+pub mod proposer_store;
 #[cfg(test)]
 mod tests;
 pub mod transaction_store;
@@ -31,6 +37,19 @@ pub const TX_SEQUENCE_INFO_MAPPING_COLUMN_FAMILY_NAME: ColumnFamilyName =
     "tx_sequence_info_mapping";
 pub const META_SEQUENCER_INFO_COLUMN_FAMILY_NAME: ColumnFamilyName = "meta_sequencer_info";
 pub const TX_ACCUMULATOR_NODE_COLUMN_FAMILY_NAME: ColumnFamilyName = "transaction_acc_node";
+pub const BLOCK_COLUMN_FAMILY_NAME: ColumnFamilyName = "block";
+pub const BLOCK_V1_COLUMN_FAMILY_NAME: ColumnFamilyName = "block_v1";
+pub const BLOCK_STATE_ROOTS_COLUMN_FAMILY_NAME: ColumnFamilyName = "block_state_roots";
+pub const META_PROPOSER_COLUMN_FAMILY_NAME: ColumnFamilyName = "meta_proposer";
+pub const PROPOSER_PENDING_TX_COLUMN_FAMILY_NAME: ColumnFamilyName = "proposer_pending_tx";
+pub const DA_PENDING_BATCH_COLUMN_FAMILY_NAME: ColumnFamilyName = "da_pending_batch";
+pub const DA_BACKEND_CONFIRMED_COLUMN_FAMILY_NAME: ColumnFamilyName = "da_backend_confirmed";
+pub const DA_CHUNK_INDEX_COLUMN_FAMILY_NAME: ColumnFamilyName = "da_chunk_index";
+pub const DA_BLOCK_CHUNK_COLUMN_FAMILY_NAME: ColumnFamilyName = "da_block_chunk";
+pub const DA_BATCH_HASH_COLUMN_FAMILY_NAME: ColumnFamilyName = "da_batch_hash";
+pub const L1_BLOCK_BODY_COLUMN_FAMILY_NAME: ColumnFamilyName = "l1_block_body";
+pub const META_BITCOIN_REORG_CHECKPOINT_COLUMN_FAMILY_NAME: ColumnFamilyName =
+    "meta_bitcoin_reorg_checkpoint";
 
 ///db store use cf_name vec to init
 /// Please note that adding a column family needs to be added in vec simultaneously, remember！！
@@ -40,6 +59,18 @@ static VEC_COLUMN_FAMILY_NAME: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
         TX_SEQUENCE_INFO_MAPPING_COLUMN_FAMILY_NAME,
         META_SEQUENCER_INFO_COLUMN_FAMILY_NAME,
         TX_ACCUMULATOR_NODE_COLUMN_FAMILY_NAME,
+        BLOCK_COLUMN_FAMILY_NAME,
+        BLOCK_V1_COLUMN_FAMILY_NAME,
+        BLOCK_STATE_ROOTS_COLUMN_FAMILY_NAME,
+        META_PROPOSER_COLUMN_FAMILY_NAME,
+        PROPOSER_PENDING_TX_COLUMN_FAMILY_NAME,
+        DA_PENDING_BATCH_COLUMN_FAMILY_NAME,
+        DA_BACKEND_CONFIRMED_COLUMN_FAMILY_NAME,
+        DA_CHUNK_INDEX_COLUMN_FAMILY_NAME,
+        DA_BLOCK_CHUNK_COLUMN_FAMILY_NAME,
+        DA_BATCH_HASH_COLUMN_FAMILY_NAME,
+        L1_BLOCK_BODY_COLUMN_FAMILY_NAME,
+        META_BITCOIN_REORG_CHECKPOINT_COLUMN_FAMILY_NAME,
     ]
 });
 
@@ -57,6 +88,8 @@ pub struct RoochStore {
     pub transaction_store: TransactionDBStore,
     pub meta_store: MetaDBStore,
     pub transaction_accumulator_store: AccumulatorStore<TransactionAccumulatorStore>,
+    pub proposer_store: ProposerDBStore,
+    pub da_store: DADBStore,
 }
 
 impl RoochStore {
@@ -86,8 +119,10 @@ impl RoochStore {
             transaction_store: TransactionDBStore::new(instance.clone()),
             meta_store: MetaDBStore::new(instance.clone()),
             transaction_accumulator_store: AccumulatorStore::new_transaction_accumulator_store(
-                instance,
+                instance.clone(),
             ),
+            proposer_store: ProposerDBStore::new(instance.clone()),
+            da_store: DADBStore::new(instance),
         };
         Ok(store)
     }
@@ -112,6 +147,14 @@ impl RoochStore {
     pub fn get_transaction_accumulator_store(&self) -> Arc<dyn AccumulatorTreeStore> {
         Arc::new(self.transaction_accumulator_store.clone())
     }
+
+    pub fn get_proposer_store(&self) -> &ProposerDBStore {
+        &self.proposer_store
+    }
+
+    pub fn get_da_store(&self) -> &DADBStore {
+        &self.da_store
+    }
 }
 
 impl Display for RoochStore {
@@ -144,6 +187,18 @@ impl TransactionStore for RoochStore {
     fn get_tx_hashs(&self, tx_orders: Vec<u64>) -> Result<Vec<Option<H256>>> {
         self.transaction_store.get_tx_hashs(tx_orders)
     }
+
+    fn save_l1_block_body(&self, l1_block: L1BlockWithBody) -> Result<()> {
+        self.transaction_store.save_l1_block_body(l1_block)
+    }
+
+    fn get_l1_block_body(&self, tx_hash: H256) -> Result<Option<L1BlockWithBody>> {
+        self.transaction_store.get_l1_block_body(tx_hash)
+    }
+
+    fn remove_l1_block_body(&self, tx_hash: H256) -> Result<()> {
+        self.transaction_store.remove_l1_block_body(tx_hash)
+    }
 }
 
 impl MetaStore for RoochStore {
@@ -155,3 +210,84 @@ impl MetaStore for RoochStore {
         self.get_meta_store().save_sequencer_info(sequencer_info)
     }
 }
+
+impl ProposerStore for RoochStore {
+    fn get_block(&self, block_number: u128) -> Result<Option<Block>> {
+        self.get_proposer_store().get_block(block_number)
+    }
+
+    fn get_last_block_number(&self) -> Result<Option<u128>> {
+        self.get_proposer_store().get_last_block_number()
+    }
+
+    fn get_last_block(&self) -> Result<Option<Block>> {
+        self.get_proposer_store().get_last_block()
+    }
+
+    fn get_block_state_roots(&self, block_number: u128) -> Result<Option<Vec<H256>>> {
+        self.get_proposer_store()
+            .get_block_state_roots(block_number)
+    }
+
+    fn save_block(&self, block: Block, state_roots: Vec<H256>) -> Result<()> {
+        self.get_proposer_store().save_block(block, state_roots)
+    }
+
+    fn save_pending_transaction(&self, tx: PendingBlockTransaction) -> Result<()> {
+        self.get_proposer_store().save_pending_transaction(tx)
+    }
+
+    fn get_pending_transactions(&self) -> Result<Vec<PendingBlockTransaction>> {
+        self.get_proposer_store().get_pending_transactions()
+    }
+
+    fn remove_pending_transactions(&self, tx_orders: Vec<u64>) -> Result<()> {
+        self.get_proposer_store()
+            .remove_pending_transactions(tx_orders)
+    }
+}
+
+impl DAStore for RoochStore {
+    fn save_pending_da_batch(&self, batch: PendingDABatch) -> Result<()> {
+        self.get_da_store().save_pending_da_batch(batch)
+    }
+
+    fn get_pending_da_batches(&self) -> Result<Vec<PendingDABatch>> {
+        self.get_da_store().get_pending_da_batches()
+    }
+
+    fn remove_pending_da_batches(&self, block_numbers: Vec<u128>) -> Result<()> {
+        self.get_da_store().remove_pending_da_batches(block_numbers)
+    }
+
+    fn get_da_backend_confirmed(&self, backend: String) -> Result<Option<u128>> {
+        self.get_da_store().get_da_backend_confirmed(backend)
+    }
+
+    fn save_da_backend_confirmed(&self, backend: String, block_number: u128) -> Result<()> {
+        self.get_da_store()
+            .save_da_backend_confirmed(backend, block_number)
+    }
+
+    fn save_da_chunk_index(&self, backend: String, index: DAChunkIndex) -> Result<()> {
+        self.get_da_store().save_da_chunk_index(backend, index)
+    }
+
+    fn get_da_chunk_index(&self, backend: String, chunk_id: u128) -> Result<Option<DAChunkIndex>> {
+        self.get_da_store().get_da_chunk_index(backend, chunk_id)
+    }
+
+    fn get_da_chunk_index_by_block(
+        &self,
+        backend: String,
+        block_number: u128,
+    ) -> Result<Option<DAChunkIndex>> {
+        self.get_da_store()
+            .get_da_chunk_index_by_block(backend, block_number)
+    }
+
+    fn get_da_block_number_by_batch_hash(&self, batch_hash: H256) -> Result<Option<u128>> {
+        self.get_da_store()
+            .get_da_block_number_by_batch_hash(batch_hash)
+    }
+}
@@ -1,13 +1,17 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::META_SEQUENCER_INFO_COLUMN_FAMILY_NAME;
+use crate::{
+    META_BITCOIN_REORG_CHECKPOINT_COLUMN_FAMILY_NAME, META_SEQUENCER_INFO_COLUMN_FAMILY_NAME,
+};
 use anyhow::Result;
 use raw_store::{derive_store, CodecKVStore, StoreInstance};
+use rooch_types::bitcoin::pending_block::DeepReorgCheckpoint;
 use rooch_types::sequencer::SequencerInfo;
 use std::string::ToString;
 
 pub const SEQUENCER_INFO_KEY: &str = "sequencer_info";
+pub const BITCOIN_REORG_CHECKPOINT_KEY: &str = "bitcoin_reorg_checkpoint";
 
 derive_store!(
     SequencerInfoStore,
@@ -16,6 +20,13 @@ derive_store!(
     META_SEQUENCER_INFO_COLUMN_FAMILY_NAME
 );
 
+derive_store!(
+    BitcoinReorgCheckpointStore,
+    String,
+    DeepReorgCheckpoint,
+    META_BITCOIN_REORG_CHECKPOINT_COLUMN_FAMILY_NAME
+);
+
 pub trait MetaStore {
     fn get_sequencer_info(&self) -> Result<Option<SequencerInfo>>;
 
@@ -25,12 +36,14 @@ pub trait MetaStore {
 #[derive(Clone)]
 pub struct MetaDBStore {
     sequencer_info_store: SequencerInfoStore,
+    bitcoin_reorg_checkpoint_store: BitcoinReorgCheckpointStore,
 }
 
 impl MetaDBStore {
     pub fn new(instance: StoreInstance) -> Self {
         MetaDBStore {
-            sequencer_info_store: SequencerInfoStore::new(instance),
+            sequencer_info_store: SequencerInfoStore::new(instance.clone()),
+            bitcoin_reorg_checkpoint_store: BitcoinReorgCheckpointStore::new(instance),
         }
     }
 
@@ -49,4 +62,35 @@ impl MetaDBStore {
         self.sequencer_info_store
             .put_sync(SEQUENCER_INFO_KEY.to_string(), sequencer_info)
     }
+
+    /// Reset the sequencer info to a previous order, only for rewinding the state.
+    pub fn rewind_sequencer_info(&self, sequencer_info: SequencerInfo) -> Result<()> {
+        let pre_sequencer_info = self.get_sequencer_info()?;
+        if let Some(pre_sequencer_info) = pre_sequencer_info {
+            if sequencer_info.last_order > pre_sequencer_info.last_order {
+                return Err(anyhow::anyhow!(
+                    "Can not rewind the sequencer order from {} to {}",
+                    pre_sequencer_info.last_order,
+                    sequencer_info.last_order
+                ));
+            }
+        }
+        self.sequencer_info_store
+            .put_sync(SEQUENCER_INFO_KEY.to_string(), sequencer_info)
+    }
+
+    pub fn get_bitcoin_reorg_checkpoint(&self) -> Result<Option<DeepReorgCheckpoint>> {
+        self.bitcoin_reorg_checkpoint_store
+            .kv_get(BITCOIN_REORG_CHECKPOINT_KEY.to_string())
+    }
+
+    pub fn save_bitcoin_reorg_checkpoint(&self, checkpoint: DeepReorgCheckpoint) -> Result<()> {
+        self.bitcoin_reorg_checkpoint_store
+            .put_sync(BITCOIN_REORG_CHECKPOINT_KEY.to_string(), checkpoint)
+    }
+
+    pub fn remove_bitcoin_reorg_checkpoint(&self) -> Result<()> {
+        self.bitcoin_reorg_checkpoint_store
+            .remove(BITCOIN_REORG_CHECKPOINT_KEY.to_string())
+    }
 }
@@ -0,0 +1,161 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BLOCK_COLUMN_FAMILY_NAME, BLOCK_STATE_ROOTS_COLUMN_FAMILY_NAME, BLOCK_V1_COLUMN_FAMILY_NAME,
+    META_PROPOSER_COLUMN_FAMILY_NAME, PROPOSER_PENDING_TX_COLUMN_FAMILY_NAME,
+};
+use anyhow::Result;
+use moveos_types::h256::H256;
+use raw_store::{derive_store, CodecKVStore, StoreInstance};
+use rooch_types::block::{Block, BlockV0, PendingBlockTransaction};
+use std::string::ToString;
+
+pub const LAST_BLOCK_NUMBER_KEY: &str = "last_block_number";
+
+// The legacy blocks, only for read
+derive_store!(BlockV0Store, u128, BlockV0, BLOCK_COLUMN_FAMILY_NAME);
+
+derive_store!(BlockStore, u128, Block, BLOCK_V1_COLUMN_FAMILY_NAME);
+
+derive_store!(
+    BlockStateRootsStore,
+    u128,
+    Vec<H256>,
+    BLOCK_STATE_ROOTS_COLUMN_FAMILY_NAME
+);
+
+derive_store!(
+    LastBlockNumberStore,
+    String,
+    u128,
+    META_PROPOSER_COLUMN_FAMILY_NAME
+);
+
+derive_store!(
+    PendingBlockTransactionStore,
+    u64,
+    PendingBlockTransaction,
+    PROPOSER_PENDING_TX_COLUMN_FAMILY_NAME
+);
+
+pub trait ProposerStore {
+    fn get_block(&self, block_number: u128) -> Result<Option<Block>>;
+
+    fn get_last_block_number(&self) -> Result<Option<u128>>;
+
+    fn get_last_block(&self) -> Result<Option<Block>>;
+
+    fn get_block_state_roots(&self, block_number: u128) -> Result<Option<Vec<H256>>>;
+
+    fn save_block(&self, block: Block, state_roots: Vec<H256>) -> Result<()>;
+
+    fn save_pending_transaction(&self, tx: PendingBlockTransaction) -> Result<()>;
+
+    fn get_pending_transactions(&self) -> Result<Vec<PendingBlockTransaction>>;
+
+    fn remove_pending_transactions(&self, tx_orders: Vec<u64>) -> Result<()>;
+}
+
+#[derive(Clone)]
+pub struct ProposerDBStore {
+    block_v0_store: BlockV0Store,
+    block_store: BlockStore,
+    block_state_roots_store: BlockStateRootsStore,
+    last_block_number_store: LastBlockNumberStore,
+    pending_tx_store: PendingBlockTransactionStore,
+}
+
+impl ProposerDBStore {
+    pub fn new(instance: StoreInstance) -> Self {
+        ProposerDBStore {
+            block_v0_store: BlockV0Store::new(instance.clone()),
+            block_store: BlockStore::new(instance.clone()),
+            block_state_roots_store: BlockStateRootsStore::new(instance.clone()),
+            last_block_number_store: LastBlockNumberStore::new(instance.clone()),
+            pending_tx_store: PendingBlockTransactionStore::new(instance),
+        }
+    }
+
+    /// Get the block, the legacy block is converted to the current format
+    pub fn get_block(&self, block_number: u128) -> Result<Option<Block>> {
+        match self.block_store.kv_get(block_number)? {
+            Some(block) => Ok(Some(block)),
+            None => Ok(self.block_v0_store.kv_get(block_number)?.map(Into::into)),
+        }
+    }
+
+    /// Get the state roots of all the transactions in the block, for the state root proof
+    pub fn get_block_state_roots(&self, block_number: u128) -> Result<Option<Vec<H256>>> {
+        match self.block_state_roots_store.kv_get(block_number)? {
+            Some(state_roots) => Ok(Some(state_roots)),
+            None => Ok(self
+                .block_v0_store
+                .kv_get(block_number)?
+                .map(|block| block.state_roots)),
+        }
+    }
+
+    pub fn get_last_block_number(&self) -> Result<Option<u128>> {
+        self.last_block_number_store
+            .kv_get(LAST_BLOCK_NUMBER_KEY.to_string())
+    }
+
+    pub fn get_last_block(&self) -> Result<Option<Block>> {
+        match self.get_last_block_number()? {
+            Some(block_number) => self.get_block(block_number),
+            None => Ok(None),
+        }
+    }
+
+    /// Save the block with the state roots of its transactions, and move the last block number forward.
+    /// The block number must be continuous with the last saved block.
+    pub fn save_block(&self, block: Block, state_roots: Vec<H256>) -> Result<()> {
+        let block_number = block.block_number;
+        let exp_block_number = match self.get_last_block_number()? {
+            Some(last_block_number) => last_block_number + 1,
+            None => 0,
+        };
+        if block_number != exp_block_number {
+            return Err(anyhow::anyhow!(
+                "Block number is not continuous, expect: {}, actual: {}",
+                exp_block_number,
+                block_number
+            ));
+        }
+        if block.batch_size != state_roots.len() as u64
+            || block.state_root_accumulator_root
+                != Block::state_root_accumulator_root_of(&state_roots)
+        {
+            return Err(anyhow::anyhow!(
+                "State roots mismatch with block {}",
+                block_number
+            ));
+        }
+        self.block_state_roots_store
+            .put_sync(block_number, state_roots)?;
+        self.block_store.put_sync(block_number, block)?;
+        self.last_block_number_store
+            .put_sync(LAST_BLOCK_NUMBER_KEY.to_string(), block_number)
+    }
+
+    pub fn save_pending_transaction(&self, tx: PendingBlockTransaction) -> Result<()> {
+        self.pending_tx_store.put_sync(tx.tx_order(), tx)
+    }
+
+    /// Get all the pending transactions, ordered by tx_order
+    pub fn get_pending_transactions(&self) -> Result<Vec<PendingBlockTransaction>> {
+        let mut iter = self.pending_tx_store.iter()?;
+        iter.seek_to_first();
+        let mut txs = iter
+            .map(|item| item.map(|(_, tx)| tx))
+            .collect::<Result<Vec<_>>>()?;
+        // the key is encoded in little endian, so the iterator order is not the tx_order
+        txs.sort_by_key(|tx| tx.tx_order());
+        Ok(txs)
+    }
+
+    pub fn remove_pending_transactions(&self, tx_orders: Vec<u64>) -> Result<()> {
+        self.pending_tx_store.delete_all(tx_orders)
+    }
+}
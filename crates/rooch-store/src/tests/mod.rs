@@ -2,3 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod test_accumulator;
+mod test_da;
+mod test_meta;
+mod test_proposer;
+mod test_transaction;
@@ -0,0 +1,105 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::da_store::DAStore;
+use crate::RoochStore;
+use moveos_types::h256;
+use rooch_types::da::{DAChunkIndex, DASegmentIndex, DASegmentLocation, PendingDABatch};
+
+#[tokio::test]
+async fn test_da_store() {
+    let (rooch_store, _) = RoochStore::mock_rooch_store().unwrap();
+    assert!(rooch_store.get_pending_da_batches().unwrap().is_empty());
+
+    // pending batches are returned in block_number
+    for block_number in [256u128, 2, 1] {
+        let data = vec![block_number as u8; 8];
+        rooch_store
+            .save_pending_da_batch(PendingDABatch::new(
+                block_number,
+                h256::sha3_256_of(&data),
+                data,
+            ))
+            .unwrap();
+    }
+    let block_numbers = rooch_store
+        .get_pending_da_batches()
+        .unwrap()
+        .iter()
+        .map(|batch| batch.block_number)
+        .collect::<Vec<_>>();
+    assert_eq!(block_numbers, vec![1, 2, 256]);
+
+    rooch_store.remove_pending_da_batches(vec![1, 2]).unwrap();
+    let pending_batches = rooch_store.get_pending_da_batches().unwrap();
+    assert_eq!(pending_batches.len(), 1);
+    assert_eq!(pending_batches[0].block_number, 256);
+
+    let backend = "DAServerOpenDA-fs".to_string();
+    assert!(rooch_store
+        .get_da_backend_confirmed(backend.clone())
+        .unwrap()
+        .is_none());
+    rooch_store
+        .save_da_backend_confirmed(backend.clone(), 2)
+        .unwrap();
+    assert_eq!(
+        rooch_store.get_da_backend_confirmed(backend).unwrap(),
+        Some(2)
+    );
+}
+
+#[tokio::test]
+async fn test_da_chunk_index() {
+    let (rooch_store, _) = RoochStore::mock_rooch_store().unwrap();
+    let backend = "DAServerOpenDA-fs".to_string();
+
+    let batch_hashes = vec![h256::sha3_256_of(&[1]), h256::sha3_256_of(&[2])];
+    let index = DAChunkIndex {
+        chunk_id: 10,
+        start_block_number: 10,
+        end_block_number: 11,
+        batch_hashes: batch_hashes.clone(),
+        segment_count: 1,
+        segments: vec![DASegmentIndex {
+            segment_number: 0,
+            is_last: true,
+            location: DASegmentLocation::OpenDA {
+                path: "10_0".to_string(),
+            },
+            submitted_at: 1,
+        }],
+        created_at: 1,
+        updated_at: 1,
+    };
+    assert!(index.is_complete());
+    rooch_store
+        .save_da_chunk_index(backend.clone(), index.clone())
+        .unwrap();
+
+    assert_eq!(
+        rooch_store.get_da_chunk_index(backend.clone(), 10).unwrap(),
+        Some(index.clone())
+    );
+    assert_eq!(
+        rooch_store
+            .get_da_chunk_index_by_block(backend.clone(), 11)
+            .unwrap(),
+        Some(index)
+    );
+    assert!(rooch_store
+        .get_da_chunk_index_by_block(backend.clone(), 12)
+        .unwrap()
+        .is_none());
+    // index is kept by backend
+    assert!(rooch_store
+        .get_da_chunk_index_by_block("DAServerCelestia".to_string(), 11)
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        rooch_store
+            .get_da_block_number_by_batch_hash(batch_hashes[1])
+            .unwrap(),
+        Some(11)
+    );
+}
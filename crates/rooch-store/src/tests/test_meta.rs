@@ -0,0 +1,60 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::RoochStore;
+use accumulator::accumulator_info::AccumulatorInfo;
+use move_core_types::account_address::AccountAddress;
+use rooch_types::bitcoin::pending_block::DeepReorgCheckpoint;
+use rooch_types::bitcoin::types::BlockHeightHash;
+use rooch_types::sequencer::SequencerInfo;
+
+#[tokio::test]
+async fn test_rewind_sequencer_info() {
+    let (rooch_store, _) = RoochStore::mock_rooch_store().unwrap();
+    let meta_store = rooch_store.get_meta_store();
+    for order in 0..3 {
+        meta_store
+            .save_sequencer_info(SequencerInfo::new(order, AccumulatorInfo::default()))
+            .unwrap();
+    }
+    assert!(meta_store
+        .rewind_sequencer_info(SequencerInfo::new(3, AccumulatorInfo::default()))
+        .is_err());
+    meta_store
+        .rewind_sequencer_info(SequencerInfo::new(1, AccumulatorInfo::default()))
+        .unwrap();
+    assert_eq!(
+        meta_store.get_sequencer_info().unwrap().unwrap().last_order,
+        1
+    );
+    meta_store
+        .save_sequencer_info(SequencerInfo::new(2, AccumulatorInfo::default()))
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_bitcoin_reorg_checkpoint() {
+    let (rooch_store, _) = RoochStore::mock_rooch_store().unwrap();
+    let meta_store = rooch_store.get_meta_store();
+    assert!(meta_store.get_bitcoin_reorg_checkpoint().unwrap().is_none());
+    let checkpoint = DeepReorgCheckpoint {
+        fork_height: 101,
+        common_ancestor: BlockHeightHash {
+            block_height: 100,
+            block_hash: AccountAddress::random(),
+        },
+        best_block: BlockHeightHash {
+            block_height: 110,
+            block_hash: AccountAddress::random(),
+        },
+        reorg_block_count: 3,
+        detected_at: 0,
+    };
+    meta_store
+        .save_bitcoin_reorg_checkpoint(checkpoint.clone())
+        .unwrap();
+    let saved = meta_store.get_bitcoin_reorg_checkpoint().unwrap().unwrap();
+    assert_eq!(saved.fork_height, checkpoint.fork_height);
+    meta_store.remove_bitcoin_reorg_checkpoint().unwrap();
+    assert!(meta_store.get_bitcoin_reorg_checkpoint().unwrap().is_none());
+}
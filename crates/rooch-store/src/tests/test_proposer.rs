@@ -0,0 +1,82 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::proposer_store::ProposerStore;
+use crate::RoochStore;
+use move_core_types::vm_status::KeptVMStatus;
+use moveos_types::h256::H256;
+use moveos_types::transaction::TransactionExecutionInfo;
+use rooch_types::block::{Block, PendingBlockTransaction};
+use rooch_types::transaction::{LedgerTransaction, RoochTransaction, TransactionSequenceInfo};
+
+fn mock_pending_tx(tx_order: u64) -> PendingBlockTransaction {
+    let mut tx = LedgerTransaction::new_l2_tx(
+        RoochTransaction::mock(),
+        TransactionSequenceInfo::new(tx_order, vec![], H256::random(), 0),
+    );
+    let tx_execution_info = TransactionExecutionInfo::new(
+        tx.tx_hash(),
+        H256::random(),
+        0,
+        H256::random(),
+        0,
+        KeptVMStatus::Executed,
+    );
+    PendingBlockTransaction::new(tx, tx_execution_info)
+}
+
+#[tokio::test]
+async fn test_proposer_store() {
+    let (rooch_store, _) = RoochStore::mock_rooch_store().unwrap();
+    assert!(rooch_store.get_last_block().unwrap().is_none());
+
+    let state_roots0 = vec![H256::random()];
+    let block0 = Block::new(0, 1, H256::zero(), H256::random(), state_roots0.clone());
+    rooch_store
+        .save_block(block0.clone(), state_roots0.clone())
+        .unwrap();
+    let state_roots1 = vec![H256::random(), H256::random()];
+    let block1 = Block::new(
+        1,
+        2,
+        block0.tx_accumulator_root,
+        H256::random(),
+        state_roots1.clone(),
+    );
+    // state roots must match the block
+    assert!(rooch_store
+        .save_block(block1.clone(), state_roots0.clone())
+        .is_err());
+    rooch_store
+        .save_block(block1.clone(), state_roots1.clone())
+        .unwrap();
+    assert_eq!(rooch_store.get_last_block_number().unwrap(), Some(1));
+    assert_eq!(rooch_store.get_last_block().unwrap(), Some(block1));
+    assert_eq!(rooch_store.get_block(0).unwrap(), Some(block0));
+    assert_eq!(
+        rooch_store.get_block_state_roots(1).unwrap(),
+        Some(state_roots1)
+    );
+
+    // block number must be continuous
+    let block3 = Block::new(3, 1, H256::random(), H256::random(), state_roots0.clone());
+    assert!(rooch_store.save_block(block3, state_roots0).is_err());
+
+    // pending transactions are returned in tx_order
+    for tx_order in [257u64, 2, 1] {
+        rooch_store
+            .save_pending_transaction(mock_pending_tx(tx_order))
+            .unwrap();
+    }
+    let pending_txs = rooch_store.get_pending_transactions().unwrap();
+    let tx_orders = pending_txs
+        .iter()
+        .map(|tx| tx.tx_order())
+        .collect::<Vec<_>>();
+    assert_eq!(tx_orders, vec![1, 2, 257]);
+
+    rooch_store.remove_pending_transactions(vec![1, 2]).unwrap();
+    let pending_txs = rooch_store.get_pending_transactions().unwrap();
+    assert_eq!(pending_txs.len(), 1);
+    assert_eq!(pending_txs[0].tx_order(), 257);
+}
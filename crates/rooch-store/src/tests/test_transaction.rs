@@ -0,0 +1,29 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::transaction_store::TransactionStore;
+use crate::RoochStore;
+use rooch_types::multichain_id::RoochMultiChainID;
+use rooch_types::transaction::{L1Block, L1BlockWithBody, LedgerTxData};
+
+#[tokio::test]
+async fn test_l1_block_body() {
+    let (rooch_store, _) = RoochStore::mock_rooch_store().unwrap();
+    let l1_block = L1BlockWithBody {
+        block: L1Block {
+            chain_id: RoochMultiChainID::Bitcoin.multichain_id(),
+            block_height: 100,
+            block_hash: vec![1u8; 32],
+        },
+        block_body: vec![2u8; 128],
+    };
+    rooch_store.save_l1_block_body(l1_block.clone()).unwrap();
+
+    // The body is indexed by the hash of the L1Block ledger transaction
+    let tx_hash = LedgerTxData::L1Block(l1_block.block.clone()).tx_hash();
+    let saved = rooch_store.get_l1_block_body(tx_hash).unwrap();
+    assert_eq!(saved, Some(l1_block));
+
+    rooch_store.remove_l1_block_body(tx_hash).unwrap();
+    assert_eq!(rooch_store.get_l1_block_body(tx_hash).unwrap(), None);
+}
@@ -1,12 +1,15 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{TRANSACTION_COLUMN_FAMILY_NAME, TX_SEQUENCE_INFO_MAPPING_COLUMN_FAMILY_NAME};
+use crate::{
+    L1_BLOCK_BODY_COLUMN_FAMILY_NAME, TRANSACTION_COLUMN_FAMILY_NAME,
+    TX_SEQUENCE_INFO_MAPPING_COLUMN_FAMILY_NAME,
+};
 use anyhow::Result;
 use moveos_types::h256::H256;
 use raw_store::CodecKVStore;
 use raw_store::{derive_store, StoreInstance};
-use rooch_types::transaction::LedgerTransaction;
+use rooch_types::transaction::{L1BlockWithBody, LedgerTransaction};
 
 derive_store!(
     LedgerTransactionStore,
@@ -22,6 +25,13 @@ derive_store!(
     TX_SEQUENCE_INFO_MAPPING_COLUMN_FAMILY_NAME
 );
 
+derive_store!(
+    L1BlockBodyStore,
+    H256,
+    L1BlockWithBody,
+    L1_BLOCK_BODY_COLUMN_FAMILY_NAME
+);
+
 pub trait TransactionStore {
     fn save_transaction(&self, transaction: LedgerTransaction) -> Result<()>;
     fn get_transaction_by_hash(&self, hash: H256) -> Result<Option<LedgerTransaction>>;
@@ -44,12 +54,20 @@ pub trait TransactionStore {
         };
         self.get_tx_hashs(tx_orders)
     }
+
+    /// Save the L1 block with body, the key is the hash of the L1Block ledger transaction.
+    /// The ledger transaction only contains the block header, the body is required to re-execute the block.
+    fn save_l1_block_body(&self, l1_block: L1BlockWithBody) -> Result<()>;
+    fn get_l1_block_body(&self, tx_hash: H256) -> Result<Option<L1BlockWithBody>>;
+    /// Remove the L1 block body after the block is executed, it is not required anymore.
+    fn remove_l1_block_body(&self, tx_hash: H256) -> Result<()>;
 }
 
 #[derive(Clone)]
 pub struct TransactionDBStore {
     tx_store: LedgerTransactionStore,
     tx_sequence_info_mapping_store: TxSequenceInfoMappingStore,
+    l1_block_body_store: L1BlockBodyStore,
 }
 
 impl TransactionDBStore {
@@ -57,6 +75,7 @@ impl TransactionDBStore {
         TransactionDBStore {
             tx_store: LedgerTransactionStore::new(instance.clone()),
             tx_sequence_info_mapping_store: TxSequenceInfoMappingStore::new(instance.clone()),
+            l1_block_body_store: L1BlockBodyStore::new(instance.clone()),
         }
     }
 
@@ -79,4 +98,17 @@ impl TransactionDBStore {
     pub fn get_tx_hashs(&self, tx_orders: Vec<u64>) -> Result<Vec<Option<H256>>> {
         self.tx_sequence_info_mapping_store.multiple_get(tx_orders)
     }
+
+    pub fn save_l1_block_body(&self, l1_block: L1BlockWithBody) -> Result<()> {
+        let tx_hash = l1_block.block.tx_hash();
+        self.l1_block_body_store.put_sync(tx_hash, l1_block)
+    }
+
+    pub fn get_l1_block_body(&self, tx_hash: H256) -> Result<Option<L1BlockWithBody>> {
+        self.l1_block_body_store.kv_get(tx_hash)
+    }
+
+    pub fn remove_l1_block_body(&self, tx_hash: H256) -> Result<()> {
+        self.l1_block_body_store.remove(tx_hash)
+    }
 }
@@ -0,0 +1,177 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+//! The preview of the unconfirmed Bitcoin transactions in the mempool.
+//! The preview is only kept in memory and never written to the state.
+
+use super::ord::{InscriptionID, SatPoint};
+use super::types::{OutPoint, Transaction};
+use crate::address::BitcoinAddress;
+use move_core_types::account_address::AccountAddress;
+use moveos_types::moveos_std::object::ObjectID;
+
+/// The UTXO spent by a pending transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingSpentUTXO {
+    pub outpoint: OutPoint,
+    pub object_id: ObjectID,
+    /// The value of the UTXO, none if the UTXO is neither in the state nor created by a pending transaction
+    pub value: Option<u64>,
+    /// Whether the UTXO is created by another pending transaction
+    pub unconfirmed: bool,
+}
+
+/// The UTXO created by a pending transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingCreatedUTXO {
+    pub outpoint: OutPoint,
+    pub object_id: ObjectID,
+    pub value: u64,
+    pub recipient_address: BitcoinAddress,
+}
+
+/// The inscription transferred or created by a pending transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingInscription {
+    pub inscription_id: InscriptionID,
+    pub object_id: ObjectID,
+    /// Whether the inscription is created by the pending transaction
+    pub is_new: bool,
+    /// The location of the inscription after the transaction is confirmed,
+    /// none if the inscription is paid to the miner as fee or the input values are unresolved
+    pub sat_point: Option<SatPoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingTx {
+    pub txid: AccountAddress,
+    pub spent_utxos: Vec<PendingSpentUTXO>,
+    pub created_utxos: Vec<PendingCreatedUTXO>,
+    pub inscriptions: Vec<PendingInscription>,
+    /// Whether the values of all the spent UTXOs are resolved
+    pub resolved: bool,
+    /// The timestamp in seconds when the transaction is first seen in the mempool
+    pub first_seen: u64,
+}
+
+impl PendingTx {
+    pub fn spends(&self, outpoint: &OutPoint) -> bool {
+        self.spent_utxos
+            .iter()
+            .any(|utxo| &utxo.outpoint == outpoint)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PendingTxFilter {
+    Txid(AccountAddress),
+    /// The pending transaction which spends the UTXO
+    SpentUTXO(OutPoint),
+    /// The pending transactions which have an output to the recipient
+    Recipient(BitcoinAddress),
+    All,
+}
+
+impl PendingTxFilter {
+    pub fn matches(&self, tx: &PendingTx) -> bool {
+        match self {
+            PendingTxFilter::Txid(txid) => &tx.txid == txid,
+            PendingTxFilter::SpentUTXO(outpoint) => tx.spends(outpoint),
+            PendingTxFilter::Recipient(address) => tx
+                .created_utxos
+                .iter()
+                .any(|utxo| &utxo.recipient_address == address),
+            PendingTxFilter::All => true,
+        }
+    }
+}
+
+/// Match the sat at the `input_offset` of all the inputs to the output, same as the `ord::match_utxo_and_generate_sat_point`,
+/// the UTXO spent follows "First in First out".
+/// Return the output index and the offset in the output, none if the sat is paid to the miner as fee.
+pub fn match_output(tx: &Transaction, input_offset: u64) -> Option<(u32, u64)> {
+    let mut output_value_accumulator = 0u64;
+    for (idx, output) in tx.output.iter().enumerate() {
+        output_value_accumulator += output.value;
+        if output_value_accumulator > input_offset {
+            return Some((
+                idx as u32,
+                output.value - (output_value_accumulator - input_offset),
+            ));
+        }
+    }
+    None
+}
+
+/// The offset of the sat in all the inputs, `offset` is the position in the input at `input_index`
+pub fn input_offset(input_values: &[u64], input_index: usize, offset: u64) -> u64 {
+    input_values.iter().take(input_index).sum::<u64>() + offset
+}
+
+/// The sat point of the new inscription, same as the `ord::process_transaction`:
+/// when the number of the outputs equals the number of the inscriptions, every inscription goes to a separate output,
+/// otherwise all the inscriptions go to the first output.
+pub fn new_inscription_sat_point(
+    tx: &Transaction,
+    inscription_index: usize,
+    inscriptions_len: usize,
+    inscription_offset: u64,
+) -> Option<SatPoint> {
+    if tx.output.is_empty() {
+        return None;
+    }
+    let (output_index, offset) = if tx.output.len() == inscriptions_len {
+        (inscription_index as u32, 0)
+    } else {
+        (0, inscription_offset)
+    };
+    Some(SatPoint {
+        outpoint: OutPoint::new(tx.id, output_index),
+        offset,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::types::{ScriptBuf, TxOut};
+
+    fn new_tx(output_values: Vec<u64>) -> Transaction {
+        Transaction {
+            id: AccountAddress::random(),
+            version: 2,
+            lock_time: 0,
+            input: vec![],
+            output: output_values
+                .into_iter()
+                .map(|value| TxOut {
+                    value,
+                    script_pubkey: ScriptBuf { bytes: vec![] },
+                    recipient_address: BitcoinAddress::default(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_match_output() {
+        let tx = new_tx(vec![100, 50]);
+        assert_eq!(match_output(&tx, 0), Some((0, 0)));
+        assert_eq!(match_output(&tx, 99), Some((0, 99)));
+        assert_eq!(match_output(&tx, 100), Some((1, 0)));
+        assert_eq!(match_output(&tx, 149), Some((1, 49)));
+        assert_eq!(match_output(&tx, 150), None);
+        assert_eq!(input_offset(&[100, 200, 300], 2, 10), 310);
+    }
+
+    #[test]
+    fn test_new_inscription_sat_point() {
+        let tx = new_tx(vec![546, 546]);
+        let sat_point = new_inscription_sat_point(&tx, 1, 2, 1000).unwrap();
+        assert_eq!(sat_point.outpoint, OutPoint::new(tx.id, 1));
+        assert_eq!(sat_point.offset, 0);
+        let sat_point = new_inscription_sat_point(&tx, 0, 1, 1000).unwrap();
+        assert_eq!(sat_point.outpoint, OutPoint::new(tx.id, 0));
+        assert_eq!(sat_point.offset, 1000);
+    }
+}
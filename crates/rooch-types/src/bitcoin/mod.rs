@@ -28,9 +28,12 @@ pub const MODULE_NAME: &IdentStr = ident_str!("bitcoin");
 /// Module binding for the Framework
 pub mod brc20;
 pub mod genesis;
+pub mod mempool;
 pub mod network;
 pub mod ord;
 pub mod pending_block;
+pub mod runes;
+pub mod sat;
 pub mod types;
 pub mod utxo;
 
@@ -25,6 +25,8 @@ use std::fmt;
 use std::fmt::{Display, Formatter};
 
 pub const MODULE_NAME: &IdentStr = ident_str!("ord");
+/// The dynamic field key of the sat which the inscription is inscribed on
+pub const INSCRIPTION_SAT_FIELD: &[u8] = b"sat";
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq)]
 pub struct InscriptionID {
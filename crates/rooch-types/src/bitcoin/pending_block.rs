@@ -39,6 +39,21 @@ impl MoveStructState for PendingTxs {
     }
 }
 
+/// The checkpoint recorded by the relayer when the Bitcoin chain reorgs deeper than the `reorg_block_count`,
+/// the blocks from the `fork_height` may already be executed and can not be rolled back by the pending block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeepReorgCheckpoint {
+    /// The first block height which is not in the Bitcoin main chain anymore
+    pub fork_height: u64,
+    /// The last block in both the Rooch and the Bitcoin main chain
+    pub common_ancestor: BlockHeightHash,
+    /// The best block in Rooch when the reorg is detected
+    pub best_block: BlockHeightHash,
+    pub reorg_block_count: u64,
+    /// The timestamp in seconds when the reorg is detected
+    pub detected_at: u64,
+}
+
 /// Rust bindings for BitcoinMove bitcoin module
 pub struct PendingBlockModule<'a> {
     caller: &'a dyn MoveFunctionCaller,
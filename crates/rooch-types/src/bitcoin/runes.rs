@@ -0,0 +1,510 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::types::OutPoint;
+use crate::addresses::BITCOIN_MOVE_ADDRESS;
+use anyhow::{bail, Result};
+use move_core_types::value::MoveValue;
+use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
+use moveos_types::state::{MoveState, MoveStructState, MoveStructType};
+use moveos_types::{
+    module_binding::{ModuleBinding, MoveFunctionCaller},
+    move_std::{option::MoveOption, string::MoveString},
+    moveos_std::{
+        object::{self, ObjectID},
+        tx_context::TxContext,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub const MODULE_NAME: &IdentStr = ident_str!("runes");
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, Eq, Default, PartialOrd, Ord)]
+pub struct RuneId {
+    pub block: u64,
+    pub tx: u32,
+}
+
+impl RuneId {
+    pub fn new(block: u64, tx: u32) -> Self {
+        Self { block, tx }
+    }
+}
+
+impl Display for RuneId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.block, self.tx)
+    }
+}
+
+impl FromStr for RuneId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (block, tx) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Invalid rune id: {}, expect block:tx", s))?;
+        Ok(Self {
+            block: block.parse()?,
+            tx: tx.parse()?,
+        })
+    }
+}
+
+impl MoveStructType for RuneId {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("RuneId");
+}
+
+impl MoveStructState for RuneId {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![u64::type_layout(), u32::type_layout()])
+    }
+}
+
+/// The rune name, it is encoded as a modified base-26 integer
+#[derive(Debug, PartialEq, Clone, Copy, Eq, PartialOrd, Ord)]
+pub struct Rune(pub u128);
+
+impl Display for Rune {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut n = self.0;
+        if n == u128::MAX {
+            return write!(f, "BCGDENLQRQWDSLRUGSNLBTMFIJAV");
+        }
+
+        n += 1;
+        let mut symbol = String::new();
+        while n > 0 {
+            symbol.push(char::from(b'A' + ((n - 1) % 26) as u8));
+            n = (n - 1) / 26;
+        }
+
+        for c in symbol.chars().rev() {
+            write!(f, "{c}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Rune {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut x = 0u128;
+        for (i, c) in s.chars().enumerate() {
+            if i > 0 {
+                x = x
+                    .checked_add(1)
+                    .ok_or_else(|| anyhow::anyhow!("Rune name out of range: {}", s))?;
+            }
+            x = x
+                .checked_mul(26)
+                .ok_or_else(|| anyhow::anyhow!("Rune name out of range: {}", s))?;
+            match c {
+                'A'..='Z' => {
+                    x = x
+                        .checked_add(c as u128 - 'A' as u128)
+                        .ok_or_else(|| anyhow::anyhow!("Rune name out of range: {}", s))?;
+                }
+                _ => bail!("Invalid character in rune name: {}", c),
+            }
+        }
+        Ok(Rune(x))
+    }
+}
+
+/// The rune name with the spacers, such as `UNCOMMON•GOODS`
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub struct SpacedRune {
+    pub rune: Rune,
+    pub spacers: u32,
+}
+
+impl SpacedRune {
+    pub fn new(rune: Rune, spacers: u32) -> Self {
+        Self { rune, spacers }
+    }
+}
+
+impl Display for SpacedRune {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let rune = self.rune.to_string();
+
+        for (i, c) in rune.chars().enumerate() {
+            write!(f, "{c}")?;
+
+            if i < rune.len() - 1 && self.spacers & 1 << i != 0 {
+                write!(f, "•")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for SpacedRune {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut rune = String::new();
+        let mut spacers = 0u32;
+
+        for c in s.chars() {
+            match c {
+                'A'..='Z' => rune.push(c),
+                '.' | '•' => {
+                    if rune.is_empty() {
+                        bail!("Leading spacer in rune name: {}", s);
+                    }
+                    let flag = 1 << (rune.len() - 1);
+                    if spacers & flag != 0 {
+                        bail!("Double spacer in rune name: {}", s);
+                    }
+                    spacers |= flag;
+                }
+                _ => bail!("Invalid character in rune name: {}", c),
+            }
+        }
+
+        if 32 - spacers.leading_zeros() >= rune.len() as u32 {
+            bail!("Trailing spacer in rune name: {}", s);
+        }
+
+        Ok(SpacedRune {
+            rune: rune.parse()?,
+            spacers,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Default)]
+pub struct Terms {
+    pub amount: MoveOption<u128>,
+    pub cap: MoveOption<u128>,
+    pub height_start: MoveOption<u64>,
+    pub height_end: MoveOption<u64>,
+    pub offset_start: MoveOption<u64>,
+    pub offset_end: MoveOption<u64>,
+}
+
+impl MoveStructType for Terms {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("Terms");
+}
+
+impl MoveStructState for Terms {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            MoveOption::<u128>::type_layout(),
+            MoveOption::<u128>::type_layout(),
+            MoveOption::<u64>::type_layout(),
+            MoveOption::<u64>::type_layout(),
+            MoveOption::<u64>::type_layout(),
+            MoveOption::<u64>::type_layout(),
+        ])
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Default)]
+pub struct Etching {
+    pub divisibility: MoveOption<u8>,
+    pub premine: MoveOption<u128>,
+    pub rune: MoveOption<u128>,
+    pub spacers: MoveOption<u32>,
+    pub symbol: MoveOption<u32>,
+    pub terms: MoveOption<Terms>,
+    pub turbo: bool,
+}
+
+impl MoveStructType for Etching {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("Etching");
+}
+
+impl MoveStructState for Etching {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            MoveOption::<u8>::type_layout(),
+            MoveOption::<u128>::type_layout(),
+            MoveOption::<u128>::type_layout(),
+            MoveOption::<u32>::type_layout(),
+            MoveOption::<u32>::type_layout(),
+            MoveOption::<Terms>::type_layout(),
+            bool::type_layout(),
+        ])
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq)]
+pub struct Edict {
+    pub id: RuneId,
+    pub amount: u128,
+    pub output: u32,
+}
+
+impl MoveStructType for Edict {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("Edict");
+}
+
+impl MoveStructState for Edict {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            RuneId::type_layout(),
+            u128::type_layout(),
+            u32::type_layout(),
+        ])
+    }
+}
+
+/// The artifact decoded from a transaction, a runestone or a cenotaph
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Default)]
+pub struct Artifact {
+    pub cenotaph: bool,
+    pub flaw: MoveOption<MoveString>,
+    pub edicts: Vec<Edict>,
+    pub etching: MoveOption<Etching>,
+    pub mint: MoveOption<RuneId>,
+    pub pointer: MoveOption<u32>,
+    pub commitment_inputs: Vec<u32>,
+}
+
+impl MoveStructType for Artifact {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("Artifact");
+}
+
+impl MoveStructState for Artifact {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            bool::type_layout(),
+            MoveOption::<MoveString>::type_layout(),
+            Vec::<Edict>::type_layout(),
+            MoveOption::<Etching>::type_layout(),
+            MoveOption::<RuneId>::type_layout(),
+            MoveOption::<u32>::type_layout(),
+            Vec::<u32>::type_layout(),
+        ])
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq)]
+pub struct RuneEntry {
+    pub id: RuneId,
+    pub rune: u128,
+    pub spacers: u32,
+    pub number: u64,
+    pub etching: AccountAddress,
+    pub divisibility: u8,
+    pub symbol: MoveOption<u32>,
+    pub premine: u128,
+    pub terms: MoveOption<Terms>,
+    pub turbo: bool,
+    pub mints: u128,
+    pub burned: u128,
+    pub timestamp: u32,
+}
+
+impl RuneEntry {
+    pub fn spaced_rune(&self) -> SpacedRune {
+        SpacedRune::new(Rune(self.rune), self.spacers)
+    }
+}
+
+impl MoveStructType for RuneEntry {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("RuneEntry");
+}
+
+impl MoveStructState for RuneEntry {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            RuneId::type_layout(),
+            u128::type_layout(),
+            u32::type_layout(),
+            u64::type_layout(),
+            AccountAddress::type_layout(),
+            u8::type_layout(),
+            MoveOption::<u32>::type_layout(),
+            u128::type_layout(),
+            MoveOption::<Terms>::type_layout(),
+            bool::type_layout(),
+            u128::type_layout(),
+            u128::type_layout(),
+            u32::type_layout(),
+        ])
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq)]
+pub struct RuneBalance {
+    pub id: RuneId,
+    pub amount: u128,
+}
+
+impl MoveStructType for RuneBalance {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("RuneBalance");
+}
+
+impl MoveStructState for RuneBalance {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            RuneId::type_layout(),
+            u128::type_layout(),
+        ])
+    }
+}
+
+/// The rune balances of an output, it is sealed to the UTXO of the output
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq)]
+pub struct RuneBalances {
+    pub outpoint: OutPoint,
+    pub balances: Vec<RuneBalance>,
+}
+
+impl MoveStructType for RuneBalances {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("RuneBalances");
+}
+
+impl MoveStructState for RuneBalances {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            OutPoint::type_layout(),
+            Vec::<RuneBalance>::type_layout(),
+        ])
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuneStore {
+    pub next_number: u64,
+}
+
+impl RuneStore {
+    pub fn object_id() -> ObjectID {
+        object::named_object_id(&Self::struct_tag())
+    }
+}
+
+impl MoveStructType for RuneStore {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("RuneStore");
+}
+
+impl MoveStructState for RuneStore {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![u64::type_layout()])
+    }
+}
+
+pub fn derive_rune_entry_id(id: &RuneId) -> ObjectID {
+    object::custom_object_id_with_parent::<RuneId, RuneEntry>(RuneStore::object_id(), id)
+}
+
+pub fn derive_rune_balances_id(outpoint: &OutPoint) -> ObjectID {
+    object::custom_object_id(outpoint, &RuneBalances::struct_tag())
+}
+
+/// Rust bindings for BitcoinMove runes module
+pub struct RunesModule<'a> {
+    caller: &'a dyn MoveFunctionCaller,
+}
+
+impl RunesModule<'_> {
+    pub const GET_RUNE_ID_FUNCTION_NAME: &'static IdentStr = ident_str!("get_rune_id");
+
+    /// Get the RuneId by the rune name
+    pub fn get_rune_id(&self, rune: &Rune) -> Result<Option<RuneId>> {
+        let call = Self::create_function_call(
+            Self::GET_RUNE_ID_FUNCTION_NAME,
+            vec![],
+            vec![MoveValue::U128(rune.0)],
+        );
+        let ctx = TxContext::new_readonly_ctx(AccountAddress::ONE);
+        let rune_id = self
+            .caller
+            .call_function(&ctx, call)?
+            .into_result()
+            .map(|mut values| {
+                let value = values.pop().expect("should have one return value");
+                bcs::from_bytes::<MoveOption<RuneId>>(&value.value)
+                    .expect("should be a valid MoveOption<RuneId>")
+                    .into()
+            })?;
+        Ok(rune_id)
+    }
+}
+
+impl<'a> ModuleBinding<'a> for RunesModule<'a> {
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const MODULE_ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+
+    fn new(caller: &'a impl MoveFunctionCaller) -> Self
+    where
+        Self: Sized,
+    {
+        Self { caller }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rune_name() {
+        for (name, value) in [
+            ("A", 0u128),
+            ("Z", 25),
+            ("AA", 26),
+            ("AAAAAAAAAAAAA", 99246114928149462),
+            ("UNCOMMONGOODS", 2055900680524219742),
+            (
+                "AAAAAAAAAAAAAAAAAAAAAAAAAAA",
+                6402364363415443603228541259936211926,
+            ),
+            ("BCGDENLQRQWDSLRUGSNLBTMFIJAV", u128::MAX),
+        ] {
+            assert_eq!(Rune::from_str(name).unwrap(), Rune(value));
+            assert_eq!(Rune(value).to_string(), name);
+        }
+        assert!(Rune::from_str("BCGDENLQRQWDSLRUGSNLBTMFIJAW").is_err());
+        assert!(Rune::from_str("a").is_err());
+    }
+
+    #[test]
+    fn test_spaced_rune() {
+        let spaced_rune = SpacedRune::from_str("UNCOMMON•GOODS").unwrap();
+        assert_eq!(spaced_rune.rune, Rune(2055900680524219742));
+        assert_eq!(spaced_rune.spacers, 1 << 7);
+        assert_eq!(spaced_rune.to_string(), "UNCOMMON•GOODS");
+        assert_eq!(SpacedRune::from_str("UNCOMMON.GOODS").unwrap(), spaced_rune);
+        assert!(SpacedRune::from_str(".A").is_err());
+        assert!(SpacedRune::from_str("A.").is_err());
+        assert!(SpacedRune::from_str("A..B").is_err());
+    }
+
+    #[test]
+    fn test_rune_id() {
+        let rune_id = RuneId::from_str("840000:1").unwrap();
+        assert_eq!(rune_id, RuneId::new(840000, 1));
+        assert_eq!(rune_id.to_string(), "840000:1");
+        assert!(RuneId::from_str("840000").is_err());
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::types::OutPoint;
+use crate::addresses::BITCOIN_MOVE_ADDRESS;
+use anyhow::{ensure, Result};
+use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
+use moveos_types::move_std::option::MoveOption;
+use moveos_types::moveos_std::object::{self, ObjectID};
+use moveos_types::state::{MoveState, MoveStructState, MoveStructType};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+pub const MODULE_NAME: &IdentStr = ident_str!("sat");
+
+pub const SUBSIDY_HALVING_INTERVAL: u64 = 210000;
+pub const DIFFCHANGE_INTERVAL: u64 = 2016;
+pub const CYCLE_EPOCHS: u64 = 6;
+pub const FIRST_POST_SUBSIDY_EPOCH: u64 = 33;
+pub const COIN_VALUE: u64 = 100000000;
+/// The total supply of sats, all the sat numbers are less than it
+pub const SUPPLY: u64 = 2099999997690000;
+
+/// The sat number of the ordinal theory
+#[derive(Debug, PartialEq, Clone, Copy, Eq, PartialOrd, Ord)]
+pub struct Sat(pub u64);
+
+impl Sat {
+    pub fn new(n: u64) -> Result<Self> {
+        ensure!(
+            n < SUPPLY,
+            "Invalid sat: {}, should be less than {}",
+            n,
+            SUPPLY
+        );
+        Ok(Sat(n))
+    }
+
+    pub fn epoch(self) -> u64 {
+        let mut epoch = 0;
+        while epoch + 1 < FIRST_POST_SUBSIDY_EPOCH && epoch_starting_sat(epoch + 1) <= self.0 {
+            epoch += 1;
+        }
+        epoch
+    }
+
+    pub fn height(self) -> u64 {
+        let epoch = self.epoch();
+        epoch * SUBSIDY_HALVING_INTERVAL
+            + (self.0 - epoch_starting_sat(epoch)) / epoch_subsidy(epoch)
+    }
+
+    /// The position of the sat in the block subsidy
+    pub fn third(self) -> u64 {
+        let epoch = self.epoch();
+        (self.0 - epoch_starting_sat(epoch)) % epoch_subsidy(epoch)
+    }
+
+    pub fn rarity(self) -> Rarity {
+        if self.third() != 0 {
+            return Rarity::Common;
+        }
+        let height = self.height();
+        let hour = height / (CYCLE_EPOCHS * SUBSIDY_HALVING_INTERVAL);
+        let minute = height % SUBSIDY_HALVING_INTERVAL;
+        let second = height % DIFFCHANGE_INTERVAL;
+        if hour == 0 && minute == 0 && second == 0 {
+            Rarity::Mythic
+        } else if minute == 0 && second == 0 {
+            Rarity::Legendary
+        } else if minute == 0 {
+            Rarity::Epic
+        } else if second == 0 {
+            Rarity::Rare
+        } else {
+            Rarity::Uncommon
+        }
+    }
+}
+
+/// The block subsidy of the epoch
+pub fn epoch_subsidy(epoch: u64) -> u64 {
+    if epoch < FIRST_POST_SUBSIDY_EPOCH {
+        (50 * COIN_VALUE) >> epoch
+    } else {
+        0
+    }
+}
+
+/// The first sat of the epoch
+pub fn epoch_starting_sat(epoch: u64) -> u64 {
+    (0..epoch.min(FIRST_POST_SUBSIDY_EPOCH))
+        .map(|i| SUBSIDY_HALVING_INTERVAL * epoch_subsidy(i))
+        .sum()
+}
+
+/// The first sat mined by the block at the given height
+pub fn height_starting_sat(height: u64) -> u64 {
+    let epoch = height / SUBSIDY_HALVING_INTERVAL;
+    if epoch >= FIRST_POST_SUBSIDY_EPOCH {
+        return SUPPLY;
+    }
+    epoch_starting_sat(epoch) + (height - epoch * SUBSIDY_HALVING_INTERVAL) * epoch_subsidy(epoch)
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+    Mythic,
+}
+
+impl Display for Rarity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            Rarity::Common => "common",
+            Rarity::Uncommon => "uncommon",
+            Rarity::Rare => "rare",
+            Rarity::Epic => "epic",
+            Rarity::Legendary => "legendary",
+            Rarity::Mythic => "mythic",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The sats in [start, end)
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Serialize, Deserialize)]
+pub struct SatRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl MoveStructType for SatRange {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("SatRange");
+}
+
+impl MoveStructState for SatRange {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![u64::type_layout(), u64::type_layout()])
+    }
+}
+
+/// The location of a sat, the `offset` is the position of the sat in the output
+#[derive(Debug, PartialEq, Clone, Eq, Serialize, Deserialize)]
+pub struct SatLocation {
+    pub outpoint: OutPoint,
+    pub offset: u64,
+}
+
+impl MoveStructType for SatLocation {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("SatLocation");
+}
+
+impl MoveStructState for SatLocation {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            OutPoint::type_layout(),
+            u64::type_layout(),
+        ])
+    }
+}
+
+/// The end and the location of an indexed sat range, the location is none if the sats are lost
+#[derive(Debug, PartialEq, Clone, Eq, Serialize, Deserialize)]
+pub struct SatRangeLocation {
+    pub end: u64,
+    pub location: MoveOption<SatLocation>,
+}
+
+impl MoveStructType for SatRangeLocation {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("SatRangeLocation");
+}
+
+impl MoveStructState for SatRangeLocation {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            u64::type_layout(),
+            MoveOption::<SatLocation>::type_layout(),
+        ])
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SatStore {
+    pub lost_sats: u64,
+}
+
+impl SatStore {
+    pub fn object_id() -> ObjectID {
+        object::named_object_id(&Self::struct_tag())
+    }
+}
+
+impl MoveStructType for SatStore {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("SatStore");
+}
+
+impl MoveStructState for SatStore {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![u64::type_layout()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sat_numbering() {
+        assert_eq!(epoch_starting_sat(1), 1050000000000000);
+        assert_eq!(epoch_starting_sat(33), SUPPLY);
+        assert_eq!(Sat(0).height(), 0);
+        assert_eq!(Sat(5000000000).height(), 1);
+        assert_eq!(Sat(1050000000000001).height(), 210000);
+        assert_eq!(Sat(1050000000000001).third(), 1);
+        assert_eq!(Sat(SUPPLY - 1).epoch(), 32);
+        assert_eq!(height_starting_sat(1), 5000000000);
+        assert_eq!(height_starting_sat(210000), 1050000000000000);
+        assert_eq!(height_starting_sat(33 * SUBSIDY_HALVING_INTERVAL), SUPPLY);
+        assert!(Sat::new(SUPPLY).is_err());
+    }
+
+    #[test]
+    fn test_rarity() {
+        assert_eq!(Sat(0).rarity(), Rarity::Mythic);
+        assert_eq!(Sat(1).rarity(), Rarity::Common);
+        assert_eq!(Sat(5000000000).rarity(), Rarity::Uncommon);
+        assert_eq!(Sat(10080000000000).rarity(), Rarity::Rare);
+        assert_eq!(Sat(1050000000000000).rarity(), Rarity::Epic);
+        assert_eq!(Sat(2067187500000000).rarity(), Rarity::Legendary);
+    }
+}
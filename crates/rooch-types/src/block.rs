@@ -1,11 +1,18 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::transaction::LedgerTransaction;
+use accumulator::inmemory::InMemoryAccumulator;
+use accumulator::proof::AccumulatorProof;
+use anyhow::{ensure, Result};
 use moveos_types::h256::H256;
+use moveos_types::transaction::TransactionExecutionInfo;
 use serde::{Deserialize, Serialize};
 
 /// The block in Rooch is constructed by the proposer, representing a batch of transactions
 /// How many transactions in the block is determined by the proposer
+/// The block commits to the state roots of all the transactions by an accumulator root,
+/// so the block size does not grow with the transaction count.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Block {
     /// The index if the block
@@ -17,9 +24,10 @@ pub struct Block {
     pub prev_tx_accumulator_root: H256,
     /// The tx accumulator root after the last transaction append to the accumulator
     pub tx_accumulator_root: H256,
-    /// The all transaction's state root
-    //TODO should we keep all the state root in the block?
-    pub state_roots: Vec<H256>,
+    /// The state root after the last transaction of the block
+    pub state_root: H256,
+    /// The accumulator root of the all transaction's state root
+    pub state_root_accumulator_root: H256,
 }
 
 impl Block {
@@ -35,7 +43,128 @@ impl Block {
             batch_size,
             prev_tx_accumulator_root,
             tx_accumulator_root,
-            state_roots,
+            state_root: state_roots.last().copied().unwrap_or_else(H256::zero),
+            state_root_accumulator_root: Self::state_root_accumulator_root_of(&state_roots),
         }
     }
+
+    /// The accumulator root of the state roots of the transactions in a block
+    pub fn state_root_accumulator_root_of(state_roots: &[H256]) -> H256 {
+        InMemoryAccumulator::from_leaves(state_roots).root_hash()
+    }
+
+    /// Prove the state root of the tx at `tx_index` is included in the block built from `state_roots`
+    pub fn state_root_proof(state_roots: &[H256], tx_index: u64) -> Result<AccumulatorProof> {
+        ensure!(
+            tx_index < state_roots.len() as u64,
+            "tx index {} out of range, batch size: {}",
+            tx_index,
+            state_roots.len()
+        );
+        InMemoryAccumulator::get_proof_from_leaves(state_roots, tx_index)
+    }
+
+    /// Verify the state root of the tx at `tx_index` is included in the block
+    pub fn verify_state_root(
+        &self,
+        tx_index: u64,
+        state_root: H256,
+        proof: &AccumulatorProof,
+    ) -> Result<()> {
+        ensure!(
+            tx_index < self.batch_size,
+            "tx index {} out of range, batch size: {}",
+            tx_index,
+            self.batch_size
+        );
+        proof.verify(self.state_root_accumulator_root, state_root, tx_index)
+    }
+}
+
+/// The legacy block format, which keeps the state roots of all the transactions in the block
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockV0 {
+    pub block_number: u128,
+    pub batch_size: u64,
+    pub prev_tx_accumulator_root: H256,
+    pub tx_accumulator_root: H256,
+    pub state_roots: Vec<H256>,
+}
+
+impl From<BlockV0> for Block {
+    fn from(block: BlockV0) -> Self {
+        Block::new(
+            block.block_number,
+            block.batch_size,
+            block.prev_tx_accumulator_root,
+            block.tx_accumulator_root,
+            block.state_roots,
+        )
+    }
+}
+
+/// The transaction which has been sequenced and executed, but not yet packed into a block by the proposer
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PendingBlockTransaction {
+    pub tx: LedgerTransaction,
+    pub tx_execution_info: TransactionExecutionInfo,
+}
+
+impl PendingBlockTransaction {
+    pub fn new(tx: LedgerTransaction, tx_execution_info: TransactionExecutionInfo) -> Self {
+        Self {
+            tx,
+            tx_execution_info,
+        }
+    }
+
+    pub fn tx_order(&self) -> u64 {
+        self.tx.sequence_info.tx_order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_state_root_proof() {
+        let state_roots = (0..5).map(|_| H256::random()).collect::<Vec<_>>();
+        let block = Block::new(1, 5, H256::random(), H256::random(), state_roots.clone());
+        assert_eq!(block.state_root, state_roots[4]);
+        for (i, state_root) in state_roots.iter().enumerate() {
+            let proof = Block::state_root_proof(&state_roots, i as u64).unwrap();
+            block
+                .verify_state_root(i as u64, *state_root, &proof)
+                .unwrap();
+            assert!(block
+                .verify_state_root(i as u64, H256::random(), &proof)
+                .is_err());
+        }
+        assert!(Block::state_root_proof(&state_roots, 5).is_err());
+    }
+
+    #[test]
+    fn test_block_v0_compat() {
+        let state_roots = vec![H256::random(), H256::random()];
+        let block_v0 = BlockV0 {
+            block_number: 0,
+            batch_size: 2,
+            prev_tx_accumulator_root: H256::zero(),
+            tx_accumulator_root: H256::random(),
+            state_roots: state_roots.clone(),
+        };
+        let bytes = bcs::to_bytes(&block_v0).unwrap();
+        let block: Block = bcs::from_bytes::<BlockV0>(&bytes).unwrap().into();
+        assert_eq!(
+            block,
+            Block::new(
+                0,
+                2,
+                H256::zero(),
+                block_v0.tx_accumulator_root,
+                state_roots
+            )
+        );
+    }
 }
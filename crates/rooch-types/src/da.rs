@@ -0,0 +1,87 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use moveos_types::h256::H256;
+use serde::{Deserialize, Serialize};
+
+/// The batch waiting in the DA submission outbox, until it is confirmed by all the DA servers
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PendingDABatch {
+    /// The L2 block number of the batch
+    pub block_number: u128,
+    /// sha3_256 hash of the batch data
+    pub batch_hash: H256,
+    /// The encoded tx list
+    pub data: Vec<u8>,
+}
+
+impl PendingDABatch {
+    pub fn new(block_number: u128, batch_hash: H256, data: Vec<u8>) -> Self {
+        Self {
+            block_number,
+            batch_hash,
+            data,
+        }
+    }
+}
+
+/// Where a segment is stored in the DA backend
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum DASegmentLocation {
+    /// Object path in the OpenDA storage
+    OpenDA { path: String },
+    /// Celestia blob, which could only be got by height and commitment
+    Celestia {
+        height: u64,
+        namespace: Vec<u8>,
+        commitment: Vec<u8>,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DASegmentIndex {
+    pub segment_number: u64,
+    /// Is the last segment in the chunk
+    pub is_last: bool,
+    pub location: DASegmentLocation,
+    /// Submitted time in milliseconds
+    pub submitted_at: u64,
+}
+
+/// The index of a chunk in a DA backend, readers could find the segments of a block by it
+/// without listing the whole storage.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DAChunkIndex {
+    pub chunk_id: u128,
+    /// The block numbers of the batches in the chunk: [start_block_number, end_block_number]
+    pub start_block_number: u128,
+    pub end_block_number: u128,
+    /// The batch hashes of the batches in the chunk, in block number order
+    pub batch_hashes: Vec<H256>,
+    pub segment_count: u64,
+    pub segments: Vec<DASegmentIndex>,
+    /// Created time in milliseconds
+    pub created_at: u64,
+    /// Updated time in milliseconds, a chunk may be rewritten after a failed submission
+    pub updated_at: u64,
+}
+
+impl DAChunkIndex {
+    pub fn contains_block(&self, block_number: u128) -> bool {
+        block_number >= self.start_block_number && block_number <= self.end_block_number
+    }
+
+    /// Is the chunk complete: all the segments from number 0 to the last one are recorded
+    pub fn is_complete(&self) -> bool {
+        self.segments.len() as u64 == self.segment_count
+            && self
+                .segments
+                .iter()
+                .enumerate()
+                .all(|(i, segment)| segment.segment_number == i as u64)
+            && self
+                .segments
+                .last()
+                .map_or(false, |segment| segment.is_last)
+    }
+}
@@ -2,9 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    address::BitcoinAddress,
     crypto::{RoochSignature, Signature, SignatureScheme},
     transaction::RoochTransactionData,
 };
+use anyhow::ensure;
+use bitcoin::{
+    address::{Address, AddressType},
+    secp256k1::Secp256k1,
+    XOnlyPublicKey,
+};
 use fastcrypto::{
     hash::Sha256,
     secp256k1::{Secp256k1PublicKey, Secp256k1Signature},
@@ -17,6 +24,7 @@ use moveos_types::{
     state::{MoveStructState, MoveStructType},
 };
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 pub const MODULE_NAME: &IdentStr = ident_str!("auth_payload");
 
@@ -129,6 +137,29 @@ impl AuthPayload {
         pk.verify_with_hash::<Sha256>(&message_hash, &signature)?;
         Ok(())
     }
+
+    /// Verify the from address is related to the public key, the same as the `bitcoin_address::verify_with_public_key` in Move,
+    /// return the from address.
+    pub fn verify_from_address(&self) -> Result<BitcoinAddress, anyhow::Error> {
+        let pk = bitcoin::PublicKey::from_slice(&self.public_key)?;
+        let from_address = std::str::from_utf8(&self.from_address)?;
+        let address = Address::from_str(from_address)?.assume_checked();
+        let is_related = match address.address_type() {
+            Some(AddressType::P2tr) => {
+                let xonly_pubkey = XOnlyPublicKey::from(pk.inner);
+                let secp = Secp256k1::verification_only();
+                let trust_addr = Address::p2tr(&secp, xonly_pubkey, None, *address.network());
+                address.is_related_to_pubkey(&pk) || trust_addr.to_string() == address.to_string()
+            }
+            _ => address.is_related_to_pubkey(&pk),
+        };
+        ensure!(
+            is_related,
+            "The from address {} is not related to the public key",
+            from_address
+        );
+        Ok(BitcoinAddress::from(address))
+    }
 }
 
 #[cfg(test)]
@@ -151,5 +182,22 @@ mod tests {
         let auth = Authenticator::bitcoin(&kp, &tx_data);
         let auth_payload = bcs::from_bytes::<AuthPayload>(&auth.payload).unwrap();
         auth_payload.verify(&tx_data).unwrap();
+        let from_address = auth_payload.verify_from_address().unwrap();
+        assert_eq!(from_address, kp.public().bitcoin_address().unwrap());
+    }
+
+    #[test]
+    pub fn test_verify_from_address_with_other_public_key() {
+        let kp = RoochKeyPair::generate_secp256k1();
+        let other_kp = RoochKeyPair::generate_secp256k1();
+        let tx_data = RoochTransactionData::new_for_test(
+            ROOCH_FRAMEWORK_ADDRESS.into(),
+            0,
+            Empty::empty_function_call().into(),
+        );
+        let auth = Authenticator::bitcoin(&kp, &tx_data);
+        let mut auth_payload = bcs::from_bytes::<AuthPayload>(&auth.payload).unwrap();
+        auth_payload.public_key = other_kp.public().as_ref().to_vec();
+        assert!(auth_payload.verify_from_address().is_err());
     }
 }
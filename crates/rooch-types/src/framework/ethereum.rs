@@ -14,6 +14,7 @@ use move_core_types::{
 };
 use moveos_types::{
     module_binding::{ModuleBinding, MoveFunctionCaller},
+    move_std::option::MoveOption,
     moveos_std::tx_context::TxContext,
     transaction::FunctionCall,
 };
@@ -100,6 +101,8 @@ pub struct EthereumModule<'a> {
 
 impl<'a> EthereumModule<'a> {
     pub const GET_BLOCK_FUNCTION_NAME: &'static IdentStr = ident_str!("get_block");
+    pub const GET_BLOCK_OPTION_FUNCTION_NAME: &'static IdentStr = ident_str!("get_block_option");
+    pub const GET_BEST_BLOCK_FUNCTION_NAME: &'static IdentStr = ident_str!("get_best_block");
     pub const EXECUTE_L1_BLOCK_FUNCTION_NAME: &'static IdentStr = ident_str!("execute_l1_block");
 
     pub fn get_block(&self, block_number: u64) -> Result<BlockHeader> {
@@ -121,6 +124,48 @@ impl<'a> EthereumModule<'a> {
         Ok(block_header)
     }
 
+    /// Get the block via block number, None if the block is not found
+    pub fn get_block_option(&self, block_number: u64) -> Result<Option<BlockHeader>> {
+        let call = FunctionCall::new(
+            Self::function_id(Self::GET_BLOCK_OPTION_FUNCTION_NAME),
+            vec![],
+            vec![MoveValue::U64(block_number).simple_serialize().unwrap()],
+        );
+        let ctx = TxContext::new_readonly_ctx(AccountAddress::ZERO);
+        let block_header =
+            self.caller
+                .call_function(&ctx, call)?
+                .into_result()
+                .map(|mut values| {
+                    let value = values.pop().expect("should have one return value");
+                    bcs::from_bytes::<MoveOption<BlockHeader>>(&value.value)
+                        .expect("should be a valid MoveOption<BlockHeader>")
+                        .into()
+                })?;
+        Ok(block_header)
+    }
+
+    /// Get the latest processed block, None if no block has been relayed
+    pub fn get_best_block(&self) -> Result<Option<BlockHeader>> {
+        let call = FunctionCall::new(
+            Self::function_id(Self::GET_BEST_BLOCK_FUNCTION_NAME),
+            vec![],
+            vec![],
+        );
+        let ctx = TxContext::new_readonly_ctx(AccountAddress::ZERO);
+        let block_header =
+            self.caller
+                .call_function(&ctx, call)?
+                .into_result()
+                .map(|mut values| {
+                    let value = values.pop().expect("should have one return value");
+                    bcs::from_bytes::<MoveOption<BlockHeader>>(&value.value)
+                        .expect("should be a valid MoveOption<BlockHeader>")
+                        .into()
+                })?;
+        Ok(block_header)
+    }
+
     pub fn create_execute_l1_block_call(block_header: &BlockHeader) -> FunctionCall {
         Self::create_function_call(
             Self::EXECUTE_L1_BLOCK_FUNCTION_NAME,
@@ -2,10 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::addresses::ROOCH_FRAMEWORK_ADDRESS;
+use anyhow::Result;
 use move_core_types::{
-    account_address::AccountAddress, ident_str, identifier::IdentStr, u256::U256,
+    account_address::AccountAddress, ident_str, identifier::IdentStr, u256::U256, value::MoveValue,
+};
+use moveos_types::{
+    module_binding::{ModuleBinding, MoveFunctionCaller},
+    moveos_std::tx_context::TxContext,
+    state::MoveStructType,
+    transaction::FunctionCall,
 };
-use moveos_types::state::MoveStructType;
 
 pub const MODULE_NAME: &IdentStr = ident_str!("gas_coin");
 pub const DECIMALS: u8 = 18;
@@ -24,3 +30,42 @@ impl GasCoin {
         U256::from(10u64.pow(DECIMALS as u32)) * value.into()
     }
 }
+
+/// Rust bindings for RoochFramework gas_coin module
+pub struct GasCoinModule<'a> {
+    caller: &'a dyn MoveFunctionCaller,
+}
+
+impl<'a> GasCoinModule<'a> {
+    pub const BALANCE_FUNCTION_NAME: &'static IdentStr = ident_str!("balance");
+
+    /// The gas coin balance of the account, the gas of its transactions is paid from it
+    pub fn balance(&self, addr: AccountAddress) -> Result<U256> {
+        let call = FunctionCall::new(
+            Self::function_id(Self::BALANCE_FUNCTION_NAME),
+            vec![],
+            vec![MoveValue::Address(addr).simple_serialize().unwrap()],
+        );
+        let ctx = TxContext::random_for_testing_only();
+        Ok(self
+            .caller
+            .call_function(&ctx, call)?
+            .into_result()
+            .map(|mut values| {
+                let value = values.pop().expect("should have one return value");
+                bcs::from_bytes::<U256>(&value.value).expect("should be a valid u256")
+            })?)
+    }
+}
+
+impl<'a> ModuleBinding<'a> for GasCoinModule<'a> {
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const MODULE_ADDRESS: AccountAddress = ROOCH_FRAMEWORK_ADDRESS;
+
+    fn new(caller: &'a impl MoveFunctionCaller) -> Self
+    where
+        Self: Sized,
+    {
+        Self { caller }
+    }
+}
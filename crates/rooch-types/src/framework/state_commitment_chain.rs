@@ -0,0 +1,185 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::addresses::ROOCH_FRAMEWORK_ADDRESS;
+use crate::block::Block;
+use crate::transaction::{LedgerTransaction, LedgerTxData};
+use anyhow::Result;
+use move_core_types::{
+    account_address::AccountAddress,
+    ident_str,
+    identifier::IdentStr,
+    value::{MoveStructLayout, MoveTypeLayout, MoveValue},
+};
+use moveos_types::{
+    module_binding::{ModuleBinding, MoveFunctionCaller},
+    move_std::option::MoveOption,
+    moveos_std::tx_context::TxContext,
+    state::{MoveStructState, MoveStructType},
+    transaction::{FunctionCall, MoveAction},
+};
+use serde::{Deserialize, Serialize};
+
+pub const MODULE_NAME: &IdentStr = ident_str!("state_commitment_chain");
+
+/// The commitment of a block in the on-chain State Commitment Chain
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct BlockCommitment {
+    pub block_number: u128,
+    pub batch_size: u64,
+    pub prev_tx_accumulator_root: Vec<u8>,
+    pub tx_accumulator_root: Vec<u8>,
+    pub state_root: Vec<u8>,
+    pub state_root_accumulator_root: Vec<u8>,
+    pub submitted_at: u64,
+}
+
+impl MoveStructType for BlockCommitment {
+    const ADDRESS: AccountAddress = ROOCH_FRAMEWORK_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("BlockCommitment");
+}
+
+impl MoveStructState for BlockCommitment {
+    fn struct_layout() -> MoveStructLayout {
+        MoveStructLayout::new(vec![
+            MoveTypeLayout::U128,
+            MoveTypeLayout::U64,
+            MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+            MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+            MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+            MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+            MoveTypeLayout::U64,
+        ])
+    }
+}
+
+impl BlockCommitment {
+    /// Check the commitment against the block proposed by the proposer
+    pub fn is_commitment_of(&self, block: &Block) -> bool {
+        self.block_number == block.block_number
+            && self.batch_size == block.batch_size
+            && self.prev_tx_accumulator_root == block.prev_tx_accumulator_root.as_bytes()
+            && self.tx_accumulator_root == block.tx_accumulator_root.as_bytes()
+            && self.state_root == block.state_root.as_bytes()
+            && self.state_root_accumulator_root == block.state_root_accumulator_root.as_bytes()
+    }
+}
+
+/// Rust bindings for RoochFramework state_commitment_chain module
+pub struct StateCommitmentChainModule<'a> {
+    caller: &'a dyn MoveFunctionCaller,
+}
+
+impl<'a> StateCommitmentChainModule<'a> {
+    pub const PROPOSER_FUNCTION_NAME: &'static IdentStr = ident_str!("proposer");
+    pub const BLOCK_COUNT_FUNCTION_NAME: &'static IdentStr = ident_str!("block_count");
+    pub const GET_BLOCK_FUNCTION_NAME: &'static IdentStr = ident_str!("get_block");
+    pub const SUBMIT_BLOCK_FUNCTION_NAME: &'static IdentStr = ident_str!("submit_block");
+    pub const SET_PROPOSER_FUNCTION_NAME: &'static IdentStr = ident_str!("set_proposer");
+
+    /// The account allowed to submit the block commitments
+    pub fn proposer(&self) -> Result<AccountAddress> {
+        let call = FunctionCall::new(
+            Self::function_id(Self::PROPOSER_FUNCTION_NAME),
+            vec![],
+            vec![],
+        );
+        let ctx = TxContext::random_for_testing_only();
+        Ok(self
+            .caller
+            .call_function(&ctx, call)?
+            .into_result()
+            .map(|mut values| {
+                let value = values.pop().expect("should have one return value");
+                bcs::from_bytes::<AccountAddress>(&value.value).expect("should be a valid address")
+            })?)
+    }
+
+    /// How many blocks have been committed, it is also the next block number to submit
+    pub fn block_count(&self) -> Result<u128> {
+        let call = FunctionCall::new(
+            Self::function_id(Self::BLOCK_COUNT_FUNCTION_NAME),
+            vec![],
+            vec![],
+        );
+        let ctx = TxContext::random_for_testing_only();
+        Ok(self
+            .caller
+            .call_function(&ctx, call)?
+            .into_result()
+            .map(|mut values| {
+                let value = values.pop().expect("should have one return value");
+                bcs::from_bytes::<u128>(&value.value).expect("should be a valid u128")
+            })?)
+    }
+
+    pub fn get_block(&self, block_number: u128) -> Result<Option<BlockCommitment>> {
+        let call = FunctionCall::new(
+            Self::function_id(Self::GET_BLOCK_FUNCTION_NAME),
+            vec![],
+            vec![MoveValue::U128(block_number).simple_serialize().unwrap()],
+        );
+        let ctx = TxContext::random_for_testing_only();
+        let block = self
+            .caller
+            .call_function(&ctx, call)?
+            .into_result()
+            .map(|mut values| {
+                let value = values.pop().expect("should have one return value");
+                bcs::from_bytes::<MoveOption<BlockCommitment>>(&value.value)
+                    .expect("should be a valid MoveOption<BlockCommitment>")
+                    .into()
+            })?;
+        Ok(block)
+    }
+
+    /// The action to submit the commitment of the block, the final state root and the state root accumulator root of the block are committed
+    pub fn create_submit_block_action(block: &Block) -> MoveAction {
+        Self::create_move_action(
+            Self::SUBMIT_BLOCK_FUNCTION_NAME,
+            vec![],
+            vec![
+                MoveValue::U128(block.block_number),
+                MoveValue::U64(block.batch_size),
+                MoveValue::vector_u8(block.prev_tx_accumulator_root.as_bytes().to_vec()),
+                MoveValue::vector_u8(block.tx_accumulator_root.as_bytes().to_vec()),
+                MoveValue::vector_u8(block.state_root.as_bytes().to_vec()),
+                MoveValue::vector_u8(block.state_root_accumulator_root.as_bytes().to_vec()),
+            ],
+        )
+    }
+
+    /// The action to change the proposer, it must be signed by the sequencer
+    pub fn create_set_proposer_action(proposer: AccountAddress) -> MoveAction {
+        Self::create_move_action(
+            Self::SET_PROPOSER_FUNCTION_NAME,
+            vec![],
+            vec![MoveValue::Address(proposer)],
+        )
+    }
+
+    /// Whether the transaction submits a block commitment to the SCC
+    pub fn is_submit_block_tx(tx: &LedgerTransaction) -> bool {
+        match &tx.data {
+            LedgerTxData::L2Tx(l2_tx) => matches!(
+                l2_tx.action(),
+                MoveAction::Function(call)
+                    if call.function_id == Self::function_id(Self::SUBMIT_BLOCK_FUNCTION_NAME)
+            ),
+            _ => false,
+        }
+    }
+}
+
+impl<'a> ModuleBinding<'a> for StateCommitmentChainModule<'a> {
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const MODULE_ADDRESS: AccountAddress = ROOCH_FRAMEWORK_ADDRESS;
+
+    fn new(caller: &'a impl MoveFunctionCaller) -> Self
+    where
+        Self: Sized,
+    {
+        Self { caller }
+    }
+}
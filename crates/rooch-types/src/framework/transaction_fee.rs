@@ -0,0 +1,54 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::addresses::ROOCH_FRAMEWORK_ADDRESS;
+use anyhow::Result;
+use move_core_types::{
+    account_address::AccountAddress, ident_str, identifier::IdentStr, u256::U256, value::MoveValue,
+};
+use moveos_types::{
+    module_binding::{ModuleBinding, MoveFunctionCaller},
+    moveos_std::tx_context::TxContext,
+    transaction::FunctionCall,
+};
+
+pub const MODULE_NAME: &IdentStr = ident_str!("transaction_fee");
+
+/// Rust bindings for RoochFramework transaction_fee module
+pub struct TransactionFeeModule<'a> {
+    caller: &'a dyn MoveFunctionCaller,
+}
+
+impl<'a> TransactionFeeModule<'a> {
+    pub const CALCULATE_GAS_FUNCTION_NAME: &'static IdentStr = ident_str!("calculate_gas");
+
+    /// The gas coin amount of the gas, the sender must hold the amount of the max gas to submit a transaction
+    pub fn calculate_gas(&self, gas_amount: u64) -> Result<U256> {
+        let call = FunctionCall::new(
+            Self::function_id(Self::CALCULATE_GAS_FUNCTION_NAME),
+            vec![],
+            vec![MoveValue::U64(gas_amount).simple_serialize().unwrap()],
+        );
+        let ctx = TxContext::random_for_testing_only();
+        Ok(self
+            .caller
+            .call_function(&ctx, call)?
+            .into_result()
+            .map(|mut values| {
+                let value = values.pop().expect("should have one return value");
+                bcs::from_bytes::<U256>(&value.value).expect("should be a valid u256")
+            })?)
+    }
+}
+
+impl<'a> ModuleBinding<'a> for TransactionFeeModule<'a> {
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const MODULE_ADDRESS: AccountAddress = ROOCH_FRAMEWORK_ADDRESS;
+
+    fn new(caller: &'a impl MoveFunctionCaller) -> Self
+    where
+        Self: Sized,
+    {
+        Self { caller }
+    }
+}
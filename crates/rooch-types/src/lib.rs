@@ -8,6 +8,7 @@ pub mod bitcoin;
 pub mod block;
 pub mod coin_type;
 pub mod crypto;
+pub mod da;
 pub mod error;
 pub mod framework;
 pub mod function_arg;
@@ -16,7 +17,9 @@ pub mod indexer;
 pub mod into_address;
 pub mod key_struct;
 pub mod multichain_id;
+pub mod relayer;
 pub mod rooch_network;
 pub mod sequencer;
+pub mod subscription;
 pub mod test_utils;
 pub mod transaction;
@@ -0,0 +1,32 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+/// The status of a L1 relayer, for monitoring the relayer is stuck or paused
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelayerStatus {
+    pub name: String,
+    /// The failure policy of the relayer, `continue`, `pause` or `retry:<times>`
+    pub failure_policy: String,
+    /// The height of the last L1 block successfully relayed
+    pub last_relayed_height: Option<u64>,
+    /// The hash of the last L1 block successfully relayed
+    pub last_relayed_hash: Option<Vec<u8>>,
+    /// The latest block height of the L1 chain seen by the relayer
+    pub l1_tip_height: Option<u64>,
+    pub last_error: Option<String>,
+    /// The timestamp in seconds of the last error
+    pub last_error_at: Option<u64>,
+    pub paused: bool,
+}
+
+impl RelayerStatus {
+    /// How many L1 blocks the relayer is behind the L1 tip
+    pub fn lag(&self) -> Option<u64> {
+        match (self.l1_tip_height, self.last_relayed_height) {
+            (Some(tip), Some(relayed)) => Some(tip.saturating_sub(relayed)),
+            _ => None,
+        }
+    }
+}
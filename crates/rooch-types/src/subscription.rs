@@ -0,0 +1,125 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::language_storage::{StructTag, TypeTag};
+use moveos_types::move_types::struct_tag_match;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
+use moveos_types::state::{ObjectChange, StateChangeSet};
+
+/// The filter of the object changes subscription
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ObjectChangeFilter {
+    /// The changes of the object
+    ObjectId(ObjectID),
+    /// The changes of the objects with the value type, the type params are ignored if the filter has none
+    ObjectType(StructTag),
+}
+
+impl ObjectChangeFilter {
+    pub fn matches(&self, metadata: &ObjectMeta) -> bool {
+        match self {
+            ObjectChangeFilter::ObjectId(object_id) => &metadata.id == object_id,
+            ObjectChangeFilter::ObjectType(object_type) => match &metadata.object_type {
+                TypeTag::Struct(struct_tag) => struct_tag_match(object_type, struct_tag),
+                _ => false,
+            },
+        }
+    }
+
+    /// Collect the matched object changes in the change set, include the changes of the child objects.
+    /// The matched change is returned with its fields, and its fields are not matched again.
+    pub fn filter_changes(&self, changeset: &StateChangeSet) -> Vec<ObjectChange> {
+        let mut matched = vec![];
+        let mut stack = changeset.changes.values().collect::<Vec<_>>();
+        while let Some(change) = stack.pop() {
+            if self.matches(&change.metadata) {
+                matched.push(change.clone());
+            } else {
+                stack.extend(change.fields.values());
+            }
+        }
+        matched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+    use moveos_types::state::Op;
+    use std::collections::BTreeMap;
+
+    fn new_struct_tag(name: &str, type_params: Vec<TypeTag>) -> StructTag {
+        StructTag {
+            address: AccountAddress::ONE,
+            module: Identifier::new("test").unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params,
+        }
+    }
+
+    fn new_change(id: ObjectID, object_type: StructTag) -> ObjectChange {
+        let metadata = ObjectMeta::new(
+            id,
+            AccountAddress::ZERO,
+            0,
+            None,
+            0,
+            0,
+            0,
+            TypeTag::Struct(Box::new(object_type)),
+        );
+        ObjectChange::new(metadata, Op::New(vec![]))
+    }
+
+    fn matched_ids(filter: ObjectChangeFilter, changeset: &StateChangeSet) -> Vec<ObjectID> {
+        filter
+            .filter_changes(changeset)
+            .into_iter()
+            .map(|change| change.metadata.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_changes() {
+        let parent_id = ObjectID::random();
+        let child_id = parent_id.child_id(ObjectID::random().field_key());
+        let mut parent = new_change(parent_id.clone(), new_struct_tag("Parent", vec![]));
+        let child = new_change(
+            child_id.clone(),
+            new_struct_tag("Child", vec![TypeTag::U64]),
+        );
+        parent.fields.insert(child_id.field_key(), child);
+        let changeset = StateChangeSet {
+            state_root: Default::default(),
+            global_size: 0,
+            changes: BTreeMap::from([(parent_id.field_key(), parent)]),
+        };
+
+        assert_eq!(
+            matched_ids(ObjectChangeFilter::ObjectId(child_id.clone()), &changeset),
+            vec![child_id.clone()]
+        );
+        assert_eq!(
+            matched_ids(ObjectChangeFilter::ObjectId(parent_id.clone()), &changeset),
+            vec![parent_id]
+        );
+        // The type params are ignored if the filter has none
+        assert_eq!(
+            matched_ids(
+                ObjectChangeFilter::ObjectType(new_struct_tag("Child", vec![])),
+                &changeset
+            ),
+            vec![child_id]
+        );
+        assert!(matched_ids(
+            ObjectChangeFilter::ObjectType(new_struct_tag("Child", vec![TypeTag::U8])),
+            &changeset
+        )
+        .is_empty());
+        assert!(
+            matched_ids(ObjectChangeFilter::ObjectId(ObjectID::random()), &changeset).is_empty()
+        );
+    }
+}
@@ -7,11 +7,12 @@
 // Copyright (c) The Diem Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
 #[cfg(any(test, feature = "fuzzing"))]
 use fastcrypto::ed25519::Ed25519KeyPair;
 #[cfg(any(test, feature = "fuzzing"))]
 use fastcrypto::traits::KeyPair;
+use fastcrypto::traits::ToFromBytes;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest::{collection::vec, prelude::*};
 #[cfg(any(test, feature = "fuzzing"))]
@@ -20,7 +21,7 @@ use serde::{Deserialize, Serialize};
 use std::{fmt, str::FromStr};
 
 use crate::{
-    crypto::{RoochKeyPair, Signature, SignatureScheme},
+    crypto::{RoochKeyPair, RoochSignature, Signature, SignatureScheme},
     framework::{
         auth_payload::{AuthPayload, SignData},
         auth_validator::BuiltinAuthValidator,
@@ -169,6 +170,38 @@ impl Authenticator {
             payload,
         }
     }
+
+    /// Verify the builtin authenticator without accessing the state.
+    /// The signature must sign the tx hash, and the bitcoin authenticator must be derived from the sender.
+    /// The session key of the rooch authenticator and the custom authenticators can only be checked
+    /// by the auth validators on chain.
+    pub fn verify_stateless(&self, tx_data: &RoochTransactionData) -> Result<()> {
+        let builtin_validator = u8::try_from(self.auth_validator_id)
+            .ok()
+            .and_then(|flag| BuiltinAuthValidator::from_flag_byte(flag).ok());
+        match builtin_validator {
+            Some(BuiltinAuthValidator::Rooch) => {
+                let signature = Signature::from_bytes(&self.payload)?;
+                ensure!(
+                    signature.scheme() == SignatureScheme::Ed25519,
+                    "The session key only supports the ed25519 signature"
+                );
+                signature.verify(tx_data.tx_hash().as_bytes())?;
+            }
+            Some(BuiltinAuthValidator::Bitcoin) => {
+                let auth_payload = bcs::from_bytes::<AuthPayload>(&self.payload)?;
+                auth_payload.verify(tx_data)?;
+                let bitcoin_address = auth_payload.verify_from_address()?;
+                ensure!(
+                    bitcoin_address.to_rooch_address() == tx_data.sender,
+                    "The bitcoin address of the authenticator is not the sender {}",
+                    tx_data.sender
+                );
+            }
+            Some(BuiltinAuthValidator::Ethereum) | None => {}
+        }
+        Ok(())
+    }
 }
 
 impl FromStr for Authenticator {
@@ -194,8 +227,52 @@ impl fmt::Display for Authenticator {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::address::{RoochAddress, RoochSupportedAddress};
+    use crate::framework::empty::Empty;
     use proptest::prelude::*;
 
+    #[test]
+    fn test_verify_stateless() {
+        for kp in [
+            RoochKeyPair::generate_ed25519(),
+            RoochKeyPair::generate_secp256k1(),
+        ] {
+            // The session key is not related to the sender statelessly
+            let sender = match kp.public().scheme() {
+                SignatureScheme::Ed25519 => RoochAddress::random(),
+                SignatureScheme::Secp256k1 => kp.public().rooch_address().unwrap(),
+            };
+            let tx_data =
+                RoochTransactionData::new_for_test(sender, 0, Empty::empty_function_call().into());
+            Authenticator::sign(&kp, &tx_data)
+                .verify_stateless(&tx_data)
+                .unwrap();
+
+            // The authenticator of another transaction is invalid
+            let other_tx_data =
+                RoochTransactionData::new_for_test(sender, 1, Empty::empty_function_call().into());
+            assert!(Authenticator::sign(&kp, &other_tx_data)
+                .verify_stateless(&tx_data)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_verify_stateless_bitcoin_sender() {
+        let kp = RoochKeyPair::generate_secp256k1();
+        let victim = RoochKeyPair::generate_secp256k1()
+            .public()
+            .rooch_address()
+            .unwrap();
+        // The signature is valid, but the signer is not the sender
+        let tx_data =
+            RoochTransactionData::new_for_test(victim, 0, Empty::empty_function_call().into());
+        assert!(Authenticator::bitcoin(&kp, &tx_data)
+            .verify_stateless(&tx_data)
+            .is_err());
+    }
+
     proptest! {
         #[test]
         fn test_rooch_authenticator_serialize_deserialize(authenticator in any::<super::RoochAuthenticator>()) {
@@ -135,6 +135,20 @@ pub struct ExecuteTransactionResponse {
     pub output: TransactionOutput,
 }
 
+/// The status of a transaction submitted to the tx pool.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TransactionStatus {
+    /// The transaction is waiting in the tx pool to be sequenced and executed.
+    Queued,
+    /// The transaction is sequenced and executed, the execution info contains the VM status.
+    Executed { tx_order: u64 },
+    /// The transaction is sequenced but failed to execute, it is in the ledger and
+    /// will be executed again when the node restarts, so it must not be submitted again.
+    Failed { tx_order: u64, reason: String },
+    /// The transaction is removed from the tx pool without being sequenced.
+    Dropped { reason: String },
+}
+
 #[cfg(test)]
 mod tests {
     use super::rooch::RoochTransaction;
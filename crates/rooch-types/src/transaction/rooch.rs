@@ -150,6 +150,11 @@ impl RoochTransaction {
         &self.authenticator
     }
 
+    /// Verify the authenticator without accessing the state, see `Authenticator::verify_stateless`
+    pub fn verify_authenticator_stateless(&self) -> Result<()> {
+        self.authenticator.verify_stateless(&self.data)
+    }
+
     pub fn tx_size(&self) -> u64 {
         bcs::serialized_size(self).expect("serialize transaction size should success") as u64
     }
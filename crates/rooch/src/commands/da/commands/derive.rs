@@ -0,0 +1,553 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use accumulator::{Accumulator, MerkleAccumulator};
+use anyhow::{anyhow, Result};
+use bitcoin::hashes::Hash;
+use clap::Parser;
+use coerce::actor::system::ActorSystem;
+use coerce::actor::IntoActor;
+use futures::StreamExt;
+use moveos_types::h256::H256;
+use moveos_types::module_binding::MoveFunctionCaller;
+use moveos_types::transaction::VerifiedMoveOSTransaction;
+use rooch_config::da_config::{
+    DAConfig, DAServerOpenDAConfig, InternalDAServerConfig, InternalDAServerConfigType,
+    OpenDAScheme,
+};
+use rooch_config::{BitcoinRelayerConfig, RelayerFailurePolicy, RoochOpt, R_OPT_NET_HELP};
+use rooch_da::actor::da::DAActor;
+use rooch_da::chunk::DABatchV0;
+use rooch_da::proxy::DAProxy;
+use rooch_db::RoochDB;
+use rooch_executor::actor::executor::ExecutorActor;
+use rooch_genesis::RoochGenesis;
+use rooch_relayer::actor::bitcoin_client::BitcoinClientActor;
+use rooch_relayer::actor::bitcoin_client_proxy::BitcoinClientProxy;
+use rooch_store::meta_store::MetaStore;
+use rooch_store::proposer_store::ProposerStore;
+use rooch_store::transaction_store::TransactionStore;
+use rooch_store::RoochStore;
+use rooch_types::block::Block;
+use rooch_types::error::RoochResult;
+use rooch_types::framework::state_commitment_chain::StateCommitmentChainModule;
+use rooch_types::multichain_id::RoochMultiChainID;
+use rooch_types::rooch_network::RoochChainID;
+use rooch_types::sequencer::SequencerInfo;
+use rooch_types::transaction::{L1Block, L1BlockWithBody, LedgerTransaction, LedgerTxData};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+/// Derive the state from DA batches.
+/// Batches are read in block order, every transaction is executed without being sequenced again,
+/// the tx accumulator root and the state root are checked against the Block records.
+/// If there is no Block record in the local store, the derived Block is recorded.
+/// Every derived block is verified against its commitment in the on-chain State Commitment Chain
+/// once the commitment is derived, the derive fails if they mismatch.
+/// The blocks which are not committed yet are reported as unverified, they are verified by the next derive.
+///
+/// The rebuild is not DA-only: L2 transactions and L1 transactions are derived from the DA data,
+/// but a DA batch only contains the header of an L1 block transaction, not the block body,
+/// so a live Bitcoin RPC (`--btc-rpc-url`) is required if the batches contain Bitcoin L1 blocks.
+#[derive(Debug, Parser)]
+pub struct DeriveCommand {
+    #[clap(long = "data-dir", short = 'd')]
+    /// Path to data dir, this dir is base dir, the final data_dir is base_dir/chain_network_name
+    pub base_data_dir: Option<PathBuf>,
+
+    /// The chain network of the DA batches, the derived state is written to base_dir/chain_network_name.
+    #[clap(long, short = 'n', help = R_OPT_NET_HELP)]
+    pub chain_id: Option<RoochChainID>,
+
+    #[clap(long)]
+    /// The genesis config file path for custom chain network.
+    /// If the file path equals to builtin chain network name(local/dev/test/main), will use builtin genesis config.
+    pub genesis_config: Option<String>,
+
+    /// The DA servers to read batches from.
+    /// If not set, the OpenDA fs directory in the data dir is used.
+    #[clap(long, default_value_t)]
+    pub da: DAConfig,
+
+    /// Stop after the block is derived, derive to the last available batch if not set.
+    #[clap(long)]
+    pub end_block_number: Option<u128>,
+
+    /// The bitcoin rpc is used to get the block body of L1 block transactions,
+    /// which is not included in DA batches. Required if the batches contain Bitcoin L1 blocks.
+    #[clap(long, env = "BTC_RPC_URL", requires = "btc_rpc_username")]
+    pub btc_rpc_url: Option<String>,
+
+    #[clap(long, env = "BTC_RPC_USERNAME", requires = "btc_rpc_password")]
+    pub btc_rpc_username: Option<String>,
+
+    #[clap(long, env = "BTC_RPC_PASSWORD")]
+    pub btc_rpc_password: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeriveResult {
+    pub derived_blocks: u64,
+    pub last_block_number: Option<u128>,
+    pub last_tx_order: u64,
+    pub state_root: H256,
+    /// The last block verified against the on-chain State Commitment Chain,
+    /// the blocks after it are not committed yet.
+    pub last_verified_block_number: Option<u128>,
+}
+
+impl DeriveCommand {
+    pub async fn execute(self) -> RoochResult<DeriveResult> {
+        let opt =
+            RoochOpt::new_with_default(self.base_data_dir, self.chain_id, self.genesis_config)?;
+        let mut da = self.da;
+        if da.internal_da_server.is_none() {
+            let fs_root = opt.da_config().get_openda_fs_dir();
+            let mut config = HashMap::new();
+            config.insert("root".to_string(), fs_root.to_string_lossy().to_string());
+            da.internal_da_server = Some(InternalDAServerConfig {
+                submit_strategy: None,
+                servers: vec![InternalDAServerConfigType::OpenDa(DAServerOpenDAConfig {
+                    scheme: OpenDAScheme::Fs,
+                    config,
+                    ..Default::default()
+                })],
+            });
+        }
+
+        let rooch_db = RoochDB::init(opt.store_config())?;
+        let _genesis = RoochGenesis::load_or_init(opt.network(), &rooch_db)?;
+
+        let actor_system = ActorSystem::global_system();
+        let da_proxy = DAProxy::new(
+            DAActor::new(
+                da,
+                &actor_system,
+                rooch_db.rooch_store.clone(),
+                &prometheus::Registry::new(),
+            )
+            .await?
+            .into_actor(Some("DAProxy"), &actor_system)
+            .await?
+            .into(),
+        );
+        let bitcoin_client = match (
+            self.btc_rpc_url,
+            self.btc_rpc_username,
+            self.btc_rpc_password,
+        ) {
+            (Some(btc_rpc_url), Some(btc_rpc_user_name), Some(btc_rpc_password)) => {
+                let config = BitcoinRelayerConfig {
+                    btc_rpc_url,
+                    btc_rpc_user_name,
+                    btc_rpc_password,
+                    btc_end_block_height: None,
+                    btc_sync_block_interval: None,
+                    btc_mempool_preview: false,
+                    btc_mempool_poll_interval: None,
+                    failure_policy: RelayerFailurePolicy::default(),
+                };
+                let bitcoin_client = BitcoinClientActor::new(config)?
+                    .into_actor(Some("BitcoinClient"), &actor_system)
+                    .await?;
+                Some(BitcoinClientProxy::new(bitcoin_client.into()))
+            }
+            _ => None,
+        };
+
+        let mut deriver = Deriver::new(rooch_db, bitcoin_client)?;
+        let start_block_number = deriver.next_block_number()?;
+        info!("derive from block_number: {}", start_block_number);
+
+        let mut derived_blocks = 0u64;
+        let mut batches = da_proxy.stream_batches(start_block_number);
+        while let Some(batch) = batches.next().await {
+            let batch = batch?;
+            if let Some(end_block_number) = self.end_block_number {
+                if batch.block_number > end_block_number {
+                    break;
+                }
+            }
+            deriver.derive_batch(batch).await?;
+            derived_blocks += 1;
+        }
+
+        let last_verified_block_number = deriver.next_verify_block_number.checked_sub(1);
+        if let Some(last_block) = &deriver.last_block {
+            if last_verified_block_number < Some(last_block.block_number) {
+                warn!(
+                    "the blocks from {} to {} are not committed on chain yet, they are not verified",
+                    deriver.next_verify_block_number, last_block.block_number
+                );
+            }
+        }
+        Ok(DeriveResult {
+            derived_blocks,
+            last_block_number: deriver.last_block.as_ref().map(|block| block.block_number),
+            last_tx_order: deriver.last_sequencer_info.last_order,
+            state_root: deriver.state_root,
+            last_verified_block_number,
+        })
+    }
+}
+
+struct Deriver {
+    executor: ExecutorActor,
+    rooch_store: RoochStore,
+    tx_accumulator: MerkleAccumulator,
+    last_sequencer_info: SequencerInfo,
+    last_block: Option<Block>,
+    state_root: H256,
+    /// The next block to verify against the on-chain State Commitment Chain
+    next_verify_block_number: u128,
+    bitcoin_client: Option<BitcoinClientProxy>,
+}
+
+impl Deriver {
+    fn new(rooch_db: RoochDB, bitcoin_client: Option<BitcoinClientProxy>) -> Result<Self> {
+        let root = rooch_db
+            .latest_root()?
+            .ok_or_else(|| anyhow!("The root object should exist after genesis"))?;
+        let rooch_store = rooch_db.rooch_store.clone();
+        let last_sequencer_info = rooch_store
+            .get_meta_store()
+            .get_sequencer_info()?
+            .ok_or_else(|| anyhow!("Load sequencer info failed"))?;
+        let tx_accumulator = MerkleAccumulator::new_with_info(
+            last_sequencer_info.last_accumulator_info.clone(),
+            rooch_store.get_transaction_accumulator_store(),
+        );
+        let state_root = root.state_root();
+        let executor = ExecutorActor::new(root, rooch_db.moveos_store, rooch_store.clone())?;
+        // The blocks before the committed block count were verified by the previous derive
+        let next_verify_block_number = executor
+            .as_module_binding::<StateCommitmentChainModule>()
+            .block_count()?;
+        Ok(Self {
+            executor,
+            rooch_store,
+            tx_accumulator,
+            last_sequencer_info,
+            last_block: None,
+            state_root,
+            next_verify_block_number,
+            bitcoin_client,
+        })
+    }
+
+    /// Find the block which contains the next tx to derive
+    fn next_block_number(&mut self) -> Result<u128> {
+        if self.last_sequencer_info.last_order == 0 {
+            return Ok(0);
+        }
+        let accumulator_root = self.tx_accumulator.root_hash();
+        let mut block_number = self.rooch_store.get_last_block_number()?;
+        while let Some(number) = block_number {
+            let block = self
+                .rooch_store
+                .get_block(number)?
+                .ok_or_else(|| anyhow!("The block {} should exist", number))?;
+            if block.tx_accumulator_root == accumulator_root {
+                self.last_block = Some(block);
+                return Ok(number + 1);
+            }
+            block_number = number.checked_sub(1);
+        }
+        Err(anyhow!(
+            "Can not find the block of the last tx_order: {}, the state is not derived from DA",
+            self.last_sequencer_info.last_order
+        ))
+    }
+
+    async fn derive_batch(&mut self, batch: DABatchV0) -> Result<()> {
+        let block_number = batch.block_number;
+        let (exp_block_number, prev_tx_accumulator_root) = match &self.last_block {
+            Some(block) => (block.block_number + 1, block.tx_accumulator_root),
+            None => (0, H256::zero()),
+        };
+        if block_number != exp_block_number {
+            return Err(anyhow!(
+                "Batch is not continuous, exp block_number: {}, act: {}",
+                exp_block_number,
+                block_number
+            ));
+        }
+
+        let txs: Vec<LedgerTransaction> = bcs::from_bytes(&batch.data)?;
+        let exp_block = self.rooch_store.get_block(block_number)?;
+        if let Some(exp_block) = &exp_block {
+            if exp_block.batch_size != txs.len() as u64
+                || exp_block.prev_tx_accumulator_root != prev_tx_accumulator_root
+            {
+                return Err(anyhow!(
+                    "Batch of block {} mismatch with the Block record, batch_size: {}, block: {:?}",
+                    block_number,
+                    txs.len(),
+                    exp_block
+                ));
+            }
+        }
+
+        let exp_state_roots = self.rooch_store.get_block_state_roots(block_number)?;
+        let mut state_roots = Vec::with_capacity(txs.len());
+        for (i, tx) in txs.into_iter().enumerate() {
+            let state_root = self.derive_tx(tx).await?;
+            if let Some(exp_state_roots) = &exp_state_roots {
+                if exp_state_roots[i] != state_root {
+                    return Err(anyhow!(
+                        "State root mismatch at block {} tx index {}, exp: {:?}, act: {:?}",
+                        block_number,
+                        i,
+                        exp_state_roots[i],
+                        state_root
+                    ));
+                }
+            }
+            state_roots.push(state_root);
+        }
+
+        let tx_accumulator_root = self.tx_accumulator.root_hash();
+        let block = match exp_block {
+            Some(exp_block) => {
+                if exp_block.tx_accumulator_root != tx_accumulator_root {
+                    return Err(anyhow!(
+                        "Tx accumulator root mismatch at block {}, exp: {:?}, act: {:?}",
+                        block_number,
+                        exp_block.tx_accumulator_root,
+                        tx_accumulator_root
+                    ));
+                }
+                let state_root_accumulator_root =
+                    Block::state_root_accumulator_root_of(&state_roots);
+                if exp_block.state_root_accumulator_root != state_root_accumulator_root {
+                    return Err(anyhow!(
+                        "State root accumulator root mismatch at block {}, exp: {:?}, act: {:?}",
+                        block_number,
+                        exp_block.state_root_accumulator_root,
+                        state_root_accumulator_root
+                    ));
+                }
+                exp_block
+            }
+            None => {
+                let block = Block::new(
+                    block_number,
+                    state_roots.len() as u64,
+                    prev_tx_accumulator_root,
+                    tx_accumulator_root,
+                    state_roots.clone(),
+                );
+                self.rooch_store.save_block(block.clone(), state_roots)?;
+                block
+            }
+        };
+        info!(
+            "derived block_number: {}, batch_size: {}, tx_accumulator_root: {:?}",
+            block.block_number, block.batch_size, block.tx_accumulator_root
+        );
+        self.last_block = Some(block);
+        self.verify_committed_blocks()
+    }
+
+    /// Verify the derived blocks against the commitments in the derived State Commitment Chain.
+    /// The commitment of a block is submitted after the block, so the blocks are verified once the
+    /// commitments are derived.
+    fn verify_committed_blocks(&mut self) -> Result<()> {
+        let last_block_number = match &self.last_block {
+            Some(block) => block.block_number,
+            None => return Ok(()),
+        };
+        let scc = self
+            .executor
+            .as_module_binding::<StateCommitmentChainModule>();
+        let block_count = scc.block_count()?;
+        while self.next_verify_block_number < block_count
+            && self.next_verify_block_number <= last_block_number
+        {
+            let block_number = self.next_verify_block_number;
+            let block = self
+                .rooch_store
+                .get_block(block_number)?
+                .ok_or_else(|| anyhow!("The block {} should exist", block_number))?;
+            let commitment = scc.get_block(block_number)?.ok_or_else(|| {
+                anyhow!(
+                    "The commitment of block {} should exist, block_count: {}",
+                    block_number,
+                    block_count
+                )
+            })?;
+            if !commitment.is_commitment_of(&block) {
+                return Err(anyhow!(
+                    "Block {} mismatch with the on-chain commitment, block: {:?}, commitment: {:?}",
+                    block_number,
+                    block,
+                    commitment
+                ));
+            }
+            info!("verified block_number: {}", block_number);
+            self.next_verify_block_number += 1;
+        }
+        Ok(())
+    }
+
+    /// Execute the tx and return the state root after execution
+    async fn derive_tx(&mut self, mut tx: LedgerTransaction) -> Result<H256> {
+        let tx_order = tx.sequence_info.tx_order;
+        let exp_tx_order = self.last_sequencer_info.last_order + 1;
+        if tx_order != exp_tx_order {
+            return Err(anyhow!(
+                "Tx order is not continuous, exp: {}, act: {}",
+                exp_tx_order,
+                tx_order
+            ));
+        }
+
+        let tx_hash = tx.tx_hash();
+        let tx_accumulator_root = self.tx_accumulator.append(vec![tx_hash].as_slice())?;
+        if tx_accumulator_root != tx.sequence_info.tx_accumulator_root {
+            return Err(anyhow!(
+                "Tx accumulator root mismatch at tx_order {}, exp: {:?}, act: {:?}",
+                tx_order,
+                tx.sequence_info.tx_accumulator_root,
+                tx_accumulator_root
+            ));
+        }
+
+        let mut moveos_tx = self.validate_tx(&tx.data).await?;
+        moveos_tx.ctx.add(tx.sequence_info.clone())?;
+        let result = self.executor.execute(moveos_tx)?;
+
+        self.tx_accumulator.flush()?;
+        let sequencer_info = SequencerInfo::new(tx_order, self.tx_accumulator.get_info());
+        self.rooch_store
+            .save_sequencer_info(sequencer_info.clone())?;
+        self.rooch_store.save_transaction(tx)?;
+        self.last_sequencer_info = sequencer_info;
+        self.state_root = result.transaction_info.state_root;
+        Ok(self.state_root)
+    }
+
+    async fn validate_tx(&self, tx_data: &LedgerTxData) -> Result<VerifiedMoveOSTransaction> {
+        match tx_data {
+            LedgerTxData::L1Block(block) => {
+                let l1_block = self.get_l1_block_with_body(block.clone()).await?;
+                self.executor.validate_l1_block(l1_block)
+            }
+            LedgerTxData::L1Tx(l1_tx) => self.executor.validate_l1_tx(l1_tx.clone()),
+            LedgerTxData::L2Tx(l2_tx) => self.executor.validate_l2_tx(l2_tx.clone()),
+        }
+    }
+
+    async fn get_l1_block_with_body(&self, block: L1Block) -> Result<L1BlockWithBody> {
+        if block.chain_id != RoochMultiChainID::Bitcoin.multichain_id() {
+            return Err(anyhow!(
+                "Unsupported L1 block chain_id: {:?}",
+                block.chain_id
+            ));
+        }
+        let bitcoin_client = self.bitcoin_client.as_ref().ok_or_else(|| {
+            anyhow!(
+                "The body of L1 block {} is not included in DA batch, bitcoin rpc is required",
+                block.block_height
+            )
+        })?;
+        let block_hash = bitcoin::BlockHash::from_slice(&block.block_hash)?;
+        let block_body = bitcoin_client.get_block(block_hash).await?;
+        let block_body = rooch_types::bitcoin::types::Block::from(block_body);
+        Ok(L1BlockWithBody {
+            block,
+            block_body: block_body.encode(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use moveos_types::h256;
+    use rooch_types::transaction::{RoochTransaction, TransactionSequenceInfo};
+
+    fn new_deriver() -> Deriver {
+        let opt = RoochOpt::new_with_temp_store().unwrap();
+        let rooch_db = RoochDB::init_with_mock_metrics_for_test(opt.store_config()).unwrap();
+        RoochGenesis::load_or_init(opt.network(), &rooch_db).unwrap();
+        Deriver::new(rooch_db, None).unwrap()
+    }
+
+    fn new_batch(block_number: u128, txs: Vec<LedgerTransaction>) -> DABatchV0 {
+        let data = bcs::to_bytes(&txs).unwrap();
+        DABatchV0::new(block_number, h256::sha3_256_of(&data), data)
+    }
+
+    /// The sequence info of the next tx, with the tx accumulator root after appending the tx
+    fn next_sequence_info(
+        deriver: &Deriver,
+        tx: &mut LedgerTransaction,
+    ) -> TransactionSequenceInfo {
+        let accumulator = MerkleAccumulator::new_with_info(
+            deriver.last_sequencer_info.last_accumulator_info.clone(),
+            deriver.rooch_store.get_transaction_accumulator_store(),
+        );
+        let tx_accumulator_root = accumulator.append(vec![tx.tx_hash()].as_slice()).unwrap();
+        TransactionSequenceInfo::new(
+            deriver.last_sequencer_info.last_order + 1,
+            vec![],
+            tx_accumulator_root,
+            0,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_derive_empty_batch() {
+        let mut deriver = new_deriver();
+        assert_eq!(deriver.next_block_number().unwrap(), 0);
+
+        deriver.derive_batch(new_batch(0, vec![])).await.unwrap();
+        let block = deriver.last_block.clone().unwrap();
+        assert_eq!(block.block_number, 0);
+        assert_eq!(block.batch_size, 0);
+        assert_eq!(deriver.rooch_store.get_block(0).unwrap(), Some(block));
+        // No commitment is submitted, the derived block is not verified yet
+        assert_eq!(deriver.next_verify_block_number, 0);
+
+        let err = deriver
+            .derive_batch(new_batch(2, vec![]))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Batch is not continuous"));
+    }
+
+    #[tokio::test]
+    async fn test_derive_tx_accumulator_root_mismatch() {
+        let mut deriver = new_deriver();
+        let tx = LedgerTransaction::new_l2_tx(
+            RoochTransaction::mock(),
+            TransactionSequenceInfo::new(1, vec![], H256::random(), 0),
+        );
+        let err = deriver
+            .derive_batch(new_batch(0, vec![tx]))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Tx accumulator root mismatch"));
+    }
+
+    #[tokio::test]
+    async fn test_derive_l1_block_requires_bitcoin_rpc() {
+        let mut deriver = new_deriver();
+        let mut tx = LedgerTransaction::new_l1_block(
+            RoochMultiChainID::Bitcoin.multichain_id(),
+            1,
+            vec![0u8; 32],
+            TransactionSequenceInfo::new(0, vec![], H256::zero(), 0),
+        );
+        tx.sequence_info = next_sequence_info(&deriver, &mut tx);
+        let err = deriver
+            .derive_batch(new_batch(0, vec![tx]))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("bitcoin rpc is required"));
+    }
+}
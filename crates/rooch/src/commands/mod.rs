@@ -3,6 +3,7 @@
 
 pub mod abi;
 pub mod account;
+pub mod da;
 pub mod env;
 pub mod event;
 pub mod genesis;
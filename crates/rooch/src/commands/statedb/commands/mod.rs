@@ -21,6 +21,7 @@ pub mod export;
 pub mod genesis_ord;
 pub mod genesis_utxo;
 pub mod import;
+pub mod rewind;
 
 pub const BATCH_SIZE: usize = 5000;
 
@@ -0,0 +1,422 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use accumulator::{Accumulator, MerkleAccumulator};
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use moveos_store::transaction_store::TransactionStore as MoveOSTransactionStore;
+use moveos_types::startup_info::StartupInfo;
+use rooch_config::{RoochOpt, R_OPT_NET_HELP};
+use rooch_db::RoochDB;
+use rooch_store::proposer_store::ProposerStore;
+use rooch_store::transaction_store::TransactionStore;
+use rooch_store::RoochStore;
+use rooch_types::error::RoochResult;
+use rooch_types::multichain_id::RoochMultiChainID;
+use rooch_types::rooch_network::RoochChainID;
+use rooch_types::sequencer::SequencerInfo;
+use rooch_types::transaction::LedgerTxData;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+const REWIND_BATCH_SIZE: u64 = 10000;
+
+/// Rewind the state to the root before a Bitcoin deep reorg, the relayer replays the blocks of the new chain after restart.
+/// The server must be stopped before rewinding.
+#[derive(Debug, Parser)]
+pub struct RewindCommand {
+    /// The Bitcoin block height to rewind, the state is rewound to the root before the block is relayed.
+    /// Default to the fork height of the deep reorg checkpoint recorded by the relayer.
+    #[clap(long)]
+    pub fork_height: Option<u64>,
+
+    /// Rewind to the tx order directly, the state is rewound to the root after the tx is executed.
+    #[clap(long, conflicts_with = "fork_height")]
+    pub tx_order: Option<u64>,
+
+    #[clap(long = "data-dir", short = 'd')]
+    /// Path to data dir, this dir is base dir, the final data_dir is base_dir/chain_network_name
+    pub base_data_dir: Option<PathBuf>,
+
+    /// If local chainid, start the service with a temporary data store.
+    /// All data will be deleted when the service is stopped.
+    #[clap(long, short = 'n', help = R_OPT_NET_HELP)]
+    pub chain_id: Option<RoochChainID>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RewindOutput {
+    pub tx_order: u64,
+    pub pre_tx_order: u64,
+    pub startup_info: StartupInfo,
+}
+
+impl RewindCommand {
+    pub async fn execute(self) -> RoochResult<RewindOutput> {
+        let opt = RoochOpt::new_with_default(self.base_data_dir, self.chain_id, None)?;
+        let rooch_db = RoochDB::init(opt.store_config())?;
+        let rooch_store = &rooch_db.rooch_store;
+        let meta_store = rooch_store.get_meta_store();
+
+        let sequencer_info = meta_store
+            .get_sequencer_info()?
+            .ok_or_else(|| anyhow!("The sequencer info is not found"))?;
+        let pre_tx_order = sequencer_info.last_order;
+        let tx_order = match self.tx_order {
+            Some(tx_order) => tx_order,
+            None => {
+                let fork_height = match self.fork_height {
+                    Some(fork_height) => fork_height,
+                    None => {
+                        meta_store
+                            .get_bitcoin_reorg_checkpoint()?
+                            .ok_or_else(|| {
+                                anyhow!("No deep reorg checkpoint, please specify the --fork-height or --tx-order")
+                            })?
+                            .fork_height
+                    }
+                };
+                find_tx_order_before_block(rooch_store, pre_tx_order, fork_height)?
+            }
+        };
+
+        let tx_hash = get_tx_hashes(rooch_store, tx_order, 1)?
+            .pop()
+            .ok_or_else(|| anyhow!("The tx hash of order {} is not found", tx_order))?;
+        let execution_info = rooch_db
+            .moveos_store
+            .get_tx_execution_info(tx_hash)?
+            .ok_or_else(|| anyhow!("The execution info of tx {:?} is not found", tx_hash))?;
+
+        rewind_rooch_store(rooch_store, tx_order)?;
+        let startup_info = StartupInfo::new(execution_info.state_root, execution_info.size);
+        rooch_db
+            .moveos_store
+            .get_config_store()
+            .save_startup_info(startup_info.clone())?;
+        meta_store.remove_bitcoin_reorg_checkpoint()?;
+
+        println!(
+            "Rewind the state from tx order {} to {}, the transactions after {} are discarded.",
+            pre_tx_order, tx_order, tx_order
+        );
+        println!("The indexer is not rewound, please rebuild it if needed.");
+        Ok(RewindOutput {
+            tx_order,
+            pre_tx_order,
+            startup_info,
+        })
+    }
+}
+
+/// Rewind the sequencer and the proposer to the tx order, the transactions after it are discarded.
+/// Rewinding proposed blocks is not supported, they are already submitted to the DA,
+/// so rewinding is refused if the last proposed block includes the transactions after the tx order.
+/// The DA outbox only keeps the batches of the proposed blocks, so it is never ahead of the tx order.
+/// The commitment transactions after the tx order are discarded with the state,
+/// the commitments of the kept blocks are submitted to the on-chain SCC again after restart.
+fn rewind_rooch_store(rooch_store: &RoochStore, tx_order: u64) -> Result<SequencerInfo> {
+    let meta_store = rooch_store.get_meta_store();
+    let last_order = meta_store
+        .get_sequencer_info()?
+        .ok_or_else(|| anyhow!("The sequencer info is not found"))?
+        .last_order;
+    if tx_order > last_order {
+        return Err(anyhow!(
+            "The tx order {} is greater than the last order {}",
+            tx_order,
+            last_order
+        ));
+    }
+    ensure_not_proposed(rooch_store, tx_order, last_order)?;
+
+    let tx_hash = get_tx_hashes(rooch_store, tx_order, 1)?
+        .pop()
+        .ok_or_else(|| anyhow!("The tx hash of order {} is not found", tx_order))?;
+    let ledger_tx = rooch_store
+        .get_transaction_by_hash(tx_hash)?
+        .ok_or_else(|| anyhow!("The tx of order {} is not found", tx_order))?;
+
+    // Rebuild the tx accumulator to the tx order, the nodes are content addressed,
+    // so the accumulator nodes of the discarded txs do not affect the rebuilt one.
+    let tx_accumulator =
+        MerkleAccumulator::new_empty(rooch_store.get_transaction_accumulator_store());
+    let mut start = 0u64;
+    while start <= tx_order {
+        let limit = REWIND_BATCH_SIZE.min(tx_order - start + 1);
+        let leaves = get_tx_hashes(rooch_store, start, limit)?;
+        tx_accumulator.append(leaves.as_slice())?;
+        start += limit;
+    }
+    tx_accumulator.flush()?;
+    let accumulator_info = tx_accumulator.get_info();
+    if accumulator_info.accumulator_root != ledger_tx.sequence_info.tx_accumulator_root {
+        return Err(anyhow!(
+            "The rebuilt tx accumulator root {:?} does not match the root {:?} of tx order {}",
+            accumulator_info.accumulator_root,
+            ledger_tx.sequence_info.tx_accumulator_root,
+            tx_order
+        ));
+    }
+
+    // The discarded transactions are not proposed yet, drop them from the proposer
+    let discarded = rooch_store
+        .get_pending_transactions()?
+        .into_iter()
+        .map(|tx| tx.tx_order())
+        .filter(|order| *order > tx_order)
+        .collect::<Vec<_>>();
+    rooch_store.remove_pending_transactions(discarded)?;
+
+    let sequencer_info = SequencerInfo::new(tx_order, accumulator_info);
+    meta_store.rewind_sequencer_info(sequencer_info.clone())?;
+    Ok(sequencer_info)
+}
+
+/// Refuse to rewind if the last proposed block ends after the tx order, the proposed blocks can not be rewound
+fn ensure_not_proposed(rooch_store: &RoochStore, tx_order: u64, last_order: u64) -> Result<()> {
+    let last_block = match rooch_store.get_last_block()? {
+        Some(block) => block,
+        None => return Ok(()),
+    };
+    let mut start = tx_order + 1;
+    while start <= last_order {
+        let limit = REWIND_BATCH_SIZE.min(last_order - start + 1);
+        let tx_hashes = get_tx_hashes(rooch_store, start, limit)?;
+        for (tx, order) in rooch_store
+            .get_transactions_by_hash(tx_hashes)?
+            .into_iter()
+            .zip(start..)
+        {
+            let tx = tx.ok_or_else(|| anyhow!("The tx of order {} is not found", order))?;
+            if tx.sequence_info.tx_accumulator_root == last_block.tx_accumulator_root {
+                return Err(anyhow!(
+                    "The proposed block {} ends at tx order {}, it is already submitted to the DA, rewinding proposed blocks is not supported, can not rewind to tx order {}",
+                    last_block.block_number,
+                    order,
+                    tx_order
+                ));
+            }
+        }
+        start += limit;
+    }
+    Ok(())
+}
+
+fn get_tx_hashes(
+    rooch_store: &RoochStore,
+    start: u64,
+    limit: u64,
+) -> Result<Vec<moveos_types::h256::H256>> {
+    let tx_orders = (start..start + limit).collect::<Vec<_>>();
+    rooch_store
+        .get_tx_hashs(tx_orders)?
+        .into_iter()
+        .zip(start..)
+        .map(|(tx_hash, tx_order)| {
+            tx_hash.ok_or_else(|| anyhow!("The tx hash of order {} is not found", tx_order))
+        })
+        .collect()
+}
+
+/// Find the order of the tx before the Bitcoin block at `block_height` is relayed
+fn find_tx_order_before_block(
+    rooch_store: &RoochStore,
+    last_order: u64,
+    block_height: u64,
+) -> Result<u64> {
+    let bitcoin_chain_id = RoochMultiChainID::Bitcoin.multichain_id();
+    let mut tx_order = last_order;
+    loop {
+        let tx_hash = get_tx_hashes(rooch_store, tx_order, 1)?
+            .pop()
+            .ok_or_else(|| anyhow!("The tx hash of order {} is not found", tx_order))?;
+        let ledger_tx = rooch_store
+            .get_transaction_by_hash(tx_hash)?
+            .ok_or_else(|| anyhow!("The tx of order {} is not found", tx_order))?;
+        if let LedgerTxData::L1Block(block) = &ledger_tx.data {
+            if block.chain_id == bitcoin_chain_id {
+                if block.block_height == block_height {
+                    return tx_order
+                        .checked_sub(1)
+                        .ok_or_else(|| anyhow!("Can not rewind the genesis tx"));
+                }
+                if block.block_height < block_height {
+                    return Err(anyhow!(
+                        "The Bitcoin block at height {} is not relayed",
+                        block_height
+                    ));
+                }
+            }
+        }
+        if tx_order == 0 {
+            return Err(anyhow!(
+                "The Bitcoin block at height {} is not found",
+                block_height
+            ));
+        }
+        tx_order -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::vm_status::KeptVMStatus;
+    use moveos_types::h256::H256;
+    use moveos_types::transaction::TransactionExecutionInfo;
+    use rooch_types::block::{Block, PendingBlockTransaction};
+    use rooch_types::transaction::{LedgerTransaction, RoochTransaction, TransactionSequenceInfo};
+
+    /// Sequence the txs, the odd orders are the Bitcoin blocks from height 100, the even orders are the L2 txs.
+    /// Return the sequenced txs.
+    fn sequence_txs(rooch_store: &RoochStore, count: u64) -> Vec<LedgerTransaction> {
+        let tx_accumulator =
+            MerkleAccumulator::new_empty(rooch_store.get_transaction_accumulator_store());
+        let bitcoin_chain_id = RoochMultiChainID::Bitcoin.multichain_id();
+        let mut txs = vec![];
+        for tx_order in 0..count {
+            let sequence_info = TransactionSequenceInfo::new(tx_order, vec![], H256::zero(), 0);
+            let mut tx = if tx_order % 2 == 1 {
+                LedgerTransaction::new_l1_block(
+                    bitcoin_chain_id,
+                    100 + tx_order / 2,
+                    H256::random().0.to_vec(),
+                    sequence_info,
+                )
+            } else {
+                LedgerTransaction::new_l2_tx(RoochTransaction::mock(), sequence_info)
+            };
+            let tx_hash = tx.tx_hash();
+            tx.sequence_info.tx_accumulator_root =
+                tx_accumulator.append(vec![tx_hash].as_slice()).unwrap();
+            rooch_store.save_transaction(tx.clone()).unwrap();
+            txs.push(tx);
+        }
+        tx_accumulator.flush().unwrap();
+        rooch_store
+            .get_meta_store()
+            .save_sequencer_info(SequencerInfo::new(count - 1, tx_accumulator.get_info()))
+            .unwrap();
+        txs
+    }
+
+    fn pending_tx(tx: &LedgerTransaction) -> PendingBlockTransaction {
+        let mut tx = tx.clone();
+        let tx_hash = tx.tx_hash();
+        let execution_info = TransactionExecutionInfo::new(
+            tx_hash,
+            H256::random(),
+            0,
+            H256::zero(),
+            0,
+            KeptVMStatus::Executed,
+        );
+        PendingBlockTransaction::new(tx, execution_info)
+    }
+
+    /// Propose the block of the txs in `[start, end)`
+    fn propose_block(
+        rooch_store: &RoochStore,
+        txs: &[LedgerTransaction],
+        block_number: u128,
+        start: usize,
+        end: usize,
+    ) {
+        let prev_tx_accumulator_root = if start == 0 {
+            H256::zero()
+        } else {
+            txs[start - 1].sequence_info.tx_accumulator_root
+        };
+        let state_roots = (start..end).map(|_| H256::random()).collect::<Vec<_>>();
+        let block = Block::new(
+            block_number,
+            (end - start) as u64,
+            prev_tx_accumulator_root,
+            txs[end - 1].sequence_info.tx_accumulator_root,
+            state_roots.clone(),
+        );
+        rooch_store.save_block(block, state_roots).unwrap();
+    }
+
+    #[test]
+    fn test_find_tx_order_before_block() {
+        let (rooch_store, _tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        sequence_txs(&rooch_store, 8);
+
+        // The block 100 is at order 1, 101 at 3, 102 at 5, 103 at 7
+        assert_eq!(find_tx_order_before_block(&rooch_store, 7, 100).unwrap(), 0);
+        assert_eq!(find_tx_order_before_block(&rooch_store, 7, 102).unwrap(), 4);
+        assert_eq!(find_tx_order_before_block(&rooch_store, 7, 103).unwrap(), 6);
+        assert!(find_tx_order_before_block(&rooch_store, 7, 104)
+            .unwrap_err()
+            .to_string()
+            .contains("is not relayed"));
+        assert!(find_tx_order_before_block(&rooch_store, 7, 99)
+            .unwrap_err()
+            .to_string()
+            .contains("is not found"));
+    }
+
+    #[test]
+    fn test_rewind_rooch_store() {
+        let (rooch_store, _tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let txs = sequence_txs(&rooch_store, 8);
+        // The block 0 includes the orders 0..=2, the orders 3..=7 are pending
+        propose_block(&rooch_store, &txs, 0, 0, 3);
+        for tx in &txs[3..] {
+            rooch_store
+                .save_pending_transaction(pending_tx(tx))
+                .unwrap();
+        }
+
+        let sequencer_info = rewind_rooch_store(&rooch_store, 4).unwrap();
+        assert_eq!(sequencer_info.last_order, 4);
+        assert_eq!(
+            sequencer_info.last_accumulator_info.accumulator_root,
+            txs[4].sequence_info.tx_accumulator_root
+        );
+        assert_eq!(sequencer_info.last_accumulator_info.num_leaves, 5);
+        assert!(rooch_store.get_meta_store().get_sequencer_info().unwrap() == Some(sequencer_info));
+        let pending_orders = rooch_store
+            .get_pending_transactions()
+            .unwrap()
+            .iter()
+            .map(|tx| tx.tx_order())
+            .collect::<Vec<_>>();
+        assert_eq!(pending_orders, vec![3, 4]);
+
+        // Can not rewind forward
+        assert!(rewind_rooch_store(&rooch_store, 5)
+            .unwrap_err()
+            .to_string()
+            .contains("is greater than the last order"));
+    }
+
+    #[test]
+    fn test_rewind_rooch_store_refuse_proposed() {
+        let (rooch_store, _tmpdir) = RoochStore::mock_rooch_store().unwrap();
+        let txs = sequence_txs(&rooch_store, 8);
+        propose_block(&rooch_store, &txs, 0, 0, 3);
+        propose_block(&rooch_store, &txs, 1, 3, 6);
+
+        let err = rewind_rooch_store(&rooch_store, 4).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("The proposed block 1 ends at tx order 5"));
+        // Nothing is rewound
+        assert_eq!(
+            rooch_store
+                .get_meta_store()
+                .get_sequencer_info()
+                .unwrap()
+                .unwrap()
+                .last_order,
+            7
+        );
+
+        // Rewind to the end of the last proposed block is allowed
+        let sequencer_info = rewind_rooch_store(&rooch_store, 5).unwrap();
+        assert_eq!(sequencer_info.last_order, 5);
+    }
+}
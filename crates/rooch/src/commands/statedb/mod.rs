@@ -5,6 +5,7 @@ use crate::cli_types::CommandAction;
 use crate::commands::statedb::commands::genesis_ord::GenesisOrdCommand;
 use crate::commands::statedb::commands::genesis_utxo::GenesisUTXOCommand;
 use crate::commands::statedb::commands::import::ImportCommand;
+use crate::commands::statedb::commands::rewind::RewindCommand;
 use async_trait::async_trait;
 use clap::Parser;
 use commands::export::ExportCommand;
@@ -35,6 +36,9 @@ impl CommandAction<String> for Statedb {
             StatedbCommand::GenesisORD(genesis_ord) => genesis_ord.execute().await.map(|resp| {
                 serde_json::to_string_pretty(&resp).expect("Failed to serialize response")
             }),
+            StatedbCommand::Rewind(rewind) => rewind.execute().await.map(|resp| {
+                serde_json::to_string_pretty(&resp).expect("Failed to serialize response")
+            }),
         }
     }
 }
@@ -46,4 +50,5 @@ pub enum StatedbCommand {
     Import(ImportCommand),
     GenesisUTXO(GenesisUTXOCommand),
     GenesisORD(GenesisOrdCommand),
+    Rewind(RewindCommand),
 }
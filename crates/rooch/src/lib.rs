@@ -1,6 +1,7 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::commands::da::DA;
 use crate::commands::event::EventCommand;
 use crate::commands::indexer::Indexer;
 use crate::commands::statedb::Statedb;
@@ -48,6 +49,7 @@ pub enum Command {
     Statedb(Statedb),
     Indexer(Indexer),
     Genesis(Genesis),
+    DA(DA),
 }
 
 pub async fn run_cli(opt: RoochCli) -> RoochResult<String> {
@@ -68,5 +70,6 @@ pub async fn run_cli(opt: RoochCli) -> RoochResult<String> {
         Command::Statedb(statedb) => statedb.execute().await,
         Command::Indexer(indexer) => indexer.execute().await,
         Command::Genesis(genesis) => genesis.execute().await,
+        Command::DA(da) => da.execute().await,
     }
 }
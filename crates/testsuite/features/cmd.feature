@@ -15,6 +15,8 @@ Feature: Rooch CLI integration tests
       Then assert: "{{$.rpc[-1][0].decoded_value.value.milliseconds}} == 0"
       Then cmd: "rpc request --method rooch_getStates --params '["/object/0x2::timestamp::Timestamp",{"decode":true}]' --json"
       Then assert: "{{$.rpc[-1][0].object_type}} == '0x2::timestamp::Timestamp'"
+      Then cmd: "rpc request --method rooch_getStatesWithProof --params '["/object/0x2::timestamp::Timestamp"]' --json"
+      Then assert: "{{$.rpc[-1].states[0].state.object_type}} == '0x2::timestamp::Timestamp'"
       Then cmd: "rpc request --method rooch_getObjectStates --params '["0x4e8d2c243339c6e02f8b7dd34436a1b1eb541b0fe4d938f845f4dbb9d9f218a2", {"decode":false}]' --json"
       Then cmd: "rpc request --method rooch_getObjectStates --params '["0x4e8d2c243339c6e02f8b7dd34436a1b1eb541b0fe4d938f845f4dbb9d9f218a2", {"decode":true}]' --json"
       Then assert: "{{$.rpc[-1][0].object_type}} == '0x2::timestamp::Timestamp'"
@@ -58,6 +60,8 @@ Feature: Rooch CLI integration tests
       Then assert: "'{{$.session-key[-1]}}' not_contains error"
       Then cmd: "move run --function 0x3::empty::empty  --session-key {{$.session-key[-1][0].name}}"
       Then assert: "{{$.move[-1].execution_info.status.type}} == executed"
+      Then cmd: "rpc request --method rooch_getTransactionStatus --params '["{{$.move[-1].execution_info.tx_hash}}"]' --json"
+      Then assert: "{{$.rpc[-1].type}} == executed"
 
       # transaction
       Then cmd: "transaction get-transactions-by-order --cursor 0 --limit 1 --descending-order false"
@@ -68,6 +72,9 @@ Feature: Rooch CLI integration tests
       Then cmd: "account balance --coin-type rooch_framework::gas_coin::GasCoin"
       Then cmd: "rpc request --method rooch_getBalance --params '["{{$.address_mapping.default}}", "0x3::gas_coin::GasCoin"]' --json"
       Then assert: "'{{$.rpc[-1].balance}}' != '0'"
+      # the balance at the genesis state
+      Then cmd: "rpc request --method rooch_getBalance --params '["{{$.address_mapping.default}}", "0x3::gas_coin::GasCoin", {"txOrder":"0"}]' --json"
+      Then assert: "'{{$.rpc[-1].balance}}' == '0'"
 
       Then stop the server
 
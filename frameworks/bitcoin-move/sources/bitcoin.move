@@ -25,7 +25,10 @@ module bitcoin_move::bitcoin{
     use bitcoin_move::types::{Self, Block, Header, Transaction, BlockHeightHash};
     use bitcoin_move::ord::{Self, Inscription,Flotsam, SatPoint};
     use bitcoin_move::utxo::{Self, UTXOSeal};
-    use bitcoin_move::pending_block;
+    use bitcoin_move::pending_block::{Self, InprocessBlock};
+    use bitcoin_move::runes;
+    use bitcoin_move::pow;
+    use bitcoin_move::sat::{Self, SatRange};
 
     friend bitcoin_move::genesis;
 
@@ -34,6 +37,16 @@ module bitcoin_move::bitcoin{
     const ErrorBlockAlreadyProcessed:u64 = 2;
     /// The reorg is too deep, we need to stop the system and fix the issue
     const ErrorReorgTooDeep:u64 = 3;
+    /// The block hash is not the hash of the block header
+    const ErrorInvalidBlockHash:u64 = 4;
+    /// The block hash does not satisfy the target of the block header
+    const ErrorInvalidProofOfWork:u64 = 5;
+    /// The block is not linked to the previous block
+    const ErrorPrevBlockHashMismatch:u64 = 6;
+    /// The block bits is not the expected difficulty
+    const ErrorInvalidDifficulty:u64 = 7;
+    /// The block time is not greater than the median time of the previous blocks
+    const ErrorBlockTimeTooOld:u64 = 8;
 
     const ORDINAL_GENESIS_HEIGHT:u64 = 767430;
 
@@ -99,24 +112,25 @@ module bitcoin_move::bitcoin{
         btc_block_store.latest_block = option::some(types::new_block_height_hash(block_height, block_hash)); 
     }
 
-    fun process_tx(btc_block_store: &mut BitcoinBlockStore, tx: &Transaction, block_height: u64): vector<Flotsam>{
-        let flotsams = process_utxo(tx, block_height);
+    fun process_tx(btc_block_store: &mut BitcoinBlockStore, inprocess_block: &InprocessBlock, tx: &Transaction, block_height: u64): (vector<Flotsam>, Option<vector<SatRange>>){
+        let (flotsams, fee_sat_ranges) = process_utxo(btc_block_store, inprocess_block, tx, block_height);
         let txid = types::tx_id(tx);
         table::add(&mut btc_block_store.txs, txid, *tx);
         table::add(&mut btc_block_store.tx_to_height, txid, block_height);
         table_vec::push_back(&mut btc_block_store.tx_ids, txid);
-        flotsams
+        (flotsams, fee_sat_ranges)
     }
 
-    fun process_coinbase_tx(btc_block_store: &mut BitcoinBlockStore, tx: &Transaction, flotsams: vector<Flotsam>, block_height: u64){
-        process_coinbase_utxo(tx, flotsams, block_height);
+    fun process_coinbase_tx(btc_block_store: &mut BitcoinBlockStore, inprocess_block: &InprocessBlock, tx: &Transaction, flotsams: vector<Flotsam>, fee_sat_ranges: Option<vector<SatRange>>, block_height: u64){
+        process_coinbase_utxo(btc_block_store, inprocess_block, tx, flotsams, fee_sat_ranges, block_height);
         let txid = types::tx_id(tx);
         table::add(&mut btc_block_store.txs, txid, *tx);
         table::add(&mut btc_block_store.tx_to_height, txid, block_height);
         table_vec::push_back(&mut btc_block_store.tx_ids, txid);
     }
 
-    fun process_utxo(tx: &Transaction, block_height: u64): vector<Flotsam>{
+    /// Return the flotsams and the fee sat ranges of the tx, the fee sat ranges are none if the inputs are not tracked
+    fun process_utxo(btc_block_store: &BitcoinBlockStore, inprocess_block: &InprocessBlock, tx: &Transaction, block_height: u64): (vector<Flotsam>, Option<vector<SatRange>>){
         let txinput = types::tx_input(tx);
         let flotsams = vector::empty();
 
@@ -139,6 +153,13 @@ module bitcoin_move::bitcoin{
         let idx = 0;
         let output_seals = simple_multimap::new<u32, UTXOSeal>();
         let need_process_oridinal = need_process_oridinals(block_height);
+        let need_process_rune = need_process_runes(block_height);
+        let rune_balances = vector::empty();
+        let input_sat_ranges = if(sat::is_enabled()){
+            option::some(vector::empty())
+        }else{
+            option::none()
+        };
         while (idx < vector::length(txinput)) {
             let txin = vector::borrow(txinput, idx);
             let outpoint = *types::txin_previous_output(txin);
@@ -150,6 +171,11 @@ module bitcoin_move::bitcoin{
                     handle_sat_point(sat_points, &mut output_seals);
                     vector::append(&mut flotsams, utxo_flotsams);
                 };
+                if(need_process_rune) {
+                    runes::spend_utxo(&mut utxo_obj, &mut rune_balances);
+                };
+                let utxo_sat_ranges = utxo::remove_sat_ranges(&mut utxo_obj);
+                append_sat_ranges(&mut input_sat_ranges, utxo_sat_ranges);
 
                 let seals = utxo::remove(utxo_obj);
                 //The seals should be empty after utxo is spent
@@ -159,6 +185,7 @@ module bitcoin_move::bitcoin{
                         txid: types::tx_id(tx),
                         message: string::utf8(b"utxo not exists"),
                 });
+                input_sat_ranges = option::none();
                 //We allow the utxo not exists in the utxo store, because we may not sync the block from genesis
                 //But we should not allow the utxo not exists in the mainnet
                 if(chain_id::is_main()){
@@ -171,7 +198,7 @@ module bitcoin_move::bitcoin{
 
         // Transfer and inscribe may happen at the same transaction
         if(need_process_oridinal) {
-            let sat_points = ord::process_transaction(tx, input_utxo_values);
+            let sat_points = ord::process_transaction(tx, input_utxo_values, &input_sat_ranges);
             let idx = 0;
             let protocol = type_info::type_name<Inscription>();
             let sat_points_len = vector::length(&sat_points);
@@ -185,22 +212,60 @@ module bitcoin_move::bitcoin{
             };
         };
 
+        if(need_process_rune) {
+            runes::process_transaction(inprocess_block, tx, rune_balances, &btc_block_store.txs, &btc_block_store.tx_to_height, &mut output_seals);
+        };
+
+        let (output_sat_ranges, fee_sat_ranges) = if(option::is_some(&input_sat_ranges)){
+            let (output_sat_ranges, fee_sat_ranges) = sat::allocate(option::destroy_some(input_sat_ranges), types::tx_output(tx));
+            (option::some(output_sat_ranges), option::some(fee_sat_ranges))
+        }else{
+            (option::none(), option::none())
+        };
+
         // create new utxo
-        handle_new_utxo(tx, &mut output_seals);
+        handle_new_utxo(tx, &mut output_seals, output_sat_ranges);
 
         simple_multimap::drop(output_seals);
-        flotsams
+        (flotsams, fee_sat_ranges)
+    }
+
+    /// Append the sat ranges of a spent UTXO to the input sat ranges, the input sat ranges become none if the UTXO is not tracked
+    fun append_sat_ranges(input_sat_ranges: &mut Option<vector<SatRange>>, utxo_sat_ranges: Option<vector<SatRange>>){
+        if(option::is_none(input_sat_ranges)){
+            return
+        };
+        if(option::is_some(&utxo_sat_ranges)){
+            vector::append(option::borrow_mut(input_sat_ranges), option::destroy_some(utxo_sat_ranges));
+        }else{
+            *input_sat_ranges = option::none();
+        };
     }
 
-    fun process_coinbase_utxo(tx: &Transaction, flotsams: vector<Flotsam>, block_height: u64){
+    fun process_coinbase_utxo(btc_block_store: &BitcoinBlockStore, inprocess_block: &InprocessBlock, tx: &Transaction, flotsams: vector<Flotsam>, fee_sat_ranges: Option<vector<SatRange>>, block_height: u64){
         let output_seals = simple_multimap::new<u32, UTXOSeal>();
         if(need_process_oridinals(block_height)) {
             let sat_points = ord::handle_coinbase_tx(tx, flotsams, block_height);
             handle_sat_point(sat_points, &mut output_seals);
         };
+        if(need_process_runes(block_height)) {
+            runes::process_transaction(inprocess_block, tx, vector::empty(), &btc_block_store.txs, &btc_block_store.tx_to_height, &mut output_seals);
+        };
+
+        // The coinbase tx claims the subsidy sats first, then the fee sats of the block
+        // If the fee sat ranges are unknown, the coinbase outputs are not tracked
+        let output_sat_ranges = if(option::is_some(&fee_sat_ranges)){
+            let input_sat_ranges = sat::subsidy_sat_ranges(block_height);
+            vector::append(&mut input_sat_ranges, option::destroy_some(fee_sat_ranges));
+            let (output_sat_ranges, lost_sat_ranges) = sat::allocate(input_sat_ranges, types::tx_output(tx));
+            sat::lose_sats(&lost_sat_ranges);
+            option::some(output_sat_ranges)
+        }else{
+            option::none()
+        };
 
         // create new utxo
-        handle_new_utxo(tx, &mut output_seals);
+        handle_new_utxo(tx, &mut output_seals, output_sat_ranges);
         simple_multimap::drop(output_seals);
     }
 
@@ -220,7 +285,7 @@ module bitcoin_move::bitcoin{
         // output_seals
     }
 
-    fun handle_new_utxo(tx: &Transaction, output_seals: &mut SimpleMultiMap<u32, UTXOSeal>) {
+    fun handle_new_utxo(tx: &Transaction, output_seals: &mut SimpleMultiMap<u32, UTXOSeal>, output_sat_ranges: Option<vector<vector<SatRange>>>) {
         let txid = types::tx_id(tx);
         let txoutput = types::tx_output(tx);
         let idx = 0;
@@ -230,6 +295,11 @@ module bitcoin_move::bitcoin{
             let vout = (idx as u32);
             let value = types::txout_value(txout);
             let utxo_obj = utxo::new(txid, vout, value);
+            if(option::is_some(&output_sat_ranges)){
+                let sat_ranges = *vector::borrow(option::borrow(&output_sat_ranges), idx);
+                sat::index_sat_ranges(&sat_ranges, types::new_outpoint(txid, vout));
+                utxo::add_sat_ranges(&mut utxo_obj, sat_ranges);
+            };
             let utxo = object::borrow_mut(&mut utxo_obj);
             let seal_index = (idx as u32);
             if(simple_multimap::contains_key(output_seals, &seal_index)){
@@ -253,12 +323,112 @@ module bitcoin_move::bitcoin{
     }
 
 
+    /// Get the header on the current chain at the given height, include the pending blocks
+    fun get_header_by_height(block_height: u64): Option<Header>{
+        let pending_header = pending_block::get_pending_block_header_by_height(block_height);
+        if(option::is_some(&pending_header)){
+            return pending_header
+        };
+        get_block_by_height(block_height)
+    }
+
+    /// The expected `bits` of the block after `prev_header`
+    /// Return none if the required previous blocks are not available
+    fun next_work_required(network: u8, block_height: u64, prev_header: &Header, block_time: u32): Option<u32>{
+        let interval = pow::difficulty_adjustment_interval();
+        if(block_height % interval != 0){
+            if(pow::allow_min_difficulty_blocks(network)){
+                let pow_limit_bits = pow::target_to_bits(pow::pow_limit(network));
+                // If no block is found in 20 minutes, a min difficulty block is allowed
+                if((block_time as u64) > (types::time(prev_header) as u64) + pow::target_spacing() * 2){
+                    return option::some(pow_limit_bits)
+                };
+                // Otherwise, use the bits of the last block not mined by the min difficulty rule
+                let height = block_height - 1;
+                let header = *prev_header;
+                while(height % interval != 0 && types::bits(&header) == pow_limit_bits){
+                    height = height - 1;
+                    let header_opt = get_header_by_height(height);
+                    if(option::is_none(&header_opt)){
+                        return option::none()
+                    };
+                    header = option::destroy_some(header_opt);
+                };
+                return option::some(types::bits(&header))
+            };
+            return option::some(types::bits(prev_header))
+        };
+        let first_header_opt = get_header_by_height(block_height - interval);
+        if(option::is_none(&first_header_opt)){
+            return option::none()
+        };
+        let first_header = option::destroy_some(first_header_opt);
+        option::some(pow::calculate_next_work_required(network, types::bits(prev_header), types::time(&first_header), types::time(prev_header)))
+    }
+
+    /// The median time of the previous blocks, return none if the previous blocks are not available
+    fun median_time_past(block_height: u64): Option<u32>{
+        let times = vector::empty<u32>();
+        let i = 1;
+        while(i <= pow::median_time_span()){
+            if(block_height < i){
+                return option::none()
+            };
+            let header_opt = get_header_by_height(block_height - i);
+            if(option::is_none(&header_opt)){
+                return option::none()
+            };
+            vector::push_back(&mut times, types::time(option::borrow(&header_opt)));
+            i = i + 1;
+        };
+        option::some(pow::median_time(times))
+    }
+
+    /// Validate the block header via the Bitcoin consensus rules before accept the block.
+    fun validate_block_header(block_height: u64, block_hash: address, header: &Header){
+        let network = network::network();
+        if(!pow::need_validate_header(network)){
+            return
+        };
+        check_block_header(network, block_hash, header);
+        contextual_check_block_header(network, block_height, header);
+    }
+
+    /// Check the block hash and the proof of work of the block header
+    fun check_block_header(network: u8, block_hash: address, header: &Header){
+        assert!(types::header_to_hash(header) == block_hash, ErrorInvalidBlockHash);
+        assert!(pow::check_proof_of_work(network, header), ErrorInvalidProofOfWork);
+    }
+
+    /// Check the block header against the previous blocks on the current chain.
+    /// The checks depend on the previous blocks are skipped if the previous blocks are not available, such as the blocks after the genesis block.
+    fun contextual_check_block_header(network: u8, block_height: u64, header: &Header){
+        if(block_height == 0){
+            return
+        };
+        let prev_header_opt = get_header_by_height(block_height - 1);
+        if(option::is_none(&prev_header_opt)){
+            return
+        };
+        let prev_header = option::destroy_some(prev_header_opt);
+        assert!(types::header_to_hash(&prev_header) == types::prev_blockhash(header), ErrorPrevBlockHashMismatch);
+        let expected_bits = next_work_required(network, block_height, &prev_header, types::time(header));
+        if(option::is_some(&expected_bits)){
+            assert!(types::bits(header) == option::destroy_some(expected_bits), ErrorInvalidDifficulty);
+        };
+        let median_time = median_time_past(block_height);
+        if(option::is_some(&median_time)){
+            assert!(types::time(header) > option::destroy_some(median_time), ErrorBlockTimeTooOld);
+        };
+    }
+
     /// The the sequencer submit a new Bitcoin block to execute
     /// This function is a system function, is the execute_l1_block entry point
     fun execute_l1_block(block_height: u64, block_hash: address, block_bytes: vector<u8>){
         let block = bcs::from_bytes<Block>(block_bytes);
         let block_header = types::header(&block);
         let time = types::time(block_header);
+        validate_block_header(block_height, block_hash, block_header);
         pending_block::add_pending_block(block_height, block_hash, block);    
         //We directly update the global time do not wait the pending block to be confirmed
         //The reorg do not affect the global time
@@ -276,13 +446,21 @@ module bitcoin_move::bitcoin{
         let tx = pending_block::inprocess_block_tx(&inprocess_block);
         if(types::is_coinbase_tx(tx)){
             let flotsams = pending_block::inprocess_block_flotsams(&inprocess_block);
-            process_coinbase_tx(btc_block_store, tx, flotsams, block_height);
+            let fee_sat_ranges = if(sat::is_enabled()){
+                pending_block::inprocess_block_fee_sat_ranges(&inprocess_block)
+            }else{
+                option::none()
+            };
+            process_coinbase_tx(btc_block_store, &inprocess_block, tx, flotsams, fee_sat_ranges, block_height);
             let header = pending_block::finish_pending_block(inprocess_block);
             process_block_header(btc_block_store, block_height, block_hash, header);
         }else{
-            let tx_flotsams = process_tx(btc_block_store, tx, block_height);
+            let (tx_flotsams, fee_sat_ranges) = process_tx(btc_block_store, &inprocess_block, tx, block_height);
             let flotsams = pending_block::inprocess_block_flotsams_mut(&mut inprocess_block);
             vector::append(flotsams, tx_flotsams);
+            if(sat::is_enabled()){
+                pending_block::inprocess_block_add_fee_sat_ranges(&mut inprocess_block, fee_sat_ranges);
+            };
             pending_block::finish_pending_tx(inprocess_block);
         };
     } 
@@ -389,6 +567,10 @@ module bitcoin_move::bitcoin{
         }
     }
 
+    fun need_process_runes(block_height: u64) : bool {
+        block_height >= runes::first_rune_height(network::network())
+    }
+
     fun bind_bitcoin_address(rooch_address: address, bitcoin_address_opt: Option<BitcoinAddress>) {
         //Auto create address mapping if not exist
         if(option::is_some(&bitcoin_address_opt)) {
@@ -420,4 +602,191 @@ module bitcoin_move::bitcoin{
         execute_l1_tx(block_hash, types::tx_id(&coinbase_tx));
     }
 
+    #[test_only]
+    use bitcoin_move::bitcoin_hash;
+
+    #[test_only]
+    fun add_header_for_test(block_height: u64, header: Header){
+        let btc_block_store = object::borrow_mut(borrow_block_store_mut());
+        process_block_header(btc_block_store, block_height, types::header_to_hash(&header), header);
+    }
+
+    #[test_only]
+    fun init_for_test(){
+        rooch_framework::genesis::init_for_test();
+        bitcoin_move::genesis::init_for_test();
+    }
+
+    /// The first 8 blocks of the Bitcoin mainnet
+    #[test_only]
+    fun mainnet_headers_for_test(): vector<Header>{
+        vector[
+            //https://mempool.space/block/000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f
+            types::new_header_for_test(1, @0x0, bitcoin_hash::from_ascii_bytes(&b"4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"), 1231006505, 0x1d00ffff, 2083236893),
+            //https://mempool.space/block/00000000839a8e6886ab5951d76f411475428afc90947ee320161bbf18eb6048
+            types::new_header_for_test(1, bitcoin_hash::from_ascii_bytes(&b"000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"), bitcoin_hash::from_ascii_bytes(&b"0e3e2357e806b6cdb1f70b54c3a3a17b6714ee1f0e68bebb44a74b1efd512098"), 1231469665, 0x1d00ffff, 2573394689),
+            //https://mempool.space/block/000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd
+            types::new_header_for_test(1, bitcoin_hash::from_ascii_bytes(&b"00000000839a8e6886ab5951d76f411475428afc90947ee320161bbf18eb6048"), bitcoin_hash::from_ascii_bytes(&b"9b0fc92260312ce44e74ef369f5c66bbb85848f2eddd5a7a1cde251e54ccfdd5"), 1231469744, 0x1d00ffff, 1639830024),
+            //https://mempool.space/block/0000000082b5015589a3fdf2d4baff403e6f0be035a5d9742c1cae6295464449
+            types::new_header_for_test(1, bitcoin_hash::from_ascii_bytes(&b"000000006a625f06636b8bb6ac7b960a8d03705d1ace08b1a19da3fdcc99ddbd"), bitcoin_hash::from_ascii_bytes(&b"999e1c837c76a1b7fbb7e57baf87b309960f5ffefbf2a9b95dd890602272f644"), 1231470173, 0x1d00ffff, 1844305925),
+            //https://mempool.space/block/000000004ebadb55ee9096c9a2f8880e09da59c0d68b1c228da88e48844a1485
+            types::new_header_for_test(1, bitcoin_hash::from_ascii_bytes(&b"0000000082b5015589a3fdf2d4baff403e6f0be035a5d9742c1cae6295464449"), bitcoin_hash::from_ascii_bytes(&b"df2b060fa2e5e9c8ed5eaf6a45c13753ec8c63282b2688322eba40cd98ea067a"), 1231470988, 0x1d00ffff, 2850094635),
+            //https://mempool.space/block/000000009b7262315dbf071787ad3656097b892abffd1f95a1a022f896f533fc
+            types::new_header_for_test(1, bitcoin_hash::from_ascii_bytes(&b"000000004ebadb55ee9096c9a2f8880e09da59c0d68b1c228da88e48844a1485"), bitcoin_hash::from_ascii_bytes(&b"63522845d294ee9b0188ae5cac91bf389a0c3723f084ca1025e7d9cdfe481ce1"), 1231471428, 0x1d00ffff, 2011431709),
+            //https://mempool.space/block/000000003031a0e73735690c5a1ff2a4be82553b2a12b776fbd3a215dc8f778d
+            types::new_header_for_test(1, bitcoin_hash::from_ascii_bytes(&b"000000009b7262315dbf071787ad3656097b892abffd1f95a1a022f896f533fc"), bitcoin_hash::from_ascii_bytes(&b"20251a76e64e920e58291a30d4b212939aae976baca40e70818ceaa596fb9d37"), 1231471789, 0x1d00ffff, 2538380312),
+            //https://mempool.space/block/0000000071966c2b1d065fd446b1e485b2c9d9594acd2007ccbd5441cfc89444
+            types::new_header_for_test(1, bitcoin_hash::from_ascii_bytes(&b"000000003031a0e73735690c5a1ff2a4be82553b2a12b776fbd3a215dc8f778d"), bitcoin_hash::from_ascii_bytes(&b"8aa673bc752f2851fd645d6a0a92917e967083007d9c1684f9423b100540673f"), 1231472369, 0x1d00ffff, 2258412857),
+        ]
+    }
+
+    /// Add the headers with the given times and bits from `start_height`, every header is linked to the previous one
+    #[test_only]
+    fun add_linked_headers_for_test(start_height: u64, times: vector<u32>, bits: u32): Header{
+        let prev_blockhash = @0x0;
+        let header = types::new_header_for_test(1, prev_blockhash, @0x0, 0, bits, 0);
+        let i = 0;
+        let len = vector::length(&times);
+        while(i < len){
+            header = types::new_header_for_test(1, prev_blockhash, @0x0, *vector::borrow(&times, i), bits, 0);
+            prev_blockhash = types::header_to_hash(&header);
+            add_header_for_test(start_height + i, header);
+            i = i + 1;
+        };
+        header
+    }
+
+    #[test]
+    fun test_replay_mainnet_headers(){
+        init_for_test();
+        let network = network::network_bitcoin();
+        let headers = mainnet_headers_for_test();
+        let genesis = vector::remove(&mut headers, 0);
+        check_block_header(network, types::header_to_hash(&genesis), &genesis);
+        add_header_for_test(0, genesis);
+        let block_height = 1;
+        while(!vector::is_empty(&headers)){
+            let header = vector::remove(&mut headers, 0);
+            check_block_header(network, types::header_to_hash(&header), &header);
+            contextual_check_block_header(network, block_height, &header);
+            add_header_for_test(block_height, header);
+            block_height = block_height + 1;
+        };
+        assert!(option::destroy_some(get_block_hash_by_height(7)) == bitcoin_hash::from_ascii_bytes(&b"0000000071966c2b1d065fd446b1e485b2c9d9594acd2007ccbd5441cfc89444"), 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ErrorPrevBlockHashMismatch, location = Self)]
+    fun test_prev_block_hash_mismatch(){
+        init_for_test();
+        let network = network::network_bitcoin();
+        let headers = mainnet_headers_for_test();
+        add_header_for_test(0, *vector::borrow(&headers, 0));
+        add_header_for_test(1, *vector::borrow(&headers, 1));
+        // block 3 is not the child of block 1
+        contextual_check_block_header(network, 2, vector::borrow(&headers, 3));
+    }
+
+    // The retarget boundary of the mainnet block 32256, the times and bits are the real mainnet values used by Bitcoin Core `pow_tests.cpp`
+    #[test_only]
+    fun add_mainnet_period_30240_for_test(): Header{
+        let interval = pow::difficulty_adjustment_interval();
+        // Block #30240 time: 1261130161
+        add_linked_headers_for_test(30240, vector[1261130161], 0x1d00ffff);
+        // Block #32255 time: 1262152739
+        add_linked_headers_for_test(30240 + interval - 1, vector[1262152739], 0x1d00ffff)
+    }
+
+    #[test]
+    fun test_retarget_boundary_mainnet_block_32256(){
+        init_for_test();
+        let network = network::network_bitcoin();
+        let prev_header = add_mainnet_period_30240_for_test();
+        let expected_bits = option::destroy_some(next_work_required(network, 32256, &prev_header, 1262153464));
+        assert!(expected_bits == 0x1d00d86a, 1);
+        let header = types::new_header_for_test(1, types::header_to_hash(&prev_header), @0x0, 1262153464, 0x1d00d86a, 0);
+        contextual_check_block_header(network, 32256, &header);
+        // The difficulty is not changed inside the period
+        add_header_for_test(32256, header);
+        let next_header = types::new_header_for_test(1, types::header_to_hash(&header), @0x0, 1262154000, 0x1d00d86a, 0);
+        contextual_check_block_header(network, 32257, &next_header);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ErrorInvalidDifficulty, location = Self)]
+    fun test_retarget_boundary_invalid_difficulty(){
+        init_for_test();
+        let network = network::network_bitcoin();
+        let prev_header = add_mainnet_period_30240_for_test();
+        // The previous difficulty is not valid after the retarget
+        let header = types::new_header_for_test(1, types::header_to_hash(&prev_header), @0x0, 1262153464, 0x1d00ffff, 0);
+        contextual_check_block_header(network, 32256, &header);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ErrorInvalidDifficulty, location = Self)]
+    fun test_invalid_difficulty_inside_period(){
+        init_for_test();
+        let network = network::network_bitcoin();
+        let prev_header = add_linked_headers_for_test(100, vector[1231006505], 0x1d00ffff);
+        let header = types::new_header_for_test(1, types::header_to_hash(&prev_header), @0x0, 1231007105, 0x1c00ffff, 0);
+        contextual_check_block_header(network, 101, &header);
+    }
+
+    #[test]
+    fun test_median_time_past(){
+        init_for_test();
+        let prev_header = add_linked_headers_for_test(100, vector[10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 20], 0x1d00ffff);
+        assert!(option::destroy_some(median_time_past(111)) == 6, 1);
+        // Not enough previous blocks
+        assert!(option::is_none(&median_time_past(110)), 2);
+        let header = types::new_header_for_test(1, types::header_to_hash(&prev_header), @0x0, 7, 0x1d00ffff, 0);
+        contextual_check_block_header(network::network_bitcoin(), 111, &header);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ErrorBlockTimeTooOld, location = Self)]
+    fun test_block_time_too_old(){
+        init_for_test();
+        let prev_header = add_linked_headers_for_test(100, vector[10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 20], 0x1d00ffff);
+        // The block time must be greater than the median time 6
+        let header = types::new_header_for_test(1, types::header_to_hash(&prev_header), @0x0, 6, 0x1d00ffff, 0);
+        contextual_check_block_header(network::network_bitcoin(), 111, &header);
+    }
+
+    #[test]
+    fun test_testnet_min_difficulty_blocks(){
+        init_for_test();
+        let network = network::network_testnet();
+        let pow_limit_bits = pow::target_to_bits(pow::pow_limit(network));
+        let interval = pow::difficulty_adjustment_interval();
+        let start_height = interval * 1000;
+        // The first block of the period is not a min difficulty block, the following two blocks are
+        add_linked_headers_for_test(start_height, vector[1700000000], 0x1c00ffff);
+        let prev_header = add_linked_headers_for_test(start_height + 1, vector[1700001300, 1700002600], pow_limit_bits);
+        let prev_time = types::time(&prev_header);
+        let block_height = start_height + 3;
+
+        // No block is found in 20 minutes, a min difficulty block is allowed
+        let bits = next_work_required(network, block_height, &prev_header, prev_time + 1201);
+        assert!(option::destroy_some(bits) == pow_limit_bits, 1);
+        // Otherwise the bits of the last block not mined by the min difficulty rule is required
+        let bits = next_work_required(network, block_height, &prev_header, prev_time + 1200);
+        assert!(option::destroy_some(bits) == 0x1c00ffff, 2);
+        let header = types::new_header_for_test(1, types::header_to_hash(&prev_header), @0x0, prev_time + 1201, pow_limit_bits, 0);
+        contextual_check_block_header(network, block_height, &header);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ErrorInvalidDifficulty, location = Self)]
+    fun test_testnet_min_difficulty_block_too_early(){
+        init_for_test();
+        let network = network::network_testnet();
+        let pow_limit_bits = pow::target_to_bits(pow::pow_limit(network));
+        let prev_header = add_linked_headers_for_test(2016 * 1000, vector[1700000000], 0x1c00ffff);
+        // The min difficulty block is found in 20 minutes
+        let header = types::new_header_for_test(1, types::header_to_hash(&prev_header), @0x0, 1700001200, pow_limit_bits, 0);
+        contextual_check_block_header(network, 2016 * 1000 + 1, &header);
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,66 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+/// The onchain configuration of the Bitcoin Move framework.
+module bitcoin_move::config{
+    use moveos_std::object::{Self, Object};
+    use rooch_framework::onchain_config;
+
+    /// Bitcoin framework onchain configuration.
+    /// The configuration is created lazily, all the options are disabled before it is created.
+    struct BitcoinConfig has key{
+        /// Whether track the sat ranges of the Bitcoin UTXOs, in the style of ord's `index-sats` mode.
+        sat_tracking: bool,
+    }
+
+    fun exists_config(): bool {
+        object::exists_object_with_type<BitcoinConfig>(object::named_object_id<BitcoinConfig>())
+    }
+
+    fun borrow_mut_config_obj(): &mut Object<BitcoinConfig> {
+        if(!exists_config()){
+            let obj = object::new_named_object(BitcoinConfig{
+                sat_tracking: false,
+            });
+            object::to_shared(obj);
+        };
+        object::borrow_mut_object_shared<BitcoinConfig>(object::named_object_id<BitcoinConfig>())
+    }
+
+    /// Whether the sat ranges tracking is enabled
+    public fun sat_tracking_enabled(): bool {
+        if(!exists_config()){
+            return false
+        };
+        let id = object::named_object_id<BitcoinConfig>();
+        object::borrow(object::borrow_object<BitcoinConfig>(id)).sat_tracking
+    }
+
+    /// Enable or disable the sat ranges tracking, only the sequencer can update it.
+    public entry fun update_sat_tracking(account: &signer, enabled: bool){
+        onchain_config::ensure_sequencer(account);
+        object::borrow_mut(borrow_mut_config_obj()).sat_tracking = enabled;
+    }
+
+    #[test_only]
+    public fun update_sat_tracking_for_test(enabled: bool){
+        object::borrow_mut(borrow_mut_config_obj()).sat_tracking = enabled;
+    }
+
+    #[test]
+    fun test_update_sat_tracking(){
+        rooch_framework::genesis::init_for_test();
+        assert!(!sat_tracking_enabled(), 1);
+        update_sat_tracking_for_test(true);
+        assert!(sat_tracking_enabled(), 2);
+        update_sat_tracking_for_test(false);
+        assert!(!sat_tracking_enabled(), 3);
+    }
+
+    #[test(account = @0x42)]
+    #[expected_failure(abort_code = 1, location = rooch_framework::onchain_config)]
+    fun test_update_sat_tracking_not_sequencer(account: &signer){
+        rooch_framework::genesis::init_for_test();
+        update_sat_tracking(account, true);
+    }
+}
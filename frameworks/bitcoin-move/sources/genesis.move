@@ -10,6 +10,8 @@ module bitcoin_move::genesis{
     use bitcoin_move::utxo;
     use bitcoin_move::network;
     use bitcoin_move::pending_block;
+    use bitcoin_move::runes;
+    use bitcoin_move::sat;
 
     const ErrorGenesisInit: u64 = 1;
 
@@ -29,6 +31,8 @@ module bitcoin_move::genesis{
         network::genesis_init(genesis_context.network);
         utxo::genesis_init();
         ord::genesis_init(&genesis_account);
+        runes::genesis_init();
+        sat::genesis_init();
         bitcoin::genesis_init(&genesis_account, genesis_context.genesis_block_height, genesis_context.genesis_block_hash);
         pending_block::genesis_init(genesis_context.reorg_block_count);
     }
@@ -20,6 +20,7 @@ module bitcoin_move::ord {
     use bitcoin_move::types::{Self, Witness, Transaction};
     use bitcoin_move::utxo::{Self, UTXO};
     use bitcoin_move::bitcoin_hash;
+    use bitcoin_move::sat::{Self, SatRange};
     
     friend bitcoin_move::genesis;
     friend bitcoin_move::bitcoin;
@@ -34,6 +35,7 @@ module bitcoin_move::ord {
     
     const METAPROTOCOL_VALIDITY: vector<u8> = b"metaprotocol_validity";
     const INSCRIPTION_CHARM: vector<u8> = b"inscription_charm";
+    const INSCRIPTION_SAT: vector<u8> = b"sat";
 
     /// How many satoshis are in "one bitcoin".
     const COIN_VALUE: u64 = 100_000_000;
@@ -496,7 +498,8 @@ module bitcoin_move::ord {
     }
 
 
-    public(friend) fun process_transaction(tx: &Transaction, input_utxo_values: vector<u64>): vector<SatPoint>{
+    /// The `input_sat_ranges` is the sat ranges of all the inputs, none if the sat tracking is disabled or the inputs are not tracked
+    public(friend) fun process_transaction(tx: &Transaction, input_utxo_values: vector<u64>, input_sat_ranges: &Option<vector<SatRange>>): vector<SatPoint>{
         let sat_points = vector::empty();
 
         let inscriptions = from_transaction(tx, option::some(input_utxo_values));
@@ -533,9 +536,16 @@ module bitcoin_move::ord {
             }else{
                 inscription.offset
             };
+            let input_offset = inscription.offset;
 
             let inscription_obj = create_obj(inscription);
             let object_id = object::id(&inscription_obj);
+            if(option::is_some(input_sat_ranges)){
+                let sat = sat::sat_at(option::borrow(input_sat_ranges), input_offset);
+                if(option::is_some(&sat)){
+                    object::add_field(&mut inscription_obj, INSCRIPTION_SAT, option::destroy_some(sat));
+                };
+            };
             object::transfer_extend(inscription_obj, to_address);
 
             let new_sat_point = new_sat_point((output_index as u32), offset, object_id);
@@ -1381,6 +1391,29 @@ module bitcoin_move::ord {
         option::some(*clarm)
     }
 
+    /// Get the sat which the inscription is inscribed on, return none if the sat is not tracked
+    public fun inscription_sat(inscription_obj: &Object<Inscription>): Option<u64> {
+        if (object::contains_field(inscription_obj, INSCRIPTION_SAT)) {
+            option::some(*object::borrow_field(inscription_obj, INSCRIPTION_SAT))
+        } else {
+            option::none()
+        }
+    }
+
+    /// Views the sat of the inscription for a given inscription ID string.
+    /// Returns None if the inscription doesn't exist or the sat is not tracked.
+    public fun view_inscription_sat(inscription_id_str: String): Option<u64> {
+        let inscription_id_option = parse_inscription_id(&inscription_id_str);
+        if (option::is_none(&inscription_id_option)) {
+            return option::none()
+        };
+        let inscription_object_id = derive_inscription_id(option::destroy_some(inscription_id_option));
+        if (!object::exists_object_with_type<Inscription>(inscription_object_id)) {
+            return option::none()
+        };
+        inscription_sat(object::borrow_object<Inscription>(inscription_object_id))
+    }
+
     #[test(genesis_account=@0x1)]
     fun test_inscription_charm(genesis_account: &signer) {
         // Setup
@@ -11,9 +11,11 @@ module bitcoin_move::pending_block{
     use moveos_std::event;
     use bitcoin_move::types::{Self, Transaction, Header, Block, BlockHeightHash};
     use bitcoin_move::ord::{Flotsam};
+    use bitcoin_move::sat::SatRange;
 
     friend bitcoin_move::genesis;
     friend bitcoin_move::bitcoin;
+    friend bitcoin_move::runes;
 
     const ErrorBlockAlreadyProcessed:u64 = 1;
     const ErrorPendingBlockNotFound:u64 = 2;
@@ -25,6 +27,7 @@ module bitcoin_move::pending_block{
 
     const TX_IDS_KEY: vector<u8> = b"tx_ids";
     const BLOCK_FLOTSAM_KEY: vector<u8> = b"block_flotsam";
+    const BLOCK_FEE_SAT_RANGES_KEY: vector<u8> = b"block_fee_sat_ranges";
 
     struct PendingBlock has key{
         block_height: u64,
@@ -142,7 +145,7 @@ module bitcoin_move::pending_block{
 
         simple_map::add(&mut store.pending_blocks, block_height, block_hash);
         //The relayer should ensure the new block is the best block
-        //Maybe we should calculate the difficulty here in the future
+        //The header is already validated by the bitcoin module
         store.best_block = option::some(types::new_block_height_hash(block_height, block_hash));
     }
 
@@ -200,6 +203,9 @@ module bitcoin_move::pending_block{
         if(object::contains_field(&obj, BLOCK_FLOTSAM_KEY)){
             let _flotsam: vector<Flotsam> = object::remove_field(&mut obj, BLOCK_FLOTSAM_KEY);
         };
+        if(object::contains_field(&obj, BLOCK_FEE_SAT_RANGES_KEY)){
+            let _fee_sat_ranges: Option<vector<SatRange>> = object::remove_field(&mut obj, BLOCK_FEE_SAT_RANGES_KEY);
+        };
         let pending_block = object::remove(obj);
         let PendingBlock{block_height:_, block_hash:_, header, processed_tx:_, next_block_hash:_} = pending_block;
         header
@@ -252,6 +258,43 @@ module bitcoin_move::pending_block{
         *object::borrow_field_with_default(&inprocess_block.block_obj, BLOCK_FLOTSAM_KEY, &default)
     }
 
+    /// Append the fee sat ranges of the inprocess tx to the block, none means the fee sat ranges of the tx are unknown.
+    /// Once the fee sat ranges of one tx are unknown, the fee sat ranges of the whole block are unknown.
+    public(friend) fun inprocess_block_add_fee_sat_ranges(inprocess_block: &mut InprocessBlock, fee_sat_ranges: Option<vector<SatRange>>){
+        let block_obj = &mut inprocess_block.block_obj;
+        if(!object::contains_field(block_obj, BLOCK_FEE_SAT_RANGES_KEY)){
+            let init_ranges = init_fee_sat_ranges(object::borrow(block_obj).processed_tx);
+            object::add_field(block_obj, BLOCK_FEE_SAT_RANGES_KEY, init_ranges);
+        };
+        let block_fee_sat_ranges: &mut Option<vector<SatRange>> = object::borrow_mut_field(block_obj, BLOCK_FEE_SAT_RANGES_KEY);
+        if(option::is_some(block_fee_sat_ranges)){
+            if(option::is_some(&fee_sat_ranges)){
+                vector::append(option::borrow_mut(block_fee_sat_ranges), option::destroy_some(fee_sat_ranges));
+            }else{
+                *block_fee_sat_ranges = option::none();
+            };
+        };
+    }
+
+    /// Get the fee sat ranges of the processed txs in the block, return none if they are unknown
+    public(friend) fun inprocess_block_fee_sat_ranges(inprocess_block: &InprocessBlock): Option<vector<SatRange>>{
+        let block_obj = &inprocess_block.block_obj;
+        if(object::contains_field(block_obj, BLOCK_FEE_SAT_RANGES_KEY)){
+            *object::borrow_field(block_obj, BLOCK_FEE_SAT_RANGES_KEY)
+        }else{
+            init_fee_sat_ranges(object::borrow(block_obj).processed_tx)
+        }
+    }
+
+    /// The txs processed before the sat tracking is enabled are not tracked
+    fun init_fee_sat_ranges(processed_tx: u64): Option<vector<SatRange>>{
+        if(processed_tx == 0){
+            option::some(vector::empty())
+        }else{
+            option::none()
+        }
+    }
+
     public(friend) fun inprocess_block_tx(inprocess_block: &InprocessBlock): &Transaction{
         &inprocess_block.tx
     }
@@ -266,6 +309,13 @@ module bitcoin_move::pending_block{
         block_obj.block_height
     }
 
+    /// The index of the inprocess tx in the block
+    public(friend) fun inprocess_block_tx_index(inprocess_block: &InprocessBlock): u64{
+        let tx_ids: &vector<address> = object::borrow_field(&inprocess_block.block_obj, TX_IDS_KEY);
+        let (_found, index) = vector::index_of(tx_ids, &types::tx_id(&inprocess_block.tx));
+        index
+    }
+
     // ============== Pending Block Query ==============
 
     struct PendingTxs has copy, drop, store{
@@ -305,6 +355,17 @@ module bitcoin_move::pending_block{
         *&store.best_block
     }
 
+    /// Get the header of the pending block at the given height
+    public fun get_pending_block_header_by_height(block_height: u64): Option<Header>{
+        let store = borrow_store();
+        if(!simple_map::contains_key(&store.pending_blocks, &block_height)){
+            return option::none()
+        };
+        let block_hash = *simple_map::borrow(&store.pending_blocks, &block_height);
+        let block_obj = borrow_pending_block(block_hash);
+        option::some(object::borrow(block_obj).header)
+    }
+
     public fun get_reorg_block_count(): u64{
         let store = borrow_store();
         store.reorg_block_count
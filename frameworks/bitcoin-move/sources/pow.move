@@ -0,0 +1,258 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+/// The Bitcoin proof of work and difficulty rules, ported from Bitcoin Core `pow.cpp`
+module bitcoin_move::pow{
+    use std::vector;
+    use moveos_std::address;
+    use bitcoin_move::network;
+    use bitcoin_move::types::{Self, Header};
+
+    /// The difficulty is adjusted every 2016 blocks
+    const DIFFICULTY_ADJUSTMENT_INTERVAL: u64 = 2016;
+    /// Two weeks, in seconds
+    const TARGET_TIMESPAN: u64 = 1209600;
+    /// Ten minutes, in seconds
+    const TARGET_SPACING: u64 = 600;
+    /// The block time must be greater than the median time of the previous 11 blocks
+    const MEDIAN_TIME_SPAN: u64 = 11;
+
+    const POW_LIMIT_BITCOIN: u256 = 0x00000000ffffffffffffffffffffffffffffffffffffffffffffffffffffffff;
+    const POW_LIMIT_TESTNET: u256 = 0x00000000ffffffffffffffffffffffffffffffffffffffffffffffffffffffff;
+    const POW_LIMIT_SIGNET: u256 = 0x00000377ae000000000000000000000000000000000000000000000000000000;
+    const POW_LIMIT_REGTEST: u256 = 0x7fffff0000000000000000000000000000000000000000000000000000000000;
+
+    public fun difficulty_adjustment_interval(): u64 {
+        DIFFICULTY_ADJUSTMENT_INTERVAL
+    }
+
+    public fun target_timespan(): u64 {
+        TARGET_TIMESPAN
+    }
+
+    public fun target_spacing(): u64 {
+        TARGET_SPACING
+    }
+
+    public fun median_time_span(): u64 {
+        MEDIAN_TIME_SPAN
+    }
+
+    /// The header validation is skipped on regtest, the blocks are generated by the local node
+    public fun need_validate_header(network: u8): bool {
+        network != network::network_regtest()
+    }
+
+    /// The easiest target allowed on the network
+    public fun pow_limit(network: u8): u256 {
+        if (network == network::network_bitcoin()) {
+            POW_LIMIT_BITCOIN
+        } else if (network == network::network_testnet()) {
+            POW_LIMIT_TESTNET
+        } else if (network == network::network_signet()) {
+            POW_LIMIT_SIGNET
+        } else {
+            POW_LIMIT_REGTEST
+        }
+    }
+
+    /// On testnet and regtest, a min difficulty block is allowed if no block is found in 20 minutes
+    public fun allow_min_difficulty_blocks(network: u8): bool {
+        network == network::network_testnet() || network == network::network_regtest()
+    }
+
+    /// On regtest, the difficulty is never adjusted
+    public fun no_retargeting(network: u8): bool {
+        network == network::network_regtest()
+    }
+
+    /// Decode the compact `bits` to the target, return 0 if the bits is negative or overflow
+    public fun bits_to_target(bits: u32): u256 {
+        let exponent = ((bits >> 24) as u8);
+        let mantissa = ((bits & 0x007fffff) as u256);
+        if (bits & 0x00800000 != 0 || exponent > 32) {
+            return 0
+        };
+        if (exponent <= 3) {
+            mantissa >> (8 * (3 - exponent))
+        } else {
+            mantissa << (8 * (exponent - 3))
+        }
+    }
+
+    /// Encode the target to the compact `bits`
+    public fun target_to_bits(target: u256): u32 {
+        let size = 0u8;
+        let value = target;
+        while (value > 0) {
+            value = value >> 8;
+            size = size + 1;
+        };
+        let compact = if (size <= 3) {
+            target << (8 * (3 - size))
+        } else {
+            target >> (8 * (size - 3))
+        };
+        // The 0x00800000 bit denotes the sign, so if it is already set, divide the mantissa by 256 and increase the exponent
+        if (compact & 0x00800000 != 0) {
+            compact = compact >> 8;
+            size = size + 1;
+        };
+        (compact as u32) | ((size as u32) << 24)
+    }
+
+    /// Convert the Bitcoin hash to a number, the hash bytes are in little-endian order
+    public fun hash_to_u256(hash: address): u256 {
+        let bytes = address::to_bytes(&hash);
+        let value = 0u256;
+        let i = vector::length(&bytes);
+        while (i > 0) {
+            i = i - 1;
+            value = (value << 8) | (*vector::borrow(&bytes, i) as u256);
+        };
+        value
+    }
+
+    /// Check the block hash satisfies the target claimed by the `bits` of the header,
+    /// and the target is not easier than the pow limit of the network
+    public fun check_proof_of_work(network: u8, header: &Header): bool {
+        let target = bits_to_target(types::bits(header));
+        if (target == 0 || target > pow_limit(network)) {
+            return false
+        };
+        hash_to_u256(types::header_to_hash(header)) <= target
+    }
+
+    /// Calculate the `bits` of the first block of the next difficulty period,
+    /// `first_block_time` and `last_block_time` are the time of the first and last block of the current period
+    public fun calculate_next_work_required(network: u8, last_bits: u32, first_block_time: u32, last_block_time: u32): u32 {
+        if (no_retargeting(network)) {
+            return last_bits
+        };
+        let actual_timespan = if (last_block_time > first_block_time) {
+            ((last_block_time - first_block_time) as u64)
+        } else {
+            0
+        };
+        // Limit the adjustment step
+        if (actual_timespan < TARGET_TIMESPAN / 4) {
+            actual_timespan = TARGET_TIMESPAN / 4;
+        };
+        if (actual_timespan > TARGET_TIMESPAN * 4) {
+            actual_timespan = TARGET_TIMESPAN * 4;
+        };
+        let new_target = bits_to_target(last_bits) * (actual_timespan as u256) / (TARGET_TIMESPAN as u256);
+        let limit = pow_limit(network);
+        if (new_target > limit) {
+            new_target = limit;
+        };
+        target_to_bits(new_target)
+    }
+
+    /// The median of the block times
+    public fun median_time(times: vector<u32>): u32 {
+        // insertion sort, the times are at most `MEDIAN_TIME_SPAN`
+        let len = vector::length(&times);
+        let i = 1;
+        while (i < len) {
+            let j = i;
+            while (j > 0 && *vector::borrow(&times, j - 1) > *vector::borrow(&times, j)) {
+                vector::swap(&mut times, j - 1, j);
+                j = j - 1;
+            };
+            i = i + 1;
+        };
+        *vector::borrow(&times, len / 2)
+    }
+
+    #[test_only]
+    use bitcoin_move::bitcoin_hash;
+
+    #[test]
+    fun test_bits_to_target() {
+        assert!(bits_to_target(0x1d00ffff) == 0x00000000ffff0000000000000000000000000000000000000000000000000000, 1);
+        assert!(bits_to_target(0x17042450) == 0x0000000000000000000424500000000000000000000000000000000000000000, 2);
+        assert!(bits_to_target(0x01003456) == 0, 3);
+        assert!(bits_to_target(0x04923456) == 0, 4);
+        assert!(target_to_bits(bits_to_target(0x1d00ffff)) == 0x1d00ffff, 5);
+        assert!(target_to_bits(bits_to_target(0x1b0e7256)) == 0x1b0e7256, 6);
+        assert!(target_to_bits(pow_limit(network::network_bitcoin())) == 0x1d00ffff, 7);
+        assert!(target_to_bits(pow_limit(network::network_signet())) == 0x1e0377ae, 8);
+        assert!(target_to_bits(pow_limit(network::network_regtest())) == 0x207fffff, 9);
+    }
+
+    #[test]
+    fun test_check_proof_of_work_mainnet() {
+        let network = network::network_bitcoin();
+        //https://mempool.space/block/000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f
+        let genesis = types::new_header_for_test(1, @0x0, bitcoin_hash::from_ascii_bytes(&b"4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"), 1231006505, 0x1d00ffff, 2083236893);
+        assert!(types::header_to_hash(&genesis) == bitcoin_hash::from_ascii_bytes(&b"000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"), 1);
+        assert!(check_proof_of_work(network, &genesis), 2);
+        //https://mempool.space/block/00000000000af0aed4792b1acee3d966af36cf5def14935db8de83d6f9306f2f
+        let block_91812 = types::new_header_for_test(1, bitcoin_hash::from_ascii_bytes(&b"000000000002afe839294d4e038b5c831bc09632fd717c0980f8f216dc2b360f"), bitcoin_hash::from_ascii_bytes(&b"49991d7653bec6efebee7d11f27ca2dffcc35ebe95ee5eebd602916b2f2fa665"), 1289757588, 0x1b0e7256, 1166692788);
+        assert!(check_proof_of_work(network, &block_91812), 3);
+        //https://mempool.space/block/000000000000000000020750f322f4e72e99c2f0b9738fb4f46607860bd18c13
+        let block_818677 = types::new_header_for_test(0x20000000, bitcoin_hash::from_ascii_bytes(&b"00000000000000000001a2dd0a20944de8b464ac51af80d7cfdab3a69791e13d"), bitcoin_hash::from_ascii_bytes(&b"2b0ea021b4c16409402d7969da590907389a8042dd4b6545077f6f37e76f54c6"), 1701075201, 0x17042450, 1616659018);
+        assert!(check_proof_of_work(network, &block_818677), 4);
+
+        // The hash does not satisfy the target if the nonce is changed
+        let invalid_nonce = types::new_header_for_test(0x20000000, bitcoin_hash::from_ascii_bytes(&b"00000000000000000001a2dd0a20944de8b464ac51af80d7cfdab3a69791e13d"), bitcoin_hash::from_ascii_bytes(&b"2b0ea021b4c16409402d7969da590907389a8042dd4b6545077f6f37e76f54c6"), 1701075201, 0x17042450, 1616659019);
+        assert!(!check_proof_of_work(network, &invalid_nonce), 5);
+        // The hash does not satisfy a harder target
+        let harder_bits = types::new_header_for_test(1, @0x0, bitcoin_hash::from_ascii_bytes(&b"4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"), 1231006505, 0x1c00ffff, 2083236893);
+        assert!(!check_proof_of_work(network, &harder_bits), 6);
+        // The target is easier than the mainnet pow limit
+        let easier_bits = types::new_header_for_test(1, @0x0, bitcoin_hash::from_ascii_bytes(&b"4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"), 1231006505, 0x1e00ffff, 2083236893);
+        assert!(!check_proof_of_work(network, &easier_bits), 7);
+    }
+
+    #[test]
+    fun test_check_proof_of_work_testnet() {
+        //https://mempool.space/testnet/block/0000000016412abe1778a347da773ff8bc087ad1a91ae5daad349bc268285c2d
+        let block_2821527 = types::new_header_for_test(0x20000000, bitcoin_hash::from_ascii_bytes(&b"0000000075b92a77d6603c7783e5658e301fa768b658d30401823ac43efd4371"), bitcoin_hash::from_ascii_bytes(&b"f33318fb3a9ff4b38b911af7532e0639a3ad97399231c0c5f35debcba202edb5"), 1718597798, 0x1d00ffff, 1366389009);
+        assert!(check_proof_of_work(network::network_testnet(), &block_2821527), 1);
+    }
+
+    // The retarget cases are the mainnet difficulty periods used by Bitcoin Core `pow_tests.cpp`
+
+    #[test]
+    fun test_retarget_mainnet_block_32256() {
+        // Block #30240 time: 1261130161, Block #32255 time: 1262152739
+        let bits = calculate_next_work_required(network::network_bitcoin(), 0x1d00ffff, 1261130161, 1262152739);
+        assert!(bits == 0x1d00d86a, 1);
+    }
+
+    #[test]
+    fun test_retarget_mainnet_pow_limit() {
+        // Block #0 time: 1231006505, Block #2015 time: 1233061996
+        let bits = calculate_next_work_required(network::network_bitcoin(), 0x1d00ffff, 1231006505, 1233061996);
+        assert!(bits == 0x1d00ffff, 1);
+    }
+
+    #[test]
+    fun test_retarget_mainnet_lower_limit_actual() {
+        // Block #66528 time: 1279008237, Block #68543 time: 1279297671
+        let bits = calculate_next_work_required(network::network_bitcoin(), 0x1c05a3f4, 1279008237, 1279297671);
+        assert!(bits == 0x1c0168fd, 1);
+    }
+
+    #[test]
+    fun test_retarget_mainnet_upper_limit_actual() {
+        // Block #46367 time: 1269211443, the first block time is not an actual block time
+        let bits = calculate_next_work_required(network::network_bitcoin(), 0x1c387f6f, 1263163443, 1269211443);
+        assert!(bits == 0x1d00e1fd, 1);
+    }
+
+    #[test]
+    fun test_retarget_regtest() {
+        let bits = calculate_next_work_required(network::network_regtest(), 0x207fffff, 1296688602, 1296688602);
+        assert!(bits == 0x207fffff, 1);
+    }
+
+    #[test]
+    fun test_median_time() {
+        assert!(median_time(vector[5, 1, 4, 2, 3]) == 3, 1);
+        assert!(median_time(vector[1231006505, 1231469665, 1231469744]) == 1231469665, 2);
+        assert!(median_time(vector[10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0]) == 5, 3);
+    }
+}
@@ -0,0 +1,1154 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+/// The Runes protocol, https://docs.ordinals.com/runes.html
+/// The runestone is decoded by the native function, this module handles the etching, mint, transfer and cenotaph.
+/// The rune balances of an output are stored in a RuneBalances object, and sealed to the UTXO of the output.
+module bitcoin_move::runes{
+    use std::vector;
+    use std::option::{Self, Option};
+    use std::string::String;
+    use moveos_std::object::{Self, Object, ObjectID};
+    use moveos_std::table::{Self, Table};
+    use moveos_std::simple_multimap::{Self, SimpleMultiMap};
+    use moveos_std::type_info;
+    use moveos_std::event;
+    use bitcoin_move::types::{Self, Transaction, TxOut, OutPoint};
+    use bitcoin_move::script_buf;
+    use bitcoin_move::network;
+    use bitcoin_move::utxo::{Self, UTXO, UTXOSeal};
+    use bitcoin_move::pending_block::{Self, InprocessBlock};
+
+    friend bitcoin_move::genesis;
+    friend bitcoin_move::bitcoin;
+
+    const SUBSIDY_HALVING_INTERVAL: u64 = 210000;
+    /// The etching commitment should be confirmed by at least 6 blocks
+    const COMMIT_CONFIRMATIONS: u64 = 6;
+    /// The rune names equal or greater than this value are reserved, it is the rune `AAAAAAAAAAAAAAAAAAAAAAAAAAA`
+    const RESERVED_RUNE: u128 = 6402364363415443603228541259936211926;
+    /// STEPS[n] is the first rune with n + 1 letters
+    const STEPS: vector<u128> = vector[
+        0,
+        26,
+        702,
+        18278,
+        475254,
+        12356630,
+        321272406,
+        8353082582,
+        217180147158,
+        5646683826134,
+        146813779479510,
+        3817158266467286,
+        99246114928149462,
+    ];
+
+    struct RuneId has store, copy, drop {
+        /// The block height of the etching transaction
+        block: u64,
+        /// The index of the etching transaction in the block
+        tx: u32,
+    }
+
+    /// The open mint terms of a rune
+    struct Terms has store, copy, drop {
+        /// The amount of each mint
+        amount: Option<u128>,
+        /// The max mint count
+        cap: Option<u128>,
+        /// The mint is open at or after this absolute height
+        height_start: Option<u64>,
+        /// The mint is closed at or after this absolute height
+        height_end: Option<u64>,
+        /// The mint is open at or after this height relative to the etching height
+        offset_start: Option<u64>,
+        /// The mint is closed at or after this height relative to the etching height
+        offset_end: Option<u64>,
+    }
+
+    struct Etching has store, copy, drop {
+        divisibility: Option<u8>,
+        premine: Option<u128>,
+        rune: Option<u128>,
+        spacers: Option<u32>,
+        /// The unicode scalar value of the currency symbol
+        symbol: Option<u32>,
+        terms: Option<Terms>,
+        turbo: bool,
+    }
+
+    struct Edict has store, copy, drop {
+        /// RuneId{block: 0, tx: 0} means the rune etched by the same transaction
+        id: RuneId,
+        /// 0 means all the unallocated balance of the rune
+        amount: u128,
+        /// The output index, equal to the outputs length means split to all the non OP_RETURN outputs
+        output: u32,
+    }
+
+    /// The artifact decoded from the transaction, a runestone or a cenotaph
+    struct Artifact has store, copy, drop {
+        /// The runestone is malformed, all the runes in the inputs are burned
+        cenotaph: bool,
+        /// The reason of the cenotaph
+        flaw: Option<String>,
+        edicts: vector<Edict>,
+        /// The etching of the runestone, only the rune is kept for a cenotaph
+        etching: Option<Etching>,
+        mint: Option<RuneId>,
+        /// The output to receive the unallocated runes
+        pointer: Option<u32>,
+        /// The inputs whose tapscript contains the commitment of the etching rune
+        commitment_inputs: vector<u32>,
+    }
+
+    struct RuneEntry has key {
+        id: RuneId,
+        rune: u128,
+        spacers: u32,
+        /// The etching sequence number
+        number: u64,
+        /// The etching txid
+        etching: address,
+        divisibility: u8,
+        symbol: Option<u32>,
+        premine: u128,
+        terms: Option<Terms>,
+        turbo: bool,
+        mints: u128,
+        burned: u128,
+        /// The etching block time in seconds
+        timestamp: u32,
+    }
+
+    struct RuneBalance has store, copy, drop {
+        id: RuneId,
+        amount: u128,
+    }
+
+    /// The rune balances of an output, the object is sealed to the UTXO of the output
+    struct RuneBalances has key {
+        outpoint: OutPoint,
+        balances: vector<RuneBalance>,
+    }
+
+    /// The RuneStore keeps the rune name -> RuneId mapping in the fields, and the RuneEntry objects are its children
+    struct RuneStore has key {
+        /// The number of the next etched rune
+        next_number: u64,
+    }
+
+    struct RuneEtchedEvent has copy, drop {
+        id: RuneId,
+        rune: u128,
+        txid: address,
+    }
+
+    struct CenotaphEvent has copy, drop {
+        txid: address,
+        flaw: Option<String>,
+    }
+
+    public(friend) fun genesis_init(){
+        create_rune_store();
+    }
+
+    fun create_rune_store(){
+        let store = RuneStore{
+            next_number: 0,
+        };
+        let store_obj = object::new_named_object(store);
+        object::to_shared(store_obj);
+    }
+
+    /// The RuneStore is created on the first access if the chain is started before the Runes support
+    fun borrow_mut_rune_store_obj(): &mut Object<RuneStore> {
+        let rune_store_object_id = object::named_object_id<RuneStore>();
+        if(!object::exists_object_with_type<RuneStore>(rune_store_object_id)){
+            create_rune_store();
+        };
+        object::borrow_mut_object_shared<RuneStore>(rune_store_object_id)
+    }
+
+    fun exists_rune_store(): bool {
+        object::exists_object_with_type<RuneStore>(object::named_object_id<RuneStore>())
+    }
+
+    fun borrow_rune_store_obj(): &Object<RuneStore> {
+        let rune_store_object_id = object::named_object_id<RuneStore>();
+        object::borrow_object<RuneStore>(rune_store_object_id)
+    }
+
+    //===== RuneId =====//
+
+    public fun new_rune_id(block: u64, tx: u32): RuneId {
+        RuneId{
+            block,
+            tx,
+        }
+    }
+
+    public fun rune_id_block(self: &RuneId): u64 {
+        self.block
+    }
+
+    public fun rune_id_tx(self: &RuneId): u32 {
+        self.tx
+    }
+
+    //===== Rune =====//
+
+    /// The height of the first rune, the Runes protocol is activated at this height
+    public fun first_rune_height(network: u8): u64 {
+        if(network == network::network_bitcoin()){
+            SUBSIDY_HALVING_INTERVAL * 4
+        }else if(network == network::network_testnet()){
+            SUBSIDY_HALVING_INTERVAL * 12
+        }else{
+            0
+        }
+    }
+
+    /// The minimum rune name could be etched at the given height, the names are unlocked gradually in four years
+    public fun minimum_at_height(network: u8, block_height: u64): u128 {
+        let offset = block_height + 1;
+        let interval = SUBSIDY_HALVING_INTERVAL / 12;
+        let start = first_rune_height(network);
+        let end = start + SUBSIDY_HALVING_INTERVAL;
+        if(offset < start){
+            return *vector::borrow(&STEPS, 12)
+        };
+        if(offset >= end){
+            return 0
+        };
+        let progress = offset - start;
+        let length = 12 - progress / interval;
+        let end = *vector::borrow(&STEPS, length - 1);
+        let start = *vector::borrow(&STEPS, length);
+        let remainder = ((progress % interval) as u128);
+        start - ((start - end) * remainder / (interval as u128))
+    }
+
+    public fun is_reserved(rune: u128): bool {
+        rune >= RESERVED_RUNE
+    }
+
+    /// The rune assigned to the etching without a rune name
+    public fun reserved_rune(block: u64, tx: u32): u128 {
+        RESERVED_RUNE + (((block as u128) << 32) | (tx as u128))
+    }
+
+    public fun exists_rune(rune: u128): bool {
+        exists_rune_store() && object::contains_field(borrow_rune_store_obj(), rune)
+    }
+
+    /// Get the RuneId by the rune name
+    public fun get_rune_id(rune: u128): Option<RuneId> {
+        if(!exists_rune_store()){
+            return option::none()
+        };
+        let store_obj = borrow_rune_store_obj();
+        if(object::contains_field(store_obj, rune)){
+            option::some(*object::borrow_field(store_obj, rune))
+        }else{
+            option::none()
+        }
+    }
+
+    //===== RuneEntry =====//
+
+    public fun derive_rune_entry_id(id: RuneId): ObjectID {
+        let parent_id = object::named_object_id<RuneStore>();
+        object::custom_object_id_with_parent<RuneId, RuneEntry>(parent_id, id)
+    }
+
+    public fun exists_rune_entry(id: RuneId): bool {
+        object::exists_object_with_type<RuneEntry>(derive_rune_entry_id(id))
+    }
+
+    public fun borrow_rune_entry(id: RuneId): &RuneEntry {
+        let rune_entry_obj = object::borrow_object<RuneEntry>(derive_rune_entry_id(id));
+        object::borrow(rune_entry_obj)
+    }
+
+    fun borrow_mut_rune_entry(id: RuneId): &mut RuneEntry {
+        let rune_entry_obj = object::borrow_mut_object_extend<RuneEntry>(derive_rune_entry_id(id));
+        object::borrow_mut(rune_entry_obj)
+    }
+
+    public fun rune_entry_rune(self: &RuneEntry): u128 {
+        self.rune
+    }
+
+    public fun rune_entry_spacers(self: &RuneEntry): u32 {
+        self.spacers
+    }
+
+    public fun rune_entry_number(self: &RuneEntry): u64 {
+        self.number
+    }
+
+    public fun rune_entry_etching(self: &RuneEntry): address {
+        self.etching
+    }
+
+    public fun rune_entry_divisibility(self: &RuneEntry): u8 {
+        self.divisibility
+    }
+
+    public fun rune_entry_symbol(self: &RuneEntry): Option<u32> {
+        self.symbol
+    }
+
+    public fun rune_entry_premine(self: &RuneEntry): u128 {
+        self.premine
+    }
+
+    public fun rune_entry_turbo(self: &RuneEntry): bool {
+        self.turbo
+    }
+
+    public fun rune_entry_mints(self: &RuneEntry): u128 {
+        self.mints
+    }
+
+    public fun rune_entry_burned(self: &RuneEntry): u128 {
+        self.burned
+    }
+
+    public fun rune_entry_timestamp(self: &RuneEntry): u32 {
+        self.timestamp
+    }
+
+    /// The height the mint is open, return none if there is no start limit
+    public fun rune_entry_mint_start(self: &RuneEntry): Option<u64> {
+        if(option::is_none(&self.terms)){
+            return option::none()
+        };
+        let terms = option::borrow(&self.terms);
+        let relative = relative_height(self.id.block, &terms.offset_start);
+        if(option::is_some(&relative) && option::is_some(&terms.height_start)){
+            let relative = option::destroy_some(relative);
+            let absolute = *option::borrow(&terms.height_start);
+            option::some(if(relative > absolute) { relative } else { absolute })
+        }else if(option::is_some(&relative)){
+            relative
+        }else{
+            terms.height_start
+        }
+    }
+
+    /// The height the mint is closed, return none if there is no end limit
+    public fun rune_entry_mint_end(self: &RuneEntry): Option<u64> {
+        if(option::is_none(&self.terms)){
+            return option::none()
+        };
+        let terms = option::borrow(&self.terms);
+        let relative = relative_height(self.id.block, &terms.offset_end);
+        if(option::is_some(&relative) && option::is_some(&terms.height_end)){
+            let relative = option::destroy_some(relative);
+            let absolute = *option::borrow(&terms.height_end);
+            option::some(if(relative < absolute) { relative } else { absolute })
+        }else if(option::is_some(&relative)){
+            relative
+        }else{
+            terms.height_end
+        }
+    }
+
+    fun relative_height(block: u64, offset: &Option<u64>): Option<u64> {
+        if(option::is_none(offset)){
+            return option::none()
+        };
+        let offset = *option::borrow(offset);
+        let max_u64 = 18446744073709551615u64;
+        if(block > max_u64 - offset){
+            option::some(max_u64)
+        }else{
+            option::some(block + offset)
+        }
+    }
+
+    /// The amount of a mint at the given height, return none if the rune can not be minted
+    public fun rune_entry_mintable(self: &RuneEntry, block_height: u64): Option<u128> {
+        if(option::is_none(&self.terms)){
+            return option::none()
+        };
+        let start = rune_entry_mint_start(self);
+        if(option::is_some(&start) && block_height < option::destroy_some(start)){
+            return option::none()
+        };
+        let end = rune_entry_mint_end(self);
+        if(option::is_some(&end) && block_height >= option::destroy_some(end)){
+            return option::none()
+        };
+        let terms = option::borrow(&self.terms);
+        let cap = option::get_with_default(&terms.cap, 0);
+        if(self.mints >= cap){
+            return option::none()
+        };
+        option::some(option::get_with_default(&terms.amount, 0))
+    }
+
+    //===== RuneBalances =====//
+
+    public fun derive_rune_balances_id(outpoint: OutPoint): ObjectID {
+        object::custom_object_id<OutPoint, RuneBalances>(outpoint)
+    }
+
+    /// Get the rune balances of the output, return empty if the output has no runes or is spent
+    public fun get_rune_balances(outpoint: OutPoint): vector<RuneBalance> {
+        let object_id = derive_rune_balances_id(outpoint);
+        if(object::exists_object_with_type<RuneBalances>(object_id)){
+            object::borrow(object::borrow_object<RuneBalances>(object_id)).balances
+        }else{
+            vector::empty()
+        }
+    }
+
+    public fun balance_id(self: &RuneBalance): RuneId {
+        self.id
+    }
+
+    public fun balance_amount(self: &RuneBalance): u128 {
+        self.amount
+    }
+
+    fun add_balance(balances: &mut vector<RuneBalance>, id: RuneId, amount: u128){
+        if(amount == 0){
+            return
+        };
+        let idx = 0;
+        let len = vector::length(balances);
+        while(idx < len){
+            let balance = vector::borrow_mut(balances, idx);
+            if(balance.id == id){
+                balance.amount = balance.amount + amount;
+                return
+            };
+            idx = idx + 1;
+        };
+        vector::push_back(balances, RuneBalance{id, amount});
+    }
+
+    //===== Artifact =====//
+
+    public fun artifact_is_cenotaph(self: &Artifact): bool {
+        self.cenotaph
+    }
+
+    public fun artifact_flaw(self: &Artifact): Option<String> {
+        self.flaw
+    }
+
+    public fun artifact_edicts(self: &Artifact): vector<Edict> {
+        self.edicts
+    }
+
+    public fun artifact_etching(self: &Artifact): Option<Etching> {
+        self.etching
+    }
+
+    public fun artifact_mint(self: &Artifact): Option<RuneId> {
+        self.mint
+    }
+
+    public fun artifact_pointer(self: &Artifact): Option<u32> {
+        self.pointer
+    }
+
+    //===== Transaction Processing =====//
+
+    /// Remove the rune balances sealed to the spent UTXO, and add them to the unallocated balances
+    public(friend) fun spend_utxo(utxo_obj: &mut Object<UTXO>, unallocated: &mut vector<RuneBalance>){
+        let utxo = object::borrow_mut(utxo_obj);
+        let seals = utxo::remove_seals<RuneBalances>(utxo);
+        vector::for_each(seals, |seal_object_id| {
+            let rune_balances_obj = object::take_object_extend<RuneBalances>(seal_object_id);
+            let RuneBalances{outpoint: _, balances} = object::remove(rune_balances_obj);
+            vector::for_each(balances, |balance| {
+                let RuneBalance{id, amount} = balance;
+                add_balance(unallocated, id, amount);
+            });
+        });
+    }
+
+    /// Handle the runestone of the transaction, the `unallocated` is the rune balances of the spent inputs.
+    /// The new rune balances are sealed to the outputs via the `output_seals`.
+    /// The `txs` and `tx_to_height` are the Bitcoin block store tables, used to check the etching commitment.
+    public(friend) fun process_transaction(
+        inprocess_block: &InprocessBlock,
+        tx: &Transaction,
+        unallocated: vector<RuneBalance>,
+        txs: &Table<address, Transaction>,
+        tx_to_height: &Table<address, u64>,
+        output_seals: &mut SimpleMultiMap<u32, UTXOSeal>,
+    ){
+        let artifact_opt = parse_runestone(tx);
+        if(option::is_none(&artifact_opt) && vector::is_empty(&unallocated)){
+            return
+        };
+        let block_height = pending_block::inprocess_block_height(inprocess_block);
+        let txid = types::tx_id(tx);
+        let outputs = types::tx_output(tx);
+        let outputs_len = vector::length(outputs);
+        let allocated = vector::empty<vector<RuneBalance>>();
+        let idx = 0;
+        while(idx < outputs_len){
+            vector::push_back(&mut allocated, vector::empty());
+            idx = idx + 1;
+        };
+        let burned = vector::empty<RuneBalance>();
+        let is_cenotaph = false;
+        let pointer = option::none<u32>();
+
+        if(option::is_some(&artifact_opt)){
+            let artifact = option::destroy_some(artifact_opt);
+            is_cenotaph = artifact.cenotaph;
+            pointer = artifact.pointer;
+            if(option::is_some(&artifact.mint)){
+                let mint_id = *option::borrow(&artifact.mint);
+                let minted = mint(mint_id, block_height);
+                if(option::is_some(&minted)){
+                    add_balance(&mut unallocated, mint_id, option::destroy_some(minted));
+                };
+            };
+
+            let tx_index = (pending_block::inprocess_block_tx_index(inprocess_block) as u32);
+            let (is_etched, etched_id, etched_rune) = etched(block_height, tx_index, tx, &artifact, txs, tx_to_height);
+
+            if(is_cenotaph){
+                event::emit(CenotaphEvent{
+                    txid,
+                    flaw: artifact.flaw,
+                });
+            }else{
+                if(is_etched){
+                    let etching = option::borrow(&artifact.etching);
+                    add_balance(&mut unallocated, etched_id, option::get_with_default(&etching.premine, 0));
+                };
+                let edicts_len = vector::length(&artifact.edicts);
+                let edict_idx = 0;
+                while(edict_idx < edicts_len){
+                    let edict = vector::borrow(&artifact.edicts, edict_idx);
+                    allocate_edict(edict, is_etched, etched_id, outputs, &mut unallocated, &mut allocated);
+                    edict_idx = edict_idx + 1;
+                };
+            };
+
+            if(is_etched){
+                let timestamp = types::time(pending_block::inprocess_block_header(inprocess_block));
+                create_rune_entry(txid, &artifact, etched_id, etched_rune, timestamp);
+            };
+        };
+
+        if(is_cenotaph){
+            vector::for_each(unallocated, |balance| {
+                let RuneBalance{id, amount} = balance;
+                add_balance(&mut burned, id, amount);
+            });
+        }else{
+            // Assign the unallocated runes to the pointer output, or the first non OP_RETURN output
+            let vout = if(option::is_some(&pointer)){
+                option::some((option::destroy_some(pointer) as u64))
+            }else{
+                first_non_op_return_output(outputs)
+            };
+            if(option::is_some(&vout)){
+                let vout = option::destroy_some(vout);
+                let output_balances = vector::borrow_mut(&mut allocated, vout);
+                vector::for_each(unallocated, |balance| {
+                    let RuneBalance{id, amount} = balance;
+                    add_balance(output_balances, id, amount);
+                });
+            }else{
+                vector::for_each(unallocated, |balance| {
+                    let RuneBalance{id, amount} = balance;
+                    add_balance(&mut burned, id, amount);
+                });
+            };
+        };
+
+        let protocol = type_info::type_name<RuneBalances>();
+        idx = 0;
+        while(idx < outputs_len){
+            let balances = *vector::borrow(&allocated, idx);
+            if(!vector::is_empty(&balances)){
+                let txout = vector::borrow(outputs, idx);
+                // The runes sent to OP_RETURN outputs are burned
+                if(script_buf::is_op_return(types::txout_script_pubkey(txout))){
+                    vector::for_each(balances, |balance| {
+                        let RuneBalance{id, amount} = balance;
+                        add_balance(&mut burned, id, amount);
+                    });
+                }else{
+                    let outpoint = types::new_outpoint(txid, (idx as u32));
+                    let rune_balances_obj = object::new_with_id(outpoint, RuneBalances{
+                        outpoint,
+                        balances,
+                    });
+                    let object_id = object::id(&rune_balances_obj);
+                    object::transfer_extend(rune_balances_obj, types::txout_object_address(txout));
+                    simple_multimap::add(output_seals, (idx as u32), utxo::new_utxo_seal(protocol, object_id));
+                };
+            };
+            idx = idx + 1;
+        };
+
+        vector::for_each(burned, |balance| {
+            let RuneBalance{id, amount} = balance;
+            if(exists_rune_entry(id)){
+                let rune_entry = borrow_mut_rune_entry(id);
+                rune_entry.burned = rune_entry.burned + amount;
+            };
+        });
+    }
+
+    fun mint(id: RuneId, block_height: u64): Option<u128> {
+        if(!exists_rune_entry(id)){
+            return option::none()
+        };
+        let rune_entry = borrow_mut_rune_entry(id);
+        let amount = rune_entry_mintable(rune_entry, block_height);
+        if(option::is_some(&amount)){
+            rune_entry.mints = rune_entry.mints + 1;
+        };
+        amount
+    }
+
+    /// Check the etching of the artifact, return (is_etched, rune_id, rune)
+    fun etched(block_height: u64, tx_index: u32, tx: &Transaction, artifact: &Artifact, txs: &Table<address, Transaction>, tx_to_height: &Table<address, u64>): (bool, RuneId, u128) {
+        if(option::is_none(&artifact.etching)){
+            return (false, RuneId{block: 0, tx: 0}, 0)
+        };
+        let id = RuneId{
+            block: block_height,
+            tx: tx_index,
+        };
+        let rune_opt = option::borrow(&artifact.etching).rune;
+        let rune = if(option::is_some(&rune_opt)){
+            let rune = option::destroy_some(rune_opt);
+            if(rune < minimum_at_height(network::network(), block_height)
+                || is_reserved(rune)
+                || exists_rune(rune)
+                || !tx_commits_to_rune(tx, &artifact.commitment_inputs, block_height, txs, tx_to_height)){
+                return (false, id, 0)
+            };
+            rune
+        }else{
+            reserved_rune(block_height, tx_index)
+        };
+        (true, id, rune)
+    }
+
+    /// The rune name should be committed by a taproot input, and the commit transaction should be mature
+    fun tx_commits_to_rune(tx: &Transaction, commitment_inputs: &vector<u32>, block_height: u64, txs: &Table<address, Transaction>, tx_to_height: &Table<address, u64>): bool {
+        let inputs = types::tx_input(tx);
+        let idx = 0;
+        let len = vector::length(commitment_inputs);
+        while(idx < len){
+            let input_index = *vector::borrow(commitment_inputs, idx);
+            let outpoint = types::txin_previous_output(vector::borrow(inputs, (input_index as u64)));
+            let prev_txid = types::outpoint_txid(outpoint);
+            let prev_vout = (types::outpoint_vout(outpoint) as u64);
+            if(table::contains(txs, prev_txid)){
+                let prev_outputs = types::tx_output(table::borrow(txs, prev_txid));
+                if(prev_vout < vector::length(prev_outputs)){
+                    let prev_output = vector::borrow(prev_outputs, prev_vout);
+                    let commit_height = *table::borrow(tx_to_height, prev_txid);
+                    if(script_buf::is_p2tr(types::txout_script_pubkey(prev_output))
+                        && block_height - commit_height + 1 >= COMMIT_CONFIRMATIONS){
+                        return true
+                    };
+                };
+            };
+            idx = idx + 1;
+        };
+        false
+    }
+
+    fun allocate_edict(edict: &Edict, is_etched: bool, etched_id: RuneId, outputs: &vector<TxOut>, unallocated: &mut vector<RuneBalance>, allocated: &mut vector<vector<RuneBalance>>){
+        let id = edict.id;
+        if(id.block == 0 && id.tx == 0){
+            if(!is_etched){
+                return
+            };
+            id = etched_id;
+        };
+        let balance_idx = 0;
+        let unallocated_len = vector::length(unallocated);
+        while(balance_idx < unallocated_len && vector::borrow(unallocated, balance_idx).id != id){
+            balance_idx = balance_idx + 1;
+        };
+        if(balance_idx == unallocated_len){
+            return
+        };
+        let balance = &mut vector::borrow_mut(unallocated, balance_idx).amount;
+        let output = (edict.output as u64);
+        if(output == vector::length(outputs)){
+            let destinations = vector::empty<u64>();
+            let idx = 0;
+            while(idx < output){
+                if(!script_buf::is_op_return(types::txout_script_pubkey(vector::borrow(outputs, idx)))){
+                    vector::push_back(&mut destinations, idx);
+                };
+                idx = idx + 1;
+            };
+            let destinations_len = vector::length(&destinations);
+            if(destinations_len == 0){
+                return
+            };
+            idx = 0;
+            if(edict.amount == 0){
+                // Divide the balance between the non OP_RETURN outputs
+                let amount = *balance / (destinations_len as u128);
+                let remainder = ((*balance % (destinations_len as u128)) as u64);
+                while(idx < destinations_len){
+                    let destination = *vector::borrow(&destinations, idx);
+                    let amount = if(idx < remainder) { amount + 1 } else { amount };
+                    allocate(balance, allocated, destination, id, amount);
+                    idx = idx + 1;
+                };
+            }else{
+                while(idx < destinations_len){
+                    let destination = *vector::borrow(&destinations, idx);
+                    let amount = if(edict.amount < *balance) { edict.amount } else { *balance };
+                    allocate(balance, allocated, destination, id, amount);
+                    idx = idx + 1;
+                };
+            };
+        }else{
+            let amount = if(edict.amount == 0 || edict.amount > *balance) { *balance } else { edict.amount };
+            allocate(balance, allocated, output, id, amount);
+        };
+    }
+
+    fun allocate(balance: &mut u128, allocated: &mut vector<vector<RuneBalance>>, output: u64, id: RuneId, amount: u128){
+        if(amount > 0){
+            *balance = *balance - amount;
+            add_balance(vector::borrow_mut(allocated, output), id, amount);
+        };
+    }
+
+    fun first_non_op_return_output(outputs: &vector<TxOut>): Option<u64> {
+        let idx = 0;
+        let len = vector::length(outputs);
+        while(idx < len){
+            if(!script_buf::is_op_return(types::txout_script_pubkey(vector::borrow(outputs, idx)))){
+                return option::some(idx)
+            };
+            idx = idx + 1;
+        };
+        option::none()
+    }
+
+    fun create_rune_entry(txid: address, artifact: &Artifact, id: RuneId, rune: u128, timestamp: u32){
+        let store_obj = borrow_mut_rune_store_obj();
+        let number = {
+            let store = object::borrow_mut(store_obj);
+            let number = store.next_number;
+            store.next_number = number + 1;
+            number
+        };
+        let rune_entry = if(artifact.cenotaph){
+            RuneEntry{
+                id,
+                rune,
+                spacers: 0,
+                number,
+                etching: txid,
+                divisibility: 0,
+                symbol: option::none(),
+                premine: 0,
+                terms: option::none(),
+                turbo: false,
+                mints: 0,
+                burned: 0,
+                timestamp,
+            }
+        }else{
+            let etching = option::borrow(&artifact.etching);
+            RuneEntry{
+                id,
+                rune,
+                spacers: option::get_with_default(&etching.spacers, 0),
+                number,
+                etching: txid,
+                divisibility: option::get_with_default(&etching.divisibility, 0),
+                symbol: etching.symbol,
+                premine: option::get_with_default(&etching.premine, 0),
+                terms: etching.terms,
+                turbo: etching.turbo,
+                mints: 0,
+                burned: 0,
+                timestamp,
+            }
+        };
+        object::add_field(store_obj, rune, id);
+        let rune_entry_obj = object::new_with_parent_and_id(store_obj, id, rune_entry);
+        object::transfer_extend(rune_entry_obj, @bitcoin_move);
+        event::emit(RuneEtchedEvent{
+            id,
+            rune,
+            txid,
+        });
+    }
+
+    native fun parse_runestone(tx: &Transaction): Option<Artifact>;
+
+    #[test_only]
+    public fun parse_runestone_for_test(tx: &Transaction): Option<Artifact> {
+        parse_runestone(tx)
+    }
+
+    #[test]
+    fun test_minimum_at_height(){
+        let mainnet = network::network_bitcoin();
+        let start = first_rune_height(mainnet);
+        assert!(minimum_at_height(mainnet, 0) == 99246114928149462, 1);
+        assert!(minimum_at_height(mainnet, start - 2) == 99246114928149462, 2);
+        assert!(minimum_at_height(mainnet, start - 1) == 99246114928149462, 3);
+        assert!(minimum_at_height(mainnet, start) == 99240661844911652, 4);
+        assert!(minimum_at_height(mainnet, start + 17500 - 1) == 3817158266467286, 5);
+        assert!(minimum_at_height(mainnet, start + 210000 - 2) == 1, 6);
+        assert!(minimum_at_height(mainnet, start + 210000) == 0, 7);
+        assert!(minimum_at_height(network::network_regtest(), 0) == 99240661844911652, 8);
+    }
+
+    #[test]
+    fun test_reserved_rune(){
+        assert!(!is_reserved(RESERVED_RUNE - 1), 1);
+        assert!(is_reserved(reserved_rune(0, 0)), 2);
+        assert!(reserved_rune(1, 2) == RESERVED_RUNE + (1 << 32) + 2, 3);
+    }
+
+    #[test_only]
+    use rooch_framework::bitcoin_address;
+
+    #[test_only]
+    fun init_for_test(){
+        rooch_framework::genesis::init_for_test();
+        bitcoin_move::genesis::init_for_test();
+    }
+
+    /// The OP_RETURN output with the runestone payload, the payload is the varint encoded integers
+    #[test_only]
+    fun runestone_txout_for_test(payload: vector<u8>): TxOut {
+        let script = vector[0x6a, 0x5d, (vector::length(&payload) as u8)];
+        vector::append(&mut script, payload);
+        types::new_script_txout_for_test(0, script)
+    }
+
+    #[test_only]
+    fun new_output_for_test(): TxOut {
+        types::new_txout_for_test(546, bitcoin_address::random_address_for_testing())
+    }
+
+    #[test_only]
+    fun balance_of(balances: &vector<RuneBalance>, id: RuneId): u128 {
+        let idx = 0;
+        let len = vector::length(balances);
+        while(idx < len){
+            let balance = vector::borrow(balances, idx);
+            if(balance.id == id){
+                return balance.amount
+            };
+            idx = idx + 1;
+        };
+        0
+    }
+
+    /// Process the transaction as the first transaction of a new pending block at the given height, return the sealed outputs
+    #[test_only]
+    fun process_transaction_for_test(block_height: u64, tx: Transaction, unallocated: vector<RuneBalance>): vector<u32> {
+        let txid = types::tx_id(&tx);
+        let header = types::new_header_for_test(1, @0x0, @0x0, 1713571767, 0x1d00ffff, 0);
+        let block_hash = moveos_std::tx_context::fresh_address_for_testing();
+        pending_block::add_pending_block(block_height, block_hash, types::new_block_for_test(header, vector[tx]));
+        let inprocess_block = pending_block::process_pending_tx(block_hash, txid);
+        let txs = table::new();
+        let tx_to_height = table::new();
+        let output_seals = simple_multimap::new();
+        process_transaction(&inprocess_block, &tx, unallocated, &txs, &tx_to_height, &mut output_seals);
+        pending_block::finish_pending_tx(inprocess_block);
+        table::drop(txs);
+        table::drop(tx_to_height);
+        let sealed_outputs = simple_multimap::keys(&output_seals);
+        simple_multimap::drop(output_seals);
+        sealed_outputs
+    }
+
+    #[test_only]
+    fun etching_artifact_for_test(rune: Option<u128>, commitment_inputs: vector<u32>): Artifact {
+        Artifact{
+            cenotaph: false,
+            flaw: option::none(),
+            edicts: vector[],
+            etching: option::some(Etching{
+                divisibility: option::some(2),
+                premine: option::some(1000),
+                rune,
+                spacers: option::none(),
+                symbol: option::none(),
+                terms: option::none(),
+                turbo: false,
+            }),
+            mint: option::none(),
+            pointer: option::none(),
+            commitment_inputs,
+        }
+    }
+
+    /// Etch a rune without name and premine 1000 at the height 100, return the etching txid
+    #[test_only]
+    fun etch_for_test(): address {
+        // Flags: Etching, Premine: 1000
+        let tx = types::new_transaction_for_test(vector[], vector[runestone_txout_for_test(x"020106e807"), new_output_for_test()]);
+        let txid = types::tx_id(&tx);
+        let sealed_outputs = process_transaction_for_test(100, tx, vector[]);
+        assert!(sealed_outputs == vector[1], 1000);
+        txid
+    }
+
+    #[test]
+    fun test_process_etching(){
+        init_for_test();
+        let txid = etch_for_test();
+        let id = new_rune_id(100, 0);
+        assert!(exists_rune_entry(id), 1);
+        let rune_entry = borrow_rune_entry(id);
+        assert!(rune_entry.rune == reserved_rune(100, 0), 2);
+        assert!(rune_entry.premine == 1000, 3);
+        assert!(rune_entry.etching == txid, 4);
+        assert!(rune_entry.number == 0, 5);
+        assert!(get_rune_id(reserved_rune(100, 0)) == option::some(id), 6);
+        // The premine is assigned to the first non OP_RETURN output
+        let balances = get_rune_balances(types::new_outpoint(txid, 1));
+        assert!(balance_of(&balances, id) == 1000, 7);
+        assert!(vector::is_empty(&get_rune_balances(types::new_outpoint(txid, 0))), 8);
+    }
+
+    #[test]
+    fun test_process_transfer(){
+        init_for_test();
+        etch_for_test();
+        let id = new_rune_id(100, 0);
+        // Edict: 100:0, amount 300, output 1
+        let tx = types::new_transaction_for_test(vector[], vector[new_output_for_test(), new_output_for_test(), runestone_txout_for_test(x"006400ac0201")]);
+        let txid = types::tx_id(&tx);
+        let sealed_outputs = process_transaction_for_test(101, tx, vector[RuneBalance{id, amount: 1000}]);
+        assert!(vector::length(&sealed_outputs) == 2, 1);
+        assert!(balance_of(&get_rune_balances(types::new_outpoint(txid, 1)), id) == 300, 2);
+        // The unallocated runes are assigned to the first non OP_RETURN output
+        assert!(balance_of(&get_rune_balances(types::new_outpoint(txid, 0)), id) == 700, 3);
+        assert!(borrow_rune_entry(id).burned == 0, 4);
+    }
+
+    #[test]
+    fun test_process_transfer_to_op_return(){
+        init_for_test();
+        etch_for_test();
+        let id = new_rune_id(100, 0);
+        // Edict: 100:0, amount 300, output 0, the unallocated runes are kept by the output 1
+        let tx = types::new_transaction_for_test(vector[], vector[runestone_txout_for_test(x"006400ac0200"), new_output_for_test()]);
+        let txid = types::tx_id(&tx);
+        process_transaction_for_test(101, tx, vector[RuneBalance{id, amount: 1000}]);
+        assert!(borrow_rune_entry(id).burned == 300, 1);
+        assert!(balance_of(&get_rune_balances(types::new_outpoint(txid, 1)), id) == 700, 2);
+    }
+
+    #[test]
+    fun test_process_cenotaph(){
+        init_for_test();
+        etch_for_test();
+        let id = new_rune_id(100, 0);
+        // The unrecognized flag makes a cenotaph, all the input runes are burned
+        let tx = types::new_transaction_for_test(vector[], vector[new_output_for_test(), runestone_txout_for_test(x"0208")]);
+        let txid = types::tx_id(&tx);
+        let sealed_outputs = process_transaction_for_test(101, tx, vector[RuneBalance{id, amount: 1000}]);
+        assert!(vector::is_empty(&sealed_outputs), 1);
+        assert!(vector::is_empty(&get_rune_balances(types::new_outpoint(txid, 0))), 2);
+        assert!(borrow_rune_entry(id).burned == 1000, 3);
+    }
+
+    #[test]
+    fun test_process_mint(){
+        init_for_test();
+        // Flags: Etching and Terms, Amount: 100, Cap: 1
+        let tx = types::new_transaction_for_test(vector[], vector[runestone_txout_for_test(x"02030a640801"), new_output_for_test()]);
+        process_transaction_for_test(100, tx, vector[]);
+        let id = new_rune_id(100, 0);
+        assert!(rune_entry_mintable(borrow_rune_entry(id), 101) == option::some(100), 1);
+
+        // Mint: 100:0
+        let tx = types::new_transaction_for_test(vector[], vector[new_output_for_test(), runestone_txout_for_test(x"14641400")]);
+        let txid = types::tx_id(&tx);
+        process_transaction_for_test(101, tx, vector[]);
+        assert!(balance_of(&get_rune_balances(types::new_outpoint(txid, 0)), id) == 100, 2);
+        assert!(borrow_rune_entry(id).mints == 1, 3);
+
+        // The cap is reached
+        let tx = types::new_transaction_for_test(vector[], vector[new_output_for_test(), runestone_txout_for_test(x"14641400")]);
+        let txid = types::tx_id(&tx);
+        process_transaction_for_test(102, tx, vector[]);
+        assert!(vector::is_empty(&get_rune_balances(types::new_outpoint(txid, 0))), 4);
+        assert!(borrow_rune_entry(id).mints == 1, 5);
+    }
+
+    #[test]
+    fun test_tx_commits_to_rune(){
+        init_for_test();
+        let p2tr_script = x"51200f0c8db753acbd17343a39c2f3f4e35e4be6da749f9e35137ab220e7b238a667";
+        let commit_tx = types::new_transaction_for_test(vector[], vector[types::new_script_txout_for_test(10000, p2tr_script), new_output_for_test()]);
+        let commit_txid = types::tx_id(&commit_tx);
+        let txs = table::new();
+        let tx_to_height = table::new();
+        table::add(&mut txs, commit_txid, commit_tx);
+        table::add(&mut tx_to_height, commit_txid, 100);
+
+        let reveal_tx = types::new_transaction_for_test(vector[
+            types::new_txin_for_test(types::new_outpoint(commit_txid, 1), vector[]),
+            types::new_txin_for_test(types::new_outpoint(commit_txid, 0), vector[]),
+            types::new_txin_for_test(types::new_outpoint(commit_txid, 2), vector[]),
+        ], vector[new_output_for_test()]);
+        // The commit transaction is confirmed by 6 blocks
+        assert!(tx_commits_to_rune(&reveal_tx, &vector[1], 105, &txs, &tx_to_height), 1);
+        assert!(!tx_commits_to_rune(&reveal_tx, &vector[1], 104, &txs, &tx_to_height), 2);
+        // The commitment input does not spend a P2TR output
+        assert!(!tx_commits_to_rune(&reveal_tx, &vector[0], 105, &txs, &tx_to_height), 3);
+        // The spent output does not exist
+        assert!(!tx_commits_to_rune(&reveal_tx, &vector[2], 105, &txs, &tx_to_height), 4);
+        assert!(!tx_commits_to_rune(&reveal_tx, &vector[], 105, &txs, &tx_to_height), 5);
+        assert!(tx_commits_to_rune(&reveal_tx, &vector[0, 1], 105, &txs, &tx_to_height), 6);
+
+        // The rune name is etched only if it is committed
+        let rune = 99246114928149462;
+        let (is_etched, id, etched_rune) = etched(105, 1, &reveal_tx, &etching_artifact_for_test(option::some(rune), vector[1]), &txs, &tx_to_height);
+        assert!(is_etched && id == new_rune_id(105, 1) && etched_rune == rune, 7);
+        let (is_etched, _, _) = etched(104, 1, &reveal_tx, &etching_artifact_for_test(option::some(rune), vector[1]), &txs, &tx_to_height);
+        assert!(!is_etched, 8);
+        table::drop(txs);
+        table::drop(tx_to_height);
+    }
+
+    #[test]
+    fun test_etched(){
+        init_for_test();
+        let txs = table::new();
+        let tx_to_height = table::new();
+        let tx = types::new_transaction_for_test(vector[], vector[new_output_for_test()]);
+
+        // The etching without the rune name gets the reserved rune
+        let (is_etched, id, rune) = etched(105, 1, &tx, &etching_artifact_for_test(option::none(), vector[]), &txs, &tx_to_height);
+        assert!(is_etched && id == new_rune_id(105, 1) && rune == reserved_rune(105, 1), 1);
+
+        // The rune name is less than the minimum at the height
+        let (is_etched, _, _) = etched(105, 1, &tx, &etching_artifact_for_test(option::some(1), vector[]), &txs, &tx_to_height);
+        assert!(!is_etched, 2);
+
+        // The reserved rune name can not be etched
+        let (is_etched, _, _) = etched(105, 1, &tx, &etching_artifact_for_test(option::some(RESERVED_RUNE), vector[]), &txs, &tx_to_height);
+        assert!(!is_etched, 3);
+
+        // The rune name is not committed
+        let (is_etched, _, _) = etched(105, 1, &tx, &etching_artifact_for_test(option::some(99246114928149462), vector[]), &txs, &tx_to_height);
+        assert!(!is_etched, 4);
+
+        // No etching
+        let artifact = etching_artifact_for_test(option::none(), vector[]);
+        artifact.etching = option::none();
+        let (is_etched, _, _) = etched(105, 1, &tx, &artifact, &txs, &tx_to_height);
+        assert!(!is_etched, 5);
+
+        // The rune name is already etched
+        let artifact = etching_artifact_for_test(option::some(99246114928149462), vector[]);
+        create_rune_entry(types::tx_id(&tx), &artifact, new_rune_id(100, 1), 99246114928149462, 0);
+        let (is_etched, _, _) = etched(105, 1, &tx, &artifact, &txs, &tx_to_height);
+        assert!(!is_etched, 6);
+        table::drop(txs);
+        table::drop(tx_to_height);
+    }
+
+    #[test]
+    fun test_create_rune_entry(){
+        // The RuneStore is not created by the genesis, it is created on the first etching
+        rooch_framework::genesis::init_for_test();
+        assert!(!exists_rune_store(), 1);
+        assert!(!exists_rune(99246114928149462), 2);
+        assert!(option::is_none(&get_rune_id(99246114928149462)), 3);
+
+        let txid = moveos_std::tx_context::fresh_address_for_testing();
+        let id = new_rune_id(100, 1);
+        create_rune_entry(txid, &etching_artifact_for_test(option::some(99246114928149462), vector[]), id, 99246114928149462, 1713571767);
+        assert!(exists_rune_store(), 4);
+        assert!(get_rune_id(99246114928149462) == option::some(id), 5);
+        let rune_entry = borrow_rune_entry(id);
+        assert!(rune_entry.number == 0, 6);
+        assert!(rune_entry.etching == txid, 7);
+        assert!(rune_entry.divisibility == 2, 8);
+        assert!(rune_entry.premine == 1000, 9);
+        assert!(rune_entry.timestamp == 1713571767, 10);
+
+        // The cenotaph only keeps the rune name
+        let artifact = etching_artifact_for_test(option::some(99246114928149463), vector[]);
+        artifact.cenotaph = true;
+        let id = new_rune_id(101, 1);
+        create_rune_entry(txid, &artifact, id, 99246114928149463, 1713571767);
+        let rune_entry = borrow_rune_entry(id);
+        assert!(rune_entry.number == 1, 11);
+        assert!(rune_entry.divisibility == 0, 12);
+        assert!(rune_entry.premine == 0, 13);
+    }
+
+    #[test]
+    fun test_allocate_edict(){
+        init_for_test();
+        let outputs = vector[new_output_for_test(), runestone_txout_for_test(x""), new_output_for_test(), new_output_for_test()];
+        let allocated = vector[vector[], vector[], vector[], vector[]];
+        let id = new_rune_id(100, 0);
+        let unallocated = vector[RuneBalance{id, amount: 1000}];
+        let no_etched = new_rune_id(0, 0);
+
+        allocate_edict(&Edict{id, amount: 100, output: 0}, false, no_etched, &outputs, &mut unallocated, &mut allocated);
+        // Split 50 to every non OP_RETURN output
+        allocate_edict(&Edict{id, amount: 50, output: 4}, false, no_etched, &outputs, &mut unallocated, &mut allocated);
+        allocate_edict(&Edict{id, amount: 1, output: 2}, false, no_etched, &outputs, &mut unallocated, &mut allocated);
+        // Split the rest 749 to the non OP_RETURN outputs, the remainder goes to the first outputs
+        allocate_edict(&Edict{id, amount: 0, output: 4}, false, no_etched, &outputs, &mut unallocated, &mut allocated);
+        assert!(balance_of(vector::borrow(&allocated, 0), id) == 400, 1);
+        assert!(balance_of(vector::borrow(&allocated, 1), id) == 0, 2);
+        assert!(balance_of(vector::borrow(&allocated, 2), id) == 301, 3);
+        assert!(balance_of(vector::borrow(&allocated, 3), id) == 299, 4);
+        assert!(balance_of(&unallocated, id) == 0, 5);
+
+        // The amount is greater than the balance
+        let other_id = new_rune_id(200, 1);
+        add_balance(&mut unallocated, other_id, 10);
+        allocate_edict(&Edict{id: other_id, amount: 100, output: 3}, false, no_etched, &outputs, &mut unallocated, &mut allocated);
+        assert!(balance_of(vector::borrow(&allocated, 3), other_id) == 10, 6);
+
+        // The edict of the rune etched by the same transaction
+        let etched_id = new_rune_id(300, 2);
+        add_balance(&mut unallocated, etched_id, 20);
+        allocate_edict(&Edict{id: new_rune_id(0, 0), amount: 5, output: 2}, false, no_etched, &outputs, &mut unallocated, &mut allocated);
+        assert!(balance_of(&unallocated, etched_id) == 20, 7);
+        allocate_edict(&Edict{id: new_rune_id(0, 0), amount: 5, output: 2}, true, etched_id, &outputs, &mut unallocated, &mut allocated);
+        assert!(balance_of(vector::borrow(&allocated, 2), etched_id) == 5, 8);
+        assert!(balance_of(&unallocated, etched_id) == 15, 9);
+
+        // The rune is not in the unallocated balances
+        allocate_edict(&Edict{id: new_rune_id(400, 0), amount: 5, output: 0}, false, no_etched, &outputs, &mut unallocated, &mut allocated);
+        assert!(balance_of(vector::borrow(&allocated, 0), new_rune_id(400, 0)) == 0, 10);
+    }
+
+    #[test]
+    fun test_add_balance(){
+        let balances = vector::empty<RuneBalance>();
+        add_balance(&mut balances, new_rune_id(1, 0), 10);
+        add_balance(&mut balances, new_rune_id(2, 1), 0);
+        add_balance(&mut balances, new_rune_id(1, 0), 5);
+        assert!(vector::length(&balances) == 1, 1);
+        assert!(vector::borrow(&balances, 0).amount == 15, 2);
+    }
+}
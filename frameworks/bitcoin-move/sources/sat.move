@@ -0,0 +1,444 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+/// The sat numbering and rarity of the ordinal theory, https://docs.ordinals.com/overview.html
+/// When the sat tracking of the Bitcoin config is enabled, the sat ranges of every UTXO are tracked in the style of ord's `index-sats` mode.
+/// The location of every sat range is indexed by its start, so the location of any tracked sat can be found.
+/// The UTXOs created before the sat tracking is enabled have no sat ranges, the transactions spending them do not assign sat ranges to their outputs.
+module bitcoin_move::sat{
+    use std::vector;
+    use std::option::{Self, Option};
+    use moveos_std::object::{Self, Object};
+    use bitcoin_move::types::{Self, TxOut, OutPoint};
+    use bitcoin_move::config;
+
+    friend bitcoin_move::genesis;
+    friend bitcoin_move::bitcoin;
+
+    const SUBSIDY_HALVING_INTERVAL: u64 = 210000;
+    const DIFFCHANGE_INTERVAL: u64 = 2016;
+    const CYCLE_EPOCHS: u64 = 6;
+    const FIRST_POST_SUBSIDY_EPOCH: u64 = 33;
+    const COIN_VALUE: u64 = 100000000;
+    /// The total supply of sats, all the sat numbers are less than it
+    const SUPPLY: u64 = 2099999997690000;
+
+    const RARITY_COMMON: u8 = 0;
+    public fun rarity_common(): u8 {
+        RARITY_COMMON
+    }
+
+    /// The first sat of each block
+    const RARITY_UNCOMMON: u8 = 1;
+    public fun rarity_uncommon(): u8 {
+        RARITY_UNCOMMON
+    }
+
+    /// The first sat of each difficulty adjustment period
+    const RARITY_RARE: u8 = 2;
+    public fun rarity_rare(): u8 {
+        RARITY_RARE
+    }
+
+    /// The first sat of each halving epoch
+    const RARITY_EPIC: u8 = 3;
+    public fun rarity_epic(): u8 {
+        RARITY_EPIC
+    }
+
+    /// The first sat of each cycle
+    const RARITY_LEGENDARY: u8 = 4;
+    public fun rarity_legendary(): u8 {
+        RARITY_LEGENDARY
+    }
+
+    /// The first sat of the genesis block
+    const RARITY_MYTHIC: u8 = 5;
+    public fun rarity_mythic(): u8 {
+        RARITY_MYTHIC
+    }
+
+    const ErrorInvalidSat: u64 = 1;
+    const ErrorInvalidSatRange: u64 = 2;
+
+    /// The sats in [start, end)
+    struct SatRange has store, copy, drop {
+        start: u64,
+        end: u64,
+    }
+
+    /// The location of a sat, the `offset` is the position of the sat in the output
+    struct SatLocation has store, copy, drop {
+        outpoint: OutPoint,
+        offset: u64,
+    }
+
+    /// The end and the location of an indexed sat range, the location is none if the sats are lost
+    struct SatRangeLocation has store, copy, drop {
+        end: u64,
+        location: Option<SatLocation>,
+    }
+
+    /// The SatStore keeps the sat range start -> SatRangeLocation mapping in the fields
+    struct SatStore has key {
+        /// The sats not claimed by the coinbase transactions
+        lost_sats: u64,
+    }
+
+    public(friend) fun genesis_init(){
+        let store_obj = object::new_named_object(SatStore{
+            lost_sats: 0,
+        });
+        object::to_shared(store_obj);
+    }
+
+    fun borrow_sat_store_obj(): &Object<SatStore> {
+        let object_id = object::named_object_id<SatStore>();
+        object::borrow_object<SatStore>(object_id)
+    }
+
+    fun exists_sat_store(): bool {
+        object::exists_object_with_type<SatStore>(object::named_object_id<SatStore>())
+    }
+
+    /// The SatStore is created lazily if the framework is upgraded from a version without it
+    fun borrow_mut_sat_store_obj(): &mut Object<SatStore> {
+        if(!exists_sat_store()){
+            genesis_init();
+        };
+        let object_id = object::named_object_id<SatStore>();
+        object::borrow_mut_object_shared<SatStore>(object_id)
+    }
+
+    /// Whether the sat ranges tracking is enabled
+    public fun is_enabled(): bool {
+        config::sat_tracking_enabled()
+    }
+
+    // ==== Sat numbering ==== //
+
+    /// The block subsidy of the epoch
+    public fun epoch_subsidy(epoch: u64): u64 {
+        if(epoch < FIRST_POST_SUBSIDY_EPOCH){
+            (50 * COIN_VALUE) >> (epoch as u8)
+        }else{
+            0
+        }
+    }
+
+    /// The first sat of the epoch
+    public fun epoch_starting_sat(epoch: u64): u64 {
+        let sat = 0;
+        let i = 0;
+        while(i < epoch && i < FIRST_POST_SUBSIDY_EPOCH){
+            sat = sat + SUBSIDY_HALVING_INTERVAL * epoch_subsidy(i);
+            i = i + 1;
+        };
+        sat
+    }
+
+    /// The first sat mined by the block at the given height
+    public fun height_starting_sat(height: u64): u64 {
+        let epoch = height / SUBSIDY_HALVING_INTERVAL;
+        if(epoch >= FIRST_POST_SUBSIDY_EPOCH){
+            return SUPPLY
+        };
+        epoch_starting_sat(epoch) + (height - epoch * SUBSIDY_HALVING_INTERVAL) * epoch_subsidy(epoch)
+    }
+
+    /// The sats mined by the block at the given height, empty if the block has no subsidy
+    public fun subsidy_sat_ranges(height: u64): vector<SatRange> {
+        let subsidy = epoch_subsidy(height / SUBSIDY_HALVING_INTERVAL);
+        if(subsidy == 0){
+            return vector::empty()
+        };
+        let start = height_starting_sat(height);
+        vector::singleton(SatRange{
+            start,
+            end: start + subsidy,
+        })
+    }
+
+    /// The halving epoch of the sat
+    public fun sat_epoch(sat: u64): u64 {
+        assert!(sat < SUPPLY, ErrorInvalidSat);
+        let epoch = 0;
+        let next_epoch_start = SUBSIDY_HALVING_INTERVAL * epoch_subsidy(0);
+        while(next_epoch_start <= sat){
+            epoch = epoch + 1;
+            next_epoch_start = next_epoch_start + SUBSIDY_HALVING_INTERVAL * epoch_subsidy(epoch);
+        };
+        epoch
+    }
+
+    /// The height of the block which mined the sat
+    public fun sat_height(sat: u64): u64 {
+        let epoch = sat_epoch(sat);
+        epoch * SUBSIDY_HALVING_INTERVAL + (sat - epoch_starting_sat(epoch)) / epoch_subsidy(epoch)
+    }
+
+    /// The position of the sat in the block subsidy
+    public fun sat_third(sat: u64): u64 {
+        let epoch = sat_epoch(sat);
+        (sat - epoch_starting_sat(epoch)) % epoch_subsidy(epoch)
+    }
+
+    /// The rarity of the sat, one of the `RARITY_*` constants
+    public fun rarity(sat: u64): u8 {
+        if(sat_third(sat) != 0){
+            return RARITY_COMMON
+        };
+        let height = sat_height(sat);
+        let hour = height / (CYCLE_EPOCHS * SUBSIDY_HALVING_INTERVAL);
+        let minute = height % SUBSIDY_HALVING_INTERVAL;
+        let second = height % DIFFCHANGE_INTERVAL;
+        if(hour == 0 && minute == 0 && second == 0){
+            RARITY_MYTHIC
+        }else if(minute == 0 && second == 0){
+            RARITY_LEGENDARY
+        }else if(minute == 0){
+            RARITY_EPIC
+        }else if(second == 0){
+            RARITY_RARE
+        }else{
+            RARITY_UNCOMMON
+        }
+    }
+
+    // ==== Sat ranges ==== //
+
+    public fun new_sat_range(start: u64, end: u64): SatRange {
+        assert!(start < end && end <= SUPPLY, ErrorInvalidSatRange);
+        SatRange{
+            start,
+            end,
+        }
+    }
+
+    public fun sat_range_start(self: &SatRange): u64 {
+        self.start
+    }
+
+    public fun sat_range_end(self: &SatRange): u64 {
+        self.end
+    }
+
+    /// The total sats of the ranges
+    public fun sat_ranges_value(ranges: &vector<SatRange>): u64 {
+        let value = 0;
+        let i = 0;
+        let len = vector::length(ranges);
+        while(i < len){
+            let range = vector::borrow(ranges, i);
+            value = value + (range.end - range.start);
+            i = i + 1;
+        };
+        value
+    }
+
+    /// The sat at the offset of the ranges, return none if the offset is out of the ranges
+    public fun sat_at(ranges: &vector<SatRange>, offset: u64): Option<u64> {
+        let i = 0;
+        let len = vector::length(ranges);
+        while(i < len){
+            let range = vector::borrow(ranges, i);
+            let size = range.end - range.start;
+            if(offset < size){
+                return option::some(range.start + offset)
+            };
+            offset = offset - size;
+            i = i + 1;
+        };
+        option::none()
+    }
+
+    /// Assign the input sat ranges to the outputs in order, "First in First out".
+    /// Return the sat ranges of each output and the remaining sat ranges which are paid as the fee.
+    public(friend) fun allocate(input_ranges: vector<SatRange>, outputs: &vector<TxOut>): (vector<vector<SatRange>>, vector<SatRange>) {
+        // reverse the ranges and pop from the end
+        vector::reverse(&mut input_ranges);
+        let output_ranges = vector::empty();
+        let i = 0;
+        let len = vector::length(outputs);
+        while(i < len){
+            let remaining = types::txout_value(vector::borrow(outputs, i));
+            let ranges = vector::empty();
+            while(remaining > 0 && !vector::is_empty(&input_ranges)){
+                let range = vector::pop_back(&mut input_ranges);
+                let size = range.end - range.start;
+                if(size > remaining){
+                    let middle = range.start + remaining;
+                    vector::push_back(&mut ranges, SatRange{start: range.start, end: middle});
+                    vector::push_back(&mut input_ranges, SatRange{start: middle, end: range.end});
+                    remaining = 0;
+                }else{
+                    vector::push_back(&mut ranges, range);
+                    remaining = remaining - size;
+                };
+            };
+            vector::push_back(&mut output_ranges, ranges);
+            i = i + 1;
+        };
+        vector::reverse(&mut input_ranges);
+        (output_ranges, input_ranges)
+    }
+
+    // ==== Sat location index ==== //
+
+    public fun sat_location_outpoint(self: &SatLocation): OutPoint {
+        self.outpoint
+    }
+
+    public fun sat_location_offset(self: &SatLocation): u64 {
+        self.offset
+    }
+
+    /// Record the location of the output sat ranges
+    public(friend) fun index_sat_ranges(ranges: &vector<SatRange>, outpoint: OutPoint) {
+        let store_obj = borrow_mut_sat_store_obj();
+        let offset = 0;
+        let i = 0;
+        let len = vector::length(ranges);
+        while(i < len){
+            let range = vector::borrow(ranges, i);
+            object::upsert_field(store_obj, range.start, SatRangeLocation{
+                end: range.end,
+                location: option::some(SatLocation{
+                    outpoint,
+                    offset,
+                }),
+            });
+            offset = offset + (range.end - range.start);
+            i = i + 1;
+        };
+    }
+
+    /// The sats are not claimed by the coinbase transaction, they are lost forever
+    public(friend) fun lose_sats(ranges: &vector<SatRange>) {
+        let store_obj = borrow_mut_sat_store_obj();
+        let i = 0;
+        let len = vector::length(ranges);
+        while(i < len){
+            let range = vector::borrow(ranges, i);
+            // Keep the lost range in the index, the following ranges of the block are found through it
+            object::upsert_field(store_obj, range.start, SatRangeLocation{
+                end: range.end,
+                location: option::none(),
+            });
+            i = i + 1;
+        };
+        let store = object::borrow_mut(store_obj);
+        store.lost_sats = store.lost_sats + sat_ranges_value(ranges);
+    }
+
+    /// Get the location of the sat, return none if the sat is lost or not tracked.
+    /// The sat ranges are split but never merged, so the indexed ranges of a block are contiguous from the first sat of the block,
+    /// the range containing the sat is found by walking the ranges from the first sat of the block.
+    public fun get_sat_location(sat: u64): Option<SatLocation> {
+        if(sat >= SUPPLY || !exists_sat_store()){
+            return option::none()
+        };
+        let store_obj = borrow_sat_store_obj();
+        let start = height_starting_sat(sat_height(sat));
+        while(object::contains_field(store_obj, start)){
+            let range_location: &SatRangeLocation = object::borrow_field(store_obj, start);
+            if(sat < range_location.end){
+                if(option::is_none(&range_location.location)){
+                    return option::none()
+                };
+                let location = *option::borrow(&range_location.location);
+                location.offset = location.offset + (sat - start);
+                return option::some(location)
+            };
+            start = range_location.end;
+        };
+        option::none()
+    }
+
+    /// The total sats which are not claimed by the coinbase transactions since the sat tracking is enabled
+    public fun lost_sats(): u64 {
+        if(!exists_sat_store()){
+            return 0
+        };
+        object::borrow(borrow_sat_store_obj()).lost_sats
+    }
+
+    #[test]
+    fun test_sat_numbering(){
+        assert!(epoch_starting_sat(1) == 1050000000000000, 1);
+        assert!(epoch_starting_sat(33) == SUPPLY, 2);
+        assert!(epoch_starting_sat(34) == SUPPLY, 3);
+        assert!(height_starting_sat(1) == 5000000000, 4);
+        assert!(height_starting_sat(210000) == 1050000000000000, 5);
+        assert!(sat_height(0) == 0, 6);
+        assert!(sat_height(4999999999) == 0, 7);
+        assert!(sat_height(5000000000) == 1, 8);
+        assert!(sat_height(1050000000000001) == 210000, 9);
+        assert!(sat_third(1050000000000001) == 1, 10);
+        assert!(sat_epoch(SUPPLY - 1) == 32, 11);
+        assert!(subsidy_sat_ranges(1) == vector[new_sat_range(5000000000, 10000000000)], 12);
+        assert!(vector::is_empty(&subsidy_sat_ranges(33 * SUBSIDY_HALVING_INTERVAL)), 13);
+    }
+
+    #[test]
+    fun test_rarity(){
+        assert!(rarity(0) == RARITY_MYTHIC, 1);
+        assert!(rarity(1) == RARITY_COMMON, 2);
+        assert!(rarity(5000000000) == RARITY_UNCOMMON, 3);
+        assert!(rarity(10080000000000) == RARITY_RARE, 4);
+        assert!(rarity(1050000000000000) == RARITY_EPIC, 5);
+        assert!(rarity(2067187500000000) == RARITY_LEGENDARY, 6);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ErrorInvalidSat, location = Self)]
+    fun test_invalid_sat(){
+        sat_height(SUPPLY);
+    }
+
+    #[test]
+    fun test_sat_at(){
+        let ranges = vector[new_sat_range(10, 20), new_sat_range(50, 55)];
+        assert!(sat_ranges_value(&ranges) == 15, 1);
+        assert!(option::destroy_some(sat_at(&ranges, 0)) == 10, 2);
+        assert!(option::destroy_some(sat_at(&ranges, 10)) == 50, 3);
+        assert!(option::is_none(&sat_at(&ranges, 15)), 4);
+    }
+
+    #[test]
+    fun test_allocate(){
+        let recipient = rooch_framework::bitcoin_address::random_address_for_testing();
+        let outputs = vector[types::new_txout_for_test(12, recipient), types::new_txout_for_test(0, recipient), types::new_txout_for_test(2, recipient)];
+        let ranges = vector[new_sat_range(10, 20), new_sat_range(50, 55)];
+        let (output_ranges, fee_ranges) = allocate(ranges, &outputs);
+        assert!(*vector::borrow(&output_ranges, 0) == vector[new_sat_range(10, 20), new_sat_range(50, 52)], 1);
+        assert!(vector::is_empty(vector::borrow(&output_ranges, 1)), 2);
+        assert!(*vector::borrow(&output_ranges, 2) == vector[new_sat_range(52, 54)], 3);
+        assert!(fee_ranges == vector[new_sat_range(54, 55)], 4);
+    }
+
+    #[test]
+    fun test_get_sat_location(){
+        rooch_framework::genesis::init_for_test();
+        bitcoin_move::genesis::init_for_test();
+        let block_start = height_starting_sat(1);
+        let outpoint_a = types::new_outpoint(@0x1, 0);
+        let outpoint_b = types::new_outpoint(@0x2, 1);
+        index_sat_ranges(&vector[new_sat_range(block_start, block_start + 100)], outpoint_a);
+        lose_sats(&vector[new_sat_range(block_start + 100, block_start + 150)]);
+        index_sat_ranges(&vector[new_sat_range(20, 40), new_sat_range(block_start + 150, block_start + 5000000000)], outpoint_b);
+        assert!(lost_sats() == 50, 1);
+
+        let location = option::destroy_some(get_sat_location(block_start));
+        assert!(location.outpoint == outpoint_a && location.offset == 0, 2);
+        let location = option::destroy_some(get_sat_location(block_start + 99));
+        assert!(location.outpoint == outpoint_a && location.offset == 99, 3);
+        assert!(option::is_none(&get_sat_location(block_start + 120)), 4);
+        let location = option::destroy_some(get_sat_location(block_start + 160));
+        assert!(location.outpoint == outpoint_b && location.offset == 30, 5);
+        // The first sat of the genesis block is not indexed, so the following ranges of the block can not be found
+        assert!(option::is_none(&get_sat_location(25)), 6);
+        assert!(option::is_none(&get_sat_location(height_starting_sat(2))), 7);
+        assert!(option::is_none(&get_sat_location(SUPPLY)), 8);
+    }
+}
@@ -50,6 +50,13 @@ module bitcoin_move::script_buf{
         sub_vector(&self.bytes, 3, 23)
     }
 
+    /// Checks if the given script is a P2TR(segwit v1 with a 32 bytes program) script.
+    public fun is_p2tr(self: &ScriptBuf): bool{
+        vector::length(&self.bytes) == 34 &&
+            *vector::borrow(&self.bytes,0) == opcode::op_pushnum_1() &&
+            *vector::borrow(&self.bytes,1) == opcode::op_pushbytes_32()
+    }
+
     public fun is_witness_program(self: &ScriptBuf): bool{
         let script_len = vector::length(&self.bytes);
 
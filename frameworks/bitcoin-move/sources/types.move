@@ -340,6 +340,45 @@ module bitcoin_move::types{
         }
     }
 
+    #[test_only]
+    public fun new_txout_for_test(value: u64, recipient_address: BitcoinAddress) : TxOut {
+        TxOut{
+            value,
+            script_pubkey: script_buf::new(vector::empty()),
+            recipient_address,
+        }
+    }
+
+    #[test_only]
+    public fun new_transaction_for_test(input: vector<TxIn>, output: vector<TxOut>) : Transaction {
+        Transaction{
+            id: moveos_std::tx_context::fresh_address_for_testing(),
+            version: 2u32,
+            lock_time: 0u32,
+            input,
+            output,
+        }
+    }
+
+    #[test_only]
+    public fun new_txin_for_test(previous_output: OutPoint, witness: vector<vector<u8>>) : TxIn {
+        TxIn{
+            previous_output,
+            script_sig: vector::empty(),
+            sequence: U32_MAX,
+            witness: Witness{witness},
+        }
+    }
+
+    #[test_only]
+    public fun new_script_txout_for_test(value: u64, script_pubkey: vector<u8>) : TxOut {
+        TxOut{
+            value,
+            script_pubkey: script_buf::new(script_pubkey),
+            recipient_address: bitcoin_address::new_witness_program(vector::empty()),
+        }
+    }
+
     #[test_only]
     public fun new_block_for_test(header: Header, txdata: vector<Transaction>) : Block {
         Block{
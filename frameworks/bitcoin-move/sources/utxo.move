@@ -3,6 +3,7 @@
 
 module bitcoin_move::utxo{
     use std::vector;
+    use std::option::{Self, Option};
     use std::string::String;
     use moveos_std::object::{Self, ObjectID, Object};
     use moveos_std::simple_multimap::{Self, SimpleMultiMap};
@@ -10,12 +11,14 @@ module bitcoin_move::utxo{
     use moveos_std::bag;
     use moveos_std::event;
     use bitcoin_move::types::{Self, OutPoint};
+    use bitcoin_move::sat::SatRange;
 
     friend bitcoin_move::genesis;
     friend bitcoin_move::ord;
     friend bitcoin_move::bitcoin;
 
     const TEMPORARY_AREA: vector<u8> = b"temporary_area";
+    const SAT_RANGES: vector<u8> = b"sat_ranges";
 
     /// The UTXO Object
     struct UTXO has key {
@@ -185,6 +188,7 @@ module bitcoin_move::utxo{
             let bag = object::remove_field(&mut utxo_obj, TEMPORARY_AREA);
             bag::drop(bag);
         };
+        let _ = remove_sat_ranges(&mut utxo_obj);
 
         event::emit<RemovingUTXOEvent>( RemovingUTXOEvent { id: object::id(&utxo_obj) } );
         
@@ -193,6 +197,30 @@ module bitcoin_move::utxo{
         seals
     }
 
+    // === Sat ranges ===
+
+    /// Attach the sat ranges to the UTXO, only called when the sat tracking is enabled
+    public(friend) fun add_sat_ranges(utxo_obj: &mut Object<UTXO>, ranges: vector<SatRange>){
+        object::add_field(utxo_obj, SAT_RANGES, ranges);
+    }
+
+    public(friend) fun remove_sat_ranges(utxo_obj: &mut Object<UTXO>): Option<vector<SatRange>>{
+        if(object::contains_field(utxo_obj, SAT_RANGES)){
+            option::some(object::remove_field(utxo_obj, SAT_RANGES))
+        }else{
+            option::none()
+        }
+    }
+
+    /// Get the sat ranges of the UTXO, return none if the UTXO is not tracked
+    public fun sat_ranges(utxo_obj: &Object<UTXO>): Option<vector<SatRange>>{
+        if(object::contains_field(utxo_obj, SAT_RANGES)){
+            option::some(*object::borrow_field(utxo_obj, SAT_RANGES))
+        }else{
+            option::none()
+        }
+    }
+
     // === UTXOSeal ===
     public fun new_utxo_seal(protocol: String, seal_object_id: ObjectID) : UTXOSeal {
         UTXOSeal{
@@ -0,0 +1,10 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::natives::runes::GasParameters;
+use rooch_framework::natives::gas_parameter::native::MUL;
+
+rooch_framework::natives::gas_parameter::native::define_gas_parameters_for_natives!(GasParameters, "runes", [
+    [.parse_runestone.base, optional "parse_runestone.base", 10000 * MUL],
+    [.parse_runestone.per_byte, optional "parse_runestone.per_byte", 50 * MUL]
+]);
@@ -8,10 +8,12 @@ pub mod inscription;
 #[allow(dead_code)]
 pub mod inscription_id;
 pub mod media;
+pub mod runestone;
 pub mod tag;
 #[cfg(test)]
 #[allow(dead_code)]
 pub(crate) mod test;
+pub mod varint;
 
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_core_types::gas_algebra::{InternalGas, InternalGasPerByte, NumBytes};
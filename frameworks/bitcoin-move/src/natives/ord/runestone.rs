@@ -0,0 +1,780 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+// Code from https://github.com/ordinals/ord/
+
+use super::varint;
+use bitcoin::{opcodes, script::Instruction, Transaction};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Display, Formatter};
+
+pub const MAX_DIVISIBILITY: u8 = 38;
+pub const MAX_SPACERS: u32 = 0b00000111_11111111_11111111_11111111;
+pub const MAGIC_NUMBER: opcodes::All = opcodes::all::OP_PUSHNUM_13;
+
+#[derive(Default, Debug, PartialEq, Copy, Clone, Eq, Ord, PartialOrd, Hash)]
+pub struct RuneId {
+    pub block: u64,
+    pub tx: u32,
+}
+
+impl RuneId {
+    pub fn new(block: u64, tx: u32) -> Option<RuneId> {
+        let id = RuneId { block, tx };
+
+        if id.block == 0 && id.tx > 0 {
+            return None;
+        }
+
+        Some(id)
+    }
+
+    /// The edict ids are delta encoded
+    pub fn next(self: RuneId, block: u128, tx: u128) -> Option<RuneId> {
+        RuneId::new(
+            self.block.checked_add(block.try_into().ok()?)?,
+            if block == 0 {
+                self.tx.checked_add(tx.try_into().ok()?)?
+            } else {
+                tx.try_into().ok()?
+            },
+        )
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Copy, Clone, Eq)]
+pub struct Edict {
+    pub id: RuneId,
+    pub amount: u128,
+    pub output: u32,
+}
+
+impl Edict {
+    pub fn from_integers(tx: &Transaction, id: RuneId, amount: u128, output: u128) -> Option<Self> {
+        let Ok(output) = u32::try_from(output) else {
+            return None;
+        };
+
+        // note that this allows `output == tx.output.len()`, which means to divide
+        // amount between all non-OP_RETURN outputs
+        if output > u32::try_from(tx.output.len()).unwrap() {
+            return None;
+        }
+
+        Some(Self { id, amount, output })
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Copy, Clone, Eq)]
+pub struct Terms {
+    pub amount: Option<u128>,
+    pub cap: Option<u128>,
+    pub height: (Option<u64>, Option<u64>),
+    pub offset: (Option<u64>, Option<u64>),
+}
+
+#[derive(Default, Debug, PartialEq, Copy, Clone, Eq)]
+pub struct Etching {
+    pub divisibility: Option<u8>,
+    pub premine: Option<u128>,
+    pub rune: Option<u128>,
+    pub spacers: Option<u32>,
+    pub symbol: Option<char>,
+    pub terms: Option<Terms>,
+    pub turbo: bool,
+}
+
+impl Etching {
+    pub fn supply(&self) -> Option<u128> {
+        let premine = self.premine.unwrap_or_default();
+        let cap = self.terms.and_then(|terms| terms.cap).unwrap_or_default();
+        let amount = self
+            .terms
+            .and_then(|terms| terms.amount)
+            .unwrap_or_default();
+        premine.checked_add(cap.checked_mul(amount)?)
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Eq)]
+pub enum Flaw {
+    EdictOutput,
+    EdictRuneId,
+    InvalidScript,
+    Opcode,
+    SupplyOverflow,
+    TrailingIntegers,
+    TruncatedField,
+    UnrecognizedEvenTag,
+    UnrecognizedFlag,
+    Varint,
+}
+
+impl Display for Flaw {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::EdictOutput => write!(f, "edict output greater than transaction output count"),
+            Self::EdictRuneId => write!(f, "invalid rune ID in edict"),
+            Self::InvalidScript => write!(f, "invalid script in OP_RETURN"),
+            Self::Opcode => write!(f, "non-pushdata opcode in OP_RETURN"),
+            Self::SupplyOverflow => write!(f, "supply overflows u128"),
+            Self::TrailingIntegers => write!(f, "trailing integers in body"),
+            Self::TruncatedField => write!(f, "field with missing value"),
+            Self::UnrecognizedEvenTag => write!(f, "unrecognized even tag"),
+            Self::UnrecognizedFlag => write!(f, "unrecognized field"),
+            Self::Varint => write!(f, "invalid varint"),
+        }
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Copy, Clone, Eq)]
+pub struct Cenotaph {
+    pub etching: Option<u128>,
+    pub flaw: Option<Flaw>,
+    pub mint: Option<RuneId>,
+}
+
+#[derive(Default, Debug, PartialEq, Clone, Eq)]
+pub struct Runestone {
+    pub edicts: Vec<Edict>,
+    pub etching: Option<Etching>,
+    pub mint: Option<RuneId>,
+    pub pointer: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub enum Artifact {
+    Cenotaph(Cenotaph),
+    Runestone(Runestone),
+}
+
+impl Artifact {
+    pub fn mint(&self) -> Option<RuneId> {
+        match self {
+            Self::Cenotaph(cenotaph) => cenotaph.mint,
+            Self::Runestone(runestone) => runestone.mint,
+        }
+    }
+
+    /// The rune name of the etching
+    pub fn etching_rune(&self) -> Option<u128> {
+        match self {
+            Self::Cenotaph(cenotaph) => cenotaph.etching,
+            Self::Runestone(runestone) => runestone.etching.and_then(|etching| etching.rune),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Tag {
+    Body = 0,
+    Flags = 2,
+    Rune = 4,
+    Premine = 6,
+    Cap = 8,
+    Amount = 10,
+    HeightStart = 12,
+    HeightEnd = 14,
+    OffsetStart = 16,
+    OffsetEnd = 18,
+    Mint = 20,
+    Pointer = 22,
+    #[allow(dead_code)]
+    Cenotaph = 126,
+
+    Divisibility = 1,
+    Spacers = 3,
+    Symbol = 5,
+    #[allow(dead_code)]
+    Nop = 127,
+}
+
+impl Tag {
+    fn take<const N: usize, T>(
+        self,
+        fields: &mut HashMap<u128, VecDeque<u128>>,
+        with: impl Fn([u128; N]) -> Option<T>,
+    ) -> Option<T> {
+        let field = fields.get_mut(&self.into())?;
+
+        let mut values: [u128; N] = [0; N];
+
+        for (i, v) in values.iter_mut().enumerate() {
+            *v = *field.get(i)?;
+        }
+
+        let value = with(values)?;
+
+        field.drain(0..N);
+
+        if field.is_empty() {
+            fields.remove(&self.into()).unwrap();
+        }
+
+        Some(value)
+    }
+}
+
+impl From<Tag> for u128 {
+    fn from(tag: Tag) -> Self {
+        tag as u128
+    }
+}
+
+impl PartialEq<u128> for Tag {
+    fn eq(&self, other: &u128) -> bool {
+        u128::from(*self) == *other
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Flag {
+    Etching = 0,
+    Terms = 1,
+    Turbo = 2,
+}
+
+impl Flag {
+    fn mask(self) -> u128 {
+        1 << self as u128
+    }
+
+    fn take(self, flags: &mut u128) -> bool {
+        let mask = self.mask();
+        let set = *flags & mask != 0;
+        *flags &= !mask;
+        set
+    }
+}
+
+struct Message {
+    flaw: Option<Flaw>,
+    edicts: Vec<Edict>,
+    fields: HashMap<u128, VecDeque<u128>>,
+}
+
+impl Message {
+    fn from_integers(tx: &Transaction, payload: &[u128]) -> Self {
+        let mut edicts = Vec::new();
+        let mut fields = HashMap::<u128, VecDeque<u128>>::new();
+        let mut flaw = None;
+
+        for i in (0..payload.len()).step_by(2) {
+            let tag = payload[i];
+
+            if Tag::Body == tag {
+                let mut id = RuneId::default();
+                for chunk in payload[i + 1..].chunks(4) {
+                    if chunk.len() != 4 {
+                        flaw.get_or_insert(Flaw::TrailingIntegers);
+                        break;
+                    }
+
+                    let Some(next) = id.next(chunk[0], chunk[1]) else {
+                        flaw.get_or_insert(Flaw::EdictRuneId);
+                        break;
+                    };
+
+                    let Some(edict) = Edict::from_integers(tx, next, chunk[2], chunk[3]) else {
+                        flaw.get_or_insert(Flaw::EdictOutput);
+                        break;
+                    };
+
+                    id = next;
+                    edicts.push(edict);
+                }
+                break;
+            }
+
+            let Some(&value) = payload.get(i + 1) else {
+                flaw.get_or_insert(Flaw::TruncatedField);
+                break;
+            };
+
+            fields.entry(tag).or_default().push_back(value);
+        }
+
+        Self {
+            flaw,
+            edicts,
+            fields,
+        }
+    }
+}
+
+enum Payload {
+    Valid(Vec<u8>),
+    Invalid(Flaw),
+}
+
+impl Runestone {
+    pub fn decipher(transaction: &Transaction) -> Option<Artifact> {
+        let payload = match Runestone::payload(transaction) {
+            Some(Payload::Valid(payload)) => payload,
+            Some(Payload::Invalid(flaw)) => {
+                return Some(Artifact::Cenotaph(Cenotaph {
+                    flaw: Some(flaw),
+                    ..Default::default()
+                }));
+            }
+            None => return None,
+        };
+
+        let Ok(integers) = Runestone::integers(&payload) else {
+            return Some(Artifact::Cenotaph(Cenotaph {
+                flaw: Some(Flaw::Varint),
+                ..Default::default()
+            }));
+        };
+
+        let Message {
+            mut flaw,
+            edicts,
+            mut fields,
+        } = Message::from_integers(transaction, &integers);
+
+        let mut flags = Tag::Flags
+            .take(&mut fields, |[flags]| Some(flags))
+            .unwrap_or_default();
+
+        let etching = Flag::Etching.take(&mut flags).then(|| Etching {
+            divisibility: Tag::Divisibility.take(&mut fields, |[divisibility]| {
+                let divisibility = u8::try_from(divisibility).ok()?;
+                (divisibility <= MAX_DIVISIBILITY).then_some(divisibility)
+            }),
+            premine: Tag::Premine.take(&mut fields, |[premine]| Some(premine)),
+            rune: Tag::Rune.take(&mut fields, |[rune]| Some(rune)),
+            spacers: Tag::Spacers.take(&mut fields, |[spacers]| {
+                let spacers = u32::try_from(spacers).ok()?;
+                (spacers <= MAX_SPACERS).then_some(spacers)
+            }),
+            symbol: Tag::Symbol.take(&mut fields, |[symbol]| {
+                char::from_u32(u32::try_from(symbol).ok()?)
+            }),
+            terms: Flag::Terms.take(&mut flags).then(|| Terms {
+                cap: Tag::Cap.take(&mut fields, |[cap]| Some(cap)),
+                height: (
+                    Tag::HeightStart.take(&mut fields, |[start_height]| {
+                        u64::try_from(start_height).ok()
+                    }),
+                    Tag::HeightEnd.take(&mut fields, |[start_height]| {
+                        u64::try_from(start_height).ok()
+                    }),
+                ),
+                amount: Tag::Amount.take(&mut fields, |[amount]| Some(amount)),
+                offset: (
+                    Tag::OffsetStart.take(&mut fields, |[start_offset]| {
+                        u64::try_from(start_offset).ok()
+                    }),
+                    Tag::OffsetEnd.take(&mut fields, |[end_offset]| u64::try_from(end_offset).ok()),
+                ),
+            }),
+            turbo: Flag::Turbo.take(&mut flags),
+        });
+
+        let mint = Tag::Mint.take(&mut fields, |[block, tx]| {
+            RuneId::new(block.try_into().ok()?, tx.try_into().ok()?)
+        });
+
+        let pointer = Tag::Pointer.take(&mut fields, |[pointer]| {
+            let pointer = u32::try_from(pointer).ok()?;
+            (u64::from(pointer) < u64::try_from(transaction.output.len()).unwrap())
+                .then_some(pointer)
+        });
+
+        if etching
+            .map(|etching| etching.supply().is_none())
+            .unwrap_or_default()
+        {
+            flaw.get_or_insert(Flaw::SupplyOverflow);
+        }
+
+        if flags != 0 {
+            flaw.get_or_insert(Flaw::UnrecognizedFlag);
+        }
+
+        if fields.keys().any(|tag| tag % 2 == 0) {
+            flaw.get_or_insert(Flaw::UnrecognizedEvenTag);
+        }
+
+        if let Some(flaw) = flaw {
+            return Some(Artifact::Cenotaph(Cenotaph {
+                flaw: Some(flaw),
+                mint,
+                etching: etching.and_then(|etching| etching.rune),
+            }));
+        }
+
+        Some(Artifact::Runestone(Self {
+            edicts,
+            etching,
+            mint,
+            pointer,
+        }))
+    }
+
+    fn payload(transaction: &Transaction) -> Option<Payload> {
+        // search transaction outputs for payload
+        for output in &transaction.output {
+            let mut instructions = output.script_pubkey.instructions();
+
+            // payload starts with OP_RETURN
+            if instructions.next() != Some(Ok(Instruction::Op(opcodes::all::OP_RETURN))) {
+                continue;
+            }
+
+            // followed by the protocol identifier, ignoring errors, since OP_RETURN
+            // scripts may be invalid
+            if instructions.next() != Some(Ok(Instruction::Op(MAGIC_NUMBER))) {
+                continue;
+            }
+
+            // construct the payload by concatenating remaining data pushes
+            let mut payload = Vec::new();
+
+            for result in instructions {
+                match result {
+                    Ok(Instruction::PushBytes(push)) => {
+                        payload.extend_from_slice(push.as_bytes());
+                    }
+                    Ok(Instruction::Op(_)) => {
+                        return Some(Payload::Invalid(Flaw::Opcode));
+                    }
+                    Err(_) => {
+                        return Some(Payload::Invalid(Flaw::InvalidScript));
+                    }
+                }
+            }
+
+            return Some(Payload::Valid(payload));
+        }
+
+        None
+    }
+
+    fn integers(payload: &[u8]) -> Result<Vec<u128>, varint::Error> {
+        let mut integers = Vec::new();
+        let mut i = 0;
+
+        while i < payload.len() {
+            let (integer, length) = varint::decode(&payload[i..])?;
+            integers.push(integer);
+            i += length;
+        }
+
+        Ok(integers)
+    }
+}
+
+/// The commitment of the rune is the little endian bytes of the rune without the trailing zeros
+pub fn commitment(rune: u128) -> Vec<u8> {
+    let bytes = rune.to_le_bytes();
+
+    let mut end = bytes.len();
+
+    while end > 0 && bytes[end - 1] == 0 {
+        end -= 1;
+    }
+
+    bytes[..end].into()
+}
+
+/// The inputs whose tapscript contains a data push of the rune commitment.
+/// Whether the spent outputs are taproot outputs and the commit transactions are mature is checked in Move.
+pub fn commitment_inputs(transaction: &Transaction, rune: u128) -> Vec<u32> {
+    let commitment = commitment(rune);
+    transaction
+        .input
+        .iter()
+        .enumerate()
+        .filter(|(_, input)| {
+            let Some(tapscript) = input.witness.tapscript() else {
+                return false;
+            };
+            for instruction in tapscript.instructions() {
+                // ignore errors, since the extracted script may not be valid
+                let Ok(instruction) = instruction else {
+                    break;
+                };
+                if let Some(pushbytes) = instruction.push_bytes() {
+                    if pushbytes.as_bytes() == commitment {
+                        return true;
+                    }
+                }
+            }
+            false
+        })
+        .map(|(index, _)| index as u32)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::{
+        absolute::LockTime,
+        script::{self, PushBytes},
+        transaction::Version,
+        Amount, OutPoint, ScriptBuf, Sequence, TxIn, TxOut, Witness,
+    };
+
+    fn payload(integers: &[u128]) -> Vec<u8> {
+        let mut payload = Vec::new();
+        for integer in integers {
+            payload.extend(varint::encode(*integer));
+        }
+        payload
+    }
+
+    fn runestone_script(payload: &[u8]) -> ScriptBuf {
+        script::Builder::new()
+            .push_opcode(opcodes::all::OP_RETURN)
+            .push_opcode(MAGIC_NUMBER)
+            .push_slice(<&PushBytes>::try_from(payload).unwrap())
+            .into_script()
+    }
+
+    fn transaction(script_pubkey: ScriptBuf, outputs: usize) -> Transaction {
+        let mut output = vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey,
+        }];
+        for _ in 1..outputs {
+            output.push(TxOut {
+                value: Amount::from_sat(1000),
+                script_pubkey: ScriptBuf::new(),
+            });
+        }
+        Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: Vec::new(),
+            output,
+        }
+    }
+
+    fn decipher(integers: &[u128]) -> Artifact {
+        Runestone::decipher(&transaction(runestone_script(&payload(integers)), 2)).unwrap()
+    }
+
+    #[test]
+    fn decipher_returns_none_if_first_opcode_is_not_op_return() {
+        let script = script::Builder::new()
+            .push_opcode(MAGIC_NUMBER)
+            .push_slice([])
+            .into_script();
+        assert_eq!(Runestone::decipher(&transaction(script, 1)), None);
+    }
+
+    #[test]
+    fn decipher_returns_none_without_magic_number() {
+        let script = script::Builder::new()
+            .push_opcode(opcodes::all::OP_RETURN)
+            .push_slice([])
+            .into_script();
+        assert_eq!(Runestone::decipher(&transaction(script, 1)), None);
+    }
+
+    #[test]
+    fn non_push_opcode_is_cenotaph() {
+        let script = script::Builder::new()
+            .push_opcode(opcodes::all::OP_RETURN)
+            .push_opcode(MAGIC_NUMBER)
+            .push_opcode(opcodes::all::OP_VERIFY)
+            .into_script();
+        assert_eq!(
+            Runestone::decipher(&transaction(script, 1)).unwrap(),
+            Artifact::Cenotaph(Cenotaph {
+                flaw: Some(Flaw::Opcode),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_varint_is_cenotaph() {
+        assert_eq!(
+            Runestone::decipher(&transaction(runestone_script(&[128]), 1)).unwrap(),
+            Artifact::Cenotaph(Cenotaph {
+                flaw: Some(Flaw::Varint),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn empty_runestone() {
+        assert_eq!(
+            Runestone::decipher(&transaction(runestone_script(&[]), 1)).unwrap(),
+            Artifact::Runestone(Runestone::default())
+        );
+    }
+
+    #[test]
+    fn decipher_edicts() {
+        assert_eq!(
+            decipher(&[0, 1, 1, 2, 0, 0, 3, 5, 1]),
+            Artifact::Runestone(Runestone {
+                edicts: vec![
+                    Edict {
+                        id: RuneId { block: 1, tx: 1 },
+                        amount: 2,
+                        output: 0,
+                    },
+                    Edict {
+                        id: RuneId { block: 1, tx: 4 },
+                        amount: 5,
+                        output: 1,
+                    },
+                ],
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn edict_output_greater_than_outputs_is_cenotaph() {
+        assert_eq!(
+            decipher(&[0, 1, 1, 2, 3]),
+            Artifact::Cenotaph(Cenotaph {
+                flaw: Some(Flaw::EdictOutput),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn trailing_integers_in_body_is_cenotaph() {
+        assert_eq!(
+            decipher(&[0, 1, 1, 2, 0, 1]),
+            Artifact::Cenotaph(Cenotaph {
+                flaw: Some(Flaw::TrailingIntegers),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn decipher_etching_with_terms() {
+        assert_eq!(
+            decipher(&[
+                2,
+                0b11,
+                4,
+                4,
+                1,
+                2,
+                5,
+                'R'.into(),
+                8,
+                10,
+                10,
+                100,
+                14,
+                900,
+                22,
+                1
+            ]),
+            Artifact::Runestone(Runestone {
+                etching: Some(Etching {
+                    divisibility: Some(2),
+                    rune: Some(4),
+                    symbol: Some('R'),
+                    terms: Some(Terms {
+                        cap: Some(10),
+                        amount: Some(100),
+                        height: (None, Some(900)),
+                        offset: (None, None),
+                    }),
+                    ..Default::default()
+                }),
+                pointer: Some(1),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn etching_supply_overflow_is_cenotaph() {
+        assert_eq!(
+            decipher(&[2, 0b11, 4, 4, 6, 1, 8, u128::MAX, 10, 2]),
+            Artifact::Cenotaph(Cenotaph {
+                etching: Some(4),
+                flaw: Some(Flaw::SupplyOverflow),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn unrecognized_even_tag_is_cenotaph() {
+        assert_eq!(
+            decipher(&[20, 1, 20, 0, 24, 0]),
+            Artifact::Cenotaph(Cenotaph {
+                mint: Some(RuneId { block: 1, tx: 0 }),
+                flaw: Some(Flaw::UnrecognizedEvenTag),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn unrecognized_flag_is_cenotaph() {
+        assert_eq!(
+            decipher(&[2, 1 << 7]),
+            Artifact::Cenotaph(Cenotaph {
+                flaw: Some(Flaw::UnrecognizedFlag),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn unrecognized_odd_tag_is_ignored() {
+        assert_eq!(
+            decipher(&[127, 100]),
+            Artifact::Runestone(Runestone::default())
+        );
+    }
+
+    #[test]
+    fn pointer_out_of_range_is_cenotaph() {
+        assert_eq!(
+            decipher(&[22, 2]),
+            Artifact::Cenotaph(Cenotaph {
+                flaw: Some(Flaw::UnrecognizedEvenTag),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_commitment() {
+        assert_eq!(commitment(0), Vec::<u8>::new());
+        assert_eq!(commitment(1), vec![1]);
+        assert_eq!(commitment(255), vec![255]);
+        assert_eq!(commitment(256), vec![0, 1]);
+        assert_eq!(commitment(u128::MAX), vec![255; 16]);
+    }
+
+    #[test]
+    fn test_commitment_inputs() {
+        let rune = 99246114928149462u128;
+        let tapscript = script::Builder::new()
+            .push_slice(<&PushBytes>::try_from(commitment(rune).as_slice()).unwrap())
+            .push_opcode(opcodes::all::OP_DROP)
+            .into_script();
+        let input = |witness: Witness| TxIn {
+            previous_output: OutPoint::null(),
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness,
+        };
+        let mut tx = transaction(ScriptBuf::new(), 1);
+        tx.input = vec![
+            input(Witness::new()),
+            input(Witness::from_slice(&[tapscript.into_bytes(), vec![0xc0]])),
+        ];
+        assert_eq!(commitment_inputs(&tx, rune), vec![1]);
+        assert_eq!(commitment_inputs(&tx, rune + 1), Vec::<u32>::new());
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+// Code from https://github.com/ordinals/ord/
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    Overlong,
+    Overflow,
+    Unterminated,
+}
+
+pub fn encode_to_vec(mut n: u128, v: &mut Vec<u8>) {
+    while n >> 7 > 0 {
+        v.push(n.to_le_bytes()[0] | 0b1000_0000);
+        n >>= 7;
+    }
+    v.push(n.to_le_bytes()[0]);
+}
+
+pub fn decode(buffer: &[u8]) -> Result<(u128, usize), Error> {
+    let mut n = 0u128;
+
+    for (i, &byte) in buffer.iter().enumerate() {
+        if i > 18 {
+            return Err(Error::Overlong);
+        }
+
+        let value = u128::from(byte) & 0b0111_1111;
+
+        if i == 18 && value & 0b0111_1100 != 0 {
+            return Err(Error::Overflow);
+        }
+
+        n |= value << (7 * i);
+
+        if byte & 0b1000_0000 == 0 {
+            return Ok((n, i + 1));
+        }
+    }
+
+    Err(Error::Unterminated)
+}
+
+pub fn encode(n: u128) -> Vec<u8> {
+    let mut v = Vec::new();
+    encode_to_vec(n, &mut v);
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_round_trips_successfully() {
+        let n = 0;
+        let encoded = encode(n);
+        let (decoded, length) = decode(&encoded).unwrap();
+        assert_eq!(decoded, n);
+        assert_eq!(length, encoded.len());
+    }
+
+    #[test]
+    fn u128_max_round_trips_successfully() {
+        let n = u128::MAX;
+        let encoded = encode(n);
+        let (decoded, length) = decode(&encoded).unwrap();
+        assert_eq!(decoded, n);
+        assert_eq!(length, encoded.len());
+    }
+
+    #[test]
+    fn powers_of_two_round_trip_successfully() {
+        for i in 0..128 {
+            let n = 1 << i;
+            let encoded = encode(n);
+            let (decoded, length) = decode(&encoded).unwrap();
+            assert_eq!(decoded, n);
+            assert_eq!(length, encoded.len());
+        }
+    }
+
+    #[test]
+    fn varints_longer_than_19_bytes_are_overlong() {
+        let mut buffer = vec![128; 19];
+        buffer.push(0);
+        assert_eq!(decode(&buffer), Err(Error::Overlong));
+    }
+
+    #[test]
+    fn varints_larger_than_u128_max_overflow() {
+        let mut buffer = vec![128; 18];
+        buffer.push(127);
+        assert_eq!(decode(&buffer), Err(Error::Overflow));
+        let mut buffer = vec![128; 18];
+        buffer.push(3);
+        assert_eq!(decode(&buffer), Ok((3 << 126, 19)));
+    }
+
+    #[test]
+    fn varints_with_continuation_bit_set_on_final_byte_are_unterminated() {
+        assert_eq!(decode(&[128]), Err(Error::Unterminated));
+    }
+
+    #[test]
+    fn varints_may_have_trailing_zeros() {
+        assert_eq!(decode(&[128, 0]), Ok((0, 2)));
+    }
+}
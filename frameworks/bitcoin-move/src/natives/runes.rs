@@ -0,0 +1,348 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::natives::ord::runestone::{self, Runestone};
+use move_binary_format::errors::{PartialVMError, PartialVMResult};
+use move_core_types::gas_algebra::{InternalGas, InternalGasPerByte, NumBytes};
+use move_core_types::vm_status::StatusCode;
+use move_vm_runtime::native_functions::{NativeContext, NativeFunction};
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::NativeResult,
+    pop_arg,
+    values::{StructRef, Value},
+};
+use moveos_stdlib::natives::helpers::{make_module_natives, make_native};
+use moveos_types::move_std::option::MoveOption;
+use moveos_types::move_std::string::MoveString;
+use moveos_types::state::MoveState;
+use rooch_types::bitcoin::runes::{Artifact, Edict, Etching, RuneId, Terms};
+use rooch_types::bitcoin::types::Transaction;
+use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq, Deserialize)]
+pub struct ParseRunestoneGasParameters {
+    pub base: Option<InternalGas>,
+    pub per_byte: Option<InternalGasPerByte>,
+}
+
+impl ParseRunestoneGasParameters {
+    pub fn zeros() -> Self {
+        Self {
+            base: None,
+            per_byte: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.base.is_none() || self.per_byte.is_none()
+    }
+}
+
+/// Rust implementation of decode the runestone from the transaction
+#[inline]
+pub(crate) fn native_parse_runestone(
+    gas_params: &ParseRunestoneGasParameters,
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert_eq!(ty_args.len(), 0);
+    debug_assert_eq!(args.len(), 1);
+
+    let mut cost = gas_params.base.unwrap();
+
+    let tx_ref = pop_arg!(args, StructRef);
+    let tx_value = tx_ref.read_ref()?;
+    let tx = Transaction::from_runtime_value(tx_value).map_err(|e| {
+        PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+            .with_message(format!("Failed to parse transaction: {}", e))
+    })?;
+    let per_byte = gas_params.per_byte.unwrap();
+    cost += per_byte
+        * NumBytes::new(
+            tx.output
+                .iter()
+                .map(|tx_out| tx_out.script_pubkey.bytes.len())
+                .sum::<usize>() as u64,
+        );
+    let val = MoveOption::from(parse_runestone(tx)).to_runtime_value();
+
+    Ok(NativeResult::ok(cost, smallvec![val]))
+}
+
+/// Decode the runestone of the transaction, return None if the transaction has no runestone
+fn parse_runestone(tx: Transaction) -> Option<Artifact> {
+    let bitcoin_tx: bitcoin::Transaction = tx.into();
+    Runestone::decipher(&bitcoin_tx).map(|artifact| {
+        let commitment_inputs = artifact
+            .etching_rune()
+            .map(|rune| runestone::commitment_inputs(&bitcoin_tx, rune))
+            .unwrap_or_default();
+        into_move_artifact(artifact, commitment_inputs)
+    })
+}
+
+fn into_move_rune_id(id: runestone::RuneId) -> RuneId {
+    RuneId::new(id.block, id.tx)
+}
+
+fn into_move_artifact(artifact: runestone::Artifact, commitment_inputs: Vec<u32>) -> Artifact {
+    match artifact {
+        runestone::Artifact::Cenotaph(cenotaph) => Artifact {
+            cenotaph: true,
+            flaw: MoveOption::from(cenotaph.flaw.map(|flaw| MoveString::from(flaw.to_string()))),
+            edicts: vec![],
+            etching: MoveOption::from(cenotaph.etching.map(|rune| Etching {
+                rune: MoveOption::some(rune),
+                ..Default::default()
+            })),
+            mint: MoveOption::from(cenotaph.mint.map(into_move_rune_id)),
+            pointer: MoveOption::none(),
+            commitment_inputs,
+        },
+        runestone::Artifact::Runestone(runestone) => Artifact {
+            cenotaph: false,
+            flaw: MoveOption::none(),
+            edicts: runestone
+                .edicts
+                .into_iter()
+                .map(|edict| Edict {
+                    id: into_move_rune_id(edict.id),
+                    amount: edict.amount,
+                    output: edict.output,
+                })
+                .collect(),
+            etching: MoveOption::from(runestone.etching.map(|etching| Etching {
+                divisibility: MoveOption::from(etching.divisibility),
+                premine: MoveOption::from(etching.premine),
+                rune: MoveOption::from(etching.rune),
+                spacers: MoveOption::from(etching.spacers),
+                symbol: MoveOption::from(etching.symbol.map(u32::from)),
+                terms: MoveOption::from(etching.terms.map(|terms| Terms {
+                    amount: MoveOption::from(terms.amount),
+                    cap: MoveOption::from(terms.cap),
+                    height_start: MoveOption::from(terms.height.0),
+                    height_end: MoveOption::from(terms.height.1),
+                    offset_start: MoveOption::from(terms.offset.0),
+                    offset_end: MoveOption::from(terms.offset.1),
+                })),
+                turbo: etching.turbo,
+            })),
+            mint: MoveOption::from(runestone.mint.map(into_move_rune_id)),
+            pointer: MoveOption::from(runestone.pointer),
+            commitment_inputs,
+        },
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq, Deserialize)]
+pub struct GasParameters {
+    pub parse_runestone: ParseRunestoneGasParameters,
+}
+
+impl GasParameters {
+    pub fn zeros() -> Self {
+        Self {
+            parse_runestone: ParseRunestoneGasParameters::zeros(),
+        }
+    }
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let mut natives = vec![];
+
+    if !gas_params.parse_runestone.is_empty() {
+        natives.push((
+            "parse_runestone",
+            make_native(gas_params.parse_runestone, native_parse_runestone),
+        ));
+    }
+
+    make_module_natives(natives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::natives::ord::varint;
+    use bitcoin::{
+        absolute::LockTime,
+        opcodes,
+        script::{self, PushBytes},
+        transaction::Version,
+        Amount, OutPoint, ScriptBuf, Sequence, TxIn, TxOut, Witness,
+    };
+
+    fn runestone_script(integers: &[u128]) -> ScriptBuf {
+        let mut payload = Vec::new();
+        for integer in integers {
+            payload.extend(varint::encode(*integer));
+        }
+        script::Builder::new()
+            .push_opcode(opcodes::all::OP_RETURN)
+            .push_opcode(runestone::MAGIC_NUMBER)
+            .push_slice(<&PushBytes>::try_from(payload.as_slice()).unwrap())
+            .into_script()
+    }
+
+    // The transaction with the runestone output and a P2TR output
+    fn transaction(runestone_script: ScriptBuf, input: Vec<TxIn>) -> Transaction {
+        let p2tr_script = ScriptBuf::from_hex(
+            "51200f0c8db753acbd17343a39c2f3f4e35e4be6da749f9e35137ab220e7b238a667",
+        )
+        .unwrap();
+        bitcoin::Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input,
+            output: vec![
+                TxOut {
+                    value: Amount::ZERO,
+                    script_pubkey: runestone_script,
+                },
+                TxOut {
+                    value: Amount::from_sat(546),
+                    script_pubkey: p2tr_script,
+                },
+            ],
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_parse_mint() {
+        // The OP_RETURN output of the UNCOMMON•GOODS mint transactions on the mainnet
+        let script = ScriptBuf::from_hex("6a5d0414011400").unwrap();
+        let artifact = parse_runestone(transaction(script, vec![])).unwrap();
+        assert_eq!(
+            artifact,
+            Artifact {
+                mint: MoveOption::some(RuneId::new(1, 0)),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_etching() {
+        let rune = 99246114928149462u128;
+        let tapscript = script::Builder::new()
+            .push_slice(<&PushBytes>::try_from(runestone::commitment(rune).as_slice()).unwrap())
+            .push_opcode(opcodes::all::OP_DROP)
+            .into_script();
+        let input = TxIn {
+            previous_output: OutPoint::null(),
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::from_slice(&[tapscript.into_bytes(), vec![0xc0]]),
+        };
+        let script = runestone_script(&[
+            2,
+            0b11,
+            4,
+            rune,
+            1,
+            2,
+            3,
+            1,
+            5,
+            'R'.into(),
+            6,
+            1000,
+            8,
+            10,
+            10,
+            100,
+            14,
+            900,
+            22,
+            1,
+        ]);
+        let artifact = parse_runestone(transaction(script, vec![input])).unwrap();
+        assert_eq!(
+            artifact,
+            Artifact {
+                etching: MoveOption::some(Etching {
+                    divisibility: MoveOption::some(2),
+                    premine: MoveOption::some(1000),
+                    rune: MoveOption::some(rune),
+                    spacers: MoveOption::some(1),
+                    symbol: MoveOption::some('R'.into()),
+                    terms: MoveOption::some(Terms {
+                        amount: MoveOption::some(100),
+                        cap: MoveOption::some(10),
+                        height_end: MoveOption::some(900),
+                        ..Default::default()
+                    }),
+                    turbo: false,
+                }),
+                pointer: MoveOption::some(1),
+                commitment_inputs: vec![0],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_transfer() {
+        // Transfer 100 of the rune 840000:1 to the output 1, and split the rest to all the outputs
+        let script = runestone_script(&[0, 840000, 1, 100, 1, 0, 0, 0, 2]);
+        let artifact = parse_runestone(transaction(script, vec![])).unwrap();
+        assert_eq!(
+            artifact,
+            Artifact {
+                edicts: vec![
+                    Edict {
+                        id: RuneId::new(840000, 1),
+                        amount: 100,
+                        output: 1,
+                    },
+                    Edict {
+                        id: RuneId::new(840000, 1),
+                        amount: 0,
+                        output: 2,
+                    },
+                ],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_cenotaph() {
+        // The unrecognized flag makes a cenotaph, the etching rune and the mint are kept
+        let script = runestone_script(&[2, 0b1001, 4, 5, 20, 1, 20, 0]);
+        let artifact = parse_runestone(transaction(script, vec![])).unwrap();
+        assert_eq!(
+            artifact,
+            Artifact {
+                cenotaph: true,
+                flaw: MoveOption::some(MoveString::from("unrecognized field".to_string())),
+                etching: MoveOption::some(Etching {
+                    rune: MoveOption::some(5),
+                    ..Default::default()
+                }),
+                mint: MoveOption::some(RuneId::new(1, 0)),
+                ..Default::default()
+            }
+        );
+
+        // The edict output is greater than the outputs count
+        let script = runestone_script(&[0, 840000, 1, 100, 3]);
+        let artifact = parse_runestone(transaction(script, vec![])).unwrap();
+        assert!(artifact.cenotaph);
+        assert!(artifact.edicts.is_empty());
+    }
+
+    #[test]
+    fn test_parse_no_runestone() {
+        let script = script::Builder::new()
+            .push_opcode(opcodes::all::OP_RETURN)
+            .push_slice(b"rooch")
+            .into_script();
+        assert!(parse_runestone(transaction(script, vec![])).is_none());
+    }
+}
@@ -18,4 +18,14 @@ module bitcoin_move::bitcoin_test{
         bitcoin::execute_l1_block_for_test(1, block);
         assert!(bitcoin::contains_header(&header), 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    #[expected_failure(abort_code = 5, location = bitcoin_move::bitcoin)]
+    fun test_submit_block_invalid_pow() {
+        rooch_framework::genesis::init_for_test();
+        bitcoin_move::genesis::init_for_test();
+        let miner = rooch_framework::bitcoin_address::random_address_for_testing();
+        let block = types::fake_block_for_test(1231006505, miner);
+        bitcoin::execute_l1_block_for_test(1, block);
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+/// The on-chain State Commitment Chain(SCC), the proposer submits the block commitments to it,
+/// so the light clients can check the block commitments against the chain state.
+module rooch_framework::state_commitment_chain {
+
+    use std::option::{Self, Option};
+    use std::vector;
+    use moveos_std::object::{Self, Object};
+    use moveos_std::table::{Self, Table};
+    use moveos_std::signer;
+    use moveos_std::event;
+    use moveos_std::timestamp;
+    use rooch_framework::onchain_config;
+
+    const ErrorNotProposer: u64 = 1;
+    const ErrorBlockNumberMismatch: u64 = 2;
+    const ErrorAccumulatorRootMismatch: u64 = 3;
+    const ErrorInvalidRoot: u64 = 4;
+
+    const ROOT_LENGTH: u64 = 32;
+
+    /// The commitment of a block proposed by the proposer
+    struct BlockCommitment has store, copy, drop {
+        block_number: u128,
+        /// How many transactions in the block
+        batch_size: u64,
+        prev_tx_accumulator_root: vector<u8>,
+        tx_accumulator_root: vector<u8>,
+        /// The state root after the last transaction of the block
+        state_root: vector<u8>,
+        /// The accumulator root of the state roots of all the transactions in the block
+        state_root_accumulator_root: vector<u8>,
+        /// The time when the commitment is submitted, in milliseconds
+        submitted_at: u64,
+    }
+
+    struct StateCommitmentChain has key {
+        /// The account allowed to submit the block commitments
+        proposer: address,
+        /// block number -> block commitment
+        blocks: Table<u128, BlockCommitment>,
+    }
+
+    struct BlockCommittedEvent has copy, drop {
+        block_number: u128,
+        tx_accumulator_root: vector<u8>,
+        state_root: vector<u8>,
+    }
+
+    /// The SCC is created on the first access, the sequencer is the proposer by default
+    fun borrow_mut_scc(): &mut Object<StateCommitmentChain> {
+        let object_id = object::named_object_id<StateCommitmentChain>();
+        if (!object::exists_object_with_type<StateCommitmentChain>(object_id)) {
+            let obj = object::new_named_object(StateCommitmentChain {
+                proposer: onchain_config::sequencer(),
+                blocks: table::new(),
+            });
+            object::transfer_extend(obj, @rooch_framework);
+        };
+        object::borrow_mut_object_extend<StateCommitmentChain>(object_id)
+    }
+
+    /// The account allowed to submit the block commitments
+    public fun proposer(): address {
+        let object_id = object::named_object_id<StateCommitmentChain>();
+        if (object::exists_object_with_type<StateCommitmentChain>(object_id)) {
+            object::borrow(object::borrow_object<StateCommitmentChain>(object_id)).proposer
+        } else {
+            onchain_config::sequencer()
+        }
+    }
+
+    /// How many blocks have been committed, it is also the next block number to submit
+    public fun block_count(): u128 {
+        let object_id = object::named_object_id<StateCommitmentChain>();
+        if (object::exists_object_with_type<StateCommitmentChain>(object_id)) {
+            let scc = object::borrow(object::borrow_object<StateCommitmentChain>(object_id));
+            (table::length(&scc.blocks) as u128)
+        } else {
+            0
+        }
+    }
+
+    /// Get the block commitment by block number
+    public fun get_block(block_number: u128): Option<BlockCommitment> {
+        let object_id = object::named_object_id<StateCommitmentChain>();
+        if (!object::exists_object_with_type<StateCommitmentChain>(object_id)) {
+            return option::none()
+        };
+        let scc = object::borrow(object::borrow_object<StateCommitmentChain>(object_id));
+        if (table::contains(&scc.blocks, block_number)) {
+            option::some(*table::borrow(&scc.blocks, block_number))
+        } else {
+            option::none()
+        }
+    }
+
+    /// Submit the commitment of the next block, only the proposer could submit.
+    /// The blocks must be submitted in order and linked by the tx accumulator root.
+    public entry fun submit_block(
+        account: &signer,
+        block_number: u128,
+        batch_size: u64,
+        prev_tx_accumulator_root: vector<u8>,
+        tx_accumulator_root: vector<u8>,
+        state_root: vector<u8>,
+        state_root_accumulator_root: vector<u8>,
+    ) {
+        assert!(signer::address_of(account) == proposer(), ErrorNotProposer);
+        assert!(block_number == block_count(), ErrorBlockNumberMismatch);
+        assert!(vector::length(&prev_tx_accumulator_root) == ROOT_LENGTH, ErrorInvalidRoot);
+        assert!(vector::length(&tx_accumulator_root) == ROOT_LENGTH, ErrorInvalidRoot);
+        assert!(vector::length(&state_root) == ROOT_LENGTH, ErrorInvalidRoot);
+        assert!(vector::length(&state_root_accumulator_root) == ROOT_LENGTH, ErrorInvalidRoot);
+
+        let scc = object::borrow_mut(borrow_mut_scc());
+        if (block_number > 0) {
+            let prev_block = table::borrow(&scc.blocks, block_number - 1);
+            assert!(prev_block.tx_accumulator_root == prev_tx_accumulator_root, ErrorAccumulatorRootMismatch);
+        };
+        table::add(&mut scc.blocks, block_number, BlockCommitment {
+            block_number,
+            batch_size,
+            prev_tx_accumulator_root,
+            tx_accumulator_root,
+            state_root,
+            state_root_accumulator_root,
+            submitted_at: timestamp::now_milliseconds(),
+        });
+        event::emit(BlockCommittedEvent {
+            block_number,
+            tx_accumulator_root,
+            state_root,
+        });
+    }
+
+    /// Change the proposer, only the sequencer could change it
+    public entry fun set_proposer(account: &signer, proposer: address) {
+        onchain_config::ensure_sequencer(account);
+        let scc = object::borrow_mut(borrow_mut_scc());
+        scc.proposer = proposer;
+    }
+
+    public fun block_number(commitment: &BlockCommitment): u128 {
+        commitment.block_number
+    }
+
+    public fun batch_size(commitment: &BlockCommitment): u64 {
+        commitment.batch_size
+    }
+
+    public fun prev_tx_accumulator_root(commitment: &BlockCommitment): vector<u8> {
+        commitment.prev_tx_accumulator_root
+    }
+
+    public fun tx_accumulator_root(commitment: &BlockCommitment): vector<u8> {
+        commitment.tx_accumulator_root
+    }
+
+    public fun state_root(commitment: &BlockCommitment): vector<u8> {
+        commitment.state_root
+    }
+
+    public fun state_root_accumulator_root(commitment: &BlockCommitment): vector<u8> {
+        commitment.state_root_accumulator_root
+    }
+
+    public fun submitted_at(commitment: &BlockCommitment): u64 {
+        commitment.submitted_at
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module rooch_framework::state_commitment_chain_test{
+
+    use std::option;
+    use std::vector;
+    use moveos_std::account;
+    use rooch_framework::onchain_config;
+    use rooch_framework::state_commitment_chain;
+
+    fun root(byte: u8): vector<u8> {
+        let root = vector::empty<u8>();
+        let i = 0;
+        while (i < 32) {
+            vector::push_back(&mut root, byte);
+            i = i + 1;
+        };
+        root
+    }
+
+    #[test]
+    fun test_submit_block(){
+        rooch_framework::genesis::init_for_test();
+        let proposer = account::create_signer_for_testing(onchain_config::sequencer());
+        assert!(state_commitment_chain::block_count() == 0, 1);
+        state_commitment_chain::submit_block(&proposer, 0, 2, root(0), root(1), root(2), root(9));
+        state_commitment_chain::submit_block(&proposer, 1, 1, root(1), root(3), root(4), root(9));
+        assert!(state_commitment_chain::block_count() == 2, 2);
+        let block = option::extract(&mut state_commitment_chain::get_block(1));
+        assert!(state_commitment_chain::tx_accumulator_root(&block) == root(3), 3);
+        assert!(state_commitment_chain::state_root(&block) == root(4), 4);
+        assert!(option::is_none(&state_commitment_chain::get_block(2)), 5);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 3, location = rooch_framework::state_commitment_chain)]
+    fun test_submit_block_not_linked(){
+        rooch_framework::genesis::init_for_test();
+        let proposer = account::create_signer_for_testing(onchain_config::sequencer());
+        state_commitment_chain::submit_block(&proposer, 0, 2, root(0), root(1), root(2), root(9));
+        state_commitment_chain::submit_block(&proposer, 1, 1, root(5), root(3), root(4), root(9));
+    }
+
+    #[test(other = @0x42)]
+    #[expected_failure(abort_code = 1, location = rooch_framework::state_commitment_chain)]
+    fun test_submit_block_not_proposer(other: &signer){
+        rooch_framework::genesis::init_for_test();
+        state_commitment_chain::submit_block(other, 0, 2, root(0), root(1), root(2), root(9));
+    }
+
+    #[test(new_proposer = @0x42)]
+    fun test_set_proposer(new_proposer: &signer){
+        rooch_framework::genesis::init_for_test();
+        let sequencer = account::create_signer_for_testing(onchain_config::sequencer());
+        state_commitment_chain::set_proposer(&sequencer, @0x42);
+        assert!(state_commitment_chain::proposer() == @0x42, 1);
+        state_commitment_chain::submit_block(new_proposer, 0, 2, root(0), root(1), root(2), root(9));
+    }
+}
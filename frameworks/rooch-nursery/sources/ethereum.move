@@ -3,17 +3,24 @@
 
 module rooch_nursery::ethereum{
 
+    use std::option::{Self, Option};
     use moveos_std::timestamp;
     use moveos_std::account;
     use moveos_std::table::{Self, Table};
     use moveos_std::bcs;
     use moveos_std::signer;
     use moveos_std::features;
+    use moveos_std::object;
     use rooch_framework::ethereum_address::ETHAddress;
 
+    #[test_only]
+    use rooch_framework::ethereum_address;
+
     friend rooch_nursery::genesis;
 
     const ErrorBlockNotFound:u64 = 1;
+    /// The parent of the block is not the processed block
+    const ErrorParentHashMismatch:u64 = 2;
 
     #[data_struct]
     struct BlockHeader has store, copy, drop {
@@ -51,6 +58,12 @@ module rooch_nursery::ethereum{
         blocks: Table<u64, BlockHeader>,
     }
 
+    /// The number of the latest processed block, it is kept in a named object,
+    /// so the BlockStore of the deployed chains is not changed.
+    struct BestBlock has key {
+        number: u64,
+    }
+
     public(friend) fun genesis_init(genesis_account: &signer){
         let block_store = BlockStore{
             blocks: table::new(),
@@ -58,17 +71,54 @@ module rooch_nursery::ethereum{
         account::move_resource_to(genesis_account, block_store);
     }
 
+    fun best_block_number(): Option<u64>{
+        let object_id = object::named_object_id<BestBlock>();
+        if(object::exists_object_with_type<BestBlock>(object_id)){
+            option::some(object::borrow(object::borrow_object<BestBlock>(object_id)).number)
+        }else{
+            option::none()
+        }
+    }
+
+    /// The BestBlock is created on the first processed block
+    fun set_best_block_number(number: u64){
+        let object_id = object::named_object_id<BestBlock>();
+        if(object::exists_object_with_type<BestBlock>(object_id)){
+            let best_block = object::borrow_mut(object::borrow_mut_object_extend<BestBlock>(object_id));
+            best_block.number = number;
+        }else{
+            let obj = object::new_named_object(BestBlock{ number });
+            object::transfer_extend(obj, @rooch_framework);
+        }
+    }
+
     fun process_block(block_header_bytes: vector<u8>){
         let block_header = bcs::from_bytes<BlockHeader>(block_header_bytes);
         //TODO validate the block hash
         //TODO validate the block via ethereum consensus(pos validators)
         let block_store = account::borrow_mut_resource<BlockStore>(@rooch_framework);
-        if(table::contains(&block_store.blocks, block_header.number)){
-            //repeat block number
-            //TODO check if it is a soft fork.
-            return
+        let number = block_header.number;
+        if(table::contains(&block_store.blocks, number)){
+            if(table::borrow(&block_store.blocks, number).hash == block_header.hash){
+                //repeat block
+                return
+            };
+            //the chain is reorganized, remove the blocks of the old fork
+            let best_block_number = option::get_with_default(&best_block_number(), number);
+            let stale_number = number;
+            while(stale_number <= best_block_number){
+                if(table::contains(&block_store.blocks, stale_number)){
+                    table::remove(&mut block_store.blocks, stale_number);
+                };
+                stale_number = stale_number + 1;
+            };
         };
-        table::add(&mut block_store.blocks, block_header.number, block_header);
+        if(number > 0 && table::contains(&block_store.blocks, number - 1)){
+            let parent = table::borrow(&block_store.blocks, number - 1);
+            assert!(parent.hash == block_header.parent_hash, ErrorParentHashMismatch);
+        };
+        table::add(&mut block_store.blocks, number, block_header);
+        set_best_block_number(number);
 
         let timestamp_seconds = (block_header.timestamp as u64);
         let module_signer = signer::module_signer<BlockStore>();
@@ -87,4 +137,101 @@ module rooch_nursery::ethereum{
         assert!(table::contains(&block_store.blocks, block_number), ErrorBlockNotFound);
         table::borrow(&block_store.blocks, block_number)
     }
+
+    /// Get block via block_number, none if the block is not found
+    public fun get_block_option(block_number: u64): Option<BlockHeader>{
+        let block_store = account::borrow_resource<BlockStore>(@rooch_framework);
+        if(table::contains(&block_store.blocks, block_number)){
+            option::some(*table::borrow(&block_store.blocks, block_number))
+        }else{
+            option::none()
+        }
+    }
+
+    /// Get the latest processed block
+    public fun get_best_block(): Option<BlockHeader>{
+        let best_block_number = best_block_number();
+        if(option::is_none(&best_block_number)){
+            return option::none()
+        };
+        get_block_option(option::destroy_some(best_block_number))
+    }
+
+    #[test_only]
+    fun new_block_header_for_test(number: u64, hash: vector<u8>, parent_hash: vector<u8>): BlockHeader{
+        BlockHeader{
+            hash,
+            parent_hash,
+            uncles_hash: x"",
+            author: ethereum_address::from_bytes(x"0000000000000000000000000000000000000000"),
+            state_root: x"",
+            transactions_root: x"",
+            receipts_root: x"",
+            logs_bloom: x"",
+            difficulty: 0,
+            number,
+            gas_limit: 0,
+            gas_used: 0,
+            timestamp: ((number + 1) as u256),
+            extra_data: x"",
+        }
+    }
+
+    #[test_only]
+    fun process_block_for_test(number: u64, hash: vector<u8>, parent_hash: vector<u8>){
+        process_block(std::bcs::to_bytes(&new_block_header_for_test(number, hash, parent_hash)));
+    }
+
+    #[test_only]
+    fun init_for_test(){
+        rooch_framework::genesis::init_for_test();
+        genesis_init(&account::create_signer_for_testing(@rooch_framework));
+    }
+
+    #[test]
+    fun test_process_linked_blocks(){
+        init_for_test();
+        assert!(option::is_none(&get_best_block()), 1);
+        process_block_for_test(10, x"0a", x"09");
+        process_block_for_test(11, x"0b", x"0a");
+        process_block_for_test(12, x"0c", x"0b");
+        let best_block = option::destroy_some(get_best_block());
+        assert!(best_block.number == 12 && best_block.hash == x"0c", 2);
+        assert!(option::is_none(&get_block_option(13)), 3);
+        assert!(get_block(11).hash == x"0b", 4);
+
+        // the repeated block is ignored
+        process_block_for_test(11, x"0b", x"0a");
+        assert!(option::destroy_some(get_best_block()).number == 12, 5);
+    }
+
+    #[test]
+    fun test_process_reorg_block(){
+        init_for_test();
+        process_block_for_test(10, x"0a", x"09");
+        process_block_for_test(11, x"0b", x"0a");
+        process_block_for_test(12, x"0c", x"0b");
+        // block 11 is replaced by the fork, the blocks after it are removed
+        process_block_for_test(11, x"1b", x"0a");
+        let best_block = option::destroy_some(get_best_block());
+        assert!(best_block.number == 11 && best_block.hash == x"1b", 1);
+        assert!(option::is_none(&get_block_option(12)), 2);
+        process_block_for_test(12, x"1c", x"1b");
+        assert!(option::destroy_some(get_best_block()).hash == x"1c", 3);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ErrorParentHashMismatch, location = Self)]
+    fun test_process_block_parent_hash_mismatch(){
+        init_for_test();
+        process_block_for_test(10, x"0a", x"09");
+        process_block_for_test(11, x"0b", x"ff");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ErrorBlockNotFound, location = Self)]
+    fun test_get_block_not_found(){
+        init_for_test();
+        get_block(10);
+    }
 }
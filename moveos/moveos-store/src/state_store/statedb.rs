@@ -4,17 +4,19 @@
 use crate::state_store::NodeDBStore;
 use anyhow::{Error, Result};
 use move_core_types::effects::Op;
+use moveos_types::access_path::AccessPath;
 use moveos_types::h256::H256;
-use moveos_types::moveos_std::object::GENESIS_STATE_ROOT;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta, GENESIS_STATE_ROOT};
 use moveos_types::state::FieldKey;
 use moveos_types::state::ObjectChange;
 use moveos_types::state::ObjectState;
 use moveos_types::state::StateChangeSet;
+use moveos_types::state_proof::{object_path, StateProof, StateWithProof};
 use moveos_types::state_resolver::RootObjectResolver;
 use moveos_types::state_resolver::StateKV;
 use moveos_types::state_resolver::StateResolver;
 use moveos_types::state_resolver::StatelessResolver;
-use smt::{SMTIterator, TreeChangeSet};
+use smt::{NodeReader, SMTIterator, SparseMerkleProof, TreeChangeSet};
 use smt::{SMTree, UpdateSet};
 use std::collections::BTreeMap;
 
@@ -139,6 +141,79 @@ impl StateDBStore {
         Ok(())
     }
 
+    /// Get the field at the state_root with the SMT proof
+    pub fn get_field_at_with_proof(
+        &self,
+        state_root: H256,
+        key: FieldKey,
+    ) -> Result<(Option<ObjectState>, SparseMerkleProof)> {
+        if state_root == *GENESIS_STATE_ROOT {
+            // The proof of the empty tree
+            return Ok((None, SparseMerkleProof::default()));
+        }
+        self.smt.get_with_proof(state_root, key)
+    }
+
+    /// Get the field of the object with the proof from the root object
+    pub fn get_field_with_proof(
+        &self,
+        root: &ObjectMeta,
+        object_id: &ObjectID,
+        field_key: FieldKey,
+    ) -> Result<StateWithProof> {
+        let mut ancestors = vec![];
+        let mut proofs = vec![];
+        let mut current_root = root.state_root();
+        for ancestor_id in object_path(object_id) {
+            let (ancestor, proof) =
+                self.get_field_at_with_proof(current_root, ancestor_id.field_key())?;
+            proofs.push(proof);
+            match ancestor {
+                Some(ancestor) => {
+                    current_root = ancestor.state_root();
+                    ancestors.push(ancestor);
+                }
+                None => {
+                    return Ok(StateWithProof {
+                        object_id: object_id.clone(),
+                        field_key,
+                        state: None,
+                        proof: StateProof::new(ancestors, proofs),
+                    });
+                }
+            }
+        }
+        let (state, proof) = self.get_field_at_with_proof(current_root, field_key)?;
+        proofs.push(proof);
+        Ok(StateWithProof {
+            object_id: object_id.clone(),
+            field_key,
+            state,
+            proof: StateProof::new(ancestors, proofs),
+        })
+    }
+
+    /// Get the states by AccessPath with the proofs from the root object
+    pub fn get_states_with_proof(
+        &self,
+        root: &ObjectMeta,
+        path: AccessPath,
+    ) -> Result<Vec<StateWithProof>> {
+        let query = path.into_state_query().into_fields_query()?;
+        query
+            .into_iter()
+            .map(|(object_id, key)| self.get_field_with_proof(root, &object_id, key))
+            .collect()
+    }
+
+    /// Check whether the tree of the state_root is in the store, the nodes of a pruned state_root are removed.
+    pub fn contains_state_root(&self, state_root: H256) -> Result<bool> {
+        if state_root == *GENESIS_STATE_ROOT {
+            return Ok(true);
+        }
+        Ok(self.node_store.get(&state_root)?.is_some())
+    }
+
     pub fn iter(
         &self,
         state_root: H256,
@@ -4,6 +4,8 @@
 use crate::MoveOSStore;
 use anyhow::Result;
 use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::{ObjectID, GENESIS_STATE_ROOT};
+use moveos_types::state::FieldKey;
 use moveos_types::test_utils::random_state_change_set;
 use smt::NodeReader;
 
@@ -45,6 +47,51 @@ async fn test_statedb_state_root() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_get_field_with_proof() -> Result<()> {
+    let (moveos_store, _) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    let state_store = moveos_store.get_state_store();
+    let mut change_set = random_state_change_set();
+    state_store.apply_change_set(&mut change_set)?;
+    let root = change_set.root_metadata();
+
+    for object_change in change_set.changes.values() {
+        let object_id = object_change.metadata.id.clone();
+        for field_key in object_change.fields.keys() {
+            let state_with_proof =
+                state_store.get_field_with_proof(&root, &object_id, *field_key)?;
+            assert!(state_with_proof.state.is_some());
+            state_with_proof.verify(change_set.state_root)?;
+            assert!(state_with_proof.verify(H256::random()).is_err());
+        }
+        let missing_field =
+            state_store.get_field_with_proof(&root, &object_id, FieldKey::random())?;
+        assert!(missing_field.state.is_none());
+        missing_field.verify(change_set.state_root)?;
+    }
+
+    let missing_object =
+        state_store.get_field_with_proof(&root, &ObjectID::random(), FieldKey::random())?;
+    assert!(missing_object.state.is_none());
+    assert!(missing_object.proof.ancestors.is_empty());
+    missing_object.verify(change_set.state_root)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_contains_state_root() -> Result<()> {
+    let (moveos_store, _) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    let state_store = moveos_store.get_state_store();
+    let mut change_set = random_state_change_set();
+    state_store.apply_change_set(&mut change_set)?;
+    assert!(state_store.contains_state_root(change_set.state_root)?);
+    assert!(state_store.contains_state_root(*GENESIS_STATE_ROOT)?);
+    assert!(!state_store.contains_state_root(H256::random())?);
+    Ok(())
+}
+
 // #[tokio::test]
 // async fn test_child_state_db_dump_and_apply() -> Result<()> {
 //     let mut moveos_store = MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
@@ -0,0 +1,204 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::h256::H256;
+use crate::moveos_std::object::ObjectID;
+use crate::state::{FieldKey, ObjectState};
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+pub use smt::SparseMerkleProof;
+
+/// The proof of a field in the global state tree.
+/// The fields of an object are stored in the object's own tree, so the proof is a chain of SMT proofs,
+/// from the root object through the nested objects to the field.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StateProof {
+    /// The objects on the path from the child of the root object to the field's object.
+    /// The state root of every object is the root hash of the next proof.
+    /// The path is truncated if an object on the path does not exist.
+    pub ancestors: Vec<ObjectState>,
+    /// The SMT proofs from the root object's tree to the field's object tree, one more than the ancestors.
+    pub proofs: Vec<SparseMerkleProof>,
+}
+
+impl StateProof {
+    pub fn new(ancestors: Vec<ObjectState>, proofs: Vec<SparseMerkleProof>) -> Self {
+        Self { ancestors, proofs }
+    }
+
+    /// Verify the `state` is the field `field_key` of the object `object_id` under the global `state_root`,
+    /// if the `state` is none, verify the field does not exist.
+    /// The `state_root` should be taken from a trusted source, such as a block or a transaction execution info.
+    pub fn verify(
+        &self,
+        state_root: H256,
+        object_id: &ObjectID,
+        field_key: FieldKey,
+        state: Option<&ObjectState>,
+    ) -> Result<()> {
+        let path = object_path(object_id);
+        ensure!(
+            self.ancestors.len() <= path.len(),
+            "The proof has {} ancestors, but the object {} has {} ancestors",
+            self.ancestors.len(),
+            object_id,
+            path.len()
+        );
+        ensure!(
+            self.proofs.len() == self.ancestors.len() + 1,
+            "The proof has {} proofs, expected {}",
+            self.proofs.len(),
+            self.ancestors.len() + 1
+        );
+
+        let mut current_root = state_root;
+        for ((ancestor, ancestor_id), proof) in self.ancestors.iter().zip(&path).zip(&self.proofs) {
+            ensure!(
+                &ancestor.metadata.id == ancestor_id,
+                "The ancestor {} in proof does not match the object path {}",
+                ancestor.metadata.id,
+                ancestor_id
+            );
+            proof.verify(
+                current_root,
+                ancestor_id.field_key(),
+                Some(ancestor.clone()),
+            )?;
+            current_root = ancestor.state_root();
+        }
+
+        let last_proof = self.proofs.last().expect("The proofs should not be empty");
+        if self.ancestors.len() < path.len() {
+            // The object on the path does not exist, so the field does not exist
+            ensure!(
+                state.is_none(),
+                "The object {} does not exist, but the state is provided",
+                path[self.ancestors.len()]
+            );
+            last_proof.verify::<FieldKey, ObjectState>(
+                current_root,
+                path[self.ancestors.len()].field_key(),
+                None,
+            )
+        } else {
+            last_proof.verify(current_root, field_key, state.cloned())
+        }
+    }
+}
+
+/// The objects on the path from the child of the root object to the `object_id`, the root object is not included.
+pub fn object_path(object_id: &ObjectID) -> Vec<ObjectID> {
+    let mut path = vec![];
+    let mut current = object_id.clone();
+    while !current.is_root() {
+        let parent = current
+            .parent()
+            .expect("The non-root object should have parent");
+        path.push(current);
+        current = parent;
+    }
+    path.reverse();
+    path
+}
+
+/// A field state with its proof
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StateWithProof {
+    pub object_id: ObjectID,
+    pub field_key: FieldKey,
+    pub state: Option<ObjectState>,
+    pub proof: StateProof,
+}
+
+impl StateWithProof {
+    pub fn verify(&self, state_root: H256) -> Result<()> {
+        self.proof.verify(
+            state_root,
+            &self.object_id,
+            self.field_key,
+            self.state.as_ref(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moveos_std::object::{ObjectMeta, GENESIS_STATE_ROOT};
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::language_storage::TypeTag;
+    use smt::{InMemoryNodeStore, SMTree};
+
+    fn new_state(id: ObjectID, state_root: Option<H256>, value: Vec<u8>) -> ObjectState {
+        let metadata = ObjectMeta::new(
+            id,
+            AccountAddress::ZERO,
+            0,
+            state_root,
+            0,
+            0,
+            0,
+            TypeTag::U8,
+        );
+        ObjectState::new(metadata, value)
+    }
+
+    #[test]
+    fn test_state_proof() {
+        let node_store = InMemoryNodeStore::default();
+        let smt: SMTree<FieldKey, ObjectState, InMemoryNodeStore> = SMTree::new(node_store.clone());
+
+        let object_id = ObjectID::random();
+        let field_key = FieldKey::random();
+        let field = new_state(object_id.child_id(field_key), None, vec![1]);
+        let object_changeset = smt
+            .put(*GENESIS_STATE_ROOT, field_key, field.clone())
+            .unwrap();
+        node_store.write_nodes(object_changeset.nodes).unwrap();
+        let object_root = object_changeset.state_root;
+
+        let object = new_state(object_id.clone(), Some(object_root), vec![]);
+        let root_changeset = smt
+            .put(*GENESIS_STATE_ROOT, object_id.field_key(), object.clone())
+            .unwrap();
+        node_store.write_nodes(root_changeset.nodes).unwrap();
+        let state_root = root_changeset.state_root;
+
+        let (_, object_proof) = smt
+            .get_with_proof(state_root, object_id.field_key())
+            .unwrap();
+        let (_, field_proof) = smt.get_with_proof(object_root, field_key).unwrap();
+        let mut state_with_proof = StateWithProof {
+            object_id: object_id.clone(),
+            field_key,
+            state: Some(field.clone()),
+            proof: StateProof::new(vec![object], vec![object_proof, field_proof]),
+        };
+        state_with_proof.verify(state_root).unwrap();
+        assert!(state_with_proof.verify(H256::random()).is_err());
+        state_with_proof.state = Some(new_state(object_id.child_id(field_key), None, vec![2]));
+        assert!(state_with_proof.verify(state_root).is_err());
+
+        // The proof of a missing object
+        let missing_object_id = ObjectID::random();
+        let (missing_object, missing_object_proof) = smt
+            .get_with_proof(state_root, missing_object_id.field_key())
+            .unwrap();
+        assert!(missing_object.is_none());
+        let state_with_proof = StateWithProof {
+            object_id: missing_object_id,
+            field_key,
+            state: None,
+            proof: StateProof::new(vec![], vec![missing_object_proof]),
+        };
+        state_with_proof.verify(state_root).unwrap();
+    }
+
+    #[test]
+    fn test_object_path() {
+        let object_id = ObjectID::random();
+        let child_id = object_id.child_id(FieldKey::random());
+        assert!(object_path(&ObjectID::root()).is_empty());
+        assert_eq!(object_path(&child_id), vec![object_id, child_id]);
+    }
+}